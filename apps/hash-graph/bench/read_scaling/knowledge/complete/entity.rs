@@ -6,7 +6,10 @@ use criterion_macro::criterion;
 use graph::{
     store::{query::Filter, AccountStore, EntityStore},
     subgraph::{
-        edges::{EdgeResolveDepths, GraphResolveDepths, OutgoingEdgeResolveDepth},
+        edges::{
+            EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphTraversalFilters,
+            OutgoingEdgeResolveDepth,
+        },
         query::StructuralQuery,
         temporal_axes::{
             PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
@@ -172,6 +175,7 @@ pub fn bench_get_entity_by_id(
                     &StructuralQuery {
                         filter: Filter::for_entity_by_entity_id(entity_record_id.entity_id),
                         graph_resolve_depths,
+                        traversal_filters: KnowledgeGraphTraversalFilters::default(),
                         temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                             pinned: PinnedTemporalAxisUnresolved::new(None),
                             variable: VariableTemporalAxisUnresolved::new(
@@ -179,6 +183,9 @@ pub fn bench_get_entity_by_id(
                                 None,
                             ),
                         },
+                        order_by: Vec::new(),
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .await
@@ -6,7 +6,7 @@ use criterion_macro::criterion;
 use graph::{
     store::{query::Filter, AccountStore, EntityStore},
     subgraph::{
-        edges::GraphResolveDepths,
+        edges::{GraphResolveDepths, KnowledgeGraphTraversalFilters},
         query::StructuralQuery,
         temporal_axes::{
             PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
@@ -134,6 +134,7 @@ pub fn bench_get_entity_by_id(
                     &StructuralQuery {
                         filter: Filter::for_entity_by_entity_id(entity_record_id.entity_id),
                         graph_resolve_depths: GraphResolveDepths::default(),
+                        traversal_filters: KnowledgeGraphTraversalFilters::default(),
                         temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                             pinned: PinnedTemporalAxisUnresolved::new(None),
                             variable: VariableTemporalAxisUnresolved::new(
@@ -141,6 +142,9 @@ pub fn bench_get_entity_by_id(
                                 None,
                             ),
                         },
+                        order_by: Vec::new(),
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .await
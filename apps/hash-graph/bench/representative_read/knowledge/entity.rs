@@ -9,7 +9,10 @@ use graph::{
         EntityStore,
     },
     subgraph::{
-        edges::{EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind},
+        edges::{
+            EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind,
+            KnowledgeGraphTraversalFilters,
+        },
         query::StructuralQuery,
         temporal_axes::{
             PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
@@ -40,7 +43,7 @@ pub fn bench_get_entity_by_id(
                 .expect("could not choose random entity")
         },
         |entity_uuid| async move {
-            let subgraph = store
+            let (subgraph, ..) = store
                 .get_entity(
                     actor_id,
                     &NoAuthorization,
@@ -52,10 +55,14 @@ pub fn bench_get_entity_by_id(
                             ))),
                         ),
                         graph_resolve_depths: GraphResolveDepths::default(),
+                        traversal_filters: KnowledgeGraphTraversalFilters::default(),
                         temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                             pinned: PinnedTemporalAxisUnresolved::new(None),
                             variable: VariableTemporalAxisUnresolved::new(None, None),
                         },
+                        order_by: Vec::new(),
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .await
@@ -87,13 +94,14 @@ pub fn bench_get_entities_by_property(
         filter
             .convert_parameters()
             .expect("failed to convert parameters");
-        let subgraph = store
+        let (subgraph, ..) = store
             .get_entity(
                 actor_id,
                 &NoAuthorization,
                 &StructuralQuery {
                     filter,
                     graph_resolve_depths,
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(
@@ -101,6 +109,9 @@ pub fn bench_get_entities_by_property(
                             None,
                         ),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
             .await
@@ -134,13 +145,14 @@ pub fn bench_get_link_by_target_by_property(
         filter
             .convert_parameters()
             .expect("failed to convert parameters");
-        let subgraph = store
+        let (subgraph, ..) = store
             .get_entity(
                 actor_id,
                 &NoAuthorization,
                 &StructuralQuery {
                     filter,
                     graph_resolve_depths,
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(
@@ -148,6 +160,9 @@ pub fn bench_get_link_by_target_by_property(
                             None,
                         ),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
             .await
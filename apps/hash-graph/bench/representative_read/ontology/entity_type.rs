@@ -3,7 +3,7 @@ use criterion::{BatchSize::SmallInput, Bencher};
 use graph::{
     store::{query::Filter, EntityTypeStore},
     subgraph::{
-        edges::GraphResolveDepths,
+        edges::{GraphResolveDepths, KnowledgeGraphTraversalFilters},
         query::StructuralQuery,
         temporal_axes::{
             PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
@@ -42,6 +42,7 @@ pub fn bench_get_entity_type_by_id(
                     &StructuralQuery {
                         filter: Filter::for_versioned_url(entity_type_id),
                         graph_resolve_depths: GraphResolveDepths::default(),
+                        traversal_filters: KnowledgeGraphTraversalFilters::default(),
                         temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                             pinned: PinnedTemporalAxisUnresolved::new(None),
                             variable: VariableTemporalAxisUnresolved::new(
@@ -49,6 +50,9 @@ pub fn bench_get_entity_type_by_id(
                                 None,
                             ),
                         },
+                        order_by: Vec::new(),
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .await
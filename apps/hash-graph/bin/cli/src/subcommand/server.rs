@@ -6,12 +6,12 @@ use std::{
     time::Duration,
 };
 
-use authorization::NoAuthorization;
 #[cfg(feature = "authorization")]
 use authorization::{
     backend::{SpiceDbOpenApi, ZanzibarBackend},
     zanzibar::ZanzibarClient,
 };
+use authorization::{AuthorizationApiPool, NoAuthorization};
 use clap::Parser;
 use error_stack::{Report, Result, ResultExt};
 use graph::{
@@ -22,6 +22,7 @@ use graph::{
         error::VersionedUrlAlreadyExists, AccountStore, DataTypeStore, DatabaseConnectionInfo,
         EntityTypeStore, FetchingPool, PostgresStorePool, StorePool,
     },
+    webhook::{WebhookDeliveryConfig, WebhookDispatcher},
 };
 use graph_types::{
     account::AccountId,
@@ -123,22 +124,47 @@ pub struct ServerArgs {
     #[clap(long, default_value_t = false, conflicts_with_all = ["type_fetcher_host", "type_fetcher_port"])]
     pub offline: bool,
 
-    /// The host the Spice DB server is listening at.
+    /// The address of the Spice DB server.
     #[cfg(feature = "authorization")]
+    #[clap(flatten)]
+    pub spicedb: SpiceDbConfig,
+}
+
+#[cfg(feature = "authorization")]
+#[derive(Debug, Parser)]
+pub struct SpiceDbConfig {
+    /// The host the Spice DB server is listening at.
     #[clap(long, env = "HASH_SPICEDB_HOST")]
     pub spicedb_host: String,
 
     /// The port the Spice DB server is listening at.
-    #[cfg(feature = "authorization")]
     #[clap(long, env = "HASH_SPICEDB_HTTP_PORT")]
     pub spicedb_http_port: u16,
 
     /// The secret key used to authenticate with the Spice DB server.
-    #[cfg(feature = "authorization")]
     #[clap(long, env = "HASH_SPICEDB_GRPC_PRESHARED_KEY")]
     pub spicedb_grpc_preshared_key: String,
 }
 
+#[cfg(feature = "authorization")]
+impl SpiceDbConfig {
+    /// Connects to the Spice DB server and imports the authorization schema.
+    pub async fn connect(&self) -> Result<ZanzibarClient<SpiceDbOpenApi>, GraphError> {
+        let mut spicedb_client = SpiceDbOpenApi::new(
+            format!("{}:{}", self.spicedb_host, self.spicedb_http_port),
+            &self.spicedb_grpc_preshared_key,
+        )
+        .change_context(GraphError)?;
+        spicedb_client
+            .import_schema(include_str!(
+                "../../../../lib/authorization/schemas/v1__initial_schema.zed"
+            ))
+            .await
+            .change_context(GraphError)?;
+        Ok(ZanzibarClient::new(spicedb_client))
+    }
+}
+
 // TODO: Consider making this a refinery migration
 /// A place to collect temporary implementations that are useful before stabilization of the Graph.
 ///
@@ -383,9 +409,33 @@ pub async fn server(args: ServerArgs) -> Result<(), GraphError> {
         .change_context(GraphError)
         .attach_printable("Connection to database failed")?;
 
-    let pool = if args.offline {
+    if args.offline {
         stop_gap_setup(&pool).await?;
+    }
 
+    #[cfg(feature = "authorization")]
+    let authorization_api = args.spicedb.connect().await?;
+    #[cfg(not(feature = "authorization"))]
+    let authorization_api = NoAuthorization;
+
+    // Ontology types created before they had permissions, or without authorization as in the
+    // stop-gap setup, don't have their relations yet. Writing them must not delay or prevent
+    // serving requests, it's retried on the next start if it fails.
+    let store = pool.acquire_owned().await.change_context(GraphError)?;
+    let mut relations_authorization_api = authorization_api
+        .acquire_owned()
+        .await
+        .change_context(GraphError)?;
+    tokio::spawn(async move {
+        if let Err(report) = store
+            .write_ontology_type_relations(&mut relations_authorization_api)
+            .await
+        {
+            tracing::error!(error = ?report, "Could not write the relations of ontology types");
+        }
+    });
+
+    let pool = if args.offline {
         FetchingPool::new_offline(pool)
     } else {
         FetchingPool::new(
@@ -398,27 +448,18 @@ pub async fn server(args: ServerArgs) -> Result<(), GraphError> {
         )
     };
 
-    #[cfg(feature = "authorization")]
-    let authorization_api = {
-        let mut spicedb_client = SpiceDbOpenApi::new(
-            format!("{}:{}", args.spicedb_host, args.spicedb_http_port),
-            &args.spicedb_grpc_preshared_key,
-        )
-        .change_context(GraphError)?;
-        spicedb_client
-            .import_schema(include_str!(
-                "../../../../lib/authorization/schemas/v1__initial_schema.zed"
-            ))
-            .await
-            .change_context(GraphError)?;
-        ZanzibarClient::new(spicedb_client)
-    };
-    #[cfg(not(feature = "authorization"))]
-    let authorization_api = NoAuthorization;
+    let pool = Arc::new(pool);
+    let authorization_api = Arc::new(authorization_api);
+
+    tokio::spawn(
+        WebhookDispatcher::new(WebhookDeliveryConfig::default())
+            .change_context(GraphError)?
+            .run(Arc::clone(&pool), Arc::clone(&authorization_api)),
+    );
 
     let router = rest_api_router(RestRouterDependencies {
-        store: Arc::new(pool),
-        authorization_api: Arc::new(authorization_api),
+        store: pool,
+        authorization_api,
         domain_regex: DomainValidator::new(args.allowed_url_domain),
     });
 
@@ -1,3 +1,5 @@
+#[cfg(not(feature = "authorization"))]
+use authorization::NoAuthorization;
 use clap::Parser;
 use error_stack::{Result, ResultExt};
 use futures::{SinkExt, StreamExt, TryStreamExt};
@@ -11,6 +13,8 @@ use tokio_postgres::NoTls;
 use tokio_util::codec::{FramedRead, FramedWrite};
 
 use crate::error::GraphError;
+#[cfg(feature = "authorization")]
+use crate::subcommand::server::SpiceDbConfig;
 
 #[derive(Debug, Parser)]
 pub struct SnapshotDumpArgs;
@@ -35,6 +39,11 @@ pub struct SnapshotArgs {
 
     #[clap(flatten)]
     pub db_info: DatabaseConnectionInfo,
+
+    /// The address of the Spice DB server.
+    #[cfg(feature = "authorization")]
+    #[clap(flatten)]
+    pub spicedb: SpiceDbConfig,
 }
 
 pub async fn snapshot(args: SnapshotArgs) -> Result<(), GraphError> {
@@ -81,6 +90,11 @@ pub async fn snapshot(args: SnapshotArgs) -> Result<(), GraphError> {
             tracing::info!("Snapshot dumped successfully");
         }
         SnapshotCommand::Restore(_) => {
+            #[cfg(feature = "authorization")]
+            let mut authorization_api = args.spicedb.connect().await?;
+            #[cfg(not(feature = "authorization"))]
+            let mut authorization_api = NoAuthorization;
+
             store
                 .restore_snapshot(
                     FramedRead::new(
@@ -88,6 +102,7 @@ pub async fn snapshot(args: SnapshotArgs) -> Result<(), GraphError> {
                         codec::JsonLinesDecoder::default(),
                     ),
                     10_000,
+                    &mut authorization_api,
                 )
                 .await
                 .change_context(GraphError)
@@ -3,13 +3,17 @@ use std::{collections::HashMap, future::Future};
 use error_stack::Result;
 use graph_types::{
     account::{AccountGroupId, AccountId},
-    knowledge::entity::EntityId,
+    knowledge::entity::{EntityId, EntityUuid},
+    ontology::{DataTypeId, EntityTypeId, PropertyTypeId},
     web::WebId,
 };
 
 use crate::{
-    backend::{CheckError, CheckResponse, ModifyRelationError},
-    schema::OwnerId,
+    backend::{
+        BulkCheckError, CheckError, CheckResponse, LookupResourcesError, ModifyRelationError,
+        ReadRelationsError,
+    },
+    schema::{DataTypeRelation, EntityRelation, EntityTypeRelation, OwnerId, PropertyTypeRelation},
     zanzibar::{Consistency, Zookie},
 };
 
@@ -85,6 +89,42 @@ pub trait AuthorizationApi {
         entity: EntityId,
     ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
 
+    fn add_entity_editor(
+        &mut self,
+        scope: VisibilityScope,
+        entity: EntityId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_entity_editor(
+        &mut self,
+        scope: VisibilityScope,
+        entity: EntityId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn add_entity_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        entity: EntityId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_entity_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        entity: EntityId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    /// Returns the direct relations of the entity alongside the scope they were granted to.
+    fn get_entity_relations(
+        &self,
+        entity: EntityId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<
+        Output = Result<
+            (Vec<(EntityRelation, VisibilityScope)>, Zookie<'static>),
+            ReadRelationsError,
+        >,
+    > + Send;
+
     fn can_create_entity(
         &self,
         actor: AccountId,
@@ -99,6 +139,13 @@ pub trait AuthorizationApi {
         consistency: Consistency<'_>,
     ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
 
+    fn can_delete_entity(
+        &self,
+        actor: AccountId,
+        entity: EntityId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
     fn can_view_entity(
         &self,
         actor: AccountId,
@@ -106,29 +153,313 @@ pub trait AuthorizationApi {
         consistency: Consistency<'_>,
     ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
 
+    fn can_share_entity(
+        &self,
+        actor: AccountId,
+        entity: EntityId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    /// Adds the owner of the data type, adding an owner which already exists is not an error.
+    fn add_data_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_data_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn add_data_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_data_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn add_data_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_data_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    /// Adds the relations of many data types at once, relations which already exist are kept.
+    fn touch_data_type_relations(
+        &mut self,
+        relations: impl IntoIterator<
+            Item = (DataTypeId, DataTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn can_create_data_type(
+        &self,
+        actor: AccountId,
+        web: impl Into<WebId> + Send,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_update_data_type(
+        &self,
+        actor: AccountId,
+        data_type: DataTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_archive_data_type(
+        &self,
+        actor: AccountId,
+        data_type: DataTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_share_data_type(
+        &self,
+        actor: AccountId,
+        data_type: DataTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_view_data_type(
+        &self,
+        actor: AccountId,
+        data_type: DataTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    /// Adds the owner of the property type, adding an owner which already exists is not an error.
+    fn add_property_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_property_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn add_property_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_property_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn add_property_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_property_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    /// Adds the relations of many property types at once, relations which already exist are kept.
+    fn touch_property_type_relations(
+        &mut self,
+        relations: impl IntoIterator<
+            Item = (PropertyTypeId, PropertyTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn can_create_property_type(
+        &self,
+        actor: AccountId,
+        web: impl Into<WebId> + Send,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_update_property_type(
+        &self,
+        actor: AccountId,
+        property_type: PropertyTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_archive_property_type(
+        &self,
+        actor: AccountId,
+        property_type: PropertyTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_share_property_type(
+        &self,
+        actor: AccountId,
+        property_type: PropertyTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_view_property_type(
+        &self,
+        actor: AccountId,
+        property_type: PropertyTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    /// Adds the owner of the entity type, adding an owner which already exists is not an error.
+    fn add_entity_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_entity_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn add_entity_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_entity_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn add_entity_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn remove_entity_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    /// Adds the relations of many entity types at once, relations which already exist are kept.
+    fn touch_entity_type_relations(
+        &mut self,
+        relations: impl IntoIterator<
+            Item = (EntityTypeId, EntityTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> impl Future<Output = Result<Zookie<'static>, ModifyRelationError>> + Send;
+
+    fn can_create_entity_type(
+        &self,
+        actor: AccountId,
+        web: impl Into<WebId> + Send,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_update_entity_type(
+        &self,
+        actor: AccountId,
+        entity_type: EntityTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_archive_entity_type(
+        &self,
+        actor: AccountId,
+        entity_type: EntityTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_share_entity_type(
+        &self,
+        actor: AccountId,
+        entity_type: EntityTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
+    fn can_view_entity_type(
+        &self,
+        actor: AccountId,
+        entity_type: EntityTypeId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<CheckResponse, CheckError>> + Send;
+
     fn can_view_entities(
         &self,
         actor: AccountId,
         entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
         consistency: Consistency<'_>,
-    ) -> impl Future<Output = Result<(HashMap<EntityId, bool>, Zookie<'static>), CheckError>> + Send
-    where
-        Self: Sync,
-    {
-        async move {
-            let mut zookie = Zookie::empty();
-            let mut result = HashMap::new();
-            for entity_id in entities {
-                let CheckResponse {
-                    has_permission,
-                    checked_at,
-                } = self.can_view_entity(actor, entity_id, consistency).await?;
-                result.insert(entity_id, has_permission);
-                zookie = checked_at;
-            }
-            Ok((result, zookie))
-        }
-    }
+    ) -> impl Future<Output = Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError>> + Send;
+
+    fn can_update_entities(
+        &self,
+        actor: AccountId,
+        entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError>> + Send;
+
+    fn can_delete_entities(
+        &self,
+        actor: AccountId,
+        entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError>> + Send;
+
+    fn can_view_data_types(
+        &self,
+        actor: AccountId,
+        data_types: impl IntoIterator<Item = DataTypeId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<(HashMap<DataTypeId, bool>, Zookie<'static>), BulkCheckError>> + Send;
+
+    fn can_view_property_types(
+        &self,
+        actor: AccountId,
+        property_types: impl IntoIterator<Item = PropertyTypeId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> impl Future<
+        Output = Result<(HashMap<PropertyTypeId, bool>, Zookie<'static>), BulkCheckError>,
+    > + Send;
+
+    fn can_view_entity_types(
+        &self,
+        actor: AccountId,
+        entity_types: impl IntoIterator<Item = EntityTypeId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<(HashMap<EntityTypeId, bool>, Zookie<'static>), BulkCheckError>>
+    + Send;
+
+    /// Returns the entities the actor is permitted to view.
+    ///
+    /// Entities are identified by their [`EntityUuid`] only, which is unique across all webs, so
+    /// each returned UUID refers to exactly one entity. If the actor is permitted to view every
+    /// entity, `None` is returned instead.
+    fn lookup_viewable_entities(
+        &self,
+        actor: AccountId,
+        consistency: Consistency<'_>,
+    ) -> impl Future<
+        Output = Result<(Option<Vec<EntityUuid>>, Zookie<'static>), LookupResourcesError>,
+    > + Send;
 }
 
 /// Managed pool to keep track about [`AuthorizationApi`]s.
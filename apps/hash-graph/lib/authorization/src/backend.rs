@@ -1,12 +1,14 @@
+mod memory;
 mod spicedb;
 
 use core::fmt;
 use std::{error::Error, future::Future};
 
 use error_stack::Report;
+use serde::de::DeserializeOwned;
 
-pub use self::spicedb::SpiceDbOpenApi;
-use crate::zanzibar::{Consistency, Tuple, UntypedTuple, Zookie};
+pub use self::{memory::InMemoryBackend, spicedb::SpiceDbOpenApi};
+use crate::zanzibar::{Affiliation, Consistency, Resource, Tuple, UntypedTuple, Zookie};
 
 /// A backend for interacting with an authorization system based on the Zanzibar model.
 pub trait ZanzibarBackend {
@@ -43,6 +45,18 @@ pub trait ZanzibarBackend {
     where
         T: Tuple + Send + Sync;
 
+    /// Creates the relations specified by the [`Tuple`]s, relations which already exist are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the relations could not be created.
+    fn touch_relations<T>(
+        &mut self,
+        tuples: impl IntoIterator<Item = T, IntoIter: Send> + Send,
+    ) -> impl Future<Output = Result<CreateRelationResponse, Report<CreateRelationError>>> + Send
+    where
+        T: Tuple + Send + Sync;
+
     /// Deletes the relation specified by the [`Tuple`].
     ///
     /// # Errors
@@ -74,6 +88,57 @@ pub trait ZanzibarBackend {
     ) -> impl Future<Output = Result<CheckResponse, Report<CheckError>>> + Send
     where
         T: Tuple + Sync;
+
+    /// Returns if the subjects of the [`Tuple`]s have the specified permissions or relations to
+    /// the [`Resource`]s.
+    ///
+    /// The checks are performed in as few requests as the backend allows. The result at a given
+    /// index of [`BulkCheckResponse::has_permission`] corresponds to the tuple at the same index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the checks could not be performed.
+    ///
+    /// [`Resource`]: crate::zanzibar::Resource
+    fn check_bulk<T>(
+        &self,
+        tuples: &[T],
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<BulkCheckResponse, Report<BulkCheckError>>> + Send
+    where
+        T: Tuple + Sync;
+
+    /// Returns the IDs of all [`Resource`]s of type `O` to which the `subject` has the specified
+    /// permission or relation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lookup could not be performed.
+    fn lookup_resources<O, A, U>(
+        &self,
+        subject: &U,
+        affiliation: &A,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<LookupResourcesResponse<O::Id>, Report<LookupResourcesError>>> + Send
+    where
+        O: Resource<Id: Sized + DeserializeOwned + Send>,
+        A: Affiliation<O> + Sync,
+        U: Resource + Sync;
+
+    /// Returns all relations for which the [`Resource`] is the object.
+    ///
+    /// Permissions are not included as they are computed from the relations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the relations could not be read.
+    fn read_relations<O>(
+        &self,
+        object: &O,
+        consistency: Consistency<'_>,
+    ) -> impl Future<Output = Result<ReadRelationsResponse, Report<ReadRelationsError>>> + Send
+    where
+        O: Resource + Sync;
 }
 
 /// Return value for [`ZanzibarBackend::import_schema`].
@@ -198,6 +263,73 @@ impl fmt::Display for CheckError {
 
 impl Error for CheckError {}
 
+/// Return value for [`ZanzibarBackend::check_bulk`].
+#[derive(Debug)]
+#[must_use]
+pub struct BulkCheckResponse {
+    /// If the subject has the specified permission or relation to an [`Resource`], in the same
+    /// order as the checked tuples.
+    ///
+    /// [`Resource`]: crate::zanzibar::Resource
+    pub has_permission: Vec<bool>,
+    /// A token to determine the time at which the checks were performed.
+    pub checked_at: Zookie<'static>,
+}
+
+/// Error returned from [`ZanzibarBackend::check_bulk`].
+#[derive(Debug)]
+pub struct BulkCheckError;
+
+impl fmt::Display for BulkCheckError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("failed to check permissions")
+    }
+}
+
+impl Error for BulkCheckError {}
+
+/// Return value for [`ZanzibarBackend::lookup_resources`].
+#[derive(Debug)]
+pub struct LookupResourcesResponse<I> {
+    /// The IDs of the [`Resource`]s the subject has the specified permission or relation to.
+    pub resources: Vec<I>,
+    /// A token to determine the time at which the lookup was performed.
+    pub looked_up_at: Zookie<'static>,
+}
+
+/// Error returned from [`ZanzibarBackend::lookup_resources`].
+#[derive(Debug)]
+pub struct LookupResourcesError;
+
+impl fmt::Display for LookupResourcesError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("failed to look up resources")
+    }
+}
+
+impl Error for LookupResourcesError {}
+
+/// Return value for [`ZanzibarBackend::read_relations`].
+#[derive(Debug)]
+pub struct ReadRelationsResponse {
+    /// The relations of the [`Resource`].
+    pub relations: Vec<UntypedTuple<'static>>,
+    /// A token to determine the time at which the relations were read.
+    pub read_at: Zookie<'static>,
+}
+
+/// Error returned from [`ZanzibarBackend::read_relations`].
+#[derive(Debug)]
+pub struct ReadRelationsError;
+
+impl fmt::Display for ReadRelationsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("failed to read relations")
+    }
+}
+
+impl Error for ReadRelationsError {}
+
 #[derive(Debug)]
 pub struct ModifyRelationError;
 
@@ -0,0 +1,621 @@
+mod schema;
+
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+use error_stack::{Report, ResultExt};
+use serde::de::DeserializeOwned;
+
+use self::schema::{AllowedSubject, Expression, Schema};
+use crate::{
+    backend::{
+        BulkCheckError, BulkCheckResponse, CheckError, CheckResponse, CreateRelationError,
+        CreateRelationResponse, DeleteRelationError, DeleteRelationResponse, ExportSchemaError,
+        ExportSchemaResponse, ImportSchemaError, ImportSchemaResponse, LookupResourcesError,
+        LookupResourcesResponse, ReadRelationsError, ReadRelationsResponse, ZanzibarBackend,
+    },
+    zanzibar::{Affiliation, Consistency, Resource, Tuple, UntypedTuple, Zookie},
+};
+
+/// The object and relation of a stored relationship.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ObjectRelation {
+    namespace: String,
+    id: String,
+    relation: String,
+}
+
+/// The subject of a stored relationship or of a check.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Subject {
+    namespace: String,
+    id: String,
+    affiliation: Option<String>,
+}
+
+fn split_tuple(tuple: UntypedTuple<'_>) -> (ObjectRelation, Subject) {
+    (
+        ObjectRelation {
+            namespace: tuple.object_namespace.into_owned(),
+            id: tuple.object_id.into_owned(),
+            relation: tuple.affiliation.into_owned(),
+        },
+        Subject {
+            namespace: tuple.user_namespace.into_owned(),
+            id: tuple.user_id.into_owned(),
+            affiliation: tuple.user_set.map(Cow::into_owned),
+        },
+    )
+}
+
+#[derive(Debug, Default)]
+struct State {
+    schema: Option<(String, Schema)>,
+    relationships: HashMap<ObjectRelation, HashSet<Subject>>,
+    revision: u64,
+}
+
+impl State {
+    fn zookie(&self) -> Zookie<'static> {
+        Zookie::new(Cow::Owned(self.revision.to_string()))
+    }
+
+    /// Returns an error message if the relationship is not allowed by the schema.
+    fn validate_relationship(&self, object: &ObjectRelation, subject: &Subject) -> Option<String> {
+        let Some((_, schema)) = &self.schema else {
+            return Some("no schema was imported".to_owned());
+        };
+        let Some(allowed_subjects) = schema
+            .definitions
+            .get(&object.namespace)
+            .and_then(|definition| definition.relations.get(&object.relation))
+        else {
+            return Some(format!(
+                "`{}#{}` is not a relation",
+                object.namespace, object.relation
+            ));
+        };
+
+        let is_allowed = allowed_subjects.iter().any(|allowed| match allowed {
+            AllowedSubject::Object { namespace } => {
+                *namespace == subject.namespace
+                    && subject.id != "*"
+                    && subject.affiliation.is_none()
+            }
+            AllowedSubject::Wildcard { namespace } => {
+                *namespace == subject.namespace
+                    && subject.id == "*"
+                    && subject.affiliation.is_none()
+            }
+            AllowedSubject::SubjectSet {
+                namespace,
+                affiliation,
+            } => {
+                *namespace == subject.namespace && subject.affiliation.as_ref() == Some(affiliation)
+            }
+        });
+        (!is_allowed).then(|| {
+            format!(
+                "`{}#{}` does not allow subjects of type `{}`",
+                object.namespace, object.relation, subject.namespace
+            )
+        })
+    }
+
+    /// Checks the consistency requirement against the current revision.
+    fn validate_consistency(&self, consistency: Consistency<'_>) -> Option<String> {
+        let parse = |zookie: &Zookie<'_>| {
+            let token = zookie.as_str();
+            if token.is_empty() {
+                Some(0)
+            } else {
+                token.parse::<u64>().ok()
+            }
+        };
+
+        match consistency {
+            Consistency::MinimalLatency | Consistency::FullyConsistent => None,
+            Consistency::AtLeastAsFresh(zookie) => match parse(zookie) {
+                Some(revision) if revision <= self.revision => None,
+                _ => Some(format!("`{}` is not a valid token", zookie.as_str())),
+            },
+            // Previous revisions are not retained, so only the current one is available.
+            Consistency::AtExactSnapshot(zookie) => match parse(zookie) {
+                Some(revision) if revision == self.revision => None,
+                _ => Some(format!("snapshot `{}` is not available", zookie.as_str())),
+            },
+        }
+    }
+}
+
+/// Evaluates whether a subject has an affiliation to an object.
+struct Evaluation<'s> {
+    schema: &'s Schema,
+    relationships: &'s HashMap<ObjectRelation, HashSet<Subject>>,
+    subject: &'s Subject,
+    /// The affiliations currently being evaluated, used to break cycles.
+    visiting: HashSet<ObjectRelation>,
+}
+
+impl Evaluation<'_> {
+    fn subjects(&self, object: &ObjectRelation) -> impl Iterator<Item = &Subject> {
+        self.relationships.get(object).into_iter().flatten()
+    }
+
+    fn has_affiliation(&mut self, object: &ObjectRelation) -> Result<bool, String> {
+        let definition = self
+            .schema
+            .definitions
+            .get(&object.namespace)
+            .ok_or_else(|| format!("`{}` is not defined", object.namespace))?;
+
+        // A subject set contains itself, e.g. `graph/account_group:a#member` is a member of
+        // `graph/account_group:a`.
+        if self.subject.namespace == object.namespace
+            && self.subject.id == object.id
+            && self.subject.affiliation.as_ref() == Some(&object.relation)
+        {
+            return Ok(true);
+        }
+
+        let result = if definition.relations.contains_key(&object.relation) {
+            if !self.visiting.insert(object.clone()) {
+                return Ok(false);
+            }
+            let mut result = false;
+            let subjects = self.subjects(object).cloned().collect::<Vec<_>>();
+            for subject in subjects {
+                result = match subject.affiliation {
+                    None => {
+                        self.subject.affiliation.is_none()
+                            && subject.namespace == self.subject.namespace
+                            && (subject.id == self.subject.id || subject.id == "*")
+                    }
+                    Some(affiliation) => self.has_affiliation(&ObjectRelation {
+                        namespace: subject.namespace,
+                        id: subject.id,
+                        relation: affiliation,
+                    })?,
+                };
+                if result {
+                    break;
+                }
+            }
+            result
+        } else if let Some(expression) = definition.permissions.get(&object.relation) {
+            if !self.visiting.insert(object.clone()) {
+                return Ok(false);
+            }
+            self.evaluate(object, expression)?
+        } else {
+            return Err(format!(
+                "`{}#{}` is not defined",
+                object.namespace, object.relation
+            ));
+        };
+
+        self.visiting.remove(object);
+        Ok(result)
+    }
+
+    fn evaluate(
+        &mut self,
+        object: &ObjectRelation,
+        expression: &Expression,
+    ) -> Result<bool, String> {
+        Ok(match expression {
+            Expression::Affiliation(affiliation) => self.has_affiliation(&ObjectRelation {
+                namespace: object.namespace.clone(),
+                id: object.id.clone(),
+                relation: affiliation.clone(),
+            })?,
+            Expression::Arrow {
+                relation,
+                permission,
+            } => {
+                let subjects = self
+                    .subjects(&ObjectRelation {
+                        namespace: object.namespace.clone(),
+                        id: object.id.clone(),
+                        relation: relation.clone(),
+                    })
+                    .filter(|subject| subject.id != "*")
+                    .filter(|subject| {
+                        self.schema
+                            .definitions
+                            .get(&subject.namespace)
+                            .is_some_and(|definition| definition.has_affiliation(permission))
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                let mut result = false;
+                for subject in subjects {
+                    if self.has_affiliation(&ObjectRelation {
+                        namespace: subject.namespace,
+                        id: subject.id,
+                        relation: permission.clone(),
+                    })? {
+                        result = true;
+                        break;
+                    }
+                }
+                result
+            }
+            Expression::Union(lhs, rhs) => {
+                self.evaluate(object, lhs)? || self.evaluate(object, rhs)?
+            }
+            Expression::Intersection(lhs, rhs) => {
+                self.evaluate(object, lhs)? && self.evaluate(object, rhs)?
+            }
+            Expression::Exclusion(lhs, rhs) => {
+                self.evaluate(object, lhs)? && !self.evaluate(object, rhs)?
+            }
+        })
+    }
+}
+
+/// A [`ZanzibarBackend`] keeping the schema and all relations in memory.
+///
+/// It supports the subset of the `SpiceDB` schema language used by the graph: relations to
+/// objects, subject sets (e.g. `graph/account_group#member`), and wildcards (e.g.
+/// `graph/account:*`), as well as permissions combining affiliations with unions, intersections,
+/// exclusions, and arrows.
+///
+/// Every write creates a new revision, which is returned as [`Zookie`]. Only the latest revision
+/// is kept, so [`Consistency::AtExactSnapshot`] fails for any other revision.
+///
+/// Clones share the same state, so the backend can be used by multiple [`AuthorizationApi`]s at
+/// once.
+///
+/// [`AuthorizationApi`]: crate::AuthorizationApi
+#[derive(Debug, Default, Clone)]
+pub struct InMemoryBackend {
+    state: Arc<RwLock<State>>,
+}
+
+impl InMemoryBackend {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, State> {
+        self.state.read().expect("lock should not be poisoned")
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, State> {
+        self.state.write().expect("lock should not be poisoned")
+    }
+}
+
+impl ZanzibarBackend for InMemoryBackend {
+    /// Replaces the schema of the backend.
+    ///
+    /// Existing relations are kept, even if they are not valid in the new schema.
+    async fn import_schema(
+        &mut self,
+        schema: &str,
+    ) -> Result<ImportSchemaResponse, Report<ImportSchemaError>> {
+        let parsed = Schema::parse(schema)?;
+
+        let mut state = self.write();
+        state.schema = Some((schema.to_owned(), parsed));
+        state.revision += 1;
+
+        Ok(ImportSchemaResponse {
+            written_at: state.zookie(),
+        })
+    }
+
+    async fn export_schema(&self) -> Result<ExportSchemaResponse, Report<ExportSchemaError>> {
+        let state = self.read();
+        let (schema, _) = state.schema.as_ref().ok_or_else(|| {
+            Report::new(ExportSchemaError).attach_printable("no schema was imported")
+        })?;
+
+        Ok(ExportSchemaResponse {
+            schema: schema.clone(),
+            read_at: state.zookie(),
+        })
+    }
+
+    /// Creates all relations or none of them.
+    async fn create_relations<T>(
+        &mut self,
+        tuples: impl IntoIterator<Item = T, IntoIter: Send> + Send,
+    ) -> Result<CreateRelationResponse, Report<CreateRelationError>>
+    where
+        T: Tuple + Send + Sync,
+    {
+        let mut state = self.write();
+
+        let mut relationships = Vec::new();
+        for tuple in tuples {
+            let untyped = UntypedTuple::from_tuple(&tuple).into_owned();
+            let (object, subject) = split_tuple(untyped.clone());
+
+            if let Some(error) = state.validate_relationship(&object, &subject) {
+                return Err(Report::new(CreateRelationError)
+                    .attach_printable(error)
+                    .attach_printable(untyped));
+            }
+            let already_exists = state
+                .relationships
+                .get(&object)
+                .is_some_and(|subjects| subjects.contains(&subject))
+                || relationships.contains(&(object.clone(), subject.clone()));
+            if already_exists {
+                return Err(Report::new(CreateRelationError)
+                    .attach_printable("relation already exists")
+                    .attach_printable(untyped));
+            }
+            relationships.push((object, subject));
+        }
+
+        for (object, subject) in relationships {
+            state
+                .relationships
+                .entry(object)
+                .or_default()
+                .insert(subject);
+        }
+        state.revision += 1;
+
+        Ok(CreateRelationResponse {
+            written_at: state.zookie(),
+        })
+    }
+
+    /// Creates all relations or none of them, relations which already exist are kept.
+    async fn touch_relations<T>(
+        &mut self,
+        tuples: impl IntoIterator<Item = T, IntoIter: Send> + Send,
+    ) -> Result<CreateRelationResponse, Report<CreateRelationError>>
+    where
+        T: Tuple + Send + Sync,
+    {
+        let mut state = self.write();
+
+        let mut relationships = Vec::new();
+        for tuple in tuples {
+            let untyped = UntypedTuple::from_tuple(&tuple).into_owned();
+            let (object, subject) = split_tuple(untyped.clone());
+
+            if let Some(error) = state.validate_relationship(&object, &subject) {
+                return Err(Report::new(CreateRelationError)
+                    .attach_printable(error)
+                    .attach_printable(untyped));
+            }
+            relationships.push((object, subject));
+        }
+
+        for (object, subject) in relationships {
+            state
+                .relationships
+                .entry(object)
+                .or_default()
+                .insert(subject);
+        }
+        state.revision += 1;
+
+        Ok(CreateRelationResponse {
+            written_at: state.zookie(),
+        })
+    }
+
+    /// Deletes the relations, relations which don't exist are ignored.
+    async fn delete_relations<T>(
+        &mut self,
+        tuples: impl IntoIterator<Item = T, IntoIter: Send> + Send,
+    ) -> Result<DeleteRelationResponse, Report<DeleteRelationError>>
+    where
+        T: Tuple + Send + Sync,
+    {
+        let mut state = self.write();
+
+        for tuple in tuples {
+            let (object, subject) = split_tuple(UntypedTuple::from_tuple(&tuple));
+            if let Some(subjects) = state.relationships.get_mut(&object) {
+                subjects.remove(&subject);
+                if subjects.is_empty() {
+                    state.relationships.remove(&object);
+                }
+            }
+        }
+        state.revision += 1;
+
+        Ok(DeleteRelationResponse {
+            deleted_at: state.zookie(),
+        })
+    }
+
+    async fn check<T>(
+        &self,
+        tuple: &T,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, Report<CheckError>>
+    where
+        T: Tuple + Sync,
+    {
+        let untyped = UntypedTuple::from_tuple(tuple).into_owned();
+        let (object, subject) = split_tuple(untyped.clone());
+        let error = |message: String| {
+            Report::new(CheckError {
+                tuple: untyped.clone(),
+            })
+            .attach_printable(message)
+        };
+
+        let state = self.read();
+        if let Some(message) = state.validate_consistency(consistency) {
+            return Err(error(message));
+        }
+        let (_, schema) = state
+            .schema
+            .as_ref()
+            .ok_or_else(|| error("no schema was imported".to_owned()))?;
+
+        let has_permission = Evaluation {
+            schema,
+            relationships: &state.relationships,
+            subject: &subject,
+            visiting: HashSet::new(),
+        }
+        .has_affiliation(&object)
+        .map_err(error)?;
+
+        Ok(CheckResponse {
+            has_permission,
+            checked_at: state.zookie(),
+        })
+    }
+
+    /// Performs all checks on the same snapshot.
+    async fn check_bulk<T>(
+        &self,
+        tuples: &[T],
+        consistency: Consistency<'_>,
+    ) -> Result<BulkCheckResponse, Report<BulkCheckError>>
+    where
+        T: Tuple + Sync,
+    {
+        let state = self.read();
+        if let Some(message) = state.validate_consistency(consistency) {
+            return Err(Report::new(BulkCheckError).attach_printable(message));
+        }
+        let (_, schema) = state.schema.as_ref().ok_or_else(|| {
+            Report::new(BulkCheckError).attach_printable("no schema was imported")
+        })?;
+
+        let has_permission = tuples
+            .iter()
+            .map(|tuple| {
+                let untyped = UntypedTuple::from_tuple(tuple);
+                let (object, subject) = split_tuple(untyped.clone());
+                Evaluation {
+                    schema,
+                    relationships: &state.relationships,
+                    subject: &subject,
+                    visiting: HashSet::new(),
+                }
+                .has_affiliation(&object)
+                .map_err(|message| {
+                    Report::new(CheckError {
+                        tuple: untyped.into_owned(),
+                    })
+                    .attach_printable(message)
+                    .change_context(BulkCheckError)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(BulkCheckResponse {
+            has_permission,
+            checked_at: state.zookie(),
+        })
+    }
+
+    /// Evaluates the affiliation for every object of type `O` which has at least one relation.
+    async fn lookup_resources<O, A, U>(
+        &self,
+        subject: &U,
+        affiliation: &A,
+        consistency: Consistency<'_>,
+    ) -> Result<LookupResourcesResponse<O::Id>, Report<LookupResourcesError>>
+    where
+        O: Resource<Id: Sized + DeserializeOwned + Send>,
+        A: Affiliation<O> + Sync,
+        U: Resource + Sync,
+    {
+        let state = self.read();
+        if let Some(message) = state.validate_consistency(consistency) {
+            return Err(Report::new(LookupResourcesError).attach_printable(message));
+        }
+        let (_, schema) = state.schema.as_ref().ok_or_else(|| {
+            Report::new(LookupResourcesError).attach_printable("no schema was imported")
+        })?;
+
+        let subject = Subject {
+            namespace: U::namespace().to_owned(),
+            id: subject.id().to_string(),
+            affiliation: None,
+        };
+        let object_ids = state
+            .relationships
+            .keys()
+            .filter(|object| object.namespace == O::namespace())
+            .map(|object| object.id.as_str())
+            .collect::<HashSet<_>>();
+
+        let mut resources = Vec::new();
+        for object_id in object_ids {
+            let object = ObjectRelation {
+                namespace: O::namespace().to_owned(),
+                id: object_id.to_owned(),
+                relation: affiliation.to_string(),
+            };
+            let has_affiliation = Evaluation {
+                schema,
+                relationships: &state.relationships,
+                subject: &subject,
+                visiting: HashSet::new(),
+            }
+            .has_affiliation(&object)
+            .map_err(|message| Report::new(LookupResourcesError).attach_printable(message))?;
+
+            if has_affiliation {
+                resources.push(
+                    serde_json::from_value(serde_json::Value::String(object.id))
+                        .change_context(LookupResourcesError)?,
+                );
+            }
+        }
+
+        Ok(LookupResourcesResponse {
+            resources,
+            looked_up_at: state.zookie(),
+        })
+    }
+
+    /// Returns the relations ordered by relation and subject.
+    async fn read_relations<O>(
+        &self,
+        object: &O,
+        consistency: Consistency<'_>,
+    ) -> Result<ReadRelationsResponse, Report<ReadRelationsError>>
+    where
+        O: Resource + Sync,
+    {
+        let state = self.read();
+        if let Some(message) = state.validate_consistency(consistency) {
+            return Err(Report::new(ReadRelationsError).attach_printable(message));
+        }
+
+        let object_id = object.id().to_string();
+        let mut relations = state
+            .relationships
+            .iter()
+            .filter(|(object, _)| object.namespace == O::namespace() && object.id == object_id)
+            .flat_map(|(object, subjects)| {
+                subjects.iter().map(|subject| UntypedTuple {
+                    object_namespace: Cow::Owned(object.namespace.clone()),
+                    object_id: Cow::Owned(object.id.clone()),
+                    affiliation: Cow::Owned(object.relation.clone()),
+                    user_namespace: Cow::Owned(subject.namespace.clone()),
+                    user_id: Cow::Owned(subject.id.clone()),
+                    user_set: subject.affiliation.clone().map(Cow::Owned),
+                })
+            })
+            .collect::<Vec<_>>();
+        relations.sort_unstable();
+
+        Ok(ReadRelationsResponse {
+            relations,
+            read_at: state.zookie(),
+        })
+    }
+}
@@ -0,0 +1,359 @@
+//! Parser for the subset of the `SpiceDB` schema language used by the graph.
+//!
+//! A schema consists of definitions, each of which declares relations and permissions:
+//!
+//! ```text
+//! definition graph/entity {
+//!     relation direct_owner: graph/account | graph/account_group#member
+//!     relation direct_viewer: graph/account | graph/account:*
+//!
+//!     permission update = direct_owner
+//!     permission view = direct_viewer + update
+//! }
+//! ```
+//!
+//! Permissions may combine affiliations with `+` (union), `&` (intersection), `-` (exclusion),
+//! parentheses, and arrows (`relation->permission`). The operators are evaluated from left to
+//! right. Caveats are not supported.
+
+use std::collections::HashMap;
+
+use error_stack::{Report, Result};
+
+use crate::backend::ImportSchemaError;
+
+/// A subject type which may be used in a relation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum AllowedSubject {
+    /// A specific object of the namespace, e.g. `graph/account`.
+    Object { namespace: String },
+    /// Every object of the namespace, e.g. `graph/account:*`.
+    Wildcard { namespace: String },
+    /// All subjects having the affiliation to an object of the namespace, e.g.
+    /// `graph/account_group#member`.
+    SubjectSet {
+        namespace: String,
+        affiliation: String,
+    },
+}
+
+/// The expression a permission is computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Expression {
+    /// A relation or permission of the same object.
+    Affiliation(String),
+    /// A permission of the subjects of a relation of the same object.
+    Arrow {
+        relation: String,
+        permission: String,
+    },
+    Union(Box<Self>, Box<Self>),
+    Intersection(Box<Self>, Box<Self>),
+    Exclusion(Box<Self>, Box<Self>),
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Definition {
+    pub(super) relations: HashMap<String, Vec<AllowedSubject>>,
+    pub(super) permissions: HashMap<String, Expression>,
+}
+
+impl Definition {
+    pub(super) fn has_affiliation(&self, affiliation: &str) -> bool {
+        self.relations.contains_key(affiliation) || self.permissions.contains_key(affiliation)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Schema {
+    pub(super) definitions: HashMap<String, Definition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'s> {
+    Identifier(&'s str),
+    Symbol(&'static str),
+}
+
+fn tokenize(schema: &str) -> Result<Vec<Token<'_>>, ImportSchemaError> {
+    // `->` has to be matched before `-`.
+    const SYMBOLS: [&str; 13] = [
+        "->", "-", "{", "}", "(", ")", ":", "|", "#", "*", "=", "+", "&",
+    ];
+
+    let mut tokens = Vec::new();
+    let mut rest = schema;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(tokens);
+        }
+
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, rest)| rest);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment
+                .split_once("*/")
+                .ok_or_else(|| {
+                    Report::new(ImportSchemaError).attach_printable("unterminated comment")
+                })?
+                .1;
+        } else if let Some((symbol, remaining)) = SYMBOLS
+            .into_iter()
+            .find_map(|symbol| Some((symbol, rest.strip_prefix(symbol)?)))
+        {
+            tokens.push(Token::Symbol(symbol));
+            rest = remaining;
+        } else {
+            let length = rest
+                .find(|character: char| {
+                    !(character.is_ascii_alphanumeric() || character == '_' || character == '/')
+                })
+                .unwrap_or(rest.len());
+            if length == 0 {
+                return Err(Report::new(ImportSchemaError).attach_printable(format!(
+                    "unexpected character `{}`",
+                    rest.chars().next().unwrap_or_default()
+                )));
+            }
+            let (identifier, remaining) = rest.split_at(length);
+            tokens.push(Token::Identifier(identifier));
+            rest = remaining;
+        }
+    }
+}
+
+struct Parser<'s> {
+    tokens: Vec<Token<'s>>,
+    position: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn peek(&self) -> Option<&Token<'s>> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token<'s>, ImportSchemaError> {
+        let token = self.tokens.get(self.position).cloned().ok_or_else(|| {
+            Report::new(ImportSchemaError).attach_printable("unexpected end of schema")
+        })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn next_if_symbol(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(found)) if *found == symbol) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &'static str) -> Result<(), ImportSchemaError> {
+        match self.next()? {
+            Token::Symbol(found) if found == symbol => Ok(()),
+            token => Err(Report::new(ImportSchemaError)
+                .attach_printable(format!("expected `{symbol}`, found {token:?}"))),
+        }
+    }
+
+    fn expect_identifier(&mut self) -> Result<&'s str, ImportSchemaError> {
+        match self.next()? {
+            Token::Identifier(identifier) => Ok(identifier),
+            token @ Token::Symbol(_) => Err(Report::new(ImportSchemaError)
+                .attach_printable(format!("expected identifier, found {token:?}"))),
+        }
+    }
+
+    fn parse_schema(&mut self) -> Result<Schema, ImportSchemaError> {
+        let mut schema = Schema::default();
+        while self.peek().is_some() {
+            match self.expect_identifier()? {
+                "definition" => {
+                    let namespace = self.expect_identifier()?;
+                    let definition = self.parse_definition()?;
+                    if schema
+                        .definitions
+                        .insert(namespace.to_owned(), definition)
+                        .is_some()
+                    {
+                        return Err(Report::new(ImportSchemaError)
+                            .attach_printable(format!("`{namespace}` is defined twice")));
+                    }
+                }
+                keyword => {
+                    return Err(Report::new(ImportSchemaError)
+                        .attach_printable(format!("unsupported keyword `{keyword}`")));
+                }
+            }
+        }
+        Ok(schema)
+    }
+
+    fn parse_definition(&mut self) -> Result<Definition, ImportSchemaError> {
+        let mut definition = Definition::default();
+        self.expect_symbol("{")?;
+        while !self.next_if_symbol("}") {
+            let keyword = self.expect_identifier()?;
+            let name = self.expect_identifier()?;
+            if definition.has_affiliation(name) {
+                return Err(Report::new(ImportSchemaError)
+                    .attach_printable(format!("`{name}` is defined twice")));
+            }
+
+            match keyword {
+                "relation" => {
+                    self.expect_symbol(":")?;
+                    let mut subjects = vec![self.parse_allowed_subject()?];
+                    while self.next_if_symbol("|") {
+                        subjects.push(self.parse_allowed_subject()?);
+                    }
+                    definition.relations.insert(name.to_owned(), subjects);
+                }
+                "permission" => {
+                    self.expect_symbol("=")?;
+                    let expression = self.parse_expression()?;
+                    definition.permissions.insert(name.to_owned(), expression);
+                }
+                keyword => {
+                    return Err(Report::new(ImportSchemaError)
+                        .attach_printable(format!("unsupported keyword `{keyword}`")));
+                }
+            }
+        }
+        Ok(definition)
+    }
+
+    fn parse_allowed_subject(&mut self) -> Result<AllowedSubject, ImportSchemaError> {
+        let namespace = self.expect_identifier()?.to_owned();
+        if self.next_if_symbol("#") {
+            Ok(AllowedSubject::SubjectSet {
+                namespace,
+                affiliation: self.expect_identifier()?.to_owned(),
+            })
+        } else if self.next_if_symbol(":") {
+            self.expect_symbol("*")?;
+            Ok(AllowedSubject::Wildcard { namespace })
+        } else {
+            Ok(AllowedSubject::Object { namespace })
+        }
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, ImportSchemaError> {
+        let mut expression = self.parse_term()?;
+        loop {
+            expression = if self.next_if_symbol("+") {
+                Expression::Union(Box::new(expression), Box::new(self.parse_term()?))
+            } else if self.next_if_symbol("&") {
+                Expression::Intersection(Box::new(expression), Box::new(self.parse_term()?))
+            } else if self.next_if_symbol("-") {
+                Expression::Exclusion(Box::new(expression), Box::new(self.parse_term()?))
+            } else {
+                return Ok(expression);
+            };
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Expression, ImportSchemaError> {
+        if self.next_if_symbol("(") {
+            let expression = self.parse_expression()?;
+            self.expect_symbol(")")?;
+            return Ok(expression);
+        }
+
+        let affiliation = self.expect_identifier()?.to_owned();
+        if self.next_if_symbol("->") {
+            Ok(Expression::Arrow {
+                relation: affiliation,
+                permission: self.expect_identifier()?.to_owned(),
+            })
+        } else {
+            Ok(Expression::Affiliation(affiliation))
+        }
+    }
+}
+
+impl Schema {
+    /// Parses and validates a schema.
+    ///
+    /// # Errors
+    ///
+    /// - if the schema is not syntactically valid
+    /// - if the schema references a namespace, relation, or permission which is not defined
+    pub(super) fn parse(schema: &str) -> Result<Self, ImportSchemaError> {
+        let schema = Parser {
+            tokens: tokenize(schema)?,
+            position: 0,
+        }
+        .parse_schema()?;
+        schema.validate()?;
+        Ok(schema)
+    }
+
+    fn validate(&self) -> Result<(), ImportSchemaError> {
+        for (namespace, definition) in &self.definitions {
+            for subject in definition.relations.values().flatten() {
+                let (AllowedSubject::Object {
+                    namespace: subject_namespace,
+                }
+                | AllowedSubject::Wildcard {
+                    namespace: subject_namespace,
+                }
+                | AllowedSubject::SubjectSet {
+                    namespace: subject_namespace,
+                    ..
+                }) = subject;
+                let subject_definition =
+                    self.definitions.get(subject_namespace).ok_or_else(|| {
+                        Report::new(ImportSchemaError).attach_printable(format!(
+                            "`{namespace}` references undefined `{subject_namespace}`"
+                        ))
+                    })?;
+                if let AllowedSubject::SubjectSet { affiliation, .. } = subject {
+                    if !subject_definition.has_affiliation(affiliation) {
+                        return Err(Report::new(ImportSchemaError).attach_printable(format!(
+                            "`{namespace}` references undefined \
+                             `{subject_namespace}#{affiliation}`"
+                        )));
+                    }
+                }
+            }
+
+            for expression in definition.permissions.values() {
+                Self::validate_expression(namespace, definition, expression)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_expression(
+        namespace: &str,
+        definition: &Definition,
+        expression: &Expression,
+    ) -> Result<(), ImportSchemaError> {
+        match expression {
+            Expression::Affiliation(affiliation) => {
+                if !definition.has_affiliation(affiliation) {
+                    return Err(Report::new(ImportSchemaError).attach_printable(format!(
+                        "`{namespace}` references undefined `{affiliation}`"
+                    )));
+                }
+            }
+            Expression::Arrow { relation, .. } => {
+                if !definition.relations.contains_key(relation) {
+                    return Err(Report::new(ImportSchemaError).attach_printable(format!(
+                        "`{namespace}` uses undefined relation `{relation}` in an arrow"
+                    )));
+                }
+            }
+            Expression::Union(lhs, rhs)
+            | Expression::Intersection(lhs, rhs)
+            | Expression::Exclusion(lhs, rhs) => {
+                Self::validate_expression(namespace, definition, lhs)?;
+                Self::validate_expression(namespace, definition, rhs)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,18 @@
-use std::{error::Error, fmt, iter::repeat};
+use std::{borrow::Cow, error::Error, fmt, iter::repeat, mem, pin::pin};
 
 use error_stack::{Report, ResultExt};
+use futures::{stream, Stream, TryStreamExt};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
     backend::{
-        spicedb::model, CheckError, CheckResponse, CreateRelationError, CreateRelationResponse,
-        DeleteRelationError, DeleteRelationResponse, ExportSchemaError, ExportSchemaResponse,
-        ImportSchemaError, ImportSchemaResponse, SpiceDbOpenApi, ZanzibarBackend,
+        spicedb::model, BulkCheckError, BulkCheckResponse, CheckError, CheckResponse,
+        CreateRelationError, CreateRelationResponse, DeleteRelationError, DeleteRelationResponse,
+        ExportSchemaError, ExportSchemaResponse, ImportSchemaError, ImportSchemaResponse,
+        LookupResourcesError, LookupResourcesResponse, ReadRelationsError, ReadRelationsResponse,
+        SpiceDbOpenApi, ZanzibarBackend,
     },
-    zanzibar::{Consistency, Tuple, UntypedTuple, Zookie},
+    zanzibar::{Affiliation, Consistency, Resource, Tuple, UntypedTuple, Zookie},
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,10 +22,14 @@ use crate::{
 )]
 pub struct Empty {}
 
+/// The maximum number of checks sent to `SpiceDB` in a single bulk request.
+const BULK_CHECK_CHUNK_SIZE: usize = 1000;
+
 #[derive(Debug)]
 enum InvocationError {
     Request(reqwest::Error),
     Rpc(model::RpcStatus),
+    Deserialization(serde_json::Error),
 }
 
 impl fmt::Display for InvocationError {
@@ -30,6 +37,7 @@ impl fmt::Display for InvocationError {
         match self {
             Self::Request(error) => fmt::Display::fmt(error, fmt),
             Self::Rpc(status) => write!(fmt, "Error {}: {}", status.code, status.message),
+            Self::Deserialization(error) => fmt::Display::fmt(error, fmt),
         }
     }
 }
@@ -40,6 +48,12 @@ impl From<reqwest::Error> for InvocationError {
     }
 }
 
+impl From<serde_json::Error> for InvocationError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Deserialization(error)
+    }
+}
+
 impl Error for InvocationError {}
 
 impl SpiceDbOpenApi {
@@ -65,6 +79,74 @@ impl SpiceDbOpenApi {
         }
     }
 
+    /// Calls an endpoint which streams its results as newline-delimited JSON objects.
+    ///
+    /// Each line is parsed as soon as it was received, so the response is never buffered as a
+    /// whole.
+    async fn call_stream<R: DeserializeOwned>(
+        &self,
+        path: &'static str,
+        body: &(impl Serialize + Sync),
+    ) -> Result<impl Stream<Item = Result<R, InvocationError>> + Send, InvocationError> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        enum StreamResult<R> {
+            Result(R),
+            Error(model::RpcStatus),
+        }
+
+        fn parse_line<R: DeserializeOwned>(line: &[u8]) -> Result<R, InvocationError> {
+            match serde_json::from_slice(line)? {
+                StreamResult::Result(result) => Ok(result),
+                StreamResult::Error(status) => Err(InvocationError::Rpc(status)),
+            }
+        }
+
+        async fn next_line<R: DeserializeOwned>(
+            mut response: reqwest::Response,
+            mut buffer: Vec<u8>,
+        ) -> Result<Option<(R, (reqwest::Response, Vec<u8>))>, InvocationError> {
+            loop {
+                if let Some(position) = buffer.iter().position(|&byte| byte == b'\n') {
+                    let line = buffer.drain(..=position).collect::<Vec<_>>();
+                    if line.iter().all(u8::is_ascii_whitespace) {
+                        continue;
+                    }
+                    return Ok(Some((parse_line(&line)?, (response, buffer))));
+                }
+
+                match response.chunk().await? {
+                    Some(chunk) => buffer.extend_from_slice(&chunk),
+                    // The last line is not necessarily terminated by a newline
+                    None if buffer.iter().all(u8::is_ascii_whitespace) => return Ok(None),
+                    None => {
+                        let line = mem::take(&mut buffer);
+                        return Ok(Some((parse_line(&line)?, (response, buffer))));
+                    }
+                }
+            }
+        }
+
+        let result = self
+            .client
+            .execute(
+                self.client
+                    .post(format!("{}{}", self.base_path, path))
+                    .json(&body)
+                    .build()?,
+            )
+            .await?;
+
+        if !result.status().is_success() {
+            return Err(InvocationError::Rpc(result.json().await?));
+        }
+
+        Ok(stream::try_unfold(
+            (result, Vec::new()),
+            |(response, buffer)| next_line(response, buffer),
+        ))
+    }
+
     // TODO: Expose batch-version
     //   see https://linear.app/hash/issue/H-642
     async fn modify_relations<T>(
@@ -180,6 +262,23 @@ impl ZanzibarBackend for SpiceDbOpenApi {
             .change_context(CreateRelationError)
     }
 
+    #[expect(
+        clippy::missing_errors_doc,
+        reason = "False positive, documented on trait"
+    )]
+    async fn touch_relations<T>(
+        &mut self,
+        tuples: impl IntoIterator<Item = T, IntoIter: Send> + Send,
+    ) -> Result<CreateRelationResponse, Report<CreateRelationError>>
+    where
+        T: Tuple + Send + Sync,
+    {
+        self.modify_relations(repeat(model::RelationshipUpdateOperation::Touch).zip(tuples))
+            .await
+            .map(|written_at| CreateRelationResponse { written_at })
+            .change_context(CreateRelationError)
+    }
+
     #[expect(
         clippy::missing_errors_doc,
         reason = "False positive, documented on trait"
@@ -218,21 +317,11 @@ impl ZanzibarBackend for SpiceDbOpenApi {
             subject: model::SubjectReference<'t, T>,
         }
 
-        #[derive(Deserialize)]
-        enum Permissionship {
-            #[serde(rename = "PERMISSIONSHIP_NO_PERMISSION")]
-            NoPermission,
-            #[serde(rename = "PERMISSIONSHIP_HAS_PERMISSION")]
-            HasPermission,
-            #[serde(rename = "PERMISSIONSHIP_CONDITIONAL_PERMISSION")]
-            Conditional,
-        }
-
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct RequestResponse {
             checked_at: model::ZedToken,
-            permissionship: Permissionship,
+            permissionship: model::Permissionship,
         }
 
         let request = RequestBody {
@@ -249,17 +338,307 @@ impl ZanzibarBackend for SpiceDbOpenApi {
                 tuple: UntypedTuple::from_tuple(tuple).into_owned(),
             })?;
 
-        let has_permission = match response.permissionship {
-            Permissionship::HasPermission => true,
-            Permissionship::NoPermission => false,
-            Permissionship::Conditional => {
-                unimplemented!("https://linear.app/hash/issue/H-614")
-            }
-        };
-
         Ok(CheckResponse {
             checked_at: response.checked_at.token,
+            has_permission: response.permissionship.into(),
+        })
+    }
+
+    #[expect(
+        clippy::missing_errors_doc,
+        reason = "False positive, documented on trait"
+    )]
+    async fn check_bulk<T>(
+        &self,
+        tuples: &[T],
+        consistency: Consistency<'_>,
+    ) -> Result<BulkCheckResponse, Report<BulkCheckError>>
+    where
+        T: Tuple + Sync,
+    {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase", bound = "")]
+        struct RequestItem<'t, T: Tuple> {
+            resource: model::ObjectReference<'t, T>,
+            permission: model::RelationReference<'t, T>,
+            subject: model::SubjectReference<'t, T>,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase", bound = "")]
+        struct RequestBody<'t, T: Tuple> {
+            consistency: model::Consistency<'t>,
+            items: Vec<RequestItem<'t, T>>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ResponseItem {
+            permissionship: model::Permissionship,
+        }
+
+        /// Either `item` or `error` is set.
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ResponsePair {
+            item: Option<ResponseItem>,
+            error: Option<model::RpcStatus>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RequestResponse {
+            checked_at: model::ZedToken,
+            pairs: Vec<ResponsePair>,
+        }
+
+        let mut has_permission = Vec::with_capacity(tuples.len());
+        let mut checked_at = None;
+        // To return a consistent result, all chunks after the first one are checked at the
+        // snapshot of the first chunk.
+        for chunk in tuples.chunks(BULK_CHECK_CHUNK_SIZE) {
+            let response: RequestResponse = self
+                .call(
+                    "/v1/experimental/permissions/bulkcheckpermission",
+                    &RequestBody {
+                        consistency: match &checked_at {
+                            Some(zookie) => Consistency::AtExactSnapshot(zookie),
+                            None => consistency,
+                        }
+                        .into(),
+                        items: chunk
+                            .iter()
+                            .map(|tuple| RequestItem {
+                                resource: model::ObjectReference(tuple),
+                                permission: model::RelationReference(tuple),
+                                subject: model::SubjectReference(tuple),
+                            })
+                            .collect(),
+                    },
+                )
+                .await
+                .change_context(BulkCheckError)?;
+
+            if response.pairs.len() != chunk.len() {
+                return Err(Report::new(BulkCheckError).attach_printable(format!(
+                    "expected {} results but received {}",
+                    chunk.len(),
+                    response.pairs.len()
+                )));
+            }
+
+            for (pair, tuple) in response.pairs.into_iter().zip(chunk) {
+                let check_error = || CheckError {
+                    tuple: UntypedTuple::from_tuple(tuple).into_owned(),
+                };
+                match pair {
+                    ResponsePair {
+                        item: Some(item), ..
+                    } => has_permission.push(item.permissionship.into()),
+                    ResponsePair {
+                        error: Some(status),
+                        ..
+                    } => {
+                        return Err(Report::new(InvocationError::Rpc(status))
+                            .change_context(check_error())
+                            .change_context(BulkCheckError));
+                    }
+                    ResponsePair { .. } => {
+                        return Err(Report::new(check_error())
+                            .attach_printable("neither a result nor an error was returned")
+                            .change_context(BulkCheckError));
+                    }
+                }
+            }
+            checked_at = Some(response.checked_at.token);
+        }
+
+        Ok(BulkCheckResponse {
             has_permission,
+            checked_at: checked_at.unwrap_or_else(Zookie::empty),
         })
     }
+
+    #[expect(
+        clippy::missing_errors_doc,
+        reason = "False positive, documented on trait"
+    )]
+    async fn lookup_resources<O, A, U>(
+        &self,
+        subject: &U,
+        affiliation: &A,
+        consistency: Consistency<'_>,
+    ) -> Result<LookupResourcesResponse<O::Id>, Report<LookupResourcesError>>
+    where
+        O: Resource<Id: Sized + DeserializeOwned + Send>,
+        A: Affiliation<O> + Sync,
+        U: Resource + Sync,
+    {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase", bound = "")]
+        struct ObjectReference<'a, U: Resource> {
+            object_type: &'static str,
+            object_id: &'a U::Id,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase", bound = "")]
+        struct SubjectReference<'a, U: Resource> {
+            object: ObjectReference<'a, U>,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase", bound = "")]
+        struct RequestBody<'a, A: Serialize, U: Resource> {
+            consistency: model::Consistency<'a>,
+            resource_object_type: &'static str,
+            permission: &'a A,
+            subject: SubjectReference<'a, U>,
+        }
+
+        #[derive(Deserialize)]
+        enum LookupPermissionship {
+            #[serde(rename = "LOOKUP_PERMISSIONSHIP_HAS_PERMISSION")]
+            HasPermission,
+            #[serde(rename = "LOOKUP_PERMISSIONSHIP_CONDITIONAL_PERMISSION")]
+            Conditional,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase", bound = "I: DeserializeOwned")]
+        struct RequestResponse<I> {
+            looked_up_at: model::ZedToken,
+            resource_object_id: I,
+            permissionship: LookupPermissionship,
+        }
+
+        // The returned stream borrows the request body, so it has to outlive the stream
+        let body = RequestBody {
+            consistency: consistency.into(),
+            resource_object_type: O::namespace(),
+            permission: affiliation,
+            subject: SubjectReference::<U> {
+                object: ObjectReference {
+                    object_type: U::namespace(),
+                    object_id: subject.id(),
+                },
+            },
+        };
+        let responses = self
+            .call_stream::<RequestResponse<O::Id>>("/v1/permissions/resources", &body)
+            .await
+            .change_context(LookupResourcesError)?;
+
+        let mut resources = Vec::new();
+        // If no resources were found, no token is returned
+        let mut looked_up_at = Zookie::empty();
+        let mut responses = pin!(responses);
+        while let Some(response) = responses
+            .try_next()
+            .await
+            .change_context(LookupResourcesError)?
+        {
+            match response.permissionship {
+                LookupPermissionship::HasPermission => resources.push(response.resource_object_id),
+                LookupPermissionship::Conditional => {
+                    unimplemented!("https://linear.app/hash/issue/H-614")
+                }
+            }
+            looked_up_at = response.looked_up_at.token;
+        }
+
+        Ok(LookupResourcesResponse {
+            resources,
+            looked_up_at,
+        })
+    }
+
+    async fn read_relations<O>(
+        &self,
+        object: &O,
+        consistency: Consistency<'_>,
+    ) -> Result<ReadRelationsResponse, Report<ReadRelationsError>>
+    where
+        O: Resource + Sync,
+    {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase", bound = "")]
+        struct RelationshipFilter<'a, O: Resource> {
+            resource_type: &'static str,
+            optional_resource_id: &'a O::Id,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase", bound = "")]
+        struct RequestBody<'a, O: Resource> {
+            consistency: model::Consistency<'a>,
+            relationship_filter: RelationshipFilter<'a, O>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ObjectReference {
+            object_type: String,
+            object_id: String,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SubjectReference {
+            object: ObjectReference,
+            #[serde(default)]
+            optional_relation: String,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Relationship {
+            resource: ObjectReference,
+            relation: String,
+            subject: SubjectReference,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RequestResponse {
+            read_at: model::ZedToken,
+            relationship: Relationship,
+        }
+
+        let body = RequestBody::<O> {
+            consistency: consistency.into(),
+            relationship_filter: RelationshipFilter {
+                resource_type: O::namespace(),
+                optional_resource_id: object.id(),
+            },
+        };
+        let responses = self
+            .call_stream::<RequestResponse>("/v1/relationships/read", &body)
+            .await
+            .change_context(ReadRelationsError)?;
+
+        let mut relations = Vec::new();
+        // If no relations were found, no token is returned
+        let mut read_at = Zookie::empty();
+        let mut responses = pin!(responses);
+        while let Some(response) = responses
+            .try_next()
+            .await
+            .change_context(ReadRelationsError)?
+        {
+            let relationship = response.relationship;
+            let user_set = relationship.subject.optional_relation;
+            relations.push(UntypedTuple {
+                object_namespace: Cow::Owned(relationship.resource.object_type),
+                object_id: Cow::Owned(relationship.resource.object_id),
+                affiliation: Cow::Owned(relationship.relation),
+                user_namespace: Cow::Owned(relationship.subject.object.object_type),
+                user_id: Cow::Owned(relationship.subject.object.object_id),
+                user_set: (!user_set.is_empty()).then_some(Cow::Owned(user_set)),
+            });
+            read_at = response.read_at.token;
+        }
+
+        Ok(ReadRelationsResponse { relations, read_at })
+    }
 }
@@ -137,6 +137,29 @@ impl From<ZedToken> for zanzibar::Zookie<'static> {
     }
 }
 
+/// The result of a permission check.
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub enum Permissionship {
+    #[serde(rename = "PERMISSIONSHIP_NO_PERMISSION")]
+    NoPermission,
+    #[serde(rename = "PERMISSIONSHIP_HAS_PERMISSION")]
+    HasPermission,
+    #[serde(rename = "PERMISSIONSHIP_CONDITIONAL_PERMISSION")]
+    Conditional,
+}
+
+impl From<Permissionship> for bool {
+    fn from(permissionship: Permissionship) -> Self {
+        match permissionship {
+            Permissionship::HasPermission => true,
+            Permissionship::NoPermission => false,
+            Permissionship::Conditional => {
+                unimplemented!("https://linear.app/hash/issue/H-614")
+            }
+        }
+    }
+}
+
 /// Used for mutating a single relationship within the service.
 #[derive(Debug, Copy, Clone, Serialize)]
 pub enum RelationshipUpdateOperation {
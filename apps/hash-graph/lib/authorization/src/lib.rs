@@ -14,16 +14,22 @@ pub use self::api::{AuthorizationApi, AuthorizationApiPool, VisibilityScope};
 
 mod api;
 
+use std::collections::HashMap;
+
 use error_stack::Result;
 use graph_types::{
     account::{AccountGroupId, AccountId},
-    knowledge::entity::EntityId,
+    knowledge::entity::{EntityId, EntityUuid},
+    ontology::{DataTypeId, EntityTypeId, PropertyTypeId},
     web::WebId,
 };
 
 use crate::{
-    backend::{CheckError, CheckResponse, ModifyRelationError},
-    schema::OwnerId,
+    backend::{
+        BulkCheckError, CheckError, CheckResponse, LookupResourcesError, ModifyRelationError,
+        ReadRelationsError,
+    },
+    schema::{DataTypeRelation, EntityRelation, EntityTypeRelation, OwnerId, PropertyTypeRelation},
     zanzibar::{Consistency, Zookie},
 };
 
@@ -119,6 +125,46 @@ impl AuthorizationApi for NoAuthorization {
         Ok(Zookie::empty())
     }
 
+    async fn add_entity_editor(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity: EntityId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_entity_editor(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity: EntityId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn add_entity_viewer(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity: EntityId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_entity_viewer(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity: EntityId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn get_entity_relations(
+        &self,
+        _entity: EntityId,
+        _consistency: Consistency<'_>,
+    ) -> Result<(Vec<(EntityRelation, VisibilityScope)>, Zookie<'static>), ReadRelationsError> {
+        Ok((Vec::new(), Zookie::empty()))
+    }
+
     async fn can_create_entity(
         &self,
         _actor: AccountId,
@@ -143,6 +189,18 @@ impl AuthorizationApi for NoAuthorization {
         })
     }
 
+    async fn can_delete_entity(
+        &self,
+        _actor: AccountId,
+        _entity: EntityId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
     async fn can_view_entity(
         &self,
         _actor: AccountId,
@@ -154,6 +212,461 @@ impl AuthorizationApi for NoAuthorization {
             checked_at: Zookie::empty(),
         })
     }
+
+    async fn can_share_entity(
+        &self,
+        _actor: AccountId,
+        _entity: EntityId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn add_data_type_owner(
+        &mut self,
+        _scope: VisibilityScope,
+        _data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_data_type_owner(
+        &mut self,
+        _scope: VisibilityScope,
+        _data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn add_data_type_editor(
+        &mut self,
+        _scope: VisibilityScope,
+        _data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_data_type_editor(
+        &mut self,
+        _scope: VisibilityScope,
+        _data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn add_data_type_viewer(
+        &mut self,
+        _scope: VisibilityScope,
+        _data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_data_type_viewer(
+        &mut self,
+        _scope: VisibilityScope,
+        _data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn touch_data_type_relations(
+        &mut self,
+        _relations: impl IntoIterator<
+            Item = (DataTypeId, DataTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn can_create_data_type(
+        &self,
+        _actor: AccountId,
+        _web: impl Into<WebId> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_update_data_type(
+        &self,
+        _actor: AccountId,
+        _data_type: DataTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_archive_data_type(
+        &self,
+        _actor: AccountId,
+        _data_type: DataTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_share_data_type(
+        &self,
+        _actor: AccountId,
+        _data_type: DataTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_view_data_type(
+        &self,
+        _actor: AccountId,
+        _data_type: DataTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn add_property_type_owner(
+        &mut self,
+        _scope: VisibilityScope,
+        _property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_property_type_owner(
+        &mut self,
+        _scope: VisibilityScope,
+        _property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn add_property_type_editor(
+        &mut self,
+        _scope: VisibilityScope,
+        _property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_property_type_editor(
+        &mut self,
+        _scope: VisibilityScope,
+        _property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn add_property_type_viewer(
+        &mut self,
+        _scope: VisibilityScope,
+        _property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_property_type_viewer(
+        &mut self,
+        _scope: VisibilityScope,
+        _property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn touch_property_type_relations(
+        &mut self,
+        _relations: impl IntoIterator<
+            Item = (PropertyTypeId, PropertyTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn can_create_property_type(
+        &self,
+        _actor: AccountId,
+        _web: impl Into<WebId> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_update_property_type(
+        &self,
+        _actor: AccountId,
+        _property_type: PropertyTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_archive_property_type(
+        &self,
+        _actor: AccountId,
+        _property_type: PropertyTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_share_property_type(
+        &self,
+        _actor: AccountId,
+        _property_type: PropertyTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_view_property_type(
+        &self,
+        _actor: AccountId,
+        _property_type: PropertyTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn add_entity_type_owner(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_entity_type_owner(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn add_entity_type_editor(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_entity_type_editor(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn add_entity_type_viewer(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn remove_entity_type_viewer(
+        &mut self,
+        _scope: VisibilityScope,
+        _entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn touch_entity_type_relations(
+        &mut self,
+        _relations: impl IntoIterator<
+            Item = (EntityTypeId, EntityTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        Ok(Zookie::empty())
+    }
+
+    async fn can_create_entity_type(
+        &self,
+        _actor: AccountId,
+        _web: impl Into<WebId> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_update_entity_type(
+        &self,
+        _actor: AccountId,
+        _entity_type: EntityTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_archive_entity_type(
+        &self,
+        _actor: AccountId,
+        _entity_type: EntityTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_share_entity_type(
+        &self,
+        _actor: AccountId,
+        _entity_type: EntityTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_view_entity_type(
+        &self,
+        _actor: AccountId,
+        _entity_type: EntityTypeId,
+        _consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        Ok(CheckResponse {
+            has_permission: true,
+            checked_at: Zookie::empty(),
+        })
+    }
+
+    async fn can_view_entities(
+        &self,
+        _actor: AccountId,
+        entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError> {
+        Ok((
+            entities.into_iter().map(|entity| (entity, true)).collect(),
+            Zookie::empty(),
+        ))
+    }
+
+    async fn can_update_entities(
+        &self,
+        _actor: AccountId,
+        entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError> {
+        Ok((
+            entities.into_iter().map(|entity| (entity, true)).collect(),
+            Zookie::empty(),
+        ))
+    }
+
+    async fn can_delete_entities(
+        &self,
+        _actor: AccountId,
+        entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError> {
+        Ok((
+            entities.into_iter().map(|entity| (entity, true)).collect(),
+            Zookie::empty(),
+        ))
+    }
+
+    async fn can_view_data_types(
+        &self,
+        _actor: AccountId,
+        data_types: impl IntoIterator<Item = DataTypeId, IntoIter: Send> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<(HashMap<DataTypeId, bool>, Zookie<'static>), BulkCheckError> {
+        Ok((
+            data_types
+                .into_iter()
+                .map(|data_type| (data_type, true))
+                .collect(),
+            Zookie::empty(),
+        ))
+    }
+
+    async fn can_view_property_types(
+        &self,
+        _actor: AccountId,
+        property_types: impl IntoIterator<Item = PropertyTypeId, IntoIter: Send> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<(HashMap<PropertyTypeId, bool>, Zookie<'static>), BulkCheckError> {
+        Ok((
+            property_types
+                .into_iter()
+                .map(|property_type| (property_type, true))
+                .collect(),
+            Zookie::empty(),
+        ))
+    }
+
+    async fn can_view_entity_types(
+        &self,
+        _actor: AccountId,
+        entity_types: impl IntoIterator<Item = EntityTypeId, IntoIter: Send> + Send,
+        _consistency: Consistency<'_>,
+    ) -> Result<(HashMap<EntityTypeId, bool>, Zookie<'static>), BulkCheckError> {
+        Ok((
+            entity_types
+                .into_iter()
+                .map(|entity_type| (entity_type, true))
+                .collect(),
+            Zookie::empty(),
+        ))
+    }
+
+    async fn lookup_viewable_entities(
+        &self,
+        _actor: AccountId,
+        _consistency: Consistency<'_>,
+    ) -> Result<(Option<Vec<EntityUuid>>, Zookie<'static>), LookupResourcesError> {
+        Ok((None, Zookie::empty()))
+    }
 }
 
 impl<A> AuthorizationApiPool for A
@@ -1,10 +1,17 @@
 mod account;
 mod account_group;
+mod data_type;
 mod entity;
+mod entity_type;
+mod property_type;
 mod web;
 
 pub use self::{
+    account::PublicAccess,
     account_group::{AccountGroupPermission, AccountGroupRelation},
+    data_type::{DataTypePermission, DataTypeRelation},
     entity::{EntityPermission, EntityRelation},
+    entity_type::{EntityTypePermission, EntityTypeRelation},
+    property_type::{PropertyTypePermission, PropertyTypeRelation},
     web::{OwnerId, WebPermission, WebRelation},
 };
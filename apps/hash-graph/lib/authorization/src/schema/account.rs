@@ -13,3 +13,19 @@ impl Resource for AccountId {
         self
     }
 }
+
+/// All accounts, used to grant an affiliation publicly.
+#[derive(Debug, Copy, Clone)]
+pub struct PublicAccess;
+
+impl Resource for PublicAccess {
+    type Id = str;
+
+    fn namespace() -> &'static str {
+        AccountId::namespace()
+    }
+
+    fn id(&self) -> &Self::Id {
+        "*"
+    }
+}
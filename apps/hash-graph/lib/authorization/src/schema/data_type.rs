@@ -0,0 +1,53 @@
+use std::fmt;
+
+use graph_types::ontology::DataTypeId;
+use serde::{Deserialize, Serialize};
+
+use crate::zanzibar::{Affiliation, Permission, Relation, Resource};
+
+impl Resource for DataTypeId {
+    type Id = Self;
+
+    fn namespace() -> &'static str {
+        "graph/data_type"
+    }
+
+    fn id(&self) -> &Self::Id {
+        self
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataTypeRelation {
+    DirectOwner,
+    DirectEditor,
+    DirectViewer,
+}
+
+impl fmt::Display for DataTypeRelation {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.serialize(fmt)
+    }
+}
+
+impl Affiliation<DataTypeId> for DataTypeRelation {}
+impl Relation<DataTypeId> for DataTypeRelation {}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataTypePermission {
+    Archive,
+    Share,
+    Update,
+    View,
+}
+
+impl fmt::Display for DataTypePermission {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.serialize(fmt)
+    }
+}
+
+impl Affiliation<DataTypeId> for DataTypePermission {}
+impl Permission<DataTypeId> for DataTypePermission {}
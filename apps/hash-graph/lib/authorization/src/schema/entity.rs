@@ -17,10 +17,11 @@ impl Resource for EntityId {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EntityRelation {
     DirectOwner,
+    DirectEditor,
     DirectViewer,
 }
 
@@ -33,10 +34,12 @@ impl fmt::Display for EntityRelation {
 impl Affiliation<EntityId> for EntityRelation {}
 impl Relation<EntityId> for EntityRelation {}
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EntityPermission {
-    Update,
+    Edit,
+    Delete,
+    Share,
     View,
 }
 
@@ -0,0 +1,53 @@
+use std::fmt;
+
+use graph_types::ontology::EntityTypeId;
+use serde::{Deserialize, Serialize};
+
+use crate::zanzibar::{Affiliation, Permission, Relation, Resource};
+
+impl Resource for EntityTypeId {
+    type Id = Self;
+
+    fn namespace() -> &'static str {
+        "graph/entity_type"
+    }
+
+    fn id(&self) -> &Self::Id {
+        self
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityTypeRelation {
+    DirectOwner,
+    DirectEditor,
+    DirectViewer,
+}
+
+impl fmt::Display for EntityTypeRelation {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.serialize(fmt)
+    }
+}
+
+impl Affiliation<EntityTypeId> for EntityTypeRelation {}
+impl Relation<EntityTypeId> for EntityTypeRelation {}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityTypePermission {
+    Archive,
+    Share,
+    Update,
+    View,
+}
+
+impl fmt::Display for EntityTypePermission {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.serialize(fmt)
+    }
+}
+
+impl Affiliation<EntityTypeId> for EntityTypePermission {}
+impl Permission<EntityTypeId> for EntityTypePermission {}
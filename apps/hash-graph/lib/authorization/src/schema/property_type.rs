@@ -0,0 +1,53 @@
+use std::fmt;
+
+use graph_types::ontology::PropertyTypeId;
+use serde::{Deserialize, Serialize};
+
+use crate::zanzibar::{Affiliation, Permission, Relation, Resource};
+
+impl Resource for PropertyTypeId {
+    type Id = Self;
+
+    fn namespace() -> &'static str {
+        "graph/property_type"
+    }
+
+    fn id(&self) -> &Self::Id {
+        self
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyTypeRelation {
+    DirectOwner,
+    DirectEditor,
+    DirectViewer,
+}
+
+impl fmt::Display for PropertyTypeRelation {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.serialize(fmt)
+    }
+}
+
+impl Affiliation<PropertyTypeId> for PropertyTypeRelation {}
+impl Relation<PropertyTypeId> for PropertyTypeRelation {}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyTypePermission {
+    Archive,
+    Share,
+    Update,
+    View,
+}
+
+impl fmt::Display for PropertyTypePermission {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.serialize(fmt)
+    }
+}
+
+impl Affiliation<PropertyTypeId> for PropertyTypePermission {}
+impl Permission<PropertyTypeId> for PropertyTypePermission {}
@@ -58,6 +58,9 @@ impl Relation<WebId> for WebRelation {}
 #[serde(rename_all = "snake_case")]
 pub enum WebPermission {
     CreateEntity,
+    CreateDataType,
+    CreatePropertyType,
+    CreateEntityType,
 }
 
 impl fmt::Display for WebPermission {
@@ -1,20 +1,71 @@
-use error_stack::{Result, ResultExt};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use error_stack::{Report, Result, ResultExt};
 use graph_types::{
     account::{AccountGroupId, AccountId},
-    knowledge::entity::EntityId,
+    knowledge::entity::{EntityId, EntityUuid},
+    ontology::{DataTypeId, EntityTypeId, PropertyTypeId},
     web::WebId,
 };
+use serde::de::DeserializeOwned;
 
 use crate::{
-    backend::{CheckError, CheckResponse, ModifyRelationError, ZanzibarBackend},
+    backend::{
+        BulkCheckError, BulkCheckResponse, CheckError, CheckResponse, LookupResourcesError,
+        LookupResourcesResponse, ModifyRelationError, ReadRelationsError, ReadRelationsResponse,
+        ZanzibarBackend,
+    },
     schema::{
-        AccountGroupPermission, AccountGroupRelation, EntityPermission, EntityRelation, OwnerId,
-        WebPermission, WebRelation,
+        AccountGroupPermission, AccountGroupRelation, DataTypePermission, DataTypeRelation,
+        EntityPermission, EntityRelation, EntityTypePermission, EntityTypeRelation, OwnerId,
+        PropertyTypePermission, PropertyTypeRelation, PublicAccess, WebPermission, WebRelation,
     },
-    zanzibar::{Consistency, Zookie},
+    zanzibar::{Consistency, Permission, Relation, Resource, UntypedTuple, Zookie},
     AuthorizationApi, VisibilityScope,
 };
 
+/// Converts a relation read from the backend into the entity relation and the scope it was
+/// granted to.
+fn parse_entity_relation(
+    tuple: &UntypedTuple<'_>,
+) -> Result<(EntityRelation, VisibilityScope), ReadRelationsError> {
+    fn parse<T: DeserializeOwned>(value: &str) -> Result<T, ReadRelationsError> {
+        serde_json::from_value(serde_json::Value::String(value.to_owned()))
+            .change_context(ReadRelationsError)
+    }
+
+    let relation = parse(&tuple.affiliation)?;
+    let scope = match (
+        tuple.user_namespace.as_ref(),
+        tuple.user_id.as_ref(),
+        tuple.user_set.as_deref(),
+    ) {
+        (namespace, "*", None) if namespace == PublicAccess::namespace() => VisibilityScope::Public,
+        (namespace, id, None) if namespace == AccountId::namespace() => {
+            VisibilityScope::Account(parse(id)?)
+        }
+        (namespace, id, Some(user_set))
+            if namespace == AccountGroupId::namespace()
+                && user_set == AccountGroupPermission::Member.to_string() =>
+        {
+            VisibilityScope::AccountGroup(parse(id)?)
+        }
+        _ => {
+            return Err(Report::new(ReadRelationsError)
+                .attach_printable("unexpected subject of entity relation")
+                .attach_printable(tuple.clone().into_owned()));
+        }
+    };
+
+    Ok((relation, scope))
+}
+
+/// The maximum number of relations written in a single request to the backend.
+const MAX_RELATIONS_PER_WRITE: usize = 1_000;
+
 #[derive(Debug, Clone)]
 pub struct ZanzibarClient<B> {
     backend: B,
@@ -26,6 +77,194 @@ impl<B> ZanzibarClient<B> {
     }
 }
 
+impl<B> ZanzibarClient<B>
+where
+    B: ZanzibarBackend + Send + Sync,
+{
+    /// Checks `permission` for all `resources` in a single bulk request.
+    async fn check_resources<R, P>(
+        &self,
+        actor: AccountId,
+        permission: P,
+        resources: impl IntoIterator<Item = R, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<(HashMap<R, bool>, Zookie<'static>), BulkCheckError>
+    where
+        R: Resource + Copy + Eq + Hash + Send + Sync,
+        P: Permission<R> + Copy + Send + Sync,
+    {
+        let resources = resources
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let tuples = resources
+            .iter()
+            .map(|&resource| (resource, permission, actor))
+            .collect::<Vec<_>>();
+
+        let BulkCheckResponse {
+            has_permission,
+            checked_at,
+        } = self.backend.check_bulk(&tuples, consistency).await?;
+
+        Ok((
+            resources.into_iter().zip(has_permission).collect(),
+            checked_at,
+        ))
+    }
+
+    async fn create_scoped_relation<R, A>(
+        &mut self,
+        resource: R,
+        relation: A,
+        scope: VisibilityScope,
+    ) -> Result<Zookie<'static>, ModifyRelationError>
+    where
+        R: Resource + Send + Sync,
+        A: Relation<R> + Send + Sync,
+    {
+        Ok(match scope {
+            VisibilityScope::Public => {
+                self.backend
+                    .create_relations([(resource, relation, PublicAccess)])
+                    .await
+            }
+            VisibilityScope::Account(account) => {
+                self.backend
+                    .create_relations([(resource, relation, account)])
+                    .await
+            }
+            VisibilityScope::AccountGroup(account_group) => {
+                self.backend
+                    .create_relations([(
+                        resource,
+                        relation,
+                        account_group,
+                        AccountGroupPermission::Member,
+                    )])
+                    .await
+            }
+        }
+        .change_context(ModifyRelationError)?
+        .written_at)
+    }
+
+    /// Like [`Self::create_scoped_relation`], but keeps the relation if it already exists.
+    async fn touch_scoped_relation<R, A>(
+        &mut self,
+        resource: R,
+        relation: A,
+        scope: VisibilityScope,
+    ) -> Result<Zookie<'static>, ModifyRelationError>
+    where
+        R: Resource + Send + Sync,
+        A: Relation<R> + Send + Sync,
+    {
+        Ok(match scope {
+            VisibilityScope::Public => {
+                self.backend
+                    .touch_relations([(resource, relation, PublicAccess)])
+                    .await
+            }
+            VisibilityScope::Account(account) => {
+                self.backend
+                    .touch_relations([(resource, relation, account)])
+                    .await
+            }
+            VisibilityScope::AccountGroup(account_group) => {
+                self.backend
+                    .touch_relations([(
+                        resource,
+                        relation,
+                        account_group,
+                        AccountGroupPermission::Member,
+                    )])
+                    .await
+            }
+        }
+        .change_context(ModifyRelationError)?
+        .written_at)
+    }
+
+    /// Like [`Self::touch_scoped_relation`], but writes the relations of many resources at once.
+    async fn touch_scoped_relations<R, A>(
+        &mut self,
+        relations: impl IntoIterator<Item = (R, A, VisibilityScope), IntoIter: Send> + Send,
+    ) -> Result<Zookie<'static>, ModifyRelationError>
+    where
+        R: Resource + Send + Sync,
+        A: Relation<R> + Send + Sync,
+    {
+        let tuples = relations
+            .into_iter()
+            .map(|(resource, relation, scope)| match scope {
+                VisibilityScope::Public => {
+                    UntypedTuple::from_tuple(&(resource, relation, PublicAccess)).into_owned()
+                }
+                VisibilityScope::Account(account) => {
+                    UntypedTuple::from_tuple(&(resource, relation, account)).into_owned()
+                }
+                VisibilityScope::AccountGroup(account_group) => UntypedTuple::from_tuple(&(
+                    resource,
+                    relation,
+                    account_group,
+                    AccountGroupPermission::Member,
+                ))
+                .into_owned(),
+            })
+            .collect::<Vec<_>>();
+
+        // The backend limits the number of relations written in a single request.
+        let mut written_at = Zookie::empty();
+        for chunk in tuples.chunks(MAX_RELATIONS_PER_WRITE) {
+            written_at = self
+                .backend
+                .touch_relations(chunk.iter().cloned())
+                .await
+                .change_context(ModifyRelationError)?
+                .written_at;
+        }
+        Ok(written_at)
+    }
+
+    async fn delete_scoped_relation<R, A>(
+        &mut self,
+        resource: R,
+        relation: A,
+        scope: VisibilityScope,
+    ) -> Result<Zookie<'static>, ModifyRelationError>
+    where
+        R: Resource + Send + Sync,
+        A: Relation<R> + Send + Sync,
+    {
+        Ok(match scope {
+            VisibilityScope::Public => {
+                self.backend
+                    .delete_relations([(resource, relation, PublicAccess)])
+                    .await
+            }
+            VisibilityScope::Account(account) => {
+                self.backend
+                    .delete_relations([(resource, relation, account)])
+                    .await
+            }
+            VisibilityScope::AccountGroup(account_group) => {
+                self.backend
+                    .delete_relations([(
+                        resource,
+                        relation,
+                        account_group,
+                        AccountGroupPermission::Member,
+                    )])
+                    .await
+            }
+        }
+        .change_context(ModifyRelationError)?
+        .deleted_at)
+    }
+}
+
 impl<B> AuthorizationApi for ZanzibarClient<B>
 where
     B: ZanzibarBackend + Send + Sync,
@@ -216,6 +455,59 @@ where
         .deleted_at)
     }
 
+    async fn add_entity_editor(
+        &mut self,
+        scope: VisibilityScope,
+        entity: EntityId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.create_scoped_relation(entity, EntityRelation::DirectEditor, scope)
+            .await
+    }
+
+    async fn remove_entity_editor(
+        &mut self,
+        scope: VisibilityScope,
+        entity: EntityId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(entity, EntityRelation::DirectEditor, scope)
+            .await
+    }
+
+    async fn add_entity_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        entity: EntityId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.create_scoped_relation(entity, EntityRelation::DirectViewer, scope)
+            .await
+    }
+
+    async fn remove_entity_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        entity: EntityId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(entity, EntityRelation::DirectViewer, scope)
+            .await
+    }
+
+    async fn get_entity_relations(
+        &self,
+        entity: EntityId,
+        consistency: Consistency<'_>,
+    ) -> Result<(Vec<(EntityRelation, VisibilityScope)>, Zookie<'static>), ReadRelationsError> {
+        let ReadRelationsResponse { relations, read_at } =
+            self.backend.read_relations(&entity, consistency).await?;
+
+        Ok((
+            relations
+                .iter()
+                .map(parse_entity_relation)
+                .collect::<Result<_, _>>()?,
+            read_at,
+        ))
+    }
+
     async fn can_create_entity(
         &self,
         actor: AccountId,
@@ -237,7 +529,18 @@ where
         consistency: Consistency<'_>,
     ) -> Result<CheckResponse, CheckError> {
         self.backend
-            .check(&(entity, EntityPermission::Update, actor), consistency)
+            .check(&(entity, EntityPermission::Edit, actor), consistency)
+            .await
+    }
+
+    async fn can_delete_entity(
+        &self,
+        actor: AccountId,
+        entity: EntityId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(&(entity, EntityPermission::Delete, actor), consistency)
             .await
     }
 
@@ -251,4 +554,489 @@ where
             .check(&(entity, EntityPermission::View, actor), consistency)
             .await
     }
+
+    async fn can_share_entity(
+        &self,
+        actor: AccountId,
+        entity: EntityId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(&(entity, EntityPermission::Share, actor), consistency)
+            .await
+    }
+
+    async fn add_data_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.touch_scoped_relation(data_type, DataTypeRelation::DirectOwner, scope)
+            .await
+    }
+
+    async fn remove_data_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(data_type, DataTypeRelation::DirectOwner, scope)
+            .await
+    }
+
+    async fn add_data_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.create_scoped_relation(data_type, DataTypeRelation::DirectEditor, scope)
+            .await
+    }
+
+    async fn remove_data_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(data_type, DataTypeRelation::DirectEditor, scope)
+            .await
+    }
+
+    async fn add_data_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.create_scoped_relation(data_type, DataTypeRelation::DirectViewer, scope)
+            .await
+    }
+
+    async fn remove_data_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        data_type: DataTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(data_type, DataTypeRelation::DirectViewer, scope)
+            .await
+    }
+
+    async fn touch_data_type_relations(
+        &mut self,
+        relations: impl IntoIterator<
+            Item = (DataTypeId, DataTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.touch_scoped_relations(relations).await
+    }
+
+    async fn can_create_data_type(
+        &self,
+        actor: AccountId,
+        web: impl Into<WebId> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(web.into(), WebPermission::CreateDataType, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_update_data_type(
+        &self,
+        actor: AccountId,
+        data_type: DataTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(&(data_type, DataTypePermission::Update, actor), consistency)
+            .await
+    }
+
+    async fn can_archive_data_type(
+        &self,
+        actor: AccountId,
+        data_type: DataTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(data_type, DataTypePermission::Archive, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_share_data_type(
+        &self,
+        actor: AccountId,
+        data_type: DataTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(&(data_type, DataTypePermission::Share, actor), consistency)
+            .await
+    }
+
+    async fn can_view_data_type(
+        &self,
+        actor: AccountId,
+        data_type: DataTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(&(data_type, DataTypePermission::View, actor), consistency)
+            .await
+    }
+
+    async fn add_property_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.touch_scoped_relation(property_type, PropertyTypeRelation::DirectOwner, scope)
+            .await
+    }
+
+    async fn remove_property_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(property_type, PropertyTypeRelation::DirectOwner, scope)
+            .await
+    }
+
+    async fn add_property_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.create_scoped_relation(property_type, PropertyTypeRelation::DirectEditor, scope)
+            .await
+    }
+
+    async fn remove_property_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(property_type, PropertyTypeRelation::DirectEditor, scope)
+            .await
+    }
+
+    async fn add_property_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.create_scoped_relation(property_type, PropertyTypeRelation::DirectViewer, scope)
+            .await
+    }
+
+    async fn remove_property_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        property_type: PropertyTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(property_type, PropertyTypeRelation::DirectViewer, scope)
+            .await
+    }
+
+    async fn touch_property_type_relations(
+        &mut self,
+        relations: impl IntoIterator<
+            Item = (PropertyTypeId, PropertyTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.touch_scoped_relations(relations).await
+    }
+
+    async fn can_create_property_type(
+        &self,
+        actor: AccountId,
+        web: impl Into<WebId> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(web.into(), WebPermission::CreatePropertyType, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_update_property_type(
+        &self,
+        actor: AccountId,
+        property_type: PropertyTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(property_type, PropertyTypePermission::Update, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_archive_property_type(
+        &self,
+        actor: AccountId,
+        property_type: PropertyTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(property_type, PropertyTypePermission::Archive, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_share_property_type(
+        &self,
+        actor: AccountId,
+        property_type: PropertyTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(property_type, PropertyTypePermission::Share, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_view_property_type(
+        &self,
+        actor: AccountId,
+        property_type: PropertyTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(property_type, PropertyTypePermission::View, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn add_entity_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.touch_scoped_relation(entity_type, EntityTypeRelation::DirectOwner, scope)
+            .await
+    }
+
+    async fn remove_entity_type_owner(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(entity_type, EntityTypeRelation::DirectOwner, scope)
+            .await
+    }
+
+    async fn add_entity_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.create_scoped_relation(entity_type, EntityTypeRelation::DirectEditor, scope)
+            .await
+    }
+
+    async fn remove_entity_type_editor(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(entity_type, EntityTypeRelation::DirectEditor, scope)
+            .await
+    }
+
+    async fn add_entity_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.create_scoped_relation(entity_type, EntityTypeRelation::DirectViewer, scope)
+            .await
+    }
+
+    async fn remove_entity_type_viewer(
+        &mut self,
+        scope: VisibilityScope,
+        entity_type: EntityTypeId,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.delete_scoped_relation(entity_type, EntityTypeRelation::DirectViewer, scope)
+            .await
+    }
+
+    async fn touch_entity_type_relations(
+        &mut self,
+        relations: impl IntoIterator<
+            Item = (EntityTypeId, EntityTypeRelation, VisibilityScope),
+            IntoIter: Send,
+        > + Send,
+    ) -> Result<Zookie<'static>, ModifyRelationError> {
+        self.touch_scoped_relations(relations).await
+    }
+
+    async fn can_create_entity_type(
+        &self,
+        actor: AccountId,
+        web: impl Into<WebId> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(web.into(), WebPermission::CreateEntityType, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_update_entity_type(
+        &self,
+        actor: AccountId,
+        entity_type: EntityTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(entity_type, EntityTypePermission::Update, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_archive_entity_type(
+        &self,
+        actor: AccountId,
+        entity_type: EntityTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(entity_type, EntityTypePermission::Archive, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_share_entity_type(
+        &self,
+        actor: AccountId,
+        entity_type: EntityTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(entity_type, EntityTypePermission::Share, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_view_entity_type(
+        &self,
+        actor: AccountId,
+        entity_type: EntityTypeId,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, CheckError> {
+        self.backend
+            .check(
+                &(entity_type, EntityTypePermission::View, actor),
+                consistency,
+            )
+            .await
+    }
+
+    async fn can_view_entities(
+        &self,
+        actor: AccountId,
+        entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError> {
+        self.check_resources(actor, EntityPermission::View, entities, consistency)
+            .await
+    }
+
+    async fn can_update_entities(
+        &self,
+        actor: AccountId,
+        entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError> {
+        self.check_resources(actor, EntityPermission::Edit, entities, consistency)
+            .await
+    }
+
+    async fn can_delete_entities(
+        &self,
+        actor: AccountId,
+        entities: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<(HashMap<EntityId, bool>, Zookie<'static>), BulkCheckError> {
+        self.check_resources(actor, EntityPermission::Delete, entities, consistency)
+            .await
+    }
+
+    async fn can_view_data_types(
+        &self,
+        actor: AccountId,
+        data_types: impl IntoIterator<Item = DataTypeId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<(HashMap<DataTypeId, bool>, Zookie<'static>), BulkCheckError> {
+        self.check_resources(actor, DataTypePermission::View, data_types, consistency)
+            .await
+    }
+
+    async fn can_view_property_types(
+        &self,
+        actor: AccountId,
+        property_types: impl IntoIterator<Item = PropertyTypeId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<(HashMap<PropertyTypeId, bool>, Zookie<'static>), BulkCheckError> {
+        self.check_resources(
+            actor,
+            PropertyTypePermission::View,
+            property_types,
+            consistency,
+        )
+        .await
+    }
+
+    async fn can_view_entity_types(
+        &self,
+        actor: AccountId,
+        entity_types: impl IntoIterator<Item = EntityTypeId, IntoIter: Send> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<(HashMap<EntityTypeId, bool>, Zookie<'static>), BulkCheckError> {
+        self.check_resources(actor, EntityTypePermission::View, entity_types, consistency)
+            .await
+    }
+
+    async fn lookup_viewable_entities(
+        &self,
+        actor: AccountId,
+        consistency: Consistency<'_>,
+    ) -> Result<(Option<Vec<EntityUuid>>, Zookie<'static>), LookupResourcesError> {
+        let LookupResourcesResponse {
+            resources,
+            looked_up_at,
+        } = self
+            .backend
+            .lookup_resources::<EntityId, _, _>(&actor, &EntityPermission::View, consistency)
+            .await?;
+
+        Ok((Some(resources), looked_up_at))
+    }
 }
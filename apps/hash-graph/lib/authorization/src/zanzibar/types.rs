@@ -202,10 +202,18 @@ impl<'t> UntypedTuple<'t> {
 #[serde(transparent)]
 pub struct Zookie<'t>(Cow<'t, str>);
 
-impl Zookie<'_> {
+impl<'t> Zookie<'t> {
+    pub(crate) const fn new(token: Cow<'t, str>) -> Self {
+        Self(token)
+    }
+
     pub(crate) const fn empty() -> Self {
         Self(Cow::Borrowed(""))
     }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
 /// Specifies the desired consistency level on a per-request basis.
@@ -3,13 +3,16 @@
 
 use authorization::{
     backend::{
-        CheckError, CheckResponse, CreateRelationError, CreateRelationResponse,
-        DeleteRelationError, DeleteRelationResponse, ExportSchemaError, ExportSchemaResponse,
-        ImportSchemaError, ImportSchemaResponse, SpiceDbOpenApi, ZanzibarBackend,
+        BulkCheckError, BulkCheckResponse, CheckError, CheckResponse, CreateRelationError,
+        CreateRelationResponse, DeleteRelationError, DeleteRelationResponse, ExportSchemaError,
+        ExportSchemaResponse, ImportSchemaError, ImportSchemaResponse, LookupResourcesError,
+        LookupResourcesResponse, ReadRelationsError, ReadRelationsResponse, SpiceDbOpenApi,
+        ZanzibarBackend,
     },
-    zanzibar::{Consistency, Tuple},
+    zanzibar::{Affiliation, Consistency, Resource, Tuple},
 };
 use error_stack::Report;
+use serde::de::DeserializeOwned;
 
 pub struct TestApi {
     client: SpiceDbOpenApi,
@@ -64,6 +67,16 @@ impl ZanzibarBackend for TestApi {
         self.client.create_relations(tuples).await
     }
 
+    async fn touch_relations<T>(
+        &mut self,
+        tuples: impl IntoIterator<Item = T, IntoIter: Send> + Send,
+    ) -> Result<CreateRelationResponse, Report<CreateRelationError>>
+    where
+        T: Tuple + Send + Sync,
+    {
+        self.client.touch_relations(tuples).await
+    }
+
     async fn delete_relations<T>(
         &mut self,
         tuples: impl IntoIterator<Item = T, IntoIter: Send> + Send,
@@ -84,4 +97,42 @@ impl ZanzibarBackend for TestApi {
     {
         self.client.check(tuple, consistency).await
     }
+
+    async fn check_bulk<T>(
+        &self,
+        tuples: &[T],
+        consistency: Consistency<'_>,
+    ) -> Result<BulkCheckResponse, Report<BulkCheckError>>
+    where
+        T: Tuple + Sync,
+    {
+        self.client.check_bulk(tuples, consistency).await
+    }
+
+    async fn lookup_resources<O, A, U>(
+        &self,
+        subject: &U,
+        affiliation: &A,
+        consistency: Consistency<'_>,
+    ) -> Result<LookupResourcesResponse<O::Id>, Report<LookupResourcesError>>
+    where
+        O: Resource<Id: Sized + DeserializeOwned + Send>,
+        A: Affiliation<O> + Sync,
+        U: Resource + Sync,
+    {
+        self.client
+            .lookup_resources(subject, affiliation, consistency)
+            .await
+    }
+
+    async fn read_relations<O>(
+        &self,
+        object: &O,
+        consistency: Consistency<'_>,
+    ) -> Result<ReadRelationsResponse, Report<ReadRelationsError>>
+    where
+        O: Resource + Sync,
+    {
+        self.client.read_relations(object, consistency).await
+    }
 }
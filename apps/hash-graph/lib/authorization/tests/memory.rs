@@ -0,0 +1,536 @@
+#![feature(async_fn_in_trait, associated_type_bounds)]
+#![allow(clippy::too_many_lines)]
+
+mod schema;
+
+use std::{borrow::Cow, error::Error};
+
+use authorization::{
+    backend::{InMemoryBackend, ZanzibarBackend},
+    schema::{
+        AccountGroupPermission, AccountGroupRelation, EntityPermission, EntityRelation, WebRelation,
+    },
+    zanzibar::{Consistency, UntypedTuple, ZanzibarClient},
+    AuthorizationApi, VisibilityScope,
+};
+use graph_types::{
+    account::{AccountGroupId, AccountId},
+    knowledge::entity::EntityId,
+    ontology::DataTypeId,
+    web::WebId,
+};
+use type_system::url::BaseUrl;
+use uuid::Uuid;
+
+use crate::schema::{ALICE, BOB, ENTITY_A, ENTITY_B};
+
+const GROUP: AccountGroupId = AccountGroupId::new(Uuid::from_fields(0, 0, 1, &[0; 8]));
+
+const SCHEMA: &str = include_str!("../schemas/v1__initial_schema.zed");
+
+#[tokio::test]
+async fn test_schema() -> Result<(), Box<dyn Error>> {
+    let mut api = InMemoryBackend::new();
+
+    assert!(api.export_schema().await.is_err());
+
+    api.import_schema(SCHEMA).await?;
+    assert_eq!(api.export_schema().await?.schema, SCHEMA);
+
+    assert!(
+        api.import_schema("definition graph/entity {")
+            .await
+            .is_err()
+    );
+    assert!(
+        api.import_schema("definition graph/entity { relation owner: graph/account }")
+            .await
+            .is_err()
+    );
+    assert!(
+        api.import_schema("definition graph/entity { permission view = owner }")
+            .await
+            .is_err()
+    );
+
+    // Failed imports don't replace the schema
+    assert_eq!(api.export_schema().await?.schema, SCHEMA);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn plain_permissions() -> Result<(), Box<dyn Error>> {
+    let mut api = InMemoryBackend::new();
+    api.import_schema(SCHEMA).await?;
+
+    let token = api
+        .create_relations([
+            (ENTITY_A, EntityRelation::DirectOwner, ALICE),
+            (ENTITY_A, EntityRelation::DirectViewer, BOB),
+            (ENTITY_B, EntityRelation::DirectOwner, BOB),
+        ])
+        .await?
+        .written_at;
+
+    assert!(
+        api.check(
+            &(ENTITY_A, EntityRelation::DirectOwner, ALICE),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+    assert!(
+        api.check(
+            &(ENTITY_A, EntityPermission::View, ALICE),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+    assert!(
+        api.check(
+            &(ENTITY_A, EntityPermission::View, BOB),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+    assert!(
+        !api.check(
+            &(ENTITY_A, EntityPermission::Edit, BOB),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+    assert!(
+        !api.check(
+            &(ENTITY_B, EntityPermission::View, ALICE),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+    assert!(
+        api.check(
+            &(ENTITY_B, EntityPermission::Delete, BOB),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+
+    let response = api
+        .check_bulk(
+            &[
+                (ENTITY_A, EntityPermission::View, ALICE),
+                (ENTITY_B, EntityPermission::View, ALICE),
+                (ENTITY_A, EntityPermission::Edit, BOB),
+                (ENTITY_B, EntityPermission::Edit, BOB),
+            ],
+            Consistency::AtLeastAsFresh(&token),
+        )
+        .await?;
+    assert_eq!(response.has_permission, [true, false, false, true]);
+
+    let token = api
+        .delete_relations([(ENTITY_A, EntityRelation::DirectViewer, BOB)])
+        .await?
+        .deleted_at;
+
+    assert!(
+        !api.check(
+            &(ENTITY_A, EntityPermission::View, BOB),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn group_and_public_permissions() -> Result<(), Box<dyn Error>> {
+    let mut api = InMemoryBackend::new();
+    api.import_schema(SCHEMA).await?;
+
+    api.create_relations([
+        (GROUP, AccountGroupRelation::DirectAdmin, ALICE),
+        (GROUP, AccountGroupRelation::DirectMember, BOB),
+    ])
+    .await?;
+    let token = api
+        .create_relations([(
+            ENTITY_A,
+            EntityRelation::DirectOwner,
+            GROUP,
+            AccountGroupPermission::Member,
+        )])
+        .await?
+        .written_at;
+
+    for account in [ALICE, BOB] {
+        assert!(
+            api.check(
+                &(ENTITY_A, EntityPermission::Edit, account),
+                Consistency::AtLeastAsFresh(&token)
+            )
+            .await?
+            .has_permission
+        );
+    }
+    assert!(
+        !api.check(
+            &(ENTITY_B, EntityPermission::View, BOB),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+
+    let token = api
+        .create_relations([UntypedTuple {
+            object_namespace: Cow::Borrowed("graph/entity"),
+            object_id: Cow::Owned(ENTITY_B.entity_uuid.to_string()),
+            affiliation: Cow::Borrowed("direct_viewer"),
+            user_namespace: Cow::Borrowed("graph/account"),
+            user_id: Cow::Borrowed("*"),
+            user_set: None,
+        }])
+        .await?
+        .written_at;
+
+    assert!(
+        api.check(
+            &(ENTITY_B, EntityPermission::View, BOB),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+    assert!(
+        !api.check(
+            &(ENTITY_B, EntityPermission::Edit, BOB),
+            Consistency::AtLeastAsFresh(&token)
+        )
+        .await?
+        .has_permission
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn consistency() -> Result<(), Box<dyn Error>> {
+    let mut api = InMemoryBackend::new();
+    let schema_token = api.import_schema(SCHEMA).await?.written_at;
+
+    let relation_token = api
+        .create_relations([(ENTITY_A, EntityRelation::DirectOwner, ALICE)])
+        .await?
+        .written_at;
+
+    assert!(
+        api.check(
+            &(ENTITY_A, EntityPermission::View, ALICE),
+            Consistency::AtLeastAsFresh(&schema_token)
+        )
+        .await?
+        .has_permission
+    );
+    assert!(
+        api.check(
+            &(ENTITY_A, EntityPermission::View, ALICE),
+            Consistency::AtExactSnapshot(&relation_token)
+        )
+        .await?
+        .has_permission
+    );
+    // Only the latest snapshot is retained
+    assert!(
+        api.check(
+            &(ENTITY_A, EntityPermission::View, ALICE),
+            Consistency::AtExactSnapshot(&schema_token)
+        )
+        .await
+        .is_err()
+    );
+
+    // The transaction is rolled back if any relation is invalid
+    assert!(
+        api.create_relations([
+            (ENTITY_B, EntityRelation::DirectOwner, BOB),
+            (ENTITY_A, EntityRelation::DirectOwner, ALICE),
+        ])
+        .await
+        .is_err()
+    );
+    assert!(
+        !api.check(
+            &(ENTITY_B, EntityPermission::View, BOB),
+            Consistency::FullyConsistent
+        )
+        .await?
+        .has_permission
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn ontology_permissions() -> Result<(), Box<dyn Error>> {
+    let mut backend = InMemoryBackend::new();
+    backend.import_schema(SCHEMA).await?;
+    backend
+        .create_relations([(WebId::from(ALICE), WebRelation::DirectOwner, ALICE)])
+        .await?;
+
+    let mut api = ZanzibarClient::new(backend);
+
+    assert!(
+        api.can_create_data_type(ALICE, ALICE, Consistency::FullyConsistent)
+            .await?
+            .has_permission
+    );
+    assert!(
+        !api.can_create_data_type(BOB, ALICE, Consistency::FullyConsistent)
+            .await?
+            .has_permission
+    );
+
+    let data_type = DataTypeId::from_base_url(
+        &BaseUrl::new("https://example.com/data-type/".to_owned()).expect("invalid base URL"),
+    );
+    api.add_data_type_owner(VisibilityScope::Account(ALICE), data_type)
+        .await?;
+    // Adding an existing owner again is not an error
+    let token = api
+        .add_data_type_owner(VisibilityScope::Account(ALICE), data_type)
+        .await?;
+
+    assert!(
+        api.can_archive_data_type(ALICE, data_type, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+    assert!(
+        !api.can_update_data_type(BOB, data_type, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+
+    let token = api
+        .add_data_type_editor(VisibilityScope::Account(BOB), data_type)
+        .await?;
+
+    assert!(
+        api.can_update_data_type(BOB, data_type, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+    assert!(
+        !api.can_archive_data_type(BOB, data_type, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn entity_sharing() -> Result<(), Box<dyn Error>> {
+    let mut backend = InMemoryBackend::new();
+    backend.import_schema(SCHEMA).await?;
+    backend
+        .create_relations([(GROUP, AccountGroupRelation::DirectMember, BOB)])
+        .await?;
+
+    let mut api = ZanzibarClient::new(backend);
+
+    api.add_entity_owner(VisibilityScope::Account(ALICE), ENTITY_A)
+        .await?;
+    let token = api
+        .add_entity_editor(VisibilityScope::Account(BOB), ENTITY_A)
+        .await?;
+
+    assert!(
+        api.can_update_entity(BOB, ENTITY_A, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+    assert!(
+        !api.can_delete_entity(BOB, ENTITY_A, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+    assert!(
+        !api.can_share_entity(BOB, ENTITY_A, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+    assert!(
+        api.can_share_entity(ALICE, ENTITY_A, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+
+    api.remove_entity_editor(VisibilityScope::Account(BOB), ENTITY_A)
+        .await?;
+    let token = api
+        .add_entity_viewer(VisibilityScope::Public, ENTITY_A)
+        .await?;
+
+    assert!(
+        api.can_view_entity(BOB, ENTITY_A, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+    assert!(
+        !api.can_update_entity(BOB, ENTITY_A, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+
+    let token = api
+        .add_entity_editor(VisibilityScope::AccountGroup(GROUP), ENTITY_B)
+        .await?;
+    assert!(
+        api.can_update_entity(BOB, ENTITY_B, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+    assert!(
+        !api.can_view_entity(ALICE, ENTITY_B, Consistency::AtLeastAsFresh(&token))
+            .await?
+            .has_permission
+    );
+
+    let (relations, _) = api
+        .get_entity_relations(ENTITY_A, Consistency::AtLeastAsFresh(&token))
+        .await?;
+    assert_eq!(
+        relations,
+        [
+            (EntityRelation::DirectOwner, VisibilityScope::Account(ALICE)),
+            (EntityRelation::DirectViewer, VisibilityScope::Public),
+        ]
+    );
+    let (relations, _) = api
+        .get_entity_relations(ENTITY_B, Consistency::AtLeastAsFresh(&token))
+        .await?;
+    assert_eq!(
+        relations,
+        [(
+            EntityRelation::DirectEditor,
+            VisibilityScope::AccountGroup(GROUP)
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn bulk_entity_permissions() -> Result<(), Box<dyn Error>> {
+    let mut backend = InMemoryBackend::new();
+    backend.import_schema(SCHEMA).await?;
+    let token = backend
+        .create_relations([
+            (ENTITY_A, EntityRelation::DirectOwner, ALICE),
+            (ENTITY_B, EntityRelation::DirectViewer, ALICE),
+        ])
+        .await?
+        .written_at;
+
+    let api = ZanzibarClient::new(backend.clone());
+
+    let (permissions, _) = api
+        .can_view_entities(
+            ALICE,
+            [ENTITY_A, ENTITY_B, ENTITY_A],
+            Consistency::AtLeastAsFresh(&token),
+        )
+        .await?;
+    assert_eq!(permissions.len(), 2);
+    assert_eq!(permissions.get(&ENTITY_A), Some(&true));
+    assert_eq!(permissions.get(&ENTITY_B), Some(&true));
+
+    let (permissions, _) = api
+        .can_update_entities(
+            ALICE,
+            [ENTITY_A, ENTITY_B],
+            Consistency::AtLeastAsFresh(&token),
+        )
+        .await?;
+    assert_eq!(permissions.get(&ENTITY_A), Some(&true));
+    assert_eq!(permissions.get(&ENTITY_B), Some(&false));
+
+    let (permissions, _) = api
+        .can_view_entities(BOB, [ENTITY_A, ENTITY_B], Consistency::FullyConsistent)
+        .await?;
+    assert!(permissions.values().all(|has_permission| !has_permission));
+
+    let response = backend
+        .check_bulk::<(EntityId, EntityPermission, AccountId)>(&[], Consistency::FullyConsistent)
+        .await?;
+    assert!(response.has_permission.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn lookup_resources() -> Result<(), Box<dyn Error>> {
+    let mut backend = InMemoryBackend::new();
+    backend.import_schema(SCHEMA).await?;
+    backend
+        .create_relations([(GROUP, AccountGroupRelation::DirectMember, BOB)])
+        .await?;
+    let token = backend
+        .create_relations([
+            (ENTITY_A, EntityRelation::DirectOwner, ALICE),
+            (ENTITY_B, EntityRelation::DirectViewer, ALICE),
+        ])
+        .await?
+        .written_at;
+
+    let mut viewable = backend
+        .lookup_resources::<EntityId, _, _>(
+            &ALICE,
+            &EntityPermission::View,
+            Consistency::AtLeastAsFresh(&token),
+        )
+        .await?
+        .resources;
+    viewable.sort();
+    assert_eq!(viewable, [ENTITY_A.entity_uuid, ENTITY_B.entity_uuid]);
+
+    let updatable = backend
+        .lookup_resources::<EntityId, _, _>(
+            &ALICE,
+            &EntityPermission::Edit,
+            Consistency::AtLeastAsFresh(&token),
+        )
+        .await?
+        .resources;
+    assert_eq!(updatable, [ENTITY_A.entity_uuid]);
+
+    let token = backend
+        .create_relations([(
+            ENTITY_B,
+            EntityRelation::DirectOwner,
+            GROUP,
+            AccountGroupPermission::Member,
+        )])
+        .await?
+        .written_at;
+
+    let api = ZanzibarClient::new(backend);
+    let (viewable, _) = api
+        .lookup_viewable_entities(BOB, Consistency::AtLeastAsFresh(&token))
+        .await?;
+    assert_eq!(viewable, Some(vec![ENTITY_B.entity_uuid]));
+
+    Ok(())
+}
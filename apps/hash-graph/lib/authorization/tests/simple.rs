@@ -11,6 +11,7 @@ use authorization::{
     schema::{EntityPermission, EntityRelation},
     zanzibar::Consistency,
 };
+use graph_types::knowledge::entity::EntityId;
 
 use crate::schema::{ALICE, BOB, ENTITY_A, ENTITY_B};
 
@@ -110,7 +111,7 @@ async fn plain_permissions() -> Result<(), Box<dyn Error>> {
     );
     assert!(
         api.check(
-            &(ENTITY_A, EntityPermission::Update, ALICE),
+            &(ENTITY_A, EntityPermission::Edit, ALICE),
             Consistency::AtLeastAsFresh(&token)
         )
         .await?
@@ -118,7 +119,7 @@ async fn plain_permissions() -> Result<(), Box<dyn Error>> {
     );
     assert!(
         !api.check(
-            &(ENTITY_B, EntityPermission::Update, ALICE),
+            &(ENTITY_B, EntityPermission::Edit, ALICE),
             Consistency::AtLeastAsFresh(&token)
         )
         .await?
@@ -126,7 +127,7 @@ async fn plain_permissions() -> Result<(), Box<dyn Error>> {
     );
     assert!(
         !api.check(
-            &(ENTITY_A, EntityPermission::Update, BOB),
+            &(ENTITY_A, EntityPermission::Edit, BOB),
             Consistency::AtLeastAsFresh(&token)
         )
         .await?
@@ -134,13 +135,53 @@ async fn plain_permissions() -> Result<(), Box<dyn Error>> {
     );
     assert!(
         api.check(
-            &(ENTITY_B, EntityPermission::Update, BOB),
+            &(ENTITY_B, EntityPermission::Edit, BOB),
             Consistency::AtLeastAsFresh(&token)
         )
         .await?
         .has_permission
     );
 
+    let response = api
+        .check_bulk(
+            &[
+                (ENTITY_A, EntityPermission::View, ALICE),
+                (ENTITY_B, EntityPermission::View, ALICE),
+                (ENTITY_A, EntityPermission::Edit, BOB),
+                (ENTITY_B, EntityPermission::Edit, BOB),
+            ],
+            Consistency::AtLeastAsFresh(&token),
+        )
+        .await?;
+    assert_eq!(response.has_permission, [true, false, false, true]);
+
+    let mut viewable = api
+        .lookup_resources::<EntityId, _, _>(
+            &BOB,
+            &EntityPermission::View,
+            Consistency::AtLeastAsFresh(&token),
+        )
+        .await?
+        .resources;
+    viewable.sort();
+    assert_eq!(viewable, [ENTITY_A.entity_uuid, ENTITY_B.entity_uuid]);
+
+    let mut relations = api
+        .read_relations(&ENTITY_A, Consistency::AtLeastAsFresh(&token))
+        .await?
+        .relations
+        .into_iter()
+        .map(|tuple| (tuple.affiliation.into_owned(), tuple.user_id.into_owned()))
+        .collect::<Vec<_>>();
+    relations.sort();
+    assert_eq!(
+        relations,
+        [
+            (EntityRelation::DirectOwner.to_string(), ALICE.to_string()),
+            (EntityRelation::DirectViewer.to_string(), BOB.to_string()),
+        ]
+    );
+
     let token = api
         .delete_relations([(ENTITY_A, EntityRelation::DirectViewer, BOB)])
         .await?
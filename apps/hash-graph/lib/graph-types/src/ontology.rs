@@ -18,6 +18,7 @@ use type_system::{
     url::{BaseUrl, VersionedUrl},
     DataTypeReference, EntityTypeReference, PropertyTypeReference,
 };
+use uuid::Uuid;
 
 pub use self::{
     data_type::DataTypeWithMetadata,
@@ -29,6 +30,55 @@ pub use self::{
 };
 use crate::provenance::{OwnedById, ProvenanceMetadata};
 
+macro_rules! define_ontology_type_id {
+    ($name:tt, $kind:literal) => {
+        #[doc = concat!("Identifies ", $kind, " across all of its versions.")]
+        ///
+        /// The ID is derived from the [`BaseUrl`] of the type, so it can be computed from any of
+        /// its [`VersionedUrl`]s.
+        #[derive(
+            Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+        )]
+        #[repr(transparent)]
+        pub struct $name(Uuid);
+
+        impl $name {
+            #[must_use]
+            pub fn from_base_url(base_url: &BaseUrl) -> Self {
+                Self(Uuid::new_v5(
+                    &Uuid::NAMESPACE_URL,
+                    base_url.as_str().as_bytes(),
+                ))
+            }
+
+            #[must_use]
+            pub fn from_url(url: &VersionedUrl) -> Self {
+                Self::from_base_url(&url.base_url)
+            }
+
+            #[must_use]
+            pub const fn as_uuid(&self) -> &Uuid {
+                &self.0
+            }
+
+            #[must_use]
+            pub const fn into_uuid(self) -> Uuid {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, fmt)
+            }
+        }
+    };
+}
+
+define_ontology_type_id!(DataTypeId, "a data type");
+define_ontology_type_id!(PropertyTypeId, "a property type");
+define_ontology_type_id!(EntityTypeId, "an entity type");
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[repr(transparent)]
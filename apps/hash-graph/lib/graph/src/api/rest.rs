@@ -13,15 +13,18 @@ mod status;
 mod utoipa_typedef;
 
 mod account;
+mod batch;
+mod change_feed;
 mod data_type;
 mod entity;
 mod entity_type;
 mod property_type;
+mod webhook;
 
 use std::{borrow::Cow, fs, io, str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
-use authorization::{AuthorizationApi, AuthorizationApiPool};
+use authorization::{AuthorizationApi, AuthorizationApiPool, VisibilityScope};
 use axum::{
     extract::{FromRequestParts, Path},
     http::{request::Parts, StatusCode},
@@ -31,7 +34,7 @@ use axum::{
 };
 use error_stack::{Report, ResultExt};
 use graph_types::{
-    account::AccountId,
+    account::{AccountGroupId, AccountId},
     ontology::{
         CustomEntityTypeMetadata, CustomOntologyMetadata, EntityTypeMetadata,
         OntologyElementMetadata, OntologyTemporalMetadata, OntologyTypeRecordId,
@@ -41,6 +44,7 @@ use graph_types::{
 };
 use include_dir::{include_dir, Dir};
 use sentry::integrations::tower::{NewSentryLayer, SentryHttpLayer};
+use serde::{Deserialize, Serialize};
 use temporal_versioning::{
     ClosedTemporalBound, DecisionTime, LeftClosedTemporalInterval, LimitedTemporalBound,
     OpenTemporalBound, RightBoundedTemporalInterval, TemporalBound, Timestamp, TransactionTime,
@@ -68,11 +72,15 @@ use crate::{
         },
     },
     ontology::{domain_validator::DomainValidator, Selector},
-    store::{error::VersionedUrlAlreadyExists, QueryError, Store, StorePool, TypeFetcher},
+    store::{
+        error::VersionedUrlAlreadyExists, OntologyTypeDependencyReference, OntologyTypeDependent,
+        OntologyTypeDependents, QueryError, Store, StorePool, TypeFetcher,
+    },
     subgraph::{
         edges::{
-            EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphEdgeKind, OntologyEdgeKind,
-            OutgoingEdgeResolveDepth, SharedEdgeKind,
+            EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphEdgeKind,
+            KnowledgeGraphTraversalFilters, OntologyEdgeKind, OutgoingEdgeResolveDepth,
+            SharedEdgeKind,
         },
         identifier::{
             DataTypeVertexId, EntityIdWithInterval, EntityTypeVertexId, EntityVertexId,
@@ -108,6 +116,52 @@ impl<S> FromRequestParts<S> for AuthenticatedUserHeader {
     }
 }
 
+/// The subject an entity or an ontology type is shared with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum SharingSubject {
+    /// All accounts.
+    Public,
+    #[serde(rename_all = "camelCase")]
+    Account { account_id: AccountId },
+    #[serde(rename_all = "camelCase")]
+    AccountGroup { account_group_id: AccountGroupId },
+}
+
+impl From<SharingSubject> for VisibilityScope {
+    fn from(subject: SharingSubject) -> Self {
+        match subject {
+            SharingSubject::Public => Self::Public,
+            SharingSubject::Account { account_id } => Self::Account(account_id),
+            SharingSubject::AccountGroup { account_group_id } => {
+                Self::AccountGroup(account_group_id)
+            }
+        }
+    }
+}
+
+impl From<VisibilityScope> for SharingSubject {
+    fn from(scope: VisibilityScope) -> Self {
+        match scope {
+            VisibilityScope::Public => Self::Public,
+            VisibilityScope::Account(account_id) => Self::Account { account_id },
+            VisibilityScope::AccountGroup(account_group_id) => {
+                Self::AccountGroup { account_group_id }
+            }
+        }
+    }
+}
+
+/// The role granted to a subject when sharing an ontology type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+enum OntologyTypeSharingRole {
+    /// The subject is allowed to view the ontology type.
+    Viewer,
+    /// The subject is allowed to view and update the ontology type.
+    Editor,
+}
+
 #[async_trait]
 pub trait RestApiStore: Store + TypeFetcher {
     async fn load_external_type<A: AuthorizationApi + Send + Sync>(
@@ -168,6 +222,9 @@ where
         property_type::PropertyTypeResource::routes::<S, A>(),
         entity_type::EntityTypeResource::routes::<S, A>(),
         entity::EntityResource::routes::<S, A>(),
+        batch::BatchResource::routes::<S, A>(),
+        change_feed::ChangeFeedResource::routes::<S, A>(),
+        webhook::WebhookResource::routes::<S, A>(),
     ]
 }
 
@@ -178,6 +235,9 @@ fn api_documentation() -> Vec<openapi::OpenApi> {
         property_type::PropertyTypeResource::documentation(),
         entity_type::EntityTypeResource::documentation(),
         entity::EntityResource::documentation(),
+        batch::BatchResource::documentation(),
+        change_feed::ChangeFeedResource::documentation(),
+        webhook::WebhookResource::documentation(),
     ]
 }
 
@@ -278,6 +338,9 @@ async fn serve_static_schema(Path(path): Path<String>) -> Result<Response, Statu
             OntologyTypeRecordId,
             OntologyElementMetadata,
             OntologyTemporalMetadata,
+            OntologyTypeDependents,
+            OntologyTypeDependent,
+            OntologyTypeDependencyReference,
             CustomOntologyMetadata,
             EntityTypeMetadata,
             CustomEntityTypeMetadata,
@@ -308,6 +371,7 @@ async fn serve_static_schema(Path(path): Path<String>) -> Result<Response, Statu
             GraphResolveDepths,
             EdgeResolveDepths,
             OutgoingEdgeResolveDepth,
+            KnowledgeGraphTraversalFilters,
             Subgraph,
             SubgraphTemporalAxes,
 
@@ -315,6 +379,9 @@ async fn serve_static_schema(Path(path): Path<String>) -> Result<Response, Statu
             TransactionTime,
             QueryTemporalAxes,
             QueryTemporalAxesUnresolved,
+
+            SharingSubject,
+            OntologyTypeSharingRole,
         )
     ),
 )]
@@ -589,6 +656,66 @@ impl Modify for FilterSchemaAddon {
                                 )
                                 .required("notEqual"),
                         )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("GreaterFilter"))
+                                .property(
+                                    "greater",
+                                    ArrayBuilder::new()
+                                        .items(Ref::from_schema_name("FilterExpression"))
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("greater"),
+                        )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("GreaterOrEqualFilter"))
+                                .property(
+                                    "greaterOrEqual",
+                                    ArrayBuilder::new()
+                                        .items(Ref::from_schema_name("FilterExpression"))
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("greaterOrEqual"),
+                        )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("LessFilter"))
+                                .property(
+                                    "less",
+                                    ArrayBuilder::new()
+                                        .items(Ref::from_schema_name("FilterExpression"))
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("less"),
+                        )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("LessOrEqualFilter"))
+                                .property(
+                                    "lessOrEqual",
+                                    ArrayBuilder::new()
+                                        .items(Ref::from_schema_name("FilterExpression"))
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("lessOrEqual"),
+                        )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("BetweenFilter"))
+                                .property(
+                                    "between",
+                                    ArrayBuilder::new()
+                                        .items(Ref::from_schema_name("FilterExpression"))
+                                        .min_items(Some(3))
+                                        .max_items(Some(3)),
+                                )
+                                .required("between"),
+                        )
                         .item(
                             ObjectBuilder::new()
                                 .title(Some("StartsWithFilter"))
@@ -625,6 +752,18 @@ impl Modify for FilterSchemaAddon {
                                 )
                                 .required("containsSegment"),
                         )
+                        .item(
+                            ObjectBuilder::new()
+                                .title(Some("SearchFilter"))
+                                .property(
+                                    "search",
+                                    ArrayBuilder::new()
+                                        .items(Ref::from_schema_name("FilterExpression"))
+                                        .min_items(Some(2))
+                                        .max_items(Some(2)),
+                                )
+                                .required("search"),
+                        )
                         .build(),
                 )
                 .into(),
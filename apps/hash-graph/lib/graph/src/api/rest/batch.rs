@@ -0,0 +1,351 @@
+//! Web routes for applying several write operations atomically.
+
+use std::{collections::HashMap, sync::Arc};
+
+use authorization::{backend::PermissionAssertion, AuthorizationApiPool};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Extension, Router,
+};
+use error_stack::Report;
+use graph_types::{
+    knowledge::entity::{EntityProperties, EntityUuid},
+    ontology::{
+        PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
+        PartialOntologyElementMetadata,
+    },
+    provenance::OwnedById,
+};
+use serde::Deserialize;
+use type_system::{
+    repr,
+    url::{BaseUrl, VersionedUrl},
+    DataType, EntityType, PropertyType,
+};
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::{
+        error::{ErrorInfo, Status, StatusPayloads},
+        rest::{
+            api_resource::RoutedResource, json::Json, status::status_to_response,
+            AuthenticatedUserHeader, RestApiStore,
+        },
+    },
+    ontology::domain_validator::{DomainValidator, ValidateOntologyType},
+    store::{
+        error::RaceConditionOnUpdate, BatchLinkData, BatchStore, BulkItemFailure, EntityReference,
+        StorePool, WriteOperation, WriteOperationOutput,
+    },
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        write_batch,
+    ),
+    components(
+        schemas(
+            WriteOperationRequest,
+            EntityReference,
+            BatchLinkData,
+            WriteOperationOutput,
+        )
+    ),
+    tags(
+        (name = "Batch", description = "batch write API")
+    )
+)]
+pub struct BatchResource;
+
+impl RoutedResource for BatchResource {
+    /// Create routes for applying batches of write operations.
+    fn routes<S, A>() -> Router
+    where
+        S: StorePool + Send + Sync + 'static,
+        A: AuthorizationApiPool + Send + Sync + 'static,
+        for<'pool> S::Store<'pool>: RestApiStore,
+    {
+        Router::new().route("/batch", post(write_batch::<S, A>))
+    }
+}
+
+/// A single write operation of a batch.
+///
+/// Entities created by an earlier operation of the same batch can be referenced by the index of
+/// the operation.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum WriteOperationRequest {
+    #[serde(rename_all = "camelCase")]
+    CreateDataType {
+        #[schema(value_type = VAR_DATA_TYPE)]
+        schema: Box<repr::DataType>,
+        owned_by_id: OwnedById,
+    },
+    #[serde(rename_all = "camelCase")]
+    CreatePropertyType {
+        #[schema(value_type = VAR_PROPERTY_TYPE)]
+        schema: Box<repr::PropertyType>,
+        owned_by_id: OwnedById,
+    },
+    #[serde(rename_all = "camelCase")]
+    CreateEntityType {
+        #[schema(value_type = VAR_ENTITY_TYPE)]
+        schema: Box<repr::EntityType>,
+        owned_by_id: OwnedById,
+        #[serde(default)]
+        #[schema(value_type = SHARED_BaseUrl)]
+        label_property: Option<BaseUrl>,
+    },
+    #[serde(rename_all = "camelCase")]
+    CreateEntity {
+        properties: EntityProperties,
+        #[schema(value_type = SHARED_VersionedUrl)]
+        entity_type_id: VersionedUrl,
+        owned_by_id: OwnedById,
+        #[serde(default)]
+        #[schema(nullable = false)]
+        entity_uuid: Option<EntityUuid>,
+        #[serde(default)]
+        #[schema(nullable = false)]
+        link_data: Option<BatchLinkData>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ArchiveDataType {
+        #[schema(value_type = SHARED_VersionedUrl)]
+        type_to_archive: VersionedUrl,
+    },
+    #[serde(rename_all = "camelCase")]
+    ArchivePropertyType {
+        #[schema(value_type = SHARED_VersionedUrl)]
+        type_to_archive: VersionedUrl,
+    },
+    #[serde(rename_all = "camelCase")]
+    ArchiveEntityType {
+        #[schema(value_type = SHARED_VersionedUrl)]
+        type_to_archive: VersionedUrl,
+    },
+    ArchiveEntity {
+        entity: EntityReference,
+        /// Whether link entities attached to the entity are archived as well.
+        #[serde(default)]
+        cascade: bool,
+    },
+}
+
+impl WriteOperationRequest {
+    /// Parses the provided schema, if any, and validates its domain.
+    fn into_write_operation(
+        self,
+        domain_validator: &DomainValidator,
+    ) -> Result<WriteOperation, StatusCode> {
+        match self {
+            Self::CreateDataType {
+                schema,
+                owned_by_id,
+            } => {
+                let data_type: DataType = (*schema).try_into().map_err(|report| {
+                    tracing::error!(error=?report, "Couldn't convert schema to Data Type");
+                    StatusCode::UNPROCESSABLE_ENTITY
+                })?;
+
+                domain_validator.validate(&data_type).map_err(|report| {
+                    tracing::error!(error=?report, id=data_type.id().to_string(), "Data Type ID failed to validate");
+                    StatusCode::UNPROCESSABLE_ENTITY
+                })?;
+
+                Ok(WriteOperation::CreateDataType {
+                    metadata: PartialOntologyElementMetadata {
+                        record_id: data_type.id().clone().into(),
+                        custom: PartialCustomOntologyMetadata::Owned { owned_by_id },
+                    },
+                    schema: Box::new(data_type),
+                })
+            }
+            Self::CreatePropertyType {
+                schema,
+                owned_by_id,
+            } => {
+                let property_type: PropertyType = (*schema).try_into().map_err(|report| {
+                    tracing::error!(error=?report, "Couldn't convert schema to Property Type");
+                    StatusCode::UNPROCESSABLE_ENTITY
+                })?;
+
+                domain_validator.validate(&property_type).map_err(|report| {
+                    tracing::error!(error=?report, id=property_type.id().to_string(), "Property Type ID failed to validate");
+                    StatusCode::UNPROCESSABLE_ENTITY
+                })?;
+
+                Ok(WriteOperation::CreatePropertyType {
+                    metadata: PartialOntologyElementMetadata {
+                        record_id: property_type.id().clone().into(),
+                        custom: PartialCustomOntologyMetadata::Owned { owned_by_id },
+                    },
+                    schema: Box::new(property_type),
+                })
+            }
+            Self::CreateEntityType {
+                schema,
+                owned_by_id,
+                label_property,
+            } => {
+                let entity_type: EntityType = (*schema).try_into().map_err(|report| {
+                    tracing::error!(error=?report, "Couldn't convert schema to Entity Type");
+                    StatusCode::UNPROCESSABLE_ENTITY
+                })?;
+
+                domain_validator.validate(&entity_type).map_err(|report| {
+                    tracing::error!(error=?report, id=entity_type.id().to_string(), "Entity Type ID failed to validate");
+                    StatusCode::UNPROCESSABLE_ENTITY
+                })?;
+
+                Ok(WriteOperation::CreateEntityType {
+                    metadata: PartialEntityTypeMetadata {
+                        record_id: entity_type.id().clone().into(),
+                        custom: PartialCustomEntityTypeMetadata {
+                            common: PartialCustomOntologyMetadata::Owned { owned_by_id },
+                            label_property,
+                        },
+                    },
+                    schema: Box::new(entity_type),
+                })
+            }
+            Self::CreateEntity {
+                properties,
+                entity_type_id,
+                owned_by_id,
+                entity_uuid,
+                link_data,
+            } => Ok(WriteOperation::CreateEntity {
+                owned_by_id,
+                entity_uuid,
+                entity_type_id,
+                properties,
+                link_data,
+            }),
+            Self::ArchiveDataType { type_to_archive } => {
+                Ok(WriteOperation::ArchiveDataType(type_to_archive))
+            }
+            Self::ArchivePropertyType { type_to_archive } => {
+                Ok(WriteOperation::ArchivePropertyType(type_to_archive))
+            }
+            Self::ArchiveEntityType { type_to_archive } => {
+                Ok(WriteOperation::ArchiveEntityType(type_to_archive))
+            }
+            Self::ArchiveEntity { entity, cascade } => {
+                Ok(WriteOperation::ArchiveEntity { entity, cascade })
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/batch",
+    request_body = [WriteOperationRequest],
+    tag = "Batch",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The outputs of the operations in the order of the request", body = [WriteOperationOutput]),
+        (status = 400, content_type = "application/json", description = "An operation was rejected. The `failures` metadata contains the `BulkItemFailure` of the rejected operation", body = VAR_STATUS),
+        (status = 403, content_type = "application/json", description = "The actor is not allowed to apply an operation. The `failures` metadata contains the `BulkItemFailure` of the operation", body = VAR_STATUS),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+        (status = 423, content_type = "text/plain", description = "An entity that should be archived was unexpectedly updated at the same time"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, domain_validator, body)
+)]
+async fn write_batch<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    domain_validator: Extension<DomainValidator>,
+    body: Json<Vec<WriteOperationRequest>>,
+) -> Result<Json<Vec<WriteOperationOutput>>, Response>
+where
+    S: StorePool + Send + Sync,
+    for<'pool> S::Store<'pool>: RestApiStore,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(operations) = body;
+
+    let operations = operations
+        .into_iter()
+        .map(|operation| operation.into_write_operation(&domain_validator))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(IntoResponse::into_response)?;
+
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    store
+        .write_batch(actor_id, &mut authorization_api, operations)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not apply batch");
+            batch_report_to_response(&report)
+        })
+        .map(Json)
+}
+
+/// Converts the error of a batch into a response describing the rejected operation.
+fn batch_report_to_response<C>(report: &Report<C>) -> Response {
+    let failures = report.request_ref::<BulkItemFailure>().collect::<Vec<_>>();
+
+    if failures.is_empty() {
+        let status_code = if report.contains::<RaceConditionOnUpdate>() {
+            StatusCode::LOCKED
+        } else {
+            // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        return status_code.into_response();
+    }
+
+    let failures = match serde_json::to_value(failures) {
+        Ok(failures) => failures,
+        Err(error) => {
+            tracing::error!(?error, "Could not serialize bulk item failures");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let (code, message, reason) = if report.contains::<PermissionAssertion>() {
+        (
+            hash_status::StatusCode::PermissionDenied,
+            "An operation of the batch is not permitted, no operation was applied.",
+            "OPERATION_FORBIDDEN",
+        )
+    } else {
+        (
+            hash_status::StatusCode::InvalidArgument,
+            "An operation of the batch was rejected, no operation was applied.",
+            "OPERATION_REJECTED",
+        )
+    };
+
+    status_to_response(Status::new(
+        code,
+        Some(message.to_owned()),
+        vec![StatusPayloads::ErrorInfo(ErrorInfo::new(
+            HashMap::from([("failures".to_owned(), failures)]),
+            reason.to_owned(),
+        ))],
+    ))
+}
@@ -0,0 +1,137 @@
+//! Web routes for reading the changes of records.
+
+use std::{sync::Arc, time::Duration};
+
+use authorization::AuthorizationApiPool;
+use axum::{http::StatusCode, routing::post, Extension, Router};
+use serde::Deserialize;
+use tokio::time::Instant;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::rest::{
+        api_resource::RoutedResource, json::Json, report_to_status_code, AuthenticatedUserHeader,
+    },
+    store::{
+        Change, ChangeCursor, ChangeFeedStore, ChangeKind, ChangedRecordId, Changes, StorePool,
+    },
+};
+
+/// The number of changes returned if no limit is requested.
+const DEFAULT_LIMIT: usize = 100;
+/// The maximum number of changes returned at once.
+const MAX_LIMIT: usize = 1000;
+/// The maximum time a request waits for new changes.
+const MAX_WAIT: Duration = Duration::from_secs(30);
+/// The interval in which the store is polled while waiting for new changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        read_changes,
+    ),
+    components(
+        schemas(
+            ReadChangesRequest,
+            ChangeCursor,
+            ChangeKind,
+            ChangedRecordId,
+            Change,
+            Changes,
+        )
+    ),
+    tags(
+        (name = "ChangeFeed", description = "change feed API")
+    )
+)]
+pub struct ChangeFeedResource;
+
+impl RoutedResource for ChangeFeedResource {
+    /// Create routes for reading the change feed.
+    fn routes<S, A>() -> Router
+    where
+        S: StorePool + Send + Sync + 'static,
+        A: AuthorizationApiPool + Send + Sync + 'static,
+    {
+        Router::new().route("/changes", post(read_changes::<S, A>))
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ReadChangesRequest {
+    /// The cursor returned by the previous request. If omitted, the feed is read from the
+    /// beginning.
+    #[serde(default)]
+    #[schema(nullable = false)]
+    cursor: Option<ChangeCursor>,
+    /// The maximum number of changes returned, at least 1 and at most 1000. Defaults to 100.
+    #[serde(default)]
+    #[schema(nullable = false)]
+    limit: Option<usize>,
+    /// The number of seconds to wait for new changes if there are none, at most 30. Defaults to
+    /// returning immediately.
+    #[serde(default)]
+    wait_seconds: u64,
+}
+
+#[utoipa::path(
+    post,
+    path = "/changes",
+    request_body = ReadChangesRequest,
+    tag = "ChangeFeed",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The changes after the cursor ordered by their transaction time", body = Changes),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn read_changes<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    Json(request): Json<ReadChangesRequest>,
+) -> Result<Json<Changes>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    // Without reading at least one change, the cursor would never advance.
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let deadline = Instant::now() + Duration::from_secs(request.wait_seconds).min(MAX_WAIT);
+
+    loop {
+        // The connections are only held while reading, not while waiting for new changes.
+        let changes = {
+            let store = store_pool.acquire().await.map_err(|error| {
+                tracing::error!(?error, "Could not acquire access to the store");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+            let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+                tracing::error!(?error, "Could not acquire access to the authorization API");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+            store
+                .read_changes(actor_id, &authorization_api, request.cursor, limit)
+                .await
+                .map_err(|report| {
+                    tracing::error!(error=?report, "Could not read changes");
+                    report_to_status_code(&report)
+                })?
+        };
+
+        if changes.cursor != request.cursor || Instant::now() >= deadline {
+            return Ok(Json(changes));
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -2,15 +2,18 @@
 
 use std::sync::Arc;
 
-use authorization::AuthorizationApiPool;
+use authorization::{
+    backend::PermissionAssertion, zanzibar::Consistency, AuthorizationApi, AuthorizationApiPool,
+};
 use axum::{
     http::StatusCode,
     routing::{post, put},
     Extension, Router,
 };
 use graph_types::{
+    account::AccountId,
     ontology::{
-        DataTypeWithMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
+        DataTypeId, DataTypeWithMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
         OntologyTypeReference, PartialCustomOntologyMetadata, PartialOntologyElementMetadata,
     },
     provenance::OwnedById,
@@ -25,14 +28,16 @@ use crate::{
         json::Json,
         report_to_status_code,
         utoipa_typedef::{subgraph::Subgraph, ListOrValue, MaybeListOfDataType},
-        AuthenticatedUserHeader, RestApiStore,
+        AuthenticatedUserHeader, OntologyTypeSharingRole, RestApiStore, SharingSubject,
     },
     ontology::{
         domain_validator::{DomainValidator, ValidateOntologyType},
         patch_id_and_parse, DataTypeQueryToken,
     },
     store::{
-        error::VersionedUrlAlreadyExists, BaseUrlAlreadyExists, ConflictBehavior, DataTypeStore,
+        error::VersionedUrlAlreadyExists,
+        query::{AggregationQuery, AggregationResult, DataTypeAggregationQuery},
+        BaseUrlAlreadyExists, ConflictBehavior, DataTypeStore, OntologyTypeDependents,
         OntologyVersionDoesNotExist, StorePool,
     },
     subgraph::query::{DataTypeStructuralQuery, StructuralQuery},
@@ -44,9 +49,13 @@ use crate::{
         create_data_type,
         load_external_data_type,
         get_data_types_by_query,
+        aggregate_data_types,
         update_data_type,
         archive_data_type,
         unarchive_data_type,
+        get_data_type_dependents,
+        share_data_type,
+        unshare_data_type,
     ),
     components(
         schemas(
@@ -57,8 +66,12 @@ use crate::{
             UpdateDataTypeRequest,
             DataTypeQueryToken,
             DataTypeStructuralQuery,
+            DataTypeAggregationQuery,
+            AggregationResult,
             ArchiveDataTypeRequest,
             UnarchiveDataTypeRequest,
+            GetDataTypeDependentsRequest,
+            ShareDataTypeRequest,
         )
     ),
     tags(
@@ -84,9 +97,13 @@ impl RoutedResource for DataTypeResource {
                     post(create_data_type::<S, A>).put(update_data_type::<S, A>),
                 )
                 .route("/query", post(get_data_types_by_query::<S, A>))
+                .route("/aggregate", post(aggregate_data_types::<S, A>))
                 .route("/load", post(load_external_data_type::<S, A>))
                 .route("/archive", put(archive_data_type::<S, A>))
-                .route("/unarchive", put(unarchive_data_type::<S, A>)),
+                .route("/unarchive", put(unarchive_data_type::<S, A>))
+                .route("/dependents", post(get_data_type_dependents::<S, A>))
+                .route("/share", post(share_data_type::<A>))
+                .route("/unshare", post(unshare_data_type::<A>)),
         )
     }
 }
@@ -111,6 +128,7 @@ struct CreateDataTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the created data type", body = MaybeListOfOntologyElementMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 409, description = "Unable to create data type in the store as the base data type URL already exists"),
         (status = 500, description = "Store error occurred"),
     ),
@@ -185,6 +203,10 @@ where
             // TODO: consider adding the data type, or at least its URL in the trace
             tracing::error!(error=?report, "Could not create data types");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<BaseUrlAlreadyExists>() {
                 return StatusCode::CONFLICT;
             }
@@ -320,6 +342,60 @@ where
     Ok(Json(subgraph.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/data-types/aggregate",
+    request_body = DataTypeAggregationQuery,
+    tag = "DataType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", body = [AggregationResult], description = "The aggregated values of the data types that satisfy the given query, one for each group."),
+
+        (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn aggregate_data_types<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    Json(query): Json<serde_json::Value>,
+) -> Result<Json<Vec<AggregationResult>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let store = store_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut query = AggregationQuery::deserialize(&query).map_err(|error| {
+        tracing::error!(?error, "Could not deserialize query");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    query.filter.convert_parameters().map_err(|error| {
+        tracing::error!(?error, "Could not validate query");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    store
+        .aggregate_data_types(actor_id, &authorization_api, &query)
+        .await
+        .map(Json)
+        .map_err(|report| {
+            tracing::error!(error=?report, ?query, "Could not aggregate data types");
+            report_to_status_code(&report)
+        })
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct UpdateDataTypeRequest {
@@ -340,6 +416,7 @@ struct UpdateDataTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the updated data type", body = OntologyElementMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Base data type ID was not found"),
         (status = 500, description = "Store error occurred"),
     ),
@@ -386,6 +463,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not update data type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -414,6 +495,7 @@ struct ArchiveDataTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the updated data type", body = OntologyTemporalMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Data type ID was not found"),
         (status = 409, description = "Data type ID is already archived"),
         (status = 500, description = "Store error occurred"),
@@ -449,6 +531,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not archive data type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -480,6 +566,7 @@ struct UnarchiveDataTypeRequest {
         (status = 200, content_type = "application/json", description = "The temporal metadata of the updated data type", body = OntologyTemporalMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Data type ID was not found"),
         (status = 409, description = "Data type ID already exists and is not archived"),
         (status = 500, description = "Store error occurred"),
@@ -515,6 +602,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not unarchive data type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -527,3 +618,213 @@ where
         })
         .map(Json)
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct GetDataTypeDependentsRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_id: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/data-types/dependents",
+    tag = "DataType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The types depending on the data type and the number of affected entities", body = OntologyTypeDependents),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Data type ID was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = GetDataTypeDependentsRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn get_data_type_dependents<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<GetDataTypeDependentsRequest>,
+) -> Result<Json<OntologyTypeDependents>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(GetDataTypeDependentsRequest { type_id }) = body;
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_data_type_dependents(actor_id, &authorization_api, &type_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read dependents of data type");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct ShareDataTypeRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_id: VersionedUrl,
+    role: OntologyTypeSharingRole,
+    subject: SharingSubject,
+}
+
+/// Checks that the actor is allowed to share the data type.
+async fn assert_can_share_data_type(
+    authorization_api: &(impl AuthorizationApi + Sync),
+    actor_id: AccountId,
+    data_type_id: DataTypeId,
+) -> Result<(), StatusCode> {
+    let has_permission = authorization_api
+        .can_share_data_type(actor_id, data_type_id, Consistency::FullyConsistent)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "Could not check if the data type can be shared");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .has_permission;
+
+    if has_permission {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/data-types/share",
+    tag = "DataType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 201, description = "The data type was shared"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 500, description = "Authorization error occurred"),
+    ),
+    request_body = ShareDataTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(authorization_api_pool))]
+async fn share_data_type<A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<ShareDataTypeRequest>,
+) -> Result<StatusCode, StatusCode>
+where
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(ShareDataTypeRequest {
+        type_id,
+        role,
+        subject,
+    }) = body;
+    let data_type_id = DataTypeId::from_url(&type_id);
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_can_share_data_type(&authorization_api, actor_id, data_type_id).await?;
+
+    match role {
+        OntologyTypeSharingRole::Viewer => {
+            authorization_api
+                .add_data_type_viewer(subject.into(), data_type_id)
+                .await
+        }
+        OntologyTypeSharingRole::Editor => {
+            authorization_api
+                .add_data_type_editor(subject.into(), data_type_id)
+                .await
+        }
+    }
+    .map_err(|error| {
+        tracing::error!(?error, "Could not share data type");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(StatusCode::CREATED)
+}
+
+#[utoipa::path(
+    post,
+    path = "/data-types/unshare",
+    tag = "DataType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 204, description = "The data type is not shared with the subject anymore"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 500, description = "Authorization error occurred"),
+    ),
+    request_body = ShareDataTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(authorization_api_pool))]
+async fn unshare_data_type<A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<ShareDataTypeRequest>,
+) -> Result<StatusCode, StatusCode>
+where
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(ShareDataTypeRequest {
+        type_id,
+        role,
+        subject,
+    }) = body;
+    let data_type_id = DataTypeId::from_url(&type_id);
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_can_share_data_type(&authorization_api, actor_id, data_type_id).await?;
+
+    match role {
+        OntologyTypeSharingRole::Viewer => {
+            authorization_api
+                .remove_data_type_viewer(subject.into(), data_type_id)
+                .await
+        }
+        OntologyTypeSharingRole::Editor => {
+            authorization_api
+                .remove_data_type_editor(subject.into(), data_type_id)
+                .await
+        }
+    }
+    .map_err(|error| {
+        tracing::error!(?error, "Could not unshare data type");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,10 +1,20 @@
-//! Web routes for CRU operations on entities.
+//! Web routes for CRUD operations on entities.
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use authorization::AuthorizationApiPool;
-use axum::{http::StatusCode, routing::post, Extension, Router};
+use authorization::{
+    backend::PermissionAssertion, schema::EntityRelation, zanzibar::Consistency, AuthorizationApi,
+    AuthorizationApiPool,
+};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{post, put},
+    Extension, Router,
+};
+use error_stack::Report;
 use graph_types::{
+    account::AccountId,
     knowledge::{
         entity::{
             Entity, EntityEditionId, EntityId, EntityMetadata, EntityProperties, EntityRecordId,
@@ -15,35 +25,100 @@ use graph_types::{
     provenance::OwnedById,
 };
 use serde::{Deserialize, Serialize};
+use temporal_versioning::{TimeAxis, Timestamp};
 use type_system::url::VersionedUrl;
 use utoipa::{OpenApi, ToSchema};
 
 use crate::{
-    api::rest::{
-        api_resource::RoutedResource, json::Json, report_to_status_code,
-        utoipa_typedef::subgraph::Subgraph, AuthenticatedUserHeader,
+    api::{
+        error::{ErrorInfo, Status, StatusPayloads},
+        rest::{
+            api_resource::RoutedResource, json::Json, report_to_status_code,
+            status::status_to_response, utoipa_typedef::subgraph::Subgraph,
+            AuthenticatedUserHeader, SharingSubject,
+        },
+    },
+    knowledge::{
+        EntityDiff, EntityQueryToken, EntityTypeIdDiff, EntityValidationError, LinkDataDiff,
+        PropertyDiff, PropertyPatchError, PropertyPatchOperation, ValidationFailure,
     },
-    knowledge::EntityQueryToken,
     store::{
-        error::{EntityDoesNotExist, RaceConditionOnUpdate},
-        EntityStore, StorePool,
+        error::{
+            EntityDoesNotExist, EntityEditionMismatch, EntityHasLinks, EntityIsAlreadyArchived,
+            EntityIsNotArchived, EntityUuidAlreadyExists, RaceConditionOnUpdate,
+        },
+        query::{
+            AggregationQuery, AggregationResult, EntityAggregationQuery, EntityQueryCursor,
+            EntityQuerySorting, Filter, NullOrdering, SortingDirection,
+        },
+        BulkItemFailure, EntityCreation, EntityHistoryEntry, EntityPathQuery, EntityStore,
+        EntityUpdate, StorePool,
+    },
+    subgraph::{
+        edges::{GraphResolveDepths, KnowledgeGraphTraversalFilters},
+        identifier::{EntityVertexId, GraphElementVertexId},
+        query::{EntityStructuralQuery, StructuralQuery},
+        temporal_axes::{
+            PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableAxis,
+            VariableTemporalAxisUnresolved,
+        },
     },
-    subgraph::query::{EntityStructuralQuery, StructuralQuery},
 };
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         create_entity,
+        create_entities,
         get_entities_by_query,
+        aggregate_entities,
+        get_entity_history,
+        diff_entity,
+        get_entity_paths,
         update_entity,
+        update_entities,
+        patch_entity,
+        archive_entity,
+        unarchive_entity,
+        erase_entity,
+        share_entity,
+        unshare_entity,
+        get_entity_shares,
     ),
     components(
         schemas(
             CreateEntityRequest,
             UpdateEntityRequest,
+            PatchEntityRequest,
+            PropertyPatchOperation,
+            ArchiveEntityRequest,
+            UnarchiveEntityRequest,
+            EraseEntityRequest,
+            ShareEntityRequest,
+            GetEntitySharesRequest,
+            EntityShare,
+            EntitySharingRole,
+            BulkItemFailure,
             EntityQueryToken,
             EntityStructuralQuery,
+            EntityAggregationQuery,
+            AggregationResult,
+            EntityQuerySorting,
+            EntityQueryCursor,
+            SortingDirection,
+            NullOrdering,
+            GetEntitiesByQueryResponse,
+            EntitySearchRank,
+            GetEntityHistoryRequest,
+            EntityHistoryEntry,
+            PropertyDiff,
+            DiffEntityRequest,
+            DiffEntityAxis,
+            EntityDiff,
+            EntityTypeIdDiff,
+            LinkDataDiff,
+            EntityPathQuery,
+            GetEntityPathsResponse,
 
             Entity,
             EntityUuid,
@@ -76,8 +151,27 @@ impl RoutedResource for EntityResource {
         Router::new().nest(
             "/entities",
             Router::new()
-                .route("/", post(create_entity::<S, A>).put(update_entity::<S, A>))
-                .route("/query", post(get_entities_by_query::<S, A>)),
+                .route(
+                    "/",
+                    post(create_entity::<S, A>)
+                        .put(update_entity::<S, A>)
+                        .patch(patch_entity::<S, A>),
+                )
+                .route(
+                    "/bulk",
+                    post(create_entities::<S, A>).put(update_entities::<S, A>),
+                )
+                .route("/archive", put(archive_entity::<S, A>))
+                .route("/unarchive", put(unarchive_entity::<S, A>))
+                .route("/erase", post(erase_entity::<S, A>))
+                .route("/share", post(share_entity::<S, A>))
+                .route("/unshare", post(unshare_entity::<S, A>))
+                .route("/shares", post(get_entity_shares::<S, A>))
+                .route("/query", post(get_entities_by_query::<S, A>))
+                .route("/aggregate", post(aggregate_entities::<S, A>))
+                .route("/history", post(get_entity_history::<S, A>))
+                .route("/diff", post(diff_entity::<S, A>))
+                .route("/paths", post(get_entity_paths::<S, A>)),
         )
     }
 }
@@ -108,6 +202,8 @@ struct CreateEntityRequest {
     ),
     responses(
         (status = 200, content_type = "application/json", description = "The metadata of the created entity", body = EntityMetadata),
+        (status = 400, content_type = "application/json", description = "The entity is not valid with respect to its entity type. The `failures` metadata contains the `BulkItemFailure` of the entity", body = VAR_STATUS),
+        (status = 409, content_type = "application/json", description = "The entity UUID is already used by another entity", body = VAR_STATUS),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
         (status = 404, description = "Entity Type URL was not found"),
@@ -120,7 +216,7 @@ async fn create_entity<S, A>(
     store_pool: Extension<Arc<S>>,
     authorization_api_pool: Extension<Arc<A>>,
     body: Json<CreateEntityRequest>,
-) -> Result<Json<EntityMetadata>, StatusCode>
+) -> Result<Json<EntityMetadata>, Response>
 where
     S: StorePool + Send + Sync,
     A: AuthorizationApiPool + Send + Sync,
@@ -135,12 +231,12 @@ where
 
     let mut store = store_pool.acquire().await.map_err(|report| {
         tracing::error!(error=?report, "Could not acquire store");
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
     let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
         tracing::error!(?error, "Could not acquire access to the authorization API");
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
     store
@@ -159,12 +255,58 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not create entity");
 
-            // Insertion/update errors are considered internal server errors.
-            StatusCode::INTERNAL_SERVER_ERROR
+            if report.contains::<EntityValidationError>() {
+                validation_report_to_response(&report)
+            } else if report.contains::<EntityUuidAlreadyExists>() {
+                entity_uuid_already_exists_response()
+            } else {
+                // Insertion/update errors are considered internal server errors.
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
         })
         .map(Json)
 }
 
+fn entity_uuid_already_exists_response() -> Response {
+    status_to_response(Status::new(
+        hash_status::StatusCode::AlreadyExists,
+        Some(
+            "Could not create the entity as its UUID is already used by another entity".to_owned(),
+        ),
+        vec![StatusPayloads::ErrorInfo(ErrorInfo::new(
+            HashMap::new(),
+            "ENTITY_UUID_ALREADY_EXISTS".to_owned(),
+        ))],
+    ))
+}
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct GetEntitiesByQueryResponse {
+    #[serde(flatten)]
+    subgraph: Subgraph,
+    /// The cursor to pass to the next query to read the next page of entities. It's only present
+    /// if a `limit` was specified and more entities may be available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = false)]
+    cursor: Option<EntityQueryCursor>,
+    /// The rank of each root entity for the `search` filters of the query in the order of the
+    /// roots. It's only present if the filter contains a `search` filter.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    search_ranks: Vec<EntitySearchRank>,
+}
+
+/// The relevance of an entity for the `search` filters of a query.
+///
+/// Higher ranks indicate a better match. Sorting by the `searchRank` path sorts entities by this
+/// rank.
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct EntitySearchRank {
+    vertex_id: EntityVertexId,
+    rank: f32,
+}
+
 #[utoipa::path(
     post,
     path = "/entities/query",
@@ -174,7 +316,7 @@ where
         ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
     ),
     responses(
-        (status = 200, content_type = "application/json", body = Subgraph, description = "A subgraph rooted at entities that satisfy the given query, each resolved to the requested depth."),
+        (status = 200, content_type = "application/json", body = GetEntitiesByQueryResponse, description = "A subgraph rooted at entities that satisfy the given query, each resolved to the requested depth."),
         (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
         (status = 500, description = "Store error occurred"),
     )
@@ -185,7 +327,7 @@ async fn get_entities_by_query<S, A>(
     store_pool: Extension<Arc<S>>,
     authorization_api_pool: Extension<Arc<A>>,
     Json(query): Json<serde_json::Value>,
-) -> Result<Json<Subgraph>, StatusCode>
+) -> Result<Json<GetEntitiesByQueryResponse>, StatusCode>
 where
     S: StorePool + Send + Sync,
     A: AuthorizationApiPool + Send + Sync,
@@ -208,7 +350,14 @@ where
         tracing::error!(?error, "Could not validate query");
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
-    let subgraph = store
+    query
+        .traversal_filters
+        .convert_parameters()
+        .map_err(|error| {
+            tracing::error!(?error, "Could not validate traversal filters");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let (subgraph, cursor, search_ranks) = store
         .get_entity(actor_id, &authorization_api, &query)
         .await
         .map_err(|report| {
@@ -216,7 +365,77 @@ where
             report_to_status_code(&report)
         })?;
 
-    Ok(Json(subgraph.into()))
+    let search_ranks = subgraph
+        .roots
+        .iter()
+        .filter_map(|vertex_id| match vertex_id {
+            GraphElementVertexId::KnowledgeGraph(vertex_id) => Some(EntitySearchRank {
+                vertex_id: *vertex_id,
+                rank: *search_ranks.get(vertex_id)?,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    Ok(Json(GetEntitiesByQueryResponse {
+        subgraph: subgraph.into(),
+        cursor,
+        search_ranks,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/aggregate",
+    request_body = EntityAggregationQuery,
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", body = [AggregationResult], description = "The aggregated values of the entities that satisfy the given query, one for each group."),
+
+        (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn aggregate_entities<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    Json(query): Json<serde_json::Value>,
+) -> Result<Json<Vec<AggregationResult>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let store = store_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut query = AggregationQuery::deserialize(&query).map_err(|error| {
+        tracing::error!(?error, "Could not deserialize query");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    query.filter.convert_parameters().map_err(|error| {
+        tracing::error!(?error, "Could not validate query");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    store
+        .aggregate_entities(actor_id, &authorization_api, &query)
+        .await
+        .map(Json)
+        .map_err(|report| {
+            tracing::error!(error=?report, ?query, "Could not aggregate entities");
+            report_to_status_code(&report)
+        })
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -229,6 +448,10 @@ struct UpdateEntityRequest {
     #[serde(flatten)]
     order: EntityLinkOrder,
     archived: bool,
+    /// If set, the entity is only updated if this is its latest edition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = false)]
+    expected_edition_id: Option<EntityEditionId>,
 }
 
 #[utoipa::path(
@@ -240,8 +463,10 @@ struct UpdateEntityRequest {
     ),
     responses(
         (status = 200, content_type = "application/json", description = "The metadata of the updated entity", body = EntityMetadata),
+        (status = 400, content_type = "application/json", description = "The entity is not valid with respect to its entity type. The `failures` metadata contains the `BulkItemFailure` of the entity", body = VAR_STATUS),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
         (status = 423, content_type = "text/plain", description = "The entity that should be updated was unexpectedly updated at the same time"),
+        (status = 409, content_type = "application/json", description = "The expected edition is not the latest edition of the entity. The `currentEditionId` metadata contains the latest edition", body = VAR_STATUS),
 
         (status = 404, description = "Entity ID or Entity Type URL was not found"),
         (status = 500, description = "Store error occurred"),
@@ -254,7 +479,7 @@ async fn update_entity<S, A>(
     store_pool: Extension<Arc<S>>,
     authorization_api_pool: Extension<Arc<A>>,
     body: Json<UpdateEntityRequest>,
-) -> Result<Json<EntityMetadata>, StatusCode>
+) -> Result<Json<EntityMetadata>, Response>
 where
     S: StorePool + Send + Sync,
     A: AuthorizationApiPool + Send + Sync,
@@ -265,16 +490,17 @@ where
         entity_type_id,
         order,
         archived,
+        expected_edition_id,
     }) = body;
 
     let mut store = store_pool.acquire().await.map_err(|report| {
         tracing::error!(error=?report, "Could not acquire store");
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
     let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
         tracing::error!(?error, "Could not acquire access to the authorization API");
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
     store
@@ -282,6 +508,7 @@ where
             actor_id,
             &mut authorization_api,
             entity_id,
+            expected_edition_id,
             None,
             archived,
             entity_type_id,
@@ -292,14 +519,1064 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not update entity");
 
+            if report.contains::<EntityEditionMismatch>() {
+                return edition_mismatch_response(&report);
+            }
+            if report.contains::<EntityValidationError>() {
+                return validation_report_to_response(&report);
+            }
+
+            if report.contains::<EntityDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else if report.contains::<RaceConditionOnUpdate>() {
+                StatusCode::LOCKED
+            } else {
+                // Insertion/update errors are considered internal server errors.
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            .into_response()
+        })
+        .map(Json)
+}
+
+/// Converts an [`EntityEditionMismatch`] into a response containing the latest edition of the
+/// entity.
+fn edition_mismatch_response<C>(report: &Report<C>) -> Response {
+    let current_edition_id = report.request_ref::<EntityEditionId>().next();
+    let current_edition_id = match serde_json::to_value(current_edition_id) {
+        Ok(current_edition_id) => current_edition_id,
+        Err(error) => {
+            tracing::error!(?error, "Could not serialize entity edition id");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    status_to_response(Status::new(
+        hash_status::StatusCode::Aborted,
+        Some(EntityEditionMismatch.to_string()),
+        vec![StatusPayloads::ErrorInfo(ErrorInfo::new(
+            HashMap::from([("currentEditionId".to_owned(), current_edition_id)]),
+            "ENTITY_EDITION_MISMATCH".to_owned(),
+        ))],
+    ))
+}
+
+/// Converts an [`EntityValidationError`] into the same response a bulk operation returns if it
+/// rejected a single item.
+fn validation_report_to_response<C>(report: &Report<C>) -> Response {
+    let failure = BulkItemFailure {
+        index: 0,
+        reason: EntityValidationError.to_string(),
+        validation_failures: report.request_ref::<ValidationFailure>().cloned().collect(),
+    };
+    bulk_failures_to_response("The entity was rejected, it was not written.", &[&failure])
+}
+
+/// Converts the error of a bulk operation into a response listing every rejected item.
+fn bulk_report_to_response<C>(report: &Report<C>) -> Response {
+    let failures = report.request_ref::<BulkItemFailure>().collect::<Vec<_>>();
+
+    if failures.is_empty() {
+        if report.contains::<EntityUuidAlreadyExists>() {
+            return entity_uuid_already_exists_response();
+        }
+        let status_code = if report.contains::<RaceConditionOnUpdate>() {
+            StatusCode::LOCKED
+        } else {
+            // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        return status_code.into_response();
+    }
+
+    bulk_failures_to_response(
+        "At least one of the provided entities was rejected, no entity was written.",
+        &failures,
+    )
+}
+
+fn bulk_failures_to_response(message: &str, failures: &[&BulkItemFailure]) -> Response {
+    let failures = match serde_json::to_value(failures) {
+        Ok(failures) => failures,
+        Err(error) => {
+            tracing::error!(?error, "Could not serialize bulk item failures");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    status_to_response(Status::new(
+        hash_status::StatusCode::InvalidArgument,
+        Some(message.to_owned()),
+        vec![StatusPayloads::ErrorInfo(ErrorInfo::new(
+            HashMap::from([("failures".to_owned(), failures)]),
+            "ENTITIES_REJECTED".to_owned(),
+        ))],
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/bulk",
+    request_body = [CreateEntityRequest],
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The metadata of the created entities in the order of the request", body = [EntityMetadata]),
+        (status = 400, content_type = "application/json", description = "At least one entity was rejected. The `failures` metadata lists a `BulkItemFailure` for every rejected entity", body = VAR_STATUS),
+        (status = 409, content_type = "application/json", description = "The UUID of at least one entity is already used by another entity", body = VAR_STATUS),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool, body))]
+async fn create_entities<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<Vec<CreateEntityRequest>>,
+) -> Result<Json<Vec<EntityMetadata>>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(entities) = body;
+
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    store
+        .create_entities(
+            actor_id,
+            &mut authorization_api,
+            entities.into_iter().map(|entity| EntityCreation {
+                owned_by_id: entity.owned_by_id,
+                entity_uuid: entity.entity_uuid,
+                decision_time: None,
+                archived: false,
+                entity_type_id: entity.entity_type_id,
+                properties: entity.properties,
+                link_data: entity.link_data,
+            }),
+        )
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not create entities");
+            bulk_report_to_response(&report)
+        })
+        .map(Json)
+}
+
+#[utoipa::path(
+    put,
+    path = "/entities/bulk",
+    request_body = [UpdateEntityRequest],
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The metadata of the updated entities in the order of the request", body = [EntityMetadata]),
+        (status = 400, content_type = "application/json", description = "At least one entity was rejected. The `failures` metadata lists a `BulkItemFailure` for every rejected entity", body = VAR_STATUS),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+        (status = 423, content_type = "text/plain", description = "One of the entities that should be updated was unexpectedly updated at the same time"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool, body))]
+async fn update_entities<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<Vec<UpdateEntityRequest>>,
+) -> Result<Json<Vec<EntityMetadata>>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(entities) = body;
+
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    store
+        .update_entities(
+            actor_id,
+            &mut authorization_api,
+            entities.into_iter().map(|entity| EntityUpdate {
+                entity_id: entity.entity_id,
+                expected_edition_id: entity.expected_edition_id,
+                decision_time: None,
+                archived: entity.archived,
+                entity_type_id: entity.entity_type_id,
+                properties: entity.properties,
+                link_order: entity.order,
+            }),
+        )
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not update entities");
+            bulk_report_to_response(&report)
+        })
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct PatchEntityRequest {
+    entity_id: EntityId,
+    /// The operations applied in order to the properties of the current edition of the entity.
+    patch: Vec<PropertyPatchOperation>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/entities",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The metadata of the new edition of the entity", body = EntityMetadata),
+        (status = 400, content_type = "application/json", description = "The patched entity is not valid with respect to its entity type. The `failures` metadata contains the `BulkItemFailure` of the entity", body = VAR_STATUS),
+        (status = 422, content_type = "text/plain", description = "The patch could not be applied"),
+        (status = 423, content_type = "text/plain", description = "The entity that should be patched was unexpectedly updated at the same time"),
+
+        (status = 404, description = "Entity ID was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = PatchEntityRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn patch_entity<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<PatchEntityRequest>,
+) -> Result<Json<EntityMetadata>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(PatchEntityRequest { entity_id, patch }) = body;
+
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    store
+        .patch_entity(actor_id, &mut authorization_api, entity_id, None, patch)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not patch entity");
+
+            if report.contains::<EntityValidationError>() {
+                return validation_report_to_response(&report);
+            }
+
             if report.contains::<EntityDoesNotExist>() {
                 StatusCode::NOT_FOUND
             } else if report.contains::<RaceConditionOnUpdate>() {
                 StatusCode::LOCKED
+            } else if report.contains::<PropertyPatchError>() {
+                StatusCode::UNPROCESSABLE_ENTITY
             } else {
                 // Insertion/update errors are considered internal server errors.
                 StatusCode::INTERNAL_SERVER_ERROR
             }
+            .into_response()
         })
         .map(Json)
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveEntityRequest {
+    entity_id: EntityId,
+    /// Whether link entities attached to the entity are archived as well.
+    #[serde(default)]
+    cascade: bool,
+}
+
+#[utoipa::path(
+    put,
+    path = "/entities/archive",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The metadata of the archived entity followed by the metadata of the archived links", body = [EntityMetadata]),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+        (status = 423, content_type = "text/plain", description = "The entity that should be archived was unexpectedly updated at the same time"),
+
+        (status = 403, description = "The actor is not allowed to archive the entity or one of the links"),
+        (status = 404, description = "Entity ID was not found"),
+        (status = 409, description = "Entity is already archived"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = ArchiveEntityRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn archive_entity<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<ArchiveEntityRequest>,
+) -> Result<Json<Vec<EntityMetadata>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(ArchiveEntityRequest { entity_id, cascade }) = body;
+
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .archive_entity(actor_id, &mut authorization_api, entity_id, cascade)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not archive entity");
+
+            if report.contains::<PermissionAssertion>() {
+                StatusCode::FORBIDDEN
+            } else if report.contains::<EntityDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else if report.contains::<EntityIsAlreadyArchived>() {
+                StatusCode::CONFLICT
+            } else if report.contains::<RaceConditionOnUpdate>() {
+                StatusCode::LOCKED
+            } else {
+                // Insertion/update errors are considered internal server errors.
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct UnarchiveEntityRequest {
+    entity_id: EntityId,
+    /// Whether archived link entities attached to the entity are unarchived as well.
+    #[serde(default)]
+    cascade: bool,
+}
+
+#[utoipa::path(
+    put,
+    path = "/entities/unarchive",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The metadata of the unarchived entity followed by the metadata of the unarchived links", body = [EntityMetadata]),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+        (status = 423, content_type = "text/plain", description = "The entity that should be unarchived was unexpectedly updated at the same time"),
+
+        (status = 403, description = "The actor is not allowed to unarchive the entity or one of the links"),
+        (status = 404, description = "Entity ID was not found"),
+        (status = 409, description = "Entity is not archived"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = UnarchiveEntityRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn unarchive_entity<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<UnarchiveEntityRequest>,
+) -> Result<Json<Vec<EntityMetadata>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(UnarchiveEntityRequest { entity_id, cascade }) = body;
+
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .unarchive_entity(actor_id, &mut authorization_api, entity_id, cascade)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not unarchive entity");
+
+            if report.contains::<PermissionAssertion>() {
+                StatusCode::FORBIDDEN
+            } else if report.contains::<EntityDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else if report.contains::<EntityIsNotArchived>() {
+                StatusCode::CONFLICT
+            } else if report.contains::<RaceConditionOnUpdate>() {
+                StatusCode::LOCKED
+            } else {
+                // Insertion/update errors are considered internal server errors.
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct GetEntityHistoryRequest {
+    entity_id: EntityId,
+    /// The temporal axes to read the editions from. By default, all editions along the decision
+    /// time axis as currently known are returned.
+    #[serde(default)]
+    temporal_axes: QueryTemporalAxesUnresolved,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/history",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "Every edition of the entity ordered by the start of its interval on the variable temporal axis", body = [EntityHistoryEntry]),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "The actor is not allowed to view the entity"),
+        (status = 404, description = "No edition of the entity was found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = GetEntityHistoryRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn get_entity_history<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<GetEntityHistoryRequest>,
+) -> Result<Json<Vec<EntityHistoryEntry>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(GetEntityHistoryRequest {
+        entity_id,
+        temporal_axes,
+    }) = body;
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_entity_history(actor_id, &authorization_api, entity_id, temporal_axes)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read entity history");
+
+            if report.contains::<PermissionAssertion>() {
+                StatusCode::FORBIDDEN
+            } else if report.contains::<EntityDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct DiffEntityRequest {
+    entity_id: EntityId,
+    /// The temporal axis both timestamps refer to. The other axis is pinned to the current time.
+    axis: DiffEntityAxis,
+    /// The point in time of the old state of the entity. Defaults to the current time.
+    #[serde(default)]
+    #[schema(nullable = false)]
+    old_timestamp: Option<Timestamp<VariableAxis>>,
+    /// The point in time of the new state of the entity. Defaults to the current time.
+    #[serde(default)]
+    #[schema(nullable = false)]
+    new_timestamp: Option<Timestamp<VariableAxis>>,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+enum DiffEntityAxis {
+    DecisionTime,
+    TransactionTime,
+}
+
+impl From<DiffEntityAxis> for TimeAxis {
+    fn from(axis: DiffEntityAxis) -> Self {
+        match axis {
+            DiffEntityAxis::DecisionTime => Self::DecisionTime,
+            DiffEntityAxis::TransactionTime => Self::TransactionTime,
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/diff",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The differences between the two states of the entity", body = EntityDiff),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "The actor is not allowed to view the entity"),
+        (status = 404, description = "The entity exists at neither point in time"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = DiffEntityRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn diff_entity<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<DiffEntityRequest>,
+) -> Result<Json<EntityDiff>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(DiffEntityRequest {
+        entity_id,
+        axis,
+        old_timestamp,
+        new_timestamp,
+    }) = body;
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .diff_entity(
+            actor_id,
+            &authorization_api,
+            entity_id,
+            TimeAxis::from(axis),
+            old_timestamp,
+            new_timestamp,
+        )
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not compare entity states");
+
+            if report.contains::<PermissionAssertion>() {
+                StatusCode::FORBIDDEN
+            } else if report.contains::<EntityDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })
+        .map(Json)
+}
+
+/// The maximum number of links a path between two entities may consist of.
+const MAX_ENTITY_PATH_DEPTH: u8 = 8;
+/// The maximum number of paths between two entities returned by a single request.
+const MAX_ENTITY_PATH_LIMIT: usize = 100;
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct GetEntityPathsResponse {
+    #[serde(flatten)]
+    subgraph: Subgraph,
+    /// The found paths, shortest first. Each path alternates between entities and links,
+    /// starting at the source and ending at the target entity.
+    paths: Vec<Vec<EntityId>>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/paths",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The shortest link paths between the two entities alongside a subgraph rooted at the source entity", body = GetEntityPathsResponse),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid or `maxDepth` or `limit` exceed their maximum"),
+
+        (status = 403, description = "The actor is not allowed to view the source entity"),
+        (status = 404, description = "The source entity was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = EntityPathQuery,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn get_entity_paths<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    Json(query): Json<EntityPathQuery>,
+) -> Result<Json<GetEntityPathsResponse>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    if query.max_depth > MAX_ENTITY_PATH_DEPTH || query.limit > MAX_ENTITY_PATH_LIMIT {
+        tracing::error!(
+            max_depth = query.max_depth,
+            limit = query.limit,
+            "Entity path query exceeds its bounds"
+        );
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let (subgraph, paths) = store
+        .get_entity_paths(actor_id, &authorization_api, &query)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not find paths between entities");
+
+            if report.contains::<PermissionAssertion>() {
+                StatusCode::FORBIDDEN
+            } else if report.contains::<EntityDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })?;
+
+    Ok(Json(GetEntityPathsResponse {
+        subgraph: subgraph.into(),
+        paths,
+    }))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct EraseEntityRequest {
+    entity_id: EntityId,
+    /// Whether link entities attached to the entity are erased as well.
+    #[serde(default)]
+    erase_links: bool,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/erase",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The IDs of the erased entity followed by the IDs of the erased links", body = [EntityId]),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "The actor is not allowed to delete the entity or one of the links"),
+        (status = 404, description = "Entity ID was not found"),
+        (status = 409, description = "Links are attached to the entity but `eraseLinks` was not set"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = EraseEntityRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn erase_entity<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<EraseEntityRequest>,
+) -> Result<Json<Vec<EntityId>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(EraseEntityRequest {
+        entity_id,
+        erase_links,
+    }) = body;
+
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .erase_entity(actor_id, &mut authorization_api, entity_id, erase_links)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not erase entity");
+
+            if report.contains::<PermissionAssertion>() {
+                StatusCode::FORBIDDEN
+            } else if report.contains::<EntityDoesNotExist>() {
+                StatusCode::NOT_FOUND
+            } else if report.contains::<EntityHasLinks>() {
+                StatusCode::CONFLICT
+            } else {
+                // Deletion errors are considered internal server errors.
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })
+        .map(Json)
+}
+
+/// The role granted to a subject when sharing an entity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+enum EntitySharingRole {
+    /// The subject is allowed to view the entity.
+    Viewer,
+    /// The subject is allowed to view and edit the entity.
+    Editor,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct EntityShare {
+    role: EntitySharingRole,
+    subject: SharingSubject,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct ShareEntityRequest {
+    entity_id: EntityId,
+    role: EntitySharingRole,
+    subject: SharingSubject,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct GetEntitySharesRequest {
+    entity_id: EntityId,
+}
+
+/// Checks that the entity currently exists.
+///
+/// Entities the actor is not allowed to view are reported as missing, so their existence is not
+/// revealed.
+async fn assert_entity_exists(
+    store: &(impl EntityStore + Sync),
+    authorization_api: &(impl AuthorizationApi + Sync),
+    actor_id: AccountId,
+    entity_id: EntityId,
+) -> Result<(), StatusCode> {
+    let (subgraph, ..) = store
+        .get_entity(
+            actor_id,
+            authorization_api,
+            &StructuralQuery {
+                filter: Filter::for_entity_by_entity_id(entity_id),
+                graph_resolve_depths: GraphResolveDepths::default(),
+                traversal_filters: KnowledgeGraphTraversalFilters::default(),
+                temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(None),
+                    variable: VariableTemporalAxisUnresolved::new(None, None),
+                },
+                order_by: Vec::new(),
+                limit: None,
+                cursor: None,
+            },
+        )
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read entity");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if subgraph.vertices.entities.is_empty() {
+        Err(StatusCode::NOT_FOUND)
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that the actor is allowed to share the entity.
+async fn assert_can_share_entity(
+    authorization_api: &(impl AuthorizationApi + Sync),
+    actor_id: AccountId,
+    entity_id: EntityId,
+) -> Result<(), StatusCode> {
+    let has_permission = authorization_api
+        .can_share_entity(actor_id, entity_id, Consistency::FullyConsistent)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "Could not check if the entity can be shared");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .has_permission;
+
+    if has_permission {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/share",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 201, description = "The entity was shared"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "The entity was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = ShareEntityRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn share_entity<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<ShareEntityRequest>,
+) -> Result<StatusCode, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(ShareEntityRequest {
+        entity_id,
+        role,
+        subject,
+    }) = body;
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_entity_exists(&store, &authorization_api, actor_id, entity_id).await?;
+    assert_can_share_entity(&authorization_api, actor_id, entity_id).await?;
+
+    match role {
+        EntitySharingRole::Viewer => {
+            authorization_api
+                .add_entity_viewer(subject.into(), entity_id)
+                .await
+        }
+        EntitySharingRole::Editor => {
+            authorization_api
+                .add_entity_editor(subject.into(), entity_id)
+                .await
+        }
+    }
+    .map_err(|error| {
+        tracing::error!(?error, "Could not share entity");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(StatusCode::CREATED)
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/unshare",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 204, description = "The entity is not shared with the subject anymore"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "The entity was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = ShareEntityRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn unshare_entity<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<ShareEntityRequest>,
+) -> Result<StatusCode, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(ShareEntityRequest {
+        entity_id,
+        role,
+        subject,
+    }) = body;
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_entity_exists(&store, &authorization_api, actor_id, entity_id).await?;
+    assert_can_share_entity(&authorization_api, actor_id, entity_id).await?;
+
+    match role {
+        EntitySharingRole::Viewer => {
+            authorization_api
+                .remove_entity_viewer(subject.into(), entity_id)
+                .await
+        }
+        EntitySharingRole::Editor => {
+            authorization_api
+                .remove_entity_editor(subject.into(), entity_id)
+                .await
+        }
+    }
+    .map_err(|error| {
+        tracing::error!(?error, "Could not unshare entity");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/shares",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The viewers and editors the entity is shared with", body = [EntityShare]),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "The entity was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = GetEntitySharesRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn get_entity_shares<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<GetEntitySharesRequest>,
+) -> Result<Json<Vec<EntityShare>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(GetEntitySharesRequest { entity_id }) = body;
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_entity_exists(&store, &authorization_api, actor_id, entity_id).await?;
+    assert_can_share_entity(&authorization_api, actor_id, entity_id).await?;
+
+    let (relations, _) = authorization_api
+        .get_entity_relations(entity_id, Consistency::FullyConsistent)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "Could not read entity relations");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(
+        relations
+            .into_iter()
+            .filter_map(|(relation, scope)| {
+                let role = match relation {
+                    EntityRelation::DirectOwner => return None,
+                    EntityRelation::DirectEditor => EntitySharingRole::Editor,
+                    EntityRelation::DirectViewer => EntitySharingRole::Viewer,
+                };
+                Some(EntityShare {
+                    role,
+                    subject: scope.into(),
+                })
+            })
+            .collect(),
+    ))
+}
@@ -2,7 +2,9 @@
 
 use std::{collections::hash_map, sync::Arc};
 
-use authorization::AuthorizationApiPool;
+use authorization::{
+    backend::PermissionAssertion, zanzibar::Consistency, AuthorizationApi, AuthorizationApiPool,
+};
 use axum::{
     http::StatusCode,
     response::Response,
@@ -10,8 +12,9 @@ use axum::{
     Extension, Router,
 };
 use graph_types::{
+    account::AccountId,
     ontology::{
-        EntityTypeMetadata, EntityTypeWithMetadata, OntologyElementMetadata,
+        EntityTypeId, EntityTypeMetadata, EntityTypeWithMetadata, OntologyElementMetadata,
         OntologyTemporalMetadata, OntologyTypeReference, PartialCustomEntityTypeMetadata,
         PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
     },
@@ -34,7 +37,7 @@ use crate::{
             report_to_status_code,
             status::status_to_response,
             utoipa_typedef::{subgraph::Subgraph, ListOrValue, MaybeListOfEntityType},
-            AuthenticatedUserHeader, RestApiStore,
+            AuthenticatedUserHeader, OntologyTypeSharingRole, RestApiStore, SharingSubject,
         },
     },
     ontology::{
@@ -43,7 +46,8 @@ use crate::{
     },
     store::{
         error::{BaseUrlAlreadyExists, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
-        ConflictBehavior, EntityTypeStore, StorePool,
+        query::{AggregationQuery, AggregationResult, EntityTypeAggregationQuery},
+        ConflictBehavior, EntityTypeStore, OntologyTypeDependents, StorePool,
     },
     subgraph::query::{EntityTypeStructuralQuery, StructuralQuery},
 };
@@ -54,9 +58,13 @@ use crate::{
         create_entity_type,
         load_external_entity_type,
         get_entity_types_by_query,
+        aggregate_entity_types,
         update_entity_type,
         archive_entity_type,
         unarchive_entity_type,
+        get_entity_type_dependents,
+        share_entity_type,
+        unshare_entity_type,
     ),
     components(
         schemas(
@@ -67,8 +75,12 @@ use crate::{
             UpdateEntityTypeRequest,
             EntityTypeQueryToken,
             EntityTypeStructuralQuery,
+            EntityTypeAggregationQuery,
+            AggregationResult,
             ArchiveEntityTypeRequest,
             UnarchiveEntityTypeRequest,
+            GetEntityTypeDependentsRequest,
+            ShareEntityTypeRequest,
         )
     ),
     tags(
@@ -94,9 +106,13 @@ impl RoutedResource for EntityTypeResource {
                     post(create_entity_type::<S, A>).put(update_entity_type::<S, A>),
                 )
                 .route("/query", post(get_entity_types_by_query::<S, A>))
+                .route("/aggregate", post(aggregate_entity_types::<S, A>))
                 .route("/load", post(load_external_entity_type::<S, A>))
                 .route("/archive", put(archive_entity_type::<S, A>))
-                .route("/unarchive", put(unarchive_entity_type::<S, A>)),
+                .route("/unarchive", put(unarchive_entity_type::<S, A>))
+                .route("/dependents", post(get_entity_type_dependents::<S, A>))
+                .route("/share", post(share_entity_type::<A>))
+                .route("/unshare", post(unshare_entity_type::<A>)),
         )
     }
 }
@@ -124,6 +140,7 @@ struct CreateEntityTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the created entity type", body = MaybeListOfEntityTypeMetadata),
         (status = 400, content_type = "application/json", description = "Provided request body is invalid", body = VAR_STATUS),
 
+        (status = 403, content_type = "application/json", description = "Permission denied", body = VAR_STATUS),
         (status = 409, content_type = "application/json", description = "Unable to create entity type in the datastore as the base entity type ID already exists", body = VAR_STATUS),
         (status = 500, content_type = "application/json", description = "Store error occurred", body = VAR_STATUS),
     ),
@@ -257,6 +274,14 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not create entity types");
 
+            if report.contains::<PermissionAssertion>() {
+                return status_to_response(Status::new(
+                    hash_status::StatusCode::PermissionDenied,
+                    Some("Permission denied".to_owned()),
+                    vec![],
+                ));
+            }
+
             if report.contains::<BaseUrlAlreadyExists>() {
                 let metadata =
                     report
@@ -478,6 +503,60 @@ where
     Ok(Json(subgraph.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/entity-types/aggregate",
+    request_body = EntityTypeAggregationQuery,
+    tag = "EntityType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", body = [AggregationResult], description = "The aggregated values of the entity types that satisfy the given query, one for each group."),
+
+        (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn aggregate_entity_types<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    Json(query): Json<serde_json::Value>,
+) -> Result<Json<Vec<AggregationResult>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let store = store_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut query = AggregationQuery::deserialize(&query).map_err(|error| {
+        tracing::error!(?error, "Could not deserialize query");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    query.filter.convert_parameters().map_err(|error| {
+        tracing::error!(?error, "Could not validate query");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    store
+        .aggregate_entity_types(actor_id, &authorization_api, &query)
+        .await
+        .map(Json)
+        .map_err(|report| {
+            tracing::error!(error=?report, ?query, "Could not aggregate entity types");
+            report_to_status_code(&report)
+        })
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct UpdateEntityTypeRequest {
@@ -501,6 +580,7 @@ struct UpdateEntityTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the updated entity type", body = OntologyElementMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Base entity type ID was not found"),
         (status = 500, description = "Store error occurred"),
     ),
@@ -554,6 +634,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not update entity type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -582,6 +666,7 @@ struct ArchiveEntityTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the updated entity type", body = OntologyTemporalMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Entity type ID was not found"),
         (status = 409, description = "Entity type ID is already archived"),
         (status = 500, description = "Store error occurred"),
@@ -617,6 +702,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not archive entity type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -648,6 +737,7 @@ struct UnarchiveEntityTypeRequest {
         (status = 200, content_type = "application/json", description = "The temporal metadata of the updated entity type", body = OntologyTemporalMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Entity type ID was not found"),
         (status = 409, description = "Entity type ID already exists and is not archived"),
         (status = 500, description = "Store error occurred"),
@@ -683,6 +773,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not unarchive entity type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -695,3 +789,213 @@ where
         })
         .map(Json)
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct GetEntityTypeDependentsRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_id: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-types/dependents",
+    tag = "EntityType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The types depending on the entity type and the number of affected entities", body = OntologyTypeDependents),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Entity type ID was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = GetEntityTypeDependentsRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn get_entity_type_dependents<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<GetEntityTypeDependentsRequest>,
+) -> Result<Json<OntologyTypeDependents>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(GetEntityTypeDependentsRequest { type_id }) = body;
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_entity_type_dependents(actor_id, &authorization_api, &type_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read dependents of entity type");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct ShareEntityTypeRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_id: VersionedUrl,
+    role: OntologyTypeSharingRole,
+    subject: SharingSubject,
+}
+
+/// Checks that the actor is allowed to share the entity type.
+async fn assert_can_share_entity_type(
+    authorization_api: &(impl AuthorizationApi + Sync),
+    actor_id: AccountId,
+    entity_type_id: EntityTypeId,
+) -> Result<(), StatusCode> {
+    let has_permission = authorization_api
+        .can_share_entity_type(actor_id, entity_type_id, Consistency::FullyConsistent)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "Could not check if the entity type can be shared");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .has_permission;
+
+    if has_permission {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-types/share",
+    tag = "EntityType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 201, description = "The entity type was shared"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 500, description = "Authorization error occurred"),
+    ),
+    request_body = ShareEntityTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(authorization_api_pool))]
+async fn share_entity_type<A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<ShareEntityTypeRequest>,
+) -> Result<StatusCode, StatusCode>
+where
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(ShareEntityTypeRequest {
+        type_id,
+        role,
+        subject,
+    }) = body;
+    let entity_type_id = EntityTypeId::from_url(&type_id);
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_can_share_entity_type(&authorization_api, actor_id, entity_type_id).await?;
+
+    match role {
+        OntologyTypeSharingRole::Viewer => {
+            authorization_api
+                .add_entity_type_viewer(subject.into(), entity_type_id)
+                .await
+        }
+        OntologyTypeSharingRole::Editor => {
+            authorization_api
+                .add_entity_type_editor(subject.into(), entity_type_id)
+                .await
+        }
+    }
+    .map_err(|error| {
+        tracing::error!(?error, "Could not share entity type");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(StatusCode::CREATED)
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-types/unshare",
+    tag = "EntityType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 204, description = "The entity type is not shared with the subject anymore"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 500, description = "Authorization error occurred"),
+    ),
+    request_body = ShareEntityTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(authorization_api_pool))]
+async fn unshare_entity_type<A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<ShareEntityTypeRequest>,
+) -> Result<StatusCode, StatusCode>
+where
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(ShareEntityTypeRequest {
+        type_id,
+        role,
+        subject,
+    }) = body;
+    let entity_type_id = EntityTypeId::from_url(&type_id);
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_can_share_entity_type(&authorization_api, actor_id, entity_type_id).await?;
+
+    match role {
+        OntologyTypeSharingRole::Viewer => {
+            authorization_api
+                .remove_entity_type_viewer(subject.into(), entity_type_id)
+                .await
+        }
+        OntologyTypeSharingRole::Editor => {
+            authorization_api
+                .remove_entity_type_editor(subject.into(), entity_type_id)
+                .await
+        }
+    }
+    .map_err(|error| {
+        tracing::error!(?error, "Could not unshare entity type");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
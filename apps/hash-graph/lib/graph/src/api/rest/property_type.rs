@@ -2,16 +2,20 @@
 
 use std::sync::Arc;
 
-use authorization::AuthorizationApiPool;
+use authorization::{
+    backend::PermissionAssertion, zanzibar::Consistency, AuthorizationApi, AuthorizationApiPool,
+};
 use axum::{
     http::StatusCode,
     routing::{post, put},
     Extension, Router,
 };
 use graph_types::{
+    account::AccountId,
     ontology::{
         OntologyElementMetadata, OntologyTemporalMetadata, OntologyTypeReference,
-        PartialCustomOntologyMetadata, PartialOntologyElementMetadata, PropertyTypeWithMetadata,
+        PartialCustomOntologyMetadata, PartialOntologyElementMetadata, PropertyTypeId,
+        PropertyTypeWithMetadata,
     },
     provenance::OwnedById,
 };
@@ -25,14 +29,16 @@ use crate::{
         json::Json,
         report_to_status_code,
         utoipa_typedef::{subgraph::Subgraph, ListOrValue, MaybeListOfPropertyType},
-        AuthenticatedUserHeader, RestApiStore,
+        AuthenticatedUserHeader, OntologyTypeSharingRole, RestApiStore, SharingSubject,
     },
     ontology::{
         domain_validator::{DomainValidator, ValidateOntologyType},
         patch_id_and_parse, PropertyTypeQueryToken,
     },
     store::{
-        error::VersionedUrlAlreadyExists, BaseUrlAlreadyExists, ConflictBehavior,
+        error::VersionedUrlAlreadyExists,
+        query::{AggregationQuery, AggregationResult, PropertyTypeAggregationQuery},
+        BaseUrlAlreadyExists, ConflictBehavior, OntologyTypeDependents,
         OntologyVersionDoesNotExist, PropertyTypeStore, StorePool,
     },
     subgraph::query::{PropertyTypeStructuralQuery, StructuralQuery},
@@ -44,9 +50,13 @@ use crate::{
         create_property_type,
         load_external_property_type,
         get_property_types_by_query,
+        aggregate_property_types,
         update_property_type,
         archive_property_type,
         unarchive_property_type,
+        get_property_type_dependents,
+        share_property_type,
+        unshare_property_type,
     ),
     components(
         schemas(
@@ -57,8 +67,12 @@ use crate::{
             UpdatePropertyTypeRequest,
             PropertyTypeQueryToken,
             PropertyTypeStructuralQuery,
+            PropertyTypeAggregationQuery,
+            AggregationResult,
             ArchivePropertyTypeRequest,
             UnarchivePropertyTypeRequest,
+            GetPropertyTypeDependentsRequest,
+            SharePropertyTypeRequest,
         )
     ),
     tags(
@@ -84,9 +98,13 @@ impl RoutedResource for PropertyTypeResource {
                     post(create_property_type::<S, A>).put(update_property_type::<S, A>),
                 )
                 .route("/query", post(get_property_types_by_query::<S, A>))
+                .route("/aggregate", post(aggregate_property_types::<S, A>))
                 .route("/load", post(load_external_property_type::<S, A>))
                 .route("/archive", put(archive_property_type::<S, A>))
-                .route("/unarchive", put(unarchive_property_type::<S, A>)),
+                .route("/unarchive", put(unarchive_property_type::<S, A>))
+                .route("/dependents", post(get_property_type_dependents::<S, A>))
+                .route("/share", post(share_property_type::<A>))
+                .route("/unshare", post(unshare_property_type::<A>)),
         )
     }
 }
@@ -111,6 +129,7 @@ struct CreatePropertyTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the created property type", body = MaybeListOfOntologyElementMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 409, description = "Unable to create property type in the store as the base property type ID already exists"),
         (status = 500, description = "Store error occurred"),
     ),
@@ -186,6 +205,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not create property types");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<BaseUrlAlreadyExists>() {
                 return StatusCode::CONFLICT;
             }
@@ -321,6 +344,60 @@ where
     Ok(Json(subgraph.into()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/property-types/aggregate",
+    request_body = PropertyTypeAggregationQuery,
+    tag = "PropertyType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", body = [AggregationResult], description = "The aggregated values of the property types that satisfy the given query, one for each group."),
+
+        (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn aggregate_property_types<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    Json(query): Json<serde_json::Value>,
+) -> Result<Json<Vec<AggregationResult>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let store = store_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut query = AggregationQuery::deserialize(&query).map_err(|error| {
+        tracing::error!(?error, "Could not deserialize query");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    query.filter.convert_parameters().map_err(|error| {
+        tracing::error!(?error, "Could not validate query");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    store
+        .aggregate_property_types(actor_id, &authorization_api, &query)
+        .await
+        .map(Json)
+        .map_err(|report| {
+            tracing::error!(error=?report, ?query, "Could not aggregate property types");
+            report_to_status_code(&report)
+        })
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct UpdatePropertyTypeRequest {
@@ -341,6 +418,7 @@ struct UpdatePropertyTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the updated property type", body = OntologyElementMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Base property type ID was not found"),
         (status = 500, description = "Store error occurred"),
     ),
@@ -387,6 +465,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not update property type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -415,6 +497,7 @@ struct ArchivePropertyTypeRequest {
         (status = 200, content_type = "application/json", description = "The metadata of the updated property type", body = OntologyTemporalMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Property type ID was not found"),
         (status = 409, description = "Property type ID is already archived"),
         (status = 500, description = "Store error occurred"),
@@ -450,6 +533,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not archive property type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -481,6 +568,7 @@ struct UnarchivePropertyTypeRequest {
         (status = 200, content_type = "application/json", description = "The temporal metadata of the updated property type", body = OntologyTemporalMetadata),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
+        (status = 403, description = "Permission denied"),
         (status = 404, description = "Property type ID was not found"),
         (status = 409, description = "Property type ID already exists and is not archived"),
         (status = 500, description = "Store error occurred"),
@@ -516,6 +604,10 @@ where
         .map_err(|report| {
             tracing::error!(error=?report, "Could not unarchive property type");
 
+            if report.contains::<PermissionAssertion>() {
+                return StatusCode::FORBIDDEN;
+            }
+
             if report.contains::<OntologyVersionDoesNotExist>() {
                 return StatusCode::NOT_FOUND;
             }
@@ -528,3 +620,213 @@ where
         })
         .map(Json)
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct GetPropertyTypeDependentsRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_id: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/property-types/dependents",
+    tag = "PropertyType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The types depending on the property type and the number of affected entities", body = OntologyTypeDependents),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Property type ID was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = GetPropertyTypeDependentsRequest,
+)]
+#[tracing::instrument(level = "info", skip(store_pool, authorization_api_pool))]
+async fn get_property_type_dependents<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<GetPropertyTypeDependentsRequest>,
+) -> Result<Json<OntologyTypeDependents>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(GetPropertyTypeDependentsRequest { type_id }) = body;
+
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_property_type_dependents(actor_id, &authorization_api, &type_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read dependents of property type");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct SharePropertyTypeRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_id: VersionedUrl,
+    role: OntologyTypeSharingRole,
+    subject: SharingSubject,
+}
+
+/// Checks that the actor is allowed to share the property type.
+async fn assert_can_share_property_type(
+    authorization_api: &(impl AuthorizationApi + Sync),
+    actor_id: AccountId,
+    property_type_id: PropertyTypeId,
+) -> Result<(), StatusCode> {
+    let has_permission = authorization_api
+        .can_share_property_type(actor_id, property_type_id, Consistency::FullyConsistent)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "Could not check if the property type can be shared");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .has_permission;
+
+    if has_permission {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/property-types/share",
+    tag = "PropertyType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 201, description = "The property type was shared"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 500, description = "Authorization error occurred"),
+    ),
+    request_body = SharePropertyTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(authorization_api_pool))]
+async fn share_property_type<A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<SharePropertyTypeRequest>,
+) -> Result<StatusCode, StatusCode>
+where
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(SharePropertyTypeRequest {
+        type_id,
+        role,
+        subject,
+    }) = body;
+    let property_type_id = PropertyTypeId::from_url(&type_id);
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_can_share_property_type(&authorization_api, actor_id, property_type_id).await?;
+
+    match role {
+        OntologyTypeSharingRole::Viewer => {
+            authorization_api
+                .add_property_type_viewer(subject.into(), property_type_id)
+                .await
+        }
+        OntologyTypeSharingRole::Editor => {
+            authorization_api
+                .add_property_type_editor(subject.into(), property_type_id)
+                .await
+        }
+    }
+    .map_err(|error| {
+        tracing::error!(?error, "Could not share property type");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(StatusCode::CREATED)
+}
+
+#[utoipa::path(
+    post,
+    path = "/property-types/unshare",
+    tag = "PropertyType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 204, description = "The property type is not shared with the subject anymore"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 403, description = "Permission denied"),
+        (status = 500, description = "Authorization error occurred"),
+    ),
+    request_body = SharePropertyTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(authorization_api_pool))]
+async fn unshare_property_type<A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    authorization_api_pool: Extension<Arc<A>>,
+    body: Json<SharePropertyTypeRequest>,
+) -> Result<StatusCode, StatusCode>
+where
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(SharePropertyTypeRequest {
+        type_id,
+        role,
+        subject,
+    }) = body;
+    let property_type_id = PropertyTypeId::from_url(&type_id);
+
+    let mut authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+        tracing::error!(?error, "Could not acquire access to the authorization API");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    assert_can_share_property_type(&authorization_api, actor_id, property_type_id).await?;
+
+    match role {
+        OntologyTypeSharingRole::Viewer => {
+            authorization_api
+                .remove_property_type_viewer(subject.into(), property_type_id)
+                .await
+        }
+        OntologyTypeSharingRole::Editor => {
+            authorization_api
+                .remove_property_type_editor(subject.into(), property_type_id)
+                .await
+        }
+    }
+    .map_err(|error| {
+        tracing::error!(?error, "Could not unshare property type");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
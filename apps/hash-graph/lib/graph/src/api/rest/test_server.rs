@@ -81,6 +81,7 @@ async fn restore_snapshot(
                 codec::JsonLinesDecoder::default(),
             ),
             10_000,
+            &mut NoAuthorization,
         )
         .await
         .map_err(|report| {
@@ -0,0 +1,291 @@
+//! Web routes for registering webhooks.
+
+use std::{net::IpAddr, sync::Arc};
+
+use authorization::AuthorizationApiPool;
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    routing::{delete, get, post},
+    Extension, Router,
+};
+use reqwest::Url;
+use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    api::rest::{
+        api_resource::RoutedResource, json::Json, report_to_status_code, AuthenticatedUserHeader,
+    },
+    store::{
+        error::WebhookDoesNotExist, StorePool, Webhook, WebhookDeadLetter, WebhookFilter,
+        WebhookStore,
+    },
+    webhook::is_public_address,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        register_webhook,
+        get_webhooks,
+        delete_webhook,
+        get_webhook_dead_letters,
+    ),
+    components(
+        schemas(
+            RegisterWebhookRequest,
+            WebhookFilter,
+            Webhook,
+            WebhookDeadLetter,
+        )
+    ),
+    tags(
+        (name = "Webhook", description = "Webhook management API")
+    )
+)]
+pub struct WebhookResource;
+
+impl RoutedResource for WebhookResource {
+    /// Create routes for interacting with webhooks.
+    fn routes<S, A>() -> Router
+    where
+        S: StorePool + Send + Sync + 'static,
+        A: AuthorizationApiPool + Send + Sync + 'static,
+    {
+        Router::new().nest(
+            "/webhooks",
+            Router::new()
+                .route(
+                    "/",
+                    post(register_webhook::<S, A>).get(get_webhooks::<S, A>),
+                )
+                .route("/:webhook_id", delete(delete_webhook::<S, A>))
+                .route(
+                    "/:webhook_id/dead-letters",
+                    get(get_webhook_dead_letters::<S, A>),
+                ),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct RegisterWebhookRequest {
+    /// The HTTP(S) URL the changes are sent to.
+    ///
+    /// The host has to resolve to public addresses only.
+    url: String,
+    /// The secret used to sign the payloads.
+    ///
+    /// It's stored in plain text and is never returned.
+    secret: String,
+    #[serde(default)]
+    filter: WebhookFilter,
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    request_body = RegisterWebhookRequest,
+    tag = "Webhook",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The registered webhook", body = Webhook),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid or the URL does not point to a public address"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(store_pool, request))]
+async fn register_webhook<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    Json(request): Json<RegisterWebhookRequest>,
+) -> Result<Json<Webhook>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    if request.secret.is_empty() || !is_public_url(&request.url).await {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .register_webhook(actor_id, request.url, request.secret, request.filter)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not register webhook");
+
+            // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
+
+/// Returns if `url` is an HTTP(S) URL, which only resolves to public addresses.
+///
+/// Webhooks are registered by users, so they must not be able to make the graph send requests to
+/// itself or to other services in its network, e.g. the metadata service of a cloud provider.
+async fn is_public_url(url: &str) -> bool {
+    let Ok(url) = Url::parse(url) else {
+        return false;
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+    let Some(port) = url.port_or_known_default() else {
+        return false;
+    };
+
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+
+    // IPv6 hosts are enclosed in brackets.
+    let addresses = if let Ok(address) = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        vec![address]
+    } else {
+        match tokio::net::lookup_host((host, port)).await {
+            Ok(addresses) => addresses.map(|address| address.ip()).collect(),
+            Err(error) => {
+                tracing::debug!(%error, host, "Could not resolve webhook host");
+                return false;
+            }
+        }
+    };
+
+    !addresses.is_empty() && addresses.into_iter().all(is_public_address)
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "Webhook",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The webhooks registered by the actor", body = [Webhook]),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(store_pool))]
+async fn get_webhooks<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+) -> Result<Json<Vec<Webhook>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_webhooks(actor_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read webhooks");
+            report_to_status_code(&report)
+        })
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{webhook_id}",
+    tag = "Webhook",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+        ("webhook_id" = Uuid, Path, description = "The ID of the webhook to delete"),
+    ),
+    responses(
+        (status = 204, description = "The webhook was deleted"),
+
+        (status = 404, description = "The actor has not registered a webhook with the ID"),
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(store_pool))]
+async fn delete_webhook<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    Path(webhook_id): Path<Uuid>,
+    store_pool: Extension<Arc<S>>,
+) -> Result<StatusCode, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let mut store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .delete_webhook(actor_id, webhook_id)
+        .await
+        .map_err(|report| {
+            if report.contains::<WebhookDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+            tracing::error!(error=?report, "Could not delete webhook");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}/dead-letters",
+    tag = "Webhook",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+        ("webhook_id" = Uuid, Path, description = "The ID of the webhook"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The payloads which could not be delivered to the webhook", body = [WebhookDeadLetter]),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(level = "info", skip(store_pool))]
+async fn get_webhook_dead_letters<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    Path(webhook_id): Path<Uuid>,
+    store_pool: Extension<Arc<S>>,
+) -> Result<Json<Vec<WebhookDeadLetter>>, StatusCode>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let store = store_pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_webhook_dead_letters(actor_id, webhook_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read dead letters");
+            report_to_status_code(&report)
+        })
+        .map(Json)
+}
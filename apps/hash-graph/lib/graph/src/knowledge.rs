@@ -1,6 +1,40 @@
 //! TODO: DOC - This module will encapsulate logic for Entities and Links, it's a parallel to the
 //!  `ontology` module, i.e you have Ontologies and Knowledge-Graphs
 
+mod diff;
+mod patch;
 mod query;
+mod validation;
 
-pub use self::query::{EntityQueryPath, EntityQueryPathVisitor, EntityQueryToken};
+pub use self::{
+    diff::{
+        diff_entities, diff_properties, EntityDiff, EntityTypeIdDiff, LinkDataDiff, PropertyDiff,
+    },
+    patch::{patch_properties, PropertyPatchError, PropertyPatchOperation},
+    query::{EntityQueryPath, EntityQueryPathVisitor, EntityQueryToken},
+    validation::{
+        collect_property_type_references, property_type_references, EntityValidationError,
+        EntityValidationSchemas, LinkValidationSchemas, ValidationFailure,
+    },
+};
+
+#[cfg(test)]
+mod test_helper {
+    use std::borrow::Cow;
+
+    use graph_types::knowledge::entity::EntityProperties;
+    use serde_json::Value;
+
+    use crate::store::query::PathToken;
+
+    pub const NAME: &str = "https://blockprotocol.org/@alice/types/property-type/name/";
+    pub const ADDRESS: &str = "https://blockprotocol.org/@alice/types/property-type/address/";
+
+    pub fn properties(value: Value) -> EntityProperties {
+        serde_json::from_value(value).expect("could not parse properties")
+    }
+
+    pub fn field(name: &str) -> PathToken<'static> {
+        PathToken::Field(Cow::Owned(name.to_owned()))
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use graph_types::knowledge::{
+    entity::{Entity, EntityMetadata, EntityProperties},
+    link::LinkData,
+};
+use serde::Serialize;
+use serde_json::Value;
+use type_system::url::VersionedUrl;
+use utoipa::ToSchema;
+
+use crate::store::query::{JsonPath, PathToken};
+
+/// A single difference between two versions of [`EntityProperties`].
+///
+/// Objects are compared key by key and arrays element by element, so only the values, which
+/// actually changed, are reported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum PropertyDiff {
+    /// The value at `path` did not exist in the old version.
+    Added {
+        #[schema(value_type = String)]
+        path: JsonPath<'static>,
+        #[schema(value_type = Object)]
+        new: Value,
+    },
+    /// The value at `path` does not exist in the new version.
+    Removed {
+        #[schema(value_type = String)]
+        path: JsonPath<'static>,
+        #[schema(value_type = Object)]
+        old: Value,
+    },
+    /// The value at `path` was replaced.
+    Changed {
+        #[schema(value_type = String)]
+        path: JsonPath<'static>,
+        #[schema(value_type = Object)]
+        old: Value,
+        #[schema(value_type = Object)]
+        new: Value,
+    },
+}
+
+/// Returns the differences between `old` and `new` ordered by their path.
+#[must_use]
+pub fn diff_properties(old: &EntityProperties, new: &EntityProperties) -> Vec<PropertyDiff> {
+    let old = old
+        .properties()
+        .iter()
+        .map(|(base_url, value)| (base_url.as_str(), value))
+        .collect::<BTreeMap<_, _>>();
+    let new = new
+        .properties()
+        .iter()
+        .map(|(base_url, value)| (base_url.as_str(), value))
+        .collect::<BTreeMap<_, _>>();
+    let keys = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+
+    let mut differ = PropertyDiffer::default();
+    for key in keys {
+        differ
+            .path
+            .push(PathToken::Field(Cow::Owned((*key).to_owned())));
+        differ.diff(old.get(key).copied(), new.get(key).copied());
+        differ.path.pop();
+    }
+    differ.diffs
+}
+
+/// The change of the [`EntityType`] of an [`Entity`].
+///
+/// [`EntityType`]: type_system::EntityType
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityTypeIdDiff {
+    #[schema(value_type = Option<String>)]
+    pub old: Option<VersionedUrl>,
+    #[schema(value_type = Option<String>)]
+    pub new: Option<VersionedUrl>,
+}
+
+/// The change of the [`LinkData`] of an [`Entity`], i.e. its endpoints or its order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkDataDiff {
+    pub old: Option<LinkData>,
+    pub new: Option<LinkData>,
+}
+
+/// The differences between two states of an [`Entity`].
+///
+/// A state is `None` if the [`Entity`] did not exist at that point in time. In this case all
+/// values of the other state are reported as added or removed respectively.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityDiff {
+    pub old_metadata: Option<EntityMetadata>,
+    pub new_metadata: Option<EntityMetadata>,
+    pub property_diff: Vec<PropertyDiff>,
+    /// Only set if the entity type changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_type_id_diff: Option<EntityTypeIdDiff>,
+    /// Only set if the link data changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_data_diff: Option<LinkDataDiff>,
+}
+
+/// Returns the differences between the `old` and the `new` state of an [`Entity`].
+#[must_use]
+pub fn diff_entities(old: Option<Entity>, new: Option<Entity>) -> EntityDiff {
+    let empty = EntityProperties::empty();
+    let property_diff = diff_properties(
+        old.as_ref().map_or(&empty, |entity| &entity.properties),
+        new.as_ref().map_or(&empty, |entity| &entity.properties),
+    );
+
+    let old_entity_type_id = old.as_ref().map(|entity| entity.metadata.entity_type_id());
+    let new_entity_type_id = new.as_ref().map(|entity| entity.metadata.entity_type_id());
+    let entity_type_id_diff =
+        (old_entity_type_id != new_entity_type_id).then(|| EntityTypeIdDiff {
+            old: old_entity_type_id.cloned(),
+            new: new_entity_type_id.cloned(),
+        });
+
+    let old_link_data = old.as_ref().and_then(|entity| entity.link_data);
+    let new_link_data = new.as_ref().and_then(|entity| entity.link_data);
+    let link_data_diff = (old_link_data != new_link_data).then_some(LinkDataDiff {
+        old: old_link_data,
+        new: new_link_data,
+    });
+
+    EntityDiff {
+        old_metadata: old.map(|entity| entity.metadata),
+        new_metadata: new.map(|entity| entity.metadata),
+        property_diff,
+        entity_type_id_diff,
+        link_data_diff,
+    }
+}
+
+#[derive(Default)]
+struct PropertyDiffer {
+    path: Vec<PathToken<'static>>,
+    diffs: Vec<PropertyDiff>,
+}
+
+impl PropertyDiffer {
+    fn path(&self) -> JsonPath<'static> {
+        JsonPath::from_path_tokens(self.path.clone())
+    }
+
+    fn diff(&mut self, old: Option<&Value>, new: Option<&Value>) {
+        match (old, new) {
+            (None, None) => {}
+            (None, Some(new)) => self.diffs.push(PropertyDiff::Added {
+                path: self.path(),
+                new: new.clone(),
+            }),
+            (Some(old), None) => self.diffs.push(PropertyDiff::Removed {
+                path: self.path(),
+                old: old.clone(),
+            }),
+            (Some(old), Some(new)) if old == new => {}
+            (Some(Value::Object(old)), Some(Value::Object(new))) => {
+                let keys = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+                for key in keys {
+                    self.path.push(PathToken::Field(Cow::Owned(key.clone())));
+                    self.diff(old.get(key), new.get(key));
+                    self.path.pop();
+                }
+            }
+            (Some(Value::Array(old)), Some(Value::Array(new))) => {
+                for index in 0..old.len().max(new.len()) {
+                    self.path.push(PathToken::Index(index));
+                    self.diff(old.get(index), new.get(index));
+                    self.path.pop();
+                }
+            }
+            (Some(old), Some(new)) => self.diffs.push(PropertyDiff::Changed {
+                path: self.path(),
+                old: old.clone(),
+                new: new.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::knowledge::test_helper::{field, properties, ADDRESS, NAME};
+
+    #[test]
+    fn equal_properties() {
+        let alice = properties(json!({ NAME: "Alice" }));
+        assert!(diff_properties(&alice, &alice).is_empty());
+    }
+
+    #[test]
+    fn top_level_properties() {
+        let old = properties(json!({ NAME: "Alice" }));
+        let new = properties(json!({ ADDRESS: "Main Street" }));
+
+        assert_eq!(
+            diff_properties(&old, &new),
+            [
+                PropertyDiff::Added {
+                    path: JsonPath::from_path_tokens(vec![field(ADDRESS)]),
+                    new: json!("Main Street"),
+                },
+                PropertyDiff::Removed {
+                    path: JsonPath::from_path_tokens(vec![field(NAME)]),
+                    old: json!("Alice"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_properties() {
+        let old = properties(json!({ ADDRESS: { "street": "Main Street", "lines": ["a", "b"] } }));
+        let new = properties(json!({ ADDRESS: { "street": "High Street", "lines": ["a"] } }));
+
+        assert_eq!(
+            diff_properties(&old, &new),
+            [
+                PropertyDiff::Removed {
+                    path: JsonPath::from_path_tokens(vec![
+                        field(ADDRESS),
+                        field("lines"),
+                        PathToken::Index(1),
+                    ]),
+                    old: json!("b"),
+                },
+                PropertyDiff::Changed {
+                    path: JsonPath::from_path_tokens(vec![field(ADDRESS), field("street")]),
+                    old: json!("Main Street"),
+                    new: json!("High Street"),
+                },
+            ]
+        );
+    }
+}
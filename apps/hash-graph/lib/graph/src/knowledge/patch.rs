@@ -0,0 +1,320 @@
+use std::fmt;
+
+use error_stack::{Context, Report, ResultExt};
+use graph_types::knowledge::entity::EntityProperties;
+use serde::Deserialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::store::query::{JsonPath, PathToken};
+
+#[derive(Debug)]
+#[must_use]
+pub struct PropertyPatchError;
+
+impl Context for PropertyPatchError {}
+
+impl fmt::Display for PropertyPatchError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Could not apply the patch to the entity properties")
+    }
+}
+
+/// A single operation to modify [`EntityProperties`], modelled after JSON Patch (RFC 6902).
+///
+/// Paths are lists of object keys and array indices starting with the base URL of a property. The
+/// key `-` refers to the end of an array when adding a value.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum PropertyPatchOperation {
+    /// Adds `value` at `path`, replaces an existing object member, or inserts it into an array.
+    Add {
+        #[schema(value_type = Vec<Object>)]
+        path: Vec<PathToken<'static>>,
+        #[schema(value_type = Object)]
+        value: Value,
+    },
+    /// Removes the value at `path`.
+    Remove {
+        #[schema(value_type = Vec<Object>)]
+        path: Vec<PathToken<'static>>,
+    },
+    /// Replaces the existing value at `path` with `value`.
+    Replace {
+        #[schema(value_type = Vec<Object>)]
+        path: Vec<PathToken<'static>>,
+        #[schema(value_type = Object)]
+        value: Value,
+    },
+    /// Removes the value at `from` and adds it at `path`.
+    Move {
+        #[schema(value_type = Vec<Object>)]
+        from: Vec<PathToken<'static>>,
+        #[schema(value_type = Vec<Object>)]
+        path: Vec<PathToken<'static>>,
+    },
+    /// Adds a copy of the value at `from` at `path`.
+    Copy {
+        #[schema(value_type = Vec<Object>)]
+        from: Vec<PathToken<'static>>,
+        #[schema(value_type = Vec<Object>)]
+        path: Vec<PathToken<'static>>,
+    },
+    /// Fails the patch if the value at `path` is not equal to `value`.
+    Test {
+        #[schema(value_type = Vec<Object>)]
+        path: Vec<PathToken<'static>>,
+        #[schema(value_type = Object)]
+        value: Value,
+    },
+}
+
+/// Applies the `operations` in order to a copy of `properties` and returns the result.
+///
+/// Either all operations are applied or the patch fails as a whole.
+///
+/// # Errors
+///
+/// - if a path does not point to an existing value, or for `add`, to an existing parent
+/// - if a `test` operation fails
+/// - if the result is not a valid [`EntityProperties`] object
+pub fn patch_properties(
+    properties: &EntityProperties,
+    operations: &[PropertyPatchOperation],
+) -> Result<EntityProperties, Report<PropertyPatchError>> {
+    let mut document = serde_json::to_value(properties).change_context(PropertyPatchError)?;
+
+    for (index, operation) in operations.iter().enumerate() {
+        apply_operation(&mut document, operation)
+            .attach_printable_lazy(|| format!("operation index: {index}"))?;
+    }
+
+    serde_json::from_value(document)
+        .change_context(PropertyPatchError)
+        .attach_printable("the patched properties are not a valid properties object")
+}
+
+fn apply_operation(
+    document: &mut Value,
+    operation: &PropertyPatchOperation,
+) -> Result<(), Report<PropertyPatchError>> {
+    match operation {
+        PropertyPatchOperation::Add { path, value } => add(document, path, value.clone()),
+        PropertyPatchOperation::Remove { path } => remove(document, path).map(drop),
+        PropertyPatchOperation::Replace { path, value } => {
+            *resolve_mut(document, path).ok_or_else(|| path_not_found(path))? = value.clone();
+            Ok(())
+        }
+        PropertyPatchOperation::Move { from, path } => {
+            if path.starts_with(from) && path.len() > from.len() {
+                return Err(Report::new(PropertyPatchError).attach_printable(format!(
+                    "cannot move {} into one of its children",
+                    JsonPath::from_path_tokens(from.clone())
+                )));
+            }
+            let value = remove(document, from)?;
+            add(document, path, value)
+        }
+        PropertyPatchOperation::Copy { from, path } => {
+            let value = resolve(document, from)
+                .ok_or_else(|| path_not_found(from))?
+                .clone();
+            add(document, path, value)
+        }
+        PropertyPatchOperation::Test { path, value } => {
+            if resolve(document, path) == Some(value) {
+                Ok(())
+            } else {
+                Err(Report::new(PropertyPatchError).attach_printable(format!(
+                    "test failed, the value at {} is not {value}",
+                    JsonPath::from_path_tokens(path.clone())
+                )))
+            }
+        }
+    }
+}
+
+fn path_not_found(path: &[PathToken<'static>]) -> Report<PropertyPatchError> {
+    Report::new(PropertyPatchError).attach_printable(format!(
+        "path {} does not exist",
+        JsonPath::from_path_tokens(path.to_vec())
+    ))
+}
+
+fn resolve<'v>(mut value: &'v Value, path: &[PathToken<'_>]) -> Option<&'v Value> {
+    for token in path {
+        value = match (value, token) {
+            (Value::Object(object), PathToken::Field(key)) => object.get(key.as_ref())?,
+            (Value::Array(array), PathToken::Index(index)) => array.get(*index)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn resolve_mut<'v>(mut value: &'v mut Value, path: &[PathToken<'_>]) -> Option<&'v mut Value> {
+    for token in path {
+        value = match (value, token) {
+            (Value::Object(object), PathToken::Field(key)) => object.get_mut(key.as_ref())?,
+            (Value::Array(array), PathToken::Index(index)) => array.get_mut(*index)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn add(
+    document: &mut Value,
+    path: &[PathToken<'static>],
+    value: Value,
+) -> Result<(), Report<PropertyPatchError>> {
+    let Some((last, parent_path)) = path.split_last() else {
+        *document = value;
+        return Ok(());
+    };
+
+    match (resolve_mut(document, parent_path), last) {
+        (Some(Value::Object(object)), PathToken::Field(key)) => {
+            object.insert(key.clone().into_owned(), value);
+        }
+        (Some(Value::Array(array)), PathToken::Index(index)) if *index <= array.len() => {
+            array.insert(*index, value);
+        }
+        (Some(Value::Array(array)), PathToken::Field(key)) if key == "-" => {
+            array.push(value);
+        }
+        _ => return Err(path_not_found(path)),
+    }
+    Ok(())
+}
+
+fn remove(
+    document: &mut Value,
+    path: &[PathToken<'static>],
+) -> Result<Value, Report<PropertyPatchError>> {
+    let (last, parent_path) = path.split_last().ok_or_else(|| {
+        Report::new(PropertyPatchError).attach_printable("cannot remove the properties object")
+    })?;
+
+    match (resolve_mut(document, parent_path), last) {
+        (Some(Value::Object(object)), PathToken::Field(key)) => object.remove(key.as_ref()),
+        (Some(Value::Array(array)), PathToken::Index(index)) if *index < array.len() => {
+            Some(array.remove(*index))
+        }
+        _ => None,
+    }
+    .ok_or_else(|| path_not_found(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::knowledge::test_helper::{field, properties, ADDRESS, NAME};
+
+    #[test]
+    fn nested_operations() {
+        let old = properties(json!({
+            NAME: "Alice",
+            ADDRESS: { "street": "Main Street", "lines": ["a", "b"] }
+        }));
+
+        let new = patch_properties(
+            &old,
+            &[
+                PropertyPatchOperation::Test {
+                    path: vec![field(NAME)],
+                    value: json!("Alice"),
+                },
+                PropertyPatchOperation::Replace {
+                    path: vec![field(ADDRESS), field("street")],
+                    value: json!("High Street"),
+                },
+                PropertyPatchOperation::Remove {
+                    path: vec![field(ADDRESS), field("lines"), PathToken::Index(0)],
+                },
+                PropertyPatchOperation::Add {
+                    path: vec![field(ADDRESS), field("lines"), field("-")],
+                    value: json!("c"),
+                },
+                PropertyPatchOperation::Copy {
+                    from: vec![field(ADDRESS), field("street")],
+                    path: vec![field(ADDRESS), field("city")],
+                },
+            ],
+        )
+        .expect("could not apply patch");
+
+        assert_eq!(
+            new,
+            properties(json!({
+                NAME: "Alice",
+                ADDRESS: { "street": "High Street", "city": "High Street", "lines": ["b", "c"] }
+            }))
+        );
+    }
+
+    #[test]
+    fn failed_test_rejects_patch() {
+        let old = properties(json!({ NAME: "Alice" }));
+
+        assert!(
+            patch_properties(
+                &old,
+                &[
+                    PropertyPatchOperation::Remove {
+                        path: vec![field(NAME)],
+                    },
+                    PropertyPatchOperation::Test {
+                        path: vec![field(NAME)],
+                        value: json!("Alice"),
+                    },
+                ]
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn missing_path() {
+        let old = properties(json!({ NAME: "Alice" }));
+
+        assert!(
+            patch_properties(
+                &old,
+                &[PropertyPatchOperation::Replace {
+                    path: vec![field(ADDRESS)],
+                    value: json!("Main Street"),
+                }]
+            )
+            .is_err()
+        );
+        assert!(
+            patch_properties(
+                &old,
+                &[PropertyPatchOperation::Add {
+                    path: vec![field(ADDRESS), field("street")],
+                    value: json!("Main Street"),
+                }]
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn invalid_property_key() {
+        let old = properties(json!({ NAME: "Alice" }));
+
+        assert!(
+            patch_properties(
+                &old,
+                &[PropertyPatchOperation::Move {
+                    from: vec![field(NAME)],
+                    path: vec![field("name")],
+                }]
+            )
+            .is_err()
+        );
+    }
+}
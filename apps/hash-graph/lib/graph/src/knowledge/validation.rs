@@ -0,0 +1,670 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use error_stack::{Context, Report};
+use graph_types::knowledge::entity::EntityProperties;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::store::query::{JsonPath, PathToken};
+
+#[derive(Debug)]
+#[must_use]
+pub struct EntityValidationError;
+
+impl fmt::Display for EntityValidationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("the entity is not valid with respect to its entity type")
+    }
+}
+
+impl Context for EntityValidationError {}
+
+/// A constraint of an ontology type, which is violated by the value at `path`.
+///
+/// Every failure is attached to the [`Report`] returned by [`EntityValidationSchemas::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationFailure {
+    pub path: JsonPath<'static>,
+    pub reason: String,
+}
+
+impl fmt::Display for ValidationFailure {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "`{}`: {}", self.path, self.reason)
+    }
+}
+
+/// The schemas of the entity types of a link entity's endpoints.
+///
+/// An endpoint is `None` if the entity does not exist.
+#[derive(Debug, Default)]
+pub struct LinkValidationSchemas {
+    /// The entity type of the left entity and all of its ancestors.
+    pub left_entity_types: Option<Vec<Value>>,
+    /// The entity type of the right entity and all of its ancestors.
+    pub right_entity_types: Option<Vec<Value>>,
+}
+
+/// The schemas of all ontology types required to validate an entity.
+///
+/// The schemas are the JSON representations of the types as stored in the database and are keyed
+/// by their `$id`.
+#[derive(Debug, Default)]
+pub struct EntityValidationSchemas {
+    /// The entity type of the entity and all of its ancestors.
+    pub entity_types: Vec<Value>,
+    pub property_types: HashMap<String, Value>,
+    pub data_types: HashMap<String, Value>,
+    /// The compiled `pattern`s of the data types keyed by the pattern.
+    ///
+    /// Patterns, which are not contained, are compiled when they are used.
+    pub patterns: HashMap<String, Result<Regex, regex::Error>>,
+    /// Only set if the entity is a link entity.
+    pub link: Option<LinkValidationSchemas>,
+}
+
+/// Returns the `$id`s of the property types referenced by the `properties` of an entity type or a
+/// property object.
+pub fn property_type_references(properties: &Value) -> impl Iterator<Item = &str> {
+    properties
+        .as_object()
+        .into_iter()
+        .flat_map(Map::values)
+        .filter_map(|property| {
+            property
+                .get("items")
+                .unwrap_or(property)
+                .get("$ref")
+                .and_then(Value::as_str)
+        })
+}
+
+/// Collects the `$id`s of the property types and data types referenced by the property type.
+pub fn collect_property_type_references<'s>(
+    property_type: &'s Value,
+    property_types: &mut Vec<&'s str>,
+    data_types: &mut Vec<&'s str>,
+) {
+    fn collect_property_values<'s>(
+        property_values: &'s [Value],
+        property_types: &mut Vec<&'s str>,
+        data_types: &mut Vec<&'s str>,
+    ) {
+        for values in property_values {
+            if let Some(data_type) = values.get("$ref").and_then(Value::as_str) {
+                data_types.push(data_type);
+            } else if let Some(properties) = values.get("properties") {
+                property_types.extend(property_type_references(properties));
+            } else if let Some(items) = values.get("items") {
+                collect_property_values(one_of(items), property_types, data_types);
+            }
+        }
+    }
+
+    collect_property_values(one_of(property_type), property_types, data_types);
+}
+
+fn one_of(schema: &Value) -> &[Value] {
+    schema
+        .get("oneOf")
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+fn schema_id(schema: &Value) -> Option<&str> {
+    schema.get("$id").and_then(Value::as_str)
+}
+
+impl EntityValidationSchemas {
+    /// Compiles the `pattern`s of the `data_types`, so they can be reused for every validated
+    /// value.
+    #[must_use]
+    pub fn compile_patterns(
+        data_types: &HashMap<String, Value>,
+    ) -> HashMap<String, Result<Regex, regex::Error>> {
+        data_types
+            .values()
+            .filter_map(|data_type| data_type.get("pattern").and_then(Value::as_str))
+            .map(|pattern| (pattern.to_owned(), Regex::new(pattern)))
+            .collect()
+    }
+
+    /// Validates the properties of an entity and, if the entity is a link, its destination.
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityValidationError`] if any constraint is violated. A [`ValidationFailure`] is
+    ///   attached for every violated constraint.
+    pub fn validate(
+        &self,
+        properties: &EntityProperties,
+    ) -> Result<(), Report<EntityValidationError>> {
+        self.validation_failures(properties)
+            .into_iter()
+            .fold(
+                None,
+                |report: Option<Report<EntityValidationError>>, failure| {
+                    Some(match report {
+                        Some(report) => report.attach(failure),
+                        None => Report::new(EntityValidationError).attach(failure),
+                    })
+                },
+            )
+            .map_or(Ok(()), Err)
+    }
+
+    /// Returns all constraints violated by the properties of an entity and, if the entity is a
+    /// link, its destination.
+    #[must_use]
+    pub fn validation_failures(&self, properties: &EntityProperties) -> Vec<ValidationFailure> {
+        let mut validator = Validator {
+            schemas: self,
+            path: Vec::new(),
+            failures: Vec::new(),
+        };
+
+        let mut defined_properties = HashMap::new();
+        let mut required_properties = HashSet::new();
+        for entity_type in &self.entity_types {
+            if let Some(properties) = entity_type.get("properties").and_then(Value::as_object) {
+                defined_properties
+                    .extend(properties.iter().map(|(key, value)| (key.as_str(), value)));
+            }
+            if let Some(required) = entity_type.get("required").and_then(Value::as_array) {
+                required_properties.extend(required.iter().filter_map(Value::as_str));
+            }
+        }
+
+        let properties = properties
+            .properties()
+            .iter()
+            .map(|(base_url, value)| (base_url.as_str(), value));
+        validator.validate_object(properties, &defined_properties, &required_properties);
+
+        if let Some(link) = &self.link {
+            validator.validate_link_destination(&self.entity_types, link);
+        }
+
+        validator.failures
+    }
+}
+
+struct Validator<'s> {
+    schemas: &'s EntityValidationSchemas,
+    path: Vec<PathToken<'static>>,
+    failures: Vec<ValidationFailure>,
+}
+
+impl Validator<'_> {
+    fn fail(&mut self, reason: impl Into<String>) {
+        self.failures.push(ValidationFailure {
+            path: JsonPath::from_path_tokens(self.path.clone()),
+            reason: reason.into(),
+        });
+    }
+
+    fn validate_object<'v>(
+        &mut self,
+        object: impl IntoIterator<Item = (&'v str, &'v Value)>,
+        defined_properties: &HashMap<&str, &Value>,
+        required_properties: &HashSet<&str>,
+    ) {
+        let mut missing_properties = required_properties.clone();
+        for (key, value) in object {
+            missing_properties.remove(key);
+
+            self.path.push(PathToken::Field(Cow::Owned(key.to_owned())));
+            if let Some(property) = defined_properties.get(key) {
+                self.validate_property(value, property);
+            } else {
+                self.fail("the property is not defined by the type");
+            }
+            self.path.pop();
+        }
+
+        let mut missing_properties = missing_properties.into_iter().collect::<Vec<_>>();
+        missing_properties.sort_unstable();
+        for key in missing_properties {
+            self.path.push(PathToken::Field(Cow::Owned(key.to_owned())));
+            self.fail("the property is required but missing");
+            self.path.pop();
+        }
+    }
+
+    /// Validates a value against a property type reference or an array of property type
+    /// references.
+    fn validate_property(&mut self, value: &Value, property: &Value) {
+        let Some(items) = property.get("items") else {
+            self.validate_property_type(value, property);
+            return;
+        };
+
+        let Some(array) = value.as_array() else {
+            self.fail("expected an array");
+            return;
+        };
+        self.validate_array_length(array, property);
+        for (index, item) in array.iter().enumerate() {
+            self.path.push(PathToken::Index(index));
+            self.validate_property_type(item, items);
+            self.path.pop();
+        }
+    }
+
+    fn validate_property_type(&mut self, value: &Value, reference: &Value) {
+        let Some(property_type_id) = reference.get("$ref").and_then(Value::as_str) else {
+            self.fail("the type does not reference a property type");
+            return;
+        };
+        let Some(property_type) = self.schemas.property_types.get(property_type_id) else {
+            self.fail(format!(
+                "the property type `{property_type_id}` does not exist"
+            ));
+            return;
+        };
+
+        self.validate_one_of(value, one_of(property_type));
+    }
+
+    /// Validates that the value matches at least one of the possible property values.
+    ///
+    /// If there is only one option, the failures of that option are reported directly.
+    fn validate_one_of(&mut self, value: &Value, property_values: &[Value]) {
+        let mut failures = Vec::new();
+        for values in property_values {
+            let mut validator = Validator {
+                schemas: self.schemas,
+                path: self.path.clone(),
+                failures: Vec::new(),
+            };
+            validator.validate_property_values(value, values);
+            if validator.failures.is_empty() {
+                return;
+            }
+            failures = validator.failures;
+        }
+
+        if property_values.len() == 1 {
+            self.failures.extend(failures);
+        } else {
+            self.fail("the value does not match any of the possible values of the property type");
+        }
+    }
+
+    fn validate_property_values(&mut self, value: &Value, property_values: &Value) {
+        if let Some(data_type_id) = property_values.get("$ref").and_then(Value::as_str) {
+            self.validate_data_type(value, data_type_id);
+            return;
+        }
+
+        match property_values.get("type").and_then(Value::as_str) {
+            Some("object") => {
+                let Some(object) = value.as_object() else {
+                    self.fail("expected an object");
+                    return;
+                };
+                let defined_properties = property_values
+                    .get("properties")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten()
+                    .map(|(key, value)| (key.as_str(), value))
+                    .collect();
+                let required_properties = property_values
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .collect();
+                self.validate_object(
+                    object.iter().map(|(key, value)| (key.as_str(), value)),
+                    &defined_properties,
+                    &required_properties,
+                );
+            }
+            Some("array") => {
+                let Some(array) = value.as_array() else {
+                    self.fail("expected an array");
+                    return;
+                };
+                self.validate_array_length(array, property_values);
+                let items = property_values.get("items").map_or(&[][..], one_of);
+                for (index, item) in array.iter().enumerate() {
+                    self.path.push(PathToken::Index(index));
+                    self.validate_one_of(item, items);
+                    self.path.pop();
+                }
+            }
+            _ => self.fail("the property type contains unsupported property values"),
+        }
+    }
+
+    fn validate_array_length(&mut self, array: &[Value], schema: &Value) {
+        let length = array.len() as u64;
+        if let Some(min_items) = schema.get("minItems").and_then(Value::as_u64) {
+            if length < min_items {
+                self.fail(format!(
+                    "expected at least {min_items} items but got {length}"
+                ));
+            }
+        }
+        if let Some(max_items) = schema.get("maxItems").and_then(Value::as_u64) {
+            if length > max_items {
+                self.fail(format!(
+                    "expected at most {max_items} items but got {length}"
+                ));
+            }
+        }
+    }
+
+    fn validate_data_type(&mut self, value: &Value, data_type_id: &str) {
+        let Some(data_type) = self.schemas.data_types.get(data_type_id) else {
+            self.fail(format!("the data type `{data_type_id}` does not exist"));
+            return;
+        };
+
+        let json_type = data_type.get("type").and_then(Value::as_str);
+        let matches_type = match json_type {
+            Some("string") => value.is_string(),
+            Some("number") => value.is_number(),
+            Some("boolean") => value.is_boolean(),
+            Some("null") => value.is_null(),
+            Some("object") => value.is_object(),
+            Some("array") => value.is_array(),
+            _ => false,
+        };
+        if !matches_type {
+            self.fail(format!(
+                "expected a value of type `{}` as specified by `{data_type_id}`",
+                json_type.unwrap_or("unknown")
+            ));
+            return;
+        }
+
+        if let Some(constant) = data_type.get("const") {
+            if value != constant {
+                self.fail(format!("expected the constant value {constant}"));
+            }
+        }
+        if let Some(variants) = data_type.get("enum").and_then(Value::as_array) {
+            if !variants.contains(value) {
+                self.fail("the value is not one of the allowed values");
+            }
+        }
+
+        if let Some(number) = value.as_f64() {
+            self.validate_number(number, data_type);
+        }
+        if let Some(string) = value.as_str() {
+            self.validate_string(string, data_type);
+        }
+    }
+
+    fn validate_number(&mut self, number: f64, data_type: &Value) {
+        let bound = |keyword: &str| data_type.get(keyword).and_then(Value::as_f64);
+
+        if let Some(minimum) = bound("minimum") {
+            if number < minimum {
+                self.fail(format!(
+                    "expected a number greater than or equal to {minimum}"
+                ));
+            }
+        }
+        if let Some(maximum) = bound("maximum") {
+            if number > maximum {
+                self.fail(format!("expected a number less than or equal to {maximum}"));
+            }
+        }
+        if let Some(minimum) = bound("exclusiveMinimum") {
+            if number <= minimum {
+                self.fail(format!("expected a number greater than {minimum}"));
+            }
+        }
+        if let Some(maximum) = bound("exclusiveMaximum") {
+            if number >= maximum {
+                self.fail(format!("expected a number less than {maximum}"));
+            }
+        }
+    }
+
+    fn validate_string(&mut self, string: &str, data_type: &Value) {
+        let length = string.chars().count() as u64;
+        if let Some(min_length) = data_type.get("minLength").and_then(Value::as_u64) {
+            if length < min_length {
+                self.fail(format!("expected at least {min_length} characters"));
+            }
+        }
+        if let Some(max_length) = data_type.get("maxLength").and_then(Value::as_u64) {
+            if length > max_length {
+                self.fail(format!("expected at most {max_length} characters"));
+            }
+        }
+        if let Some(pattern) = data_type.get("pattern").and_then(Value::as_str) {
+            let compiled;
+            let regex = if let Some(regex) = self.schemas.patterns.get(pattern) {
+                regex
+            } else {
+                compiled = Regex::new(pattern);
+                &compiled
+            };
+            match regex {
+                Ok(regex) if regex.is_match(string) => {}
+                Ok(_) => self.fail(format!(
+                    "expected the value to match the pattern `{pattern}`"
+                )),
+                Err(error) => self.fail(format!("the pattern `{pattern}` is invalid: {error}")),
+            }
+        }
+    }
+
+    /// Validates that the right entity's type is one of the destinations, which the left entity's
+    /// type allows for the link's entity type.
+    ///
+    /// Link entity types are matched by their `$id` including their ancestors, so a link
+    /// constrained on a parent link type also constrains its children. The same applies to the
+    /// destinations.
+    fn validate_link_destination(
+        &mut self,
+        link_entity_types: &[Value],
+        link: &LinkValidationSchemas,
+    ) {
+        let link_entity_type_ids = link_entity_types
+            .iter()
+            .filter_map(schema_id)
+            .collect::<HashSet<_>>();
+        let (Some(left_entity_types), Some(right_entity_types)) =
+            (&link.left_entity_types, &link.right_entity_types)
+        else {
+            for (field, entity_types) in [
+                ("leftEntityId", &link.left_entity_types),
+                ("rightEntityId", &link.right_entity_types),
+            ] {
+                if entity_types.is_none() {
+                    self.path.push(PathToken::Field(Cow::Borrowed(field)));
+                    self.fail("the linked entity does not exist");
+                    self.path.pop();
+                }
+            }
+            return;
+        };
+
+        let right_entity_type_ids = right_entity_types
+            .iter()
+            .filter_map(schema_id)
+            .collect::<HashSet<_>>();
+
+        let destinations = left_entity_types
+            .iter()
+            .filter_map(|entity_type| entity_type.get("links").and_then(Value::as_object))
+            .flatten()
+            .filter(|(link_entity_type_id, _)| {
+                link_entity_type_ids.contains(link_entity_type_id.as_str())
+            })
+            .filter_map(|(_, link)| link.get("items"))
+            .map(one_of)
+            .filter(|destinations| !destinations.is_empty());
+
+        for destinations in destinations {
+            let allowed = destinations
+                .iter()
+                .filter_map(|destination| destination.get("$ref").and_then(Value::as_str))
+                .any(|destination| right_entity_type_ids.contains(destination));
+            if !allowed {
+                self.path
+                    .push(PathToken::Field(Cow::Borrowed("rightEntityId")));
+                self.fail(
+                    "the type of the right entity is not a valid destination of the link for the \
+                     type of the left entity",
+                );
+                self.path.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const NAME: &str = "https://example.com/@alice/types/property-type/name/";
+    const AGE: &str = "https://example.com/@alice/types/property-type/age/";
+    const TAGS: &str = "https://example.com/@alice/types/property-type/tags/";
+    const UNKNOWN: &str = "https://example.com/@alice/types/property-type/unknown/";
+    const TEXT: &str = "https://example.com/data-type/text/v/1";
+
+    fn schemas() -> EntityValidationSchemas {
+        EntityValidationSchemas {
+            entity_types: vec![json!({
+                "$id": "https://example.com/@alice/types/entity-type/person/v/1",
+                "properties": {
+                    NAME: { "$ref": format!("{NAME}v/1") },
+                    AGE: { "$ref": format!("{AGE}v/1") },
+                    TAGS: {
+                        "type": "array",
+                        "items": { "$ref": format!("{TAGS}v/1") },
+                        "maxItems": 2,
+                    },
+                },
+                "required": [NAME],
+            })],
+            property_types: HashMap::from([
+                (
+                    format!("{NAME}v/1"),
+                    json!({ "oneOf": [{ "$ref": "https://example.com/data-type/text/v/1" }] }),
+                ),
+                (
+                    format!("{AGE}v/1"),
+                    json!({ "oneOf": [{ "$ref": "https://example.com/data-type/age/v/1" }] }),
+                ),
+                (
+                    format!("{TAGS}v/1"),
+                    json!({ "oneOf": [{ "$ref": "https://example.com/data-type/text/v/1" }] }),
+                ),
+            ]),
+            data_types: HashMap::from([
+                (
+                    "https://example.com/data-type/text/v/1".to_owned(),
+                    json!({ "type": "string" }),
+                ),
+                (
+                    "https://example.com/data-type/age/v/1".to_owned(),
+                    json!({ "type": "number", "minimum": 0 }),
+                ),
+            ]),
+            link: None,
+        }
+    }
+
+    fn failures(properties: Value) -> Vec<String> {
+        let properties: EntityProperties =
+            serde_json::from_value(properties).expect("could not parse properties");
+        match schemas().validate(&properties) {
+            Ok(()) => Vec::new(),
+            Err(report) => {
+                let mut failures = report
+                    .frames()
+                    .filter_map(|frame| frame.downcast_ref::<ValidationFailure>())
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                failures.sort();
+                failures
+            }
+        }
+    }
+
+    #[test]
+    fn valid_properties() {
+        assert!(failures(json!({ NAME: "Alice", AGE: 42, TAGS: ["a", "b"] })).is_empty());
+    }
+
+    #[test]
+    fn invalid_properties() {
+        assert_eq!(
+            failures(json!({
+                AGE: -1,
+                TAGS: ["a", 1, "c"],
+                UNKNOWN: true,
+            })),
+            [
+                format!(r#"`$."{AGE}"`: expected a number greater than or equal to 0"#),
+                format!(r#"`$."{NAME}"`: the property is required but missing"#),
+                format!(r#"`$."{TAGS}"`: expected at most 2 items but got 3"#),
+                format!(
+                    r#"`$."{TAGS}"[1]`: expected a value of type `string` as specified by `{TEXT}`"#
+                ),
+                format!(r#"`$."{UNKNOWN}"`: the property is not defined by the type"#),
+            ]
+        );
+    }
+
+    #[test]
+    fn link_destination() {
+        let mut schemas = EntityValidationSchemas {
+            entity_types: vec![json!({ "$id": "https://example.com/link/v/1" })],
+            link: Some(LinkValidationSchemas {
+                left_entity_types: Some(vec![json!({
+                    "$id": "https://example.com/person/v/1",
+                    "links": {
+                        "https://example.com/link/v/1": {
+                            "type": "array",
+                            "items": { "oneOf": [{ "$ref": "https://example.com/person/v/1" }] },
+                        },
+                    },
+                })]),
+                right_entity_types: Some(vec![json!({ "$id": "https://example.com/person/v/1" })]),
+            }),
+            ..EntityValidationSchemas::default()
+        };
+        assert!(schemas.validate(&EntityProperties::empty()).is_ok());
+
+        schemas
+            .link
+            .as_mut()
+            .expect("link schemas should be set")
+            .right_entity_types = Some(vec![json!({ "$id": "https://example.com/company/v/1" })]);
+        assert!(schemas.validate(&EntityProperties::empty()).is_err());
+
+        schemas
+            .link
+            .as_mut()
+            .expect("link schemas should be set")
+            .right_entity_types = None;
+        assert_eq!(
+            schemas
+                .validation_failures(&EntityProperties::empty())
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [r#"`$."rightEntityId"`: the linked entity does not exist"#]
+        );
+    }
+}
@@ -30,6 +30,8 @@ pub mod snapshot;
 
 pub mod logging;
 
+pub mod webhook;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Environment {
     Development,
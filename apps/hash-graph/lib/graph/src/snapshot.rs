@@ -8,6 +8,7 @@ mod ontology;
 mod restore;
 
 use async_trait::async_trait;
+use authorization::AuthorizationApi;
 use error_stack::{ensure, Context, Report, Result, ResultExt};
 use futures::{stream, SinkExt, Stream, StreamExt, TryFutureExt, TryStreamExt};
 use graph_types::{
@@ -264,6 +265,9 @@ impl<C: AsClient> SnapshotStore<C> {
     ///      this stage might fail. In this case, the transaction is rolled back and the error is
     ///      returned.
     ///
+    /// After the snapshot was committed, the owner relations of the restored ontology types are
+    /// written to the authorization backend.
+    ///
     /// If the input stream contains an `Err` value, the snapshot restore is aborted and the error
     /// is returned.
     ///
@@ -271,10 +275,12 @@ impl<C: AsClient> SnapshotStore<C> {
     ///
     /// - If reading a record from the provided stream fails
     /// - If writing a record into the datastore fails
-    pub async fn restore_snapshot(
+    /// - If writing the relations of the restored records fails
+    pub async fn restore_snapshot<A: AuthorizationApi + Send>(
         &mut self,
         snapshot: impl Stream<Item = Result<SnapshotEntry, impl Context>> + Send + 'static,
         chunk_size: usize,
+        authorization_api: &mut A,
     ) -> Result<(), SnapshotRestoreError> {
         tracing::info!("snapshot restore started");
 
@@ -359,6 +365,12 @@ impl<C: AsClient> SnapshotStore<C> {
 
         ensure!(found_metadata, SnapshotRestoreError::MissingMetadata);
 
+        self.0
+            .write_ontology_type_relations(authorization_api)
+            .await
+            .change_context(SnapshotRestoreError::Write)
+            .attach_printable("unable to write the relations of the restored ontology types")?;
+
         tracing::info!("snapshot restore finished");
 
         Ok(())
@@ -68,6 +68,8 @@ impl Sink<EntitySnapshotRecord> for EntitySender {
             .change_context(SnapshotRestoreError::Read)
             .attach_printable("could not send entity id")?;
 
+        let provenance = entity.metadata.custom.provenance;
+
         self.edition
             .start_send_unpin(EntityEditionRow {
                 entity_edition_id: entity.metadata.record_id.edition_id,
@@ -78,7 +80,8 @@ impl Sink<EntitySnapshotRecord> for EntitySender {
                 right_to_left_order: entity
                     .link_data
                     .and_then(|link_data| link_data.order.right_to_left),
-                record_created_by_id: entity.metadata.custom.provenance.record_created_by_id,
+                record_created_by_id: provenance.record_created_by_id,
+                record_archived_by_id: provenance.record_archived_by_id,
                 archived: entity.metadata.custom.archived,
                 entity_type_base_url: entity.metadata.entity_type_id.base_url.as_str().to_owned(),
                 entity_type_version: OntologyTypeVersion::new(
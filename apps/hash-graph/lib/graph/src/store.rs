@@ -3,12 +3,15 @@ pub mod error;
 pub mod query;
 
 mod account;
+mod batch;
+mod change_feed;
 mod config;
 mod knowledge;
 mod migration;
 mod ontology;
 mod pool;
 mod record;
+mod webhook;
 
 mod fetcher;
 mod postgres;
@@ -17,18 +20,27 @@ use async_trait::async_trait;
 
 pub use self::{
     account::AccountStore,
+    batch::{BatchLinkData, BatchStore, EntityReference, WriteOperation, WriteOperationOutput},
+    change_feed::{Change, ChangeCursor, ChangeFeedStore, ChangeKind, ChangedRecordId, Changes},
     config::{DatabaseConnectionInfo, DatabaseType},
     error::{
         BaseUrlAlreadyExists, InsertionError, OntologyVersionDoesNotExist, QueryError, StoreError,
         UpdateError,
     },
     fetcher::{FetchingPool, TypeFetcher},
-    knowledge::EntityStore,
+    knowledge::{
+        BulkItemFailure, EntityCreation, EntityHistoryEntry, EntityPathQuery, EntityStore,
+        EntityUpdate,
+    },
     migration::{Migration, MigrationState, StoreMigration},
-    ontology::{DataTypeStore, EntityTypeStore, PropertyTypeStore},
+    ontology::{
+        DataTypeStore, EntityTypeStore, OntologyTypeDependencyReference, OntologyTypeDependent,
+        OntologyTypeDependents, PropertyTypeStore,
+    },
     pool::StorePool,
     postgres::{AsClient, PostgresStore, PostgresStorePool},
     record::Record,
+    webhook::{Webhook, WebhookDeadLetter, WebhookFilter, WebhookStore, WebhookTarget},
 };
 
 /// Describes the API of a store implementation.
@@ -39,11 +51,25 @@ pub use self::{
 /// raised depending on the implementation, e.g. connection issues.
 #[async_trait]
 pub trait Store:
-    AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore
+    AccountStore
+    + DataTypeStore
+    + PropertyTypeStore
+    + EntityTypeStore
+    + EntityStore
+    + BatchStore
+    + ChangeFeedStore
+    + WebhookStore
 {
 }
 impl<S> Store for S where
-    S: AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore
+    S: AccountStore
+        + DataTypeStore
+        + PropertyTypeStore
+        + EntityTypeStore
+        + EntityStore
+        + BatchStore
+        + ChangeFeedStore
+        + WebhookStore
 {
 }
 
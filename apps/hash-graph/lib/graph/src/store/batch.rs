@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use authorization::AuthorizationApi;
+use error_stack::Result;
+use graph_types::{
+    account::AccountId,
+    knowledge::{
+        entity::{EntityId, EntityMetadata, EntityProperties, EntityUuid},
+        link::EntityLinkOrder,
+    },
+    ontology::{
+        EntityTypeMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
+        PartialEntityTypeMetadata, PartialOntologyElementMetadata,
+    },
+    provenance::OwnedById,
+};
+use serde::{Deserialize, Serialize};
+use type_system::{url::VersionedUrl, DataType, EntityType, PropertyType};
+use utoipa::ToSchema;
+
+use crate::store::InsertionError;
+
+/// Refers to an [`Entity`] which either exists already or is created by an earlier operation of
+/// the same batch.
+///
+/// [`Entity`]: graph_types::knowledge::entity::Entity
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum EntityReference {
+    Existing(EntityId),
+    /// The entity created by the operation at `operationIndex` of the batch.
+    #[serde(rename_all = "camelCase")]
+    Created {
+        operation_index: usize,
+    },
+}
+
+/// The endpoints of a link entity created as part of a batch.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct BatchLinkData {
+    pub left_entity: EntityReference,
+    pub right_entity: EntityReference,
+    #[serde(flatten)]
+    pub order: EntityLinkOrder,
+}
+
+/// A single write operation of a batch passed to [`BatchStore::write_batch`].
+#[derive(Debug)]
+pub enum WriteOperation {
+    CreateDataType {
+        schema: Box<DataType>,
+        metadata: PartialOntologyElementMetadata,
+    },
+    CreatePropertyType {
+        schema: Box<PropertyType>,
+        metadata: PartialOntologyElementMetadata,
+    },
+    CreateEntityType {
+        schema: Box<EntityType>,
+        metadata: PartialEntityTypeMetadata,
+    },
+    CreateEntity {
+        owned_by_id: OwnedById,
+        entity_uuid: Option<EntityUuid>,
+        entity_type_id: VersionedUrl,
+        properties: EntityProperties,
+        link_data: Option<BatchLinkData>,
+    },
+    ArchiveDataType(VersionedUrl),
+    ArchivePropertyType(VersionedUrl),
+    ArchiveEntityType(VersionedUrl),
+    ArchiveEntity {
+        entity: EntityReference,
+        cascade: bool,
+    },
+}
+
+/// The result of a single [`WriteOperation`] of a batch.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(tag = "kind", content = "metadata", rename_all = "camelCase")]
+pub enum WriteOperationOutput {
+    DataType(OntologyElementMetadata),
+    PropertyType(OntologyElementMetadata),
+    EntityType(EntityTypeMetadata),
+    Entity(EntityMetadata),
+    /// The temporal metadata of an archived ontology type.
+    ArchivedOntologyType(OntologyTemporalMetadata),
+    /// The metadata of the archived entity followed by the metadata of the archived links.
+    ArchivedEntity(Vec<EntityMetadata>),
+}
+
+/// Describes the API of a store implementation for writing several elements atomically.
+#[async_trait]
+pub trait BatchStore {
+    /// Applies the `operations` in order within a single transaction.
+    ///
+    /// Operations may refer to entities created by earlier operations of the same batch by using
+    /// [`EntityReference::Created`]. Either all operations are applied or none of them. The
+    /// outputs are returned in the order of the operations.
+    ///
+    /// # Errors
+    ///
+    /// - if an operation is rejected, e.g. because the entity is not valid or the type already
+    ///   exists, a [`BulkItemFailure`] for the operation is attached
+    /// - [`InvalidEntityReference`] if a reference does not point to an entity created by an
+    ///   earlier operation
+    ///
+    /// [`BulkItemFailure`]: crate::store::BulkItemFailure
+    /// [`InvalidEntityReference`]: crate::store::error::InvalidEntityReference
+    async fn write_batch<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        operations: Vec<WriteOperation>,
+    ) -> Result<Vec<WriteOperationOutput>, InsertionError>;
+}
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use authorization::AuthorizationApi;
+use error_stack::Result;
+use graph_types::{
+    account::AccountId,
+    knowledge::entity::{EntityId, EntityRecordId},
+    ontology::OntologyTypeRecordId,
+};
+use serde::{Deserialize, Serialize};
+use temporal_versioning::{Timestamp, TransactionTime};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::store::QueryError;
+
+/// The position in the change feed after which changes are read.
+///
+/// The cursor is returned by [`ChangeFeedStore::read_changes`] and is meant to be passed back as
+/// is to read the next changes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChangeCursor {
+    pub transaction_time: Timestamp<TransactionTime>,
+    pub change_id: Uuid,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Created,
+    /// A new edition of an entity was created or an archived ontology type was unarchived.
+    Updated,
+    Archived,
+    /// An entity was permanently erased together with all of its editions.
+    Erased,
+}
+
+/// Identifies the record which was changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "recordKind", content = "recordId", rename_all = "camelCase")]
+pub enum ChangedRecordId {
+    DataType(OntologyTypeRecordId),
+    PropertyType(OntologyTypeRecordId),
+    EntityType(OntologyTypeRecordId),
+    Entity(EntityRecordId),
+    /// The editions of an erased entity don't exist anymore, so only its ID is known.
+    ErasedEntity(EntityId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    pub transaction_time: Timestamp<TransactionTime>,
+    pub kind: ChangeKind,
+    #[serde(flatten)]
+    pub record_id: ChangedRecordId,
+}
+
+/// A page of the change feed returned by [`ChangeFeedStore::read_changes`].
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Changes {
+    pub changes: Vec<Change>,
+    /// The cursor to read the following changes.
+    ///
+    /// It may advance even if no change is returned, e.g. if the actor is not allowed to view
+    /// the changed entities. It's `None` if there were no changes at all yet.
+    pub cursor: Option<ChangeCursor>,
+}
+
+/// Describes the API of a store implementation for reading changes of records.
+#[async_trait]
+pub trait ChangeFeedStore {
+    /// Reads up to `limit` changes of entities and ontology types after `cursor` ordered by their
+    /// transaction time.
+    ///
+    /// If `cursor` is `None` the feed is read from the beginning. Only changes of entities the
+    /// actor is allowed to view are returned. As erased entities can't be viewed anymore, their
+    /// erasure is returned if the actor is allowed to create entities in the web of the entity.
+    ///
+    /// A change is only returned after every other transaction, which started before it, has
+    /// finished. Otherwise, a transaction committing late could add changes before the cursor,
+    /// which would never be read.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the changes from the store failed
+    /// - if the permissions of the actor could not be checked
+    async fn read_changes<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        cursor: Option<ChangeCursor>,
+        limit: usize,
+    ) -> Result<Changes, QueryError>;
+}
@@ -11,7 +11,10 @@ use error_stack::{ensure, Report, Result};
 use futures::TryStreamExt;
 
 use crate::{
-    store::{query::Filter, QueryError, Record},
+    store::{
+        query::{Filter, QueryCursor, Sorting},
+        QueryError, Record,
+    },
     subgraph::temporal_axes::QueryTemporalAxes,
 };
 
@@ -70,4 +73,31 @@ pub trait Read<R>: Sync {
     }
 }
 
+/// Paginated read access to a [`Store`].
+///
+/// Records are returned in a stable order, so that a subsequent read can continue after the last
+/// record of the previous one by passing its vertex id as `cursor`.
+///
+/// [`Store`]: crate::store::Store
+#[async_trait]
+pub trait ReadPaginated<R: Record>: Sync {
+    type ReadPaginatedStream: futures::Stream<Item = Result<R, QueryError>> + Send + Sync;
+
+    /// Returns at most `limit` values from the [`Store`] specified by the passed `query`, which
+    /// are ordered after `cursor`.
+    ///
+    /// If `sorting` is not empty, the values are sorted by the specified paths first. The sort key
+    /// of the `cursor` has to match the `sorting`.
+    ///
+    /// [`Store`]: crate::store::Store
+    async fn read_paginated(
+        &self,
+        query: &Filter<R>,
+        temporal_axes: Option<&QueryTemporalAxes>,
+        sorting: &[Sorting<R>],
+        cursor: Option<&QueryCursor<R::VertexId>>,
+        limit: Option<usize>,
+    ) -> Result<Self::ReadPaginatedStream, QueryError>;
+}
+
 // TODO: Add remaining CRUD traits (but probably don't implement the `D`-part)
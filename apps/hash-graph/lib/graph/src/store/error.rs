@@ -73,6 +73,18 @@ impl fmt::Display for BaseUrlAlreadyExists {
 
 impl Context for BaseUrlAlreadyExists {}
 
+#[derive(Debug)]
+#[must_use]
+pub struct EntityUuidAlreadyExists;
+
+impl fmt::Display for EntityUuidAlreadyExists {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("tried to insert a new entity but its UUID is already used")
+    }
+}
+
+impl Context for EntityUuidAlreadyExists {}
+
 #[derive(Debug)]
 #[must_use]
 pub struct EntityDoesNotExist;
@@ -85,6 +97,42 @@ impl fmt::Display for EntityDoesNotExist {
 
 impl Context for EntityDoesNotExist {}
 
+#[derive(Debug)]
+#[must_use]
+pub struct EntityIsAlreadyArchived;
+
+impl fmt::Display for EntityIsAlreadyArchived {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Entity is already archived")
+    }
+}
+
+impl Context for EntityIsAlreadyArchived {}
+
+#[derive(Debug)]
+#[must_use]
+pub struct EntityIsNotArchived;
+
+impl fmt::Display for EntityIsNotArchived {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Entity is not archived")
+    }
+}
+
+impl Context for EntityIsNotArchived {}
+
+#[derive(Debug)]
+#[must_use]
+pub struct EntityHasLinks;
+
+impl fmt::Display for EntityHasLinks {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Entity is the left or right entity of a link")
+    }
+}
+
+impl Context for EntityHasLinks {}
+
 #[derive(Debug)]
 #[must_use]
 pub struct RaceConditionOnUpdate;
@@ -97,6 +145,18 @@ impl fmt::Display for RaceConditionOnUpdate {
 
 impl Context for RaceConditionOnUpdate {}
 
+#[derive(Debug)]
+#[must_use]
+pub struct EntityEditionMismatch;
+
+impl fmt::Display for EntityEditionMismatch {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The entity was updated since the expected edition")
+    }
+}
+
+impl Context for EntityEditionMismatch {}
+
 #[derive(Debug)]
 #[must_use]
 pub struct VersionedUrlAlreadyExists;
@@ -133,6 +193,30 @@ impl fmt::Display for OntologyTypeIsNotOwned {
 
 impl Context for OntologyTypeIsNotOwned {}
 
+#[derive(Debug)]
+#[must_use]
+pub struct InvalidEntityReference;
+
+impl fmt::Display for InvalidEntityReference {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("the reference does not point to an entity created by an earlier operation")
+    }
+}
+
+impl Context for InvalidEntityReference {}
+
+#[derive(Debug)]
+#[must_use]
+pub struct WebhookDoesNotExist;
+
+impl fmt::Display for WebhookDoesNotExist {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Webhook does not exist")
+    }
+}
+
+impl Context for WebhookDoesNotExist {}
+
 #[derive(Debug)]
 pub struct MigrationError;
 
@@ -1,4 +1,7 @@
-use std::{collections::HashSet, mem};
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
 
 use async_trait::async_trait;
 use authorization::AuthorizationApi;
@@ -6,7 +9,7 @@ use error_stack::{Report, Result, ResultExt};
 use graph_types::{
     account::{AccountGroupId, AccountId},
     knowledge::{
-        entity::{Entity, EntityId, EntityMetadata, EntityProperties, EntityUuid},
+        entity::{Entity, EntityEditionId, EntityId, EntityMetadata, EntityProperties, EntityUuid},
         link::{EntityLinkOrder, LinkData},
     },
     ontology::{
@@ -18,7 +21,7 @@ use graph_types::{
     provenance::OwnedById,
 };
 use tarpc::context;
-use temporal_versioning::{DecisionTime, Timestamp};
+use temporal_versioning::{DecisionTime, TimeAxis, Timestamp};
 use tokio::net::ToSocketAddrs;
 use tokio_serde::formats::Json;
 use type_fetcher::fetcher::{FetcherClient, OntologyTypeRepr};
@@ -26,22 +29,30 @@ use type_system::{
     url::{BaseUrl, VersionedUrl},
     DataType, EntityType, EntityTypeReference, PropertyType,
 };
+use uuid::Uuid;
 
 use crate::{
+    knowledge::{EntityDiff, PropertyPatchOperation},
     ontology::domain_validator::DomainValidator,
     store::{
         crud::Read,
-        query::{Filter, OntologyQueryPath},
-        AccountStore, ConflictBehavior, DataTypeStore, EntityStore, EntityTypeStore,
-        InsertionError, PropertyTypeStore, QueryError, Record, StoreError, StorePool, UpdateError,
+        error::DeletionError,
+        query::{
+            AggregationQuery, AggregationResult, EntityQueryCursor, Filter, OntologyQueryPath,
+        },
+        AccountStore, BatchStore, Change, ChangeCursor, ChangeFeedStore, Changes, ConflictBehavior,
+        DataTypeStore, EntityCreation, EntityHistoryEntry, EntityPathQuery, EntityStore,
+        EntityTypeStore, EntityUpdate, InsertionError, OntologyTypeDependents, PropertyTypeStore,
+        QueryError, Record, StoreError, StorePool, UpdateError, Webhook, WebhookDeadLetter,
+        WebhookFilter, WebhookStore, WebhookTarget, WriteOperation, WriteOperationOutput,
     },
     subgraph::{
-        edges::GraphResolveDepths,
-        identifier::VertexId,
+        edges::{GraphResolveDepths, KnowledgeGraphTraversalFilters},
+        identifier::{EntityVertexId, VertexId},
         query::StructuralQuery,
         temporal_axes::{
             PinnedTemporalAxisUnresolved, QueryTemporalAxes, QueryTemporalAxesUnresolved,
-            VariableTemporalAxisUnresolved,
+            VariableAxis, VariableTemporalAxisUnresolved,
         },
         Subgraph,
     },
@@ -187,10 +198,14 @@ where
             StructuralQuery {
                 filter: Filter::for_versioned_url(versioned_url),
                 graph_resolve_depths: GraphResolveDepths::default(),
+                traversal_filters: KnowledgeGraphTraversalFilters::default(),
                 temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                     pinned: PinnedTemporalAxisUnresolved::new(None),
                     variable: VariableTemporalAxisUnresolved::new(None, None),
                 },
+                order_by: Vec::new(),
+                limit: None,
+                cursor: None,
             }
         }
 
@@ -643,6 +658,17 @@ where
             .await
     }
 
+    async fn aggregate_data_types<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        query: &AggregationQuery<DataTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError> {
+        self.store
+            .aggregate_data_types(actor_id, authorization_api, query)
+            .await
+    }
+
     async fn update_data_type<Au: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -679,6 +705,17 @@ where
             .unarchive_data_type(actor_id, authorization_api, id)
             .await
     }
+
+    async fn get_data_type_dependents<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError> {
+        self.store
+            .get_data_type_dependents(actor_id, authorization_api, id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -724,6 +761,17 @@ where
             .await
     }
 
+    async fn aggregate_property_types<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        query: &AggregationQuery<PropertyTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError> {
+        self.store
+            .aggregate_property_types(actor_id, authorization_api, query)
+            .await
+    }
+
     async fn update_property_type<Au: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -760,6 +808,17 @@ where
             .unarchive_property_type(actor_id, authorization_api, id)
             .await
     }
+
+    async fn get_property_type_dependents<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError> {
+        self.store
+            .get_property_type_dependents(actor_id, authorization_api, id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -801,6 +860,17 @@ where
             .await
     }
 
+    async fn aggregate_entity_types<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        query: &AggregationQuery<EntityTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError> {
+        self.store
+            .aggregate_entity_types(actor_id, authorization_api, query)
+            .await
+    }
+
     async fn update_entity_type<Au: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -838,6 +908,17 @@ where
             .unarchive_entity_type(actor_id, authorization_api, id)
             .await
     }
+
+    async fn get_entity_type_dependents<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError> {
+        self.store
+            .get_entity_type_dependents(actor_id, authorization_api, id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -883,6 +964,34 @@ where
             .await
     }
 
+    async fn create_entities<Au: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut Au,
+        entities: impl IntoIterator<Item = EntityCreation, IntoIter: Send> + Send,
+    ) -> Result<Vec<EntityMetadata>, InsertionError> {
+        let entities = entities.into_iter().collect::<Vec<_>>();
+        let entity_type_ids = entities
+            .iter()
+            .map(|entity| &entity.entity_type_id)
+            .collect::<HashSet<_>>();
+        for entity_type_id in entity_type_ids {
+            let entity_type_reference = EntityTypeReference::new(entity_type_id.clone());
+            self.insert_external_types_by_reference(
+                actor_id,
+                authorization_api,
+                OntologyTypeReference::EntityTypeReference(&entity_type_reference),
+                ConflictBehavior::Skip,
+                FetchBehavior::ExcludeProvidedReferences,
+            )
+            .await?;
+        }
+
+        self.store
+            .create_entities(actor_id, authorization_api, entities)
+            .await
+    }
+
     #[doc(hidden)]
     #[cfg(hash_graph_test_environment)]
     async fn insert_entities_batched_by_type<Au: AuthorizationApi + Send + Sync>(
@@ -921,17 +1030,80 @@ where
         actor_id: AccountId,
         authorization_api: &Au,
         query: &StructuralQuery<Entity>,
-    ) -> Result<Subgraph, QueryError> {
+    ) -> Result<
+        (
+            Subgraph,
+            Option<EntityQueryCursor>,
+            HashMap<EntityVertexId, f32>,
+        ),
+        QueryError,
+    > {
         self.store
             .get_entity(actor_id, authorization_api, query)
             .await
     }
 
+    async fn aggregate_entities<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        query: &AggregationQuery<Entity>,
+    ) -> Result<Vec<AggregationResult>, QueryError> {
+        self.store
+            .aggregate_entities(actor_id, authorization_api, query)
+            .await
+    }
+
+    async fn get_entity_history<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        entity_id: EntityId,
+        temporal_axes: QueryTemporalAxesUnresolved,
+    ) -> Result<Vec<EntityHistoryEntry>, QueryError> {
+        self.store
+            .get_entity_history(actor_id, authorization_api, entity_id, temporal_axes)
+            .await
+    }
+
+    async fn diff_entity<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        entity_id: EntityId,
+        time_axis: TimeAxis,
+        old_timestamp: Option<Timestamp<VariableAxis>>,
+        new_timestamp: Option<Timestamp<VariableAxis>>,
+    ) -> Result<EntityDiff, QueryError> {
+        self.store
+            .diff_entity(
+                actor_id,
+                authorization_api,
+                entity_id,
+                time_axis,
+                old_timestamp,
+                new_timestamp,
+            )
+            .await
+    }
+
+    async fn get_entity_paths<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        query: &EntityPathQuery,
+    ) -> Result<(Subgraph, Vec<Vec<EntityId>>), QueryError> {
+        self.store
+            .get_entity_paths(actor_id, authorization_api, query)
+            .await
+    }
+
     async fn update_entity<Au: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
         authorization_api: &mut Au,
         entity_id: EntityId,
+        expected_edition_id: Option<EntityEditionId>,
         decision_time: Option<Timestamp<DecisionTime>>,
         archived: bool,
         entity_type_id: VersionedUrl,
@@ -954,6 +1126,7 @@ where
                 actor_id,
                 authorization_api,
                 entity_id,
+                expected_edition_id,
                 decision_time,
                 archived,
                 entity_type_id,
@@ -962,4 +1135,235 @@ where
             )
             .await
     }
+
+    async fn update_entities<Au: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut Au,
+        entities: impl IntoIterator<Item = EntityUpdate, IntoIter: Send> + Send,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        let entities = entities.into_iter().collect::<Vec<_>>();
+        let entity_type_ids = entities
+            .iter()
+            .map(|entity| &entity.entity_type_id)
+            .collect::<HashSet<_>>();
+        for entity_type_id in entity_type_ids {
+            let entity_type_reference = EntityTypeReference::new(entity_type_id.clone());
+            self.insert_external_types_by_reference(
+                actor_id,
+                authorization_api,
+                OntologyTypeReference::EntityTypeReference(&entity_type_reference),
+                ConflictBehavior::Skip,
+                FetchBehavior::ExcludeProvidedReferences,
+            )
+            .await
+            .change_context(UpdateError)?;
+        }
+
+        self.store
+            .update_entities(actor_id, authorization_api, entities)
+            .await
+    }
+
+    async fn patch_entity<Au: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut Au,
+        entity_id: EntityId,
+        decision_time: Option<Timestamp<DecisionTime>>,
+        operations: Vec<PropertyPatchOperation>,
+    ) -> Result<EntityMetadata, UpdateError> {
+        self.store
+            .patch_entity(
+                actor_id,
+                authorization_api,
+                entity_id,
+                decision_time,
+                operations,
+            )
+            .await
+    }
+
+    async fn archive_entity<Au: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut Au,
+        entity_id: EntityId,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        self.store
+            .archive_entity(actor_id, authorization_api, entity_id, cascade)
+            .await
+    }
+
+    async fn unarchive_entity<Au: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut Au,
+        entity_id: EntityId,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        self.store
+            .unarchive_entity(actor_id, authorization_api, entity_id, cascade)
+            .await
+    }
+
+    async fn erase_entity<Au: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut Au,
+        entity_id: EntityId,
+        erase_links: bool,
+    ) -> Result<Vec<EntityId>, DeletionError> {
+        self.store
+            .erase_entity(actor_id, authorization_api, entity_id, erase_links)
+            .await
+    }
+}
+
+#[async_trait]
+impl<S, A> BatchStore for FetchingStore<S, A>
+where
+    S: DataTypeStore + PropertyTypeStore + EntityTypeStore + BatchStore + Send,
+    A: ToSocketAddrs + Send + Sync,
+{
+    async fn write_batch<Au: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut Au,
+        operations: Vec<WriteOperation>,
+    ) -> Result<Vec<WriteOperationOutput>, InsertionError> {
+        // External types are inserted before the batch is applied, so they are kept even if the
+        // batch fails.
+        for operation in &operations {
+            match operation {
+                WriteOperation::CreateDataType { schema, .. } => {
+                    self.insert_external_types(actor_id, authorization_api, [schema.as_ref()])
+                        .await?;
+                }
+                WriteOperation::CreatePropertyType { schema, .. } => {
+                    self.insert_external_types(actor_id, authorization_api, [schema.as_ref()])
+                        .await?;
+                }
+                WriteOperation::CreateEntityType { schema, .. } => {
+                    self.insert_external_types(actor_id, authorization_api, [schema.as_ref()])
+                        .await?;
+                }
+                WriteOperation::CreateEntity { entity_type_id, .. } => {
+                    let entity_type_reference = EntityTypeReference::new(entity_type_id.clone());
+                    self.insert_external_types_by_reference(
+                        actor_id,
+                        authorization_api,
+                        OntologyTypeReference::EntityTypeReference(&entity_type_reference),
+                        ConflictBehavior::Skip,
+                        FetchBehavior::ExcludeProvidedReferences,
+                    )
+                    .await?;
+                }
+                WriteOperation::ArchiveDataType(_)
+                | WriteOperation::ArchivePropertyType(_)
+                | WriteOperation::ArchiveEntityType(_)
+                | WriteOperation::ArchiveEntity { .. } => {}
+            }
+        }
+
+        self.store
+            .write_batch(actor_id, authorization_api, operations)
+            .await
+    }
+}
+
+#[async_trait]
+impl<S, A> ChangeFeedStore for FetchingStore<S, A>
+where
+    S: ChangeFeedStore + Send + Sync,
+    A: ToSocketAddrs + Send + Sync,
+{
+    async fn read_changes<Au: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &Au,
+        cursor: Option<ChangeCursor>,
+        limit: usize,
+    ) -> Result<Changes, QueryError> {
+        self.store
+            .read_changes(actor_id, authorization_api, cursor, limit)
+            .await
+    }
+}
+
+#[async_trait]
+impl<S, A> WebhookStore for FetchingStore<S, A>
+where
+    S: WebhookStore + Send + Sync,
+    A: ToSocketAddrs + Send + Sync,
+{
+    async fn register_webhook(
+        &mut self,
+        actor_id: AccountId,
+        url: String,
+        secret: String,
+        filter: WebhookFilter,
+    ) -> Result<Webhook, InsertionError> {
+        self.store
+            .register_webhook(actor_id, url, secret, filter)
+            .await
+    }
+
+    async fn get_webhooks(&self, actor_id: AccountId) -> Result<Vec<Webhook>, QueryError> {
+        self.store.get_webhooks(actor_id).await
+    }
+
+    async fn delete_webhook(
+        &mut self,
+        actor_id: AccountId,
+        webhook_id: Uuid,
+    ) -> Result<(), DeletionError> {
+        self.store.delete_webhook(actor_id, webhook_id).await
+    }
+
+    async fn get_webhook_dead_letters(
+        &self,
+        actor_id: AccountId,
+        webhook_id: Uuid,
+    ) -> Result<Vec<WebhookDeadLetter>, QueryError> {
+        self.store
+            .get_webhook_dead_letters(actor_id, webhook_id)
+            .await
+    }
+
+    async fn lock_webhook_delivery(&mut self) -> Result<bool, QueryError> {
+        self.store.lock_webhook_delivery().await
+    }
+
+    async fn unlock_webhook_delivery(&mut self) -> Result<(), QueryError> {
+        self.store.unlock_webhook_delivery().await
+    }
+
+    async fn get_webhook_targets(&self) -> Result<Vec<WebhookTarget>, QueryError> {
+        self.store.get_webhook_targets().await
+    }
+
+    async fn filter_webhook_changes(
+        &self,
+        filter: &WebhookFilter,
+        changes: Vec<Change>,
+    ) -> Result<Vec<Change>, QueryError> {
+        self.store.filter_webhook_changes(filter, changes).await
+    }
+
+    async fn advance_webhook_cursor(
+        &mut self,
+        webhook_id: Uuid,
+        cursor: ChangeCursor,
+    ) -> Result<(), UpdateError> {
+        self.store.advance_webhook_cursor(webhook_id, cursor).await
+    }
+
+    async fn insert_webhook_dead_letter(
+        &mut self,
+        dead_letter: WebhookDeadLetter,
+    ) -> Result<(), InsertionError> {
+        self.store.insert_webhook_dead_letter(dead_letter).await
+    }
 }
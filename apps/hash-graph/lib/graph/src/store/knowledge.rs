@@ -1,22 +1,123 @@
+use std::{collections::HashMap, fmt};
+
 use async_trait::async_trait;
 use authorization::AuthorizationApi;
 use error_stack::Result;
 use graph_types::{
     account::AccountId,
     knowledge::{
-        entity::{Entity, EntityId, EntityMetadata, EntityProperties, EntityUuid},
+        entity::{Entity, EntityEditionId, EntityId, EntityMetadata, EntityProperties, EntityUuid},
         link::{EntityLinkOrder, LinkData},
     },
     provenance::OwnedById,
 };
-use temporal_versioning::{DecisionTime, Timestamp};
+use serde::{Deserialize, Serialize};
+use temporal_versioning::{DecisionTime, TimeAxis, Timestamp};
 use type_system::url::VersionedUrl;
+use utoipa::ToSchema;
 
 use crate::{
-    store::{crud, InsertionError, QueryError, UpdateError},
-    subgraph::{query::StructuralQuery, Subgraph},
+    knowledge::{EntityDiff, PropertyDiff, PropertyPatchOperation, ValidationFailure},
+    store::{
+        crud,
+        error::DeletionError,
+        query::{AggregationQuery, AggregationResult, EntityQueryCursor},
+        InsertionError, QueryError, UpdateError,
+    },
+    subgraph::{
+        identifier::EntityVertexId,
+        query::StructuralQuery,
+        temporal_axes::{QueryTemporalAxesUnresolved, VariableAxis},
+        Subgraph,
+    },
 };
 
+/// The parameters to create a single [`Entity`] with [`EntityStore::create_entities`].
+#[derive(Debug, Clone)]
+pub struct EntityCreation {
+    pub owned_by_id: OwnedById,
+    pub entity_uuid: Option<EntityUuid>,
+    pub decision_time: Option<Timestamp<DecisionTime>>,
+    pub archived: bool,
+    pub entity_type_id: VersionedUrl,
+    pub properties: EntityProperties,
+    pub link_data: Option<LinkData>,
+}
+
+/// The parameters to update a single [`Entity`] with [`EntityStore::update_entities`].
+#[derive(Debug, Clone)]
+pub struct EntityUpdate {
+    pub entity_id: EntityId,
+    /// If set, the entity is only updated if this is its latest edition.
+    pub expected_edition_id: Option<EntityEditionId>,
+    pub decision_time: Option<Timestamp<DecisionTime>>,
+    pub archived: bool,
+    pub entity_type_id: VersionedUrl,
+    pub properties: EntityProperties,
+    pub link_order: EntityLinkOrder,
+}
+
+/// Describes why a single item of a bulk operation could not be processed.
+///
+/// It's attached to the error of the bulk operation once for each rejected item.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemFailure {
+    /// The position of the item in the request.
+    pub index: usize,
+    pub reason: String,
+    /// The constraints violated by the item, if it failed to validate against its type.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<Object>)]
+    pub validation_failures: Vec<ValidationFailure>,
+}
+
+impl BulkItemFailure {
+    #[must_use]
+    pub fn new(index: usize, reason: impl Into<String>) -> Self {
+        Self {
+            index,
+            reason: reason.into(),
+            validation_failures: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for BulkItemFailure {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "item {}: {}", self.index, self.reason)
+    }
+}
+
+/// A single edition of an [`Entity`] as returned by [`EntityStore::get_entity_history`].
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityHistoryEntry {
+    /// The metadata of the edition including its temporal versioning and provenance.
+    pub metadata: EntityMetadata,
+    /// The changes of the properties compared to the previous edition. All properties of the first
+    /// edition are reported as added.
+    pub property_diff: Vec<PropertyDiff>,
+}
+
+/// Describes a search for the shortest link paths between two [`Entities`].
+///
+/// A path alternates between entities and links, starting at the source and ending at the target
+/// entity. Links are followed regardless of their direction.
+///
+/// [`Entities`]: Entity
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EntityPathQuery {
+    pub source_entity_id: EntityId,
+    pub target_entity_id: EntityId,
+    /// The maximum number of links a path may consist of, at most 8.
+    pub max_depth: u8,
+    /// The maximum number of paths to return, at most 100.
+    pub limit: usize,
+    pub temporal_axes: QueryTemporalAxesUnresolved,
+}
+
 /// Describes the API of a store implementation for [Entities].
 ///
 /// [Entities]: Entity
@@ -27,11 +128,13 @@ pub trait EntityStore: crud::Read<Entity> {
     /// # Errors:
     ///
     /// - if the [`EntityType`] doesn't exist
-    /// - if the [`EntityProperties`] is not valid with respect to the specified [`EntityType`]
+    /// - [`EntityValidationError`] if the [`EntityProperties`] is not valid with respect to the
+    ///   specified [`EntityType`] or the [`LinkData`] points to an invalid destination
     /// - if the account referred to by `owned_by_id` does not exist
     /// - if an [`EntityUuid`] was supplied and already exists in the store
     ///
     /// [`EntityType`]: type_system::EntityType
+    /// [`EntityValidationError`]: crate::knowledge::EntityValidationError
     #[expect(clippy::too_many_arguments)]
     async fn create_entity<A: AuthorizationApi + Send + Sync>(
         &mut self,
@@ -46,6 +149,30 @@ pub trait EntityStore: crud::Read<Entity> {
         link_data: Option<LinkData>,
     ) -> Result<EntityMetadata, InsertionError>;
 
+    /// Creates the provided [`Entity`]s in a single transaction.
+    ///
+    /// The entities may have different [`EntityType`]s and may link to each other. Permissions are
+    /// checked once per web and every entity is validated before anything is written. Either all
+    /// entities are created or none.
+    ///
+    /// The returned metadata is in the same order as the provided entities.
+    ///
+    /// # Errors:
+    ///
+    /// - if the actor is not allowed to create an entity in one of the webs, or if an entity is not
+    ///   valid with respect to its [`EntityType`]. A [`BulkItemFailure`] is attached for every
+    ///   rejected entity.
+    /// - if an [`EntityType`] doesn't exist
+    /// - if an [`EntityUuid`] was supplied and already exists in the store
+    ///
+    /// [`EntityType`]: type_system::EntityType
+    async fn create_entities<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entities: impl IntoIterator<Item = EntityCreation, IntoIter: Send> + Send,
+    ) -> Result<Vec<EntityMetadata>, InsertionError>;
+
     /// Inserts the entities with the specified [`EntityType`] into the `Store`.
     ///
     /// This is only supporting a single [`EntityType`], not one [`EntityType`] per entity.
@@ -84,15 +211,109 @@ pub trait EntityStore: crud::Read<Entity> {
 
     /// Get the [`Subgraph`]s specified by the [`StructuralQuery`].
     ///
+    /// If the query specifies a `limit` and there may be more entities to read, an
+    /// [`EntityQueryCursor`] pointing to the last read entity is returned alongside the
+    /// [`Subgraph`]. It can be passed as `cursor` to a subsequent query to read the next page.
+    ///
+    /// If the filter of the query contains a [`Filter::Search`], the rank of each root entity for
+    /// the search is returned as well.
+    ///
     /// # Errors
     ///
     /// - if the requested [`Entity`] doesn't exist
+    ///
+    /// [`Filter::Search`]: crate::store::query::Filter::Search
     async fn get_entity<A: AuthorizationApi + Sync>(
         &self,
         actor_id: AccountId,
         authorization_api: &A,
         query: &StructuralQuery<Entity>,
-    ) -> Result<Subgraph, QueryError>;
+    ) -> Result<
+        (
+            Subgraph,
+            Option<EntityQueryCursor>,
+            HashMap<EntityVertexId, f32>,
+        ),
+        QueryError,
+    >;
+
+    /// Aggregates the [`Entity`]s matching the [`AggregationQuery`].
+    ///
+    /// Only entities the actor is allowed to view are taken into account.
+    ///
+    /// # Errors
+    ///
+    /// - if the aggregation could not be computed, e.g. if the aggregated path is not comparable
+    async fn aggregate_entities<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        query: &AggregationQuery<Entity>,
+    ) -> Result<Vec<AggregationResult>, QueryError>;
+
+    /// Returns every edition of the [`Entity`] within the specified temporal axes.
+    ///
+    /// The editions are ordered by the start of their interval on the variable temporal axis.
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityDoesNotExist`] if no edition of the [`Entity`] exists within the temporal axes
+    /// - if the actor is not allowed to view the [`Entity`]
+    ///
+    /// [`EntityDoesNotExist`]: crate::store::error::EntityDoesNotExist
+    async fn get_entity_history<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        entity_id: EntityId,
+        temporal_axes: QueryTemporalAxesUnresolved,
+    ) -> Result<Vec<EntityHistoryEntry>, QueryError>;
+
+    /// Compares the states of an [`Entity`] at two points in time along the `time_axis`.
+    ///
+    /// The other time axis is pinned to the current time. A timestamp, which is not specified,
+    /// refers to the current time as well. If more than one edition lies at a point in time, the
+    /// latest one is used.
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityDoesNotExist`] if the [`Entity`] exists at neither point in time
+    /// - if the actor is not allowed to view the [`Entity`]
+    ///
+    /// [`EntityDoesNotExist`]: crate::store::error::EntityDoesNotExist
+    async fn diff_entity<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        entity_id: EntityId,
+        time_axis: TimeAxis,
+        old_timestamp: Option<Timestamp<VariableAxis>>,
+        new_timestamp: Option<Timestamp<VariableAxis>>,
+    ) -> Result<EntityDiff, QueryError>;
+
+    /// Searches for the shortest link paths between two [`Entities`] as described by the
+    /// [`EntityPathQuery`].
+    ///
+    /// Up to [`limit`] paths with at most [`max_depth`] links each are returned, shortest first.
+    /// Every hop is checked against the temporal axes and links or entities the actor is not
+    /// allowed to view are not followed. The returned [`Subgraph`] is rooted at the source entity
+    /// and contains all entities on the paths.
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityDoesNotExist`] if the source [`Entity`] does not exist within the temporal axes
+    /// - if the actor is not allowed to view the source [`Entity`]
+    ///
+    /// [`Entities`]: Entity
+    /// [`limit`]: EntityPathQuery::limit
+    /// [`max_depth`]: EntityPathQuery::max_depth
+    /// [`EntityDoesNotExist`]: crate::store::error::EntityDoesNotExist
+    async fn get_entity_paths<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        query: &EntityPathQuery,
+    ) -> Result<(Subgraph, Vec<Vec<EntityId>>), QueryError>;
 
     /// Update an existing [`Entity`].
     ///
@@ -100,20 +321,148 @@ pub trait EntityStore: crud::Read<Entity> {
     ///
     /// - if the [`Entity`] doesn't exist
     /// - if the [`EntityType`] doesn't exist
-    /// - if the [`Entity`] is not valid with respect to its [`EntityType`]
+    /// - [`EntityValidationError`] if the [`Entity`] is not valid with respect to its
+    ///   [`EntityType`]
     /// - if the account referred to by `actor_id` does not exist
+    /// - [`EntityEditionMismatch`] if `expected_edition_id` is provided but is not the latest
+    ///   edition of the [`Entity`]. The latest [`EntityEditionId`] is attached to the error.
     ///
     /// [`EntityType`]: type_system::EntityType
+    /// [`EntityValidationError`]: crate::knowledge::EntityValidationError
+    /// [`EntityEditionMismatch`]: crate::store::error::EntityEditionMismatch
     #[expect(clippy::too_many_arguments)]
     async fn update_entity<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
         authorization_api: &mut A,
         entity_id: EntityId,
+        expected_edition_id: Option<EntityEditionId>,
         decision_time: Option<Timestamp<DecisionTime>>,
         archived: bool,
         entity_type_id: VersionedUrl,
         properties: EntityProperties,
         link_order: EntityLinkOrder,
     ) -> Result<EntityMetadata, UpdateError>;
+
+    /// Updates the provided [`Entity`]s in a single transaction.
+    ///
+    /// Permissions are checked for all entities at once and every entity is validated before
+    /// anything is written. Either all entities are updated or none.
+    ///
+    /// The returned metadata is in the same order as the provided updates.
+    ///
+    /// # Errors
+    ///
+    /// - if the actor is not allowed to update one of the entities, one of the entities doesn't
+    ///   exist, is not at its expected edition, or is not valid with respect to its [`EntityType`].
+    ///   A [`BulkItemFailure`] is attached for every rejected entity.
+    /// - if an [`EntityType`] doesn't exist
+    ///
+    /// [`EntityType`]: type_system::EntityType
+    async fn update_entities<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entities: impl IntoIterator<Item = EntityUpdate, IntoIter: Send> + Send,
+    ) -> Result<Vec<EntityMetadata>, UpdateError>;
+
+    /// Applies the patch `operations` to the properties of the current edition of an existing
+    /// [`Entity`] and creates a new edition with the result.
+    ///
+    /// The [`EntityType`], the link order and the archived state of the [`Entity`] are kept. The
+    /// patch is applied within the same transaction as the update, so concurrent updates are not
+    /// overwritten.
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityDoesNotExist`] if the [`Entity`] doesn't exist
+    /// - [`PropertyPatchError`] if one of the `operations` could not be applied
+    /// - [`EntityValidationError`] if the patched [`Entity`] is not valid with respect to its
+    ///   [`EntityType`]
+    /// - if the actor is not allowed to update the [`Entity`]
+    ///
+    /// [`EntityType`]: type_system::EntityType
+    /// [`EntityDoesNotExist`]: crate::store::error::EntityDoesNotExist
+    /// [`PropertyPatchError`]: crate::knowledge::PropertyPatchError
+    /// [`EntityValidationError`]: crate::knowledge::EntityValidationError
+    async fn patch_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        decision_time: Option<Timestamp<DecisionTime>>,
+        operations: Vec<PropertyPatchOperation>,
+    ) -> Result<EntityMetadata, UpdateError>;
+
+    /// Archives an existing [`Entity`] without changing its properties.
+    ///
+    /// If `cascade` is set, all link entities attached to the [`Entity`] are archived as well.
+    ///
+    /// The first returned metadata belongs to the archived [`Entity`], followed by the metadata of
+    /// the archived links.
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityDoesNotExist`] if the [`Entity`] doesn't exist
+    /// - [`EntityIsAlreadyArchived`] if the [`Entity`] is already archived
+    /// - if the actor is not allowed to update the [`Entity`] or one of the cascaded links
+    ///
+    /// [`EntityDoesNotExist`]: crate::store::error::EntityDoesNotExist
+    /// [`EntityIsAlreadyArchived`]: crate::store::error::EntityIsAlreadyArchived
+    async fn archive_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError>;
+
+    /// Unarchives an archived [`Entity`] without changing its properties.
+    ///
+    /// If `cascade` is set, all archived link entities attached to the [`Entity`] are unarchived
+    /// as well.
+    ///
+    /// The first returned metadata belongs to the unarchived [`Entity`], followed by the metadata
+    /// of the unarchived links.
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityDoesNotExist`] if the [`Entity`] doesn't exist
+    /// - [`EntityIsNotArchived`] if the [`Entity`] is not archived
+    /// - if the actor is not allowed to update the [`Entity`] or one of the cascaded links
+    ///
+    /// [`EntityDoesNotExist`]: crate::store::error::EntityDoesNotExist
+    /// [`EntityIsNotArchived`]: crate::store::error::EntityIsNotArchived
+    async fn unarchive_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError>;
+
+    /// Permanently removes all editions of an [`Entity`].
+    ///
+    /// If `erase_links` is set, all link entities attached to the [`Entity`], directly or through
+    /// other erased links, are erased as well. For every erased entity a tombstone is kept, which
+    /// only records the [`EntityId`], the actor, and the time of the erasure. All relations of the
+    /// erased entities are removed from the authorization backend.
+    ///
+    /// Returns the IDs of the erased entities, starting with the requested [`Entity`].
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityDoesNotExist`] if the [`Entity`] doesn't exist
+    /// - [`EntityHasLinks`] if links are attached to the [`Entity`] and `erase_links` is not set
+    /// - if the actor is not allowed to delete the [`Entity`] or one of the erased links
+    ///
+    /// [`EntityDoesNotExist`]: crate::store::error::EntityDoesNotExist
+    /// [`EntityHasLinks`]: crate::store::error::EntityHasLinks
+    async fn erase_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        erase_links: bool,
+    ) -> Result<Vec<EntityId>, DeletionError>;
 }
@@ -11,16 +11,60 @@ use graph_types::{
         PropertyTypeWithMetadata,
     },
 };
+use serde::Serialize;
 use type_system::{
     url::{BaseUrl, VersionedUrl},
     DataType, EntityType, PropertyType,
 };
+use utoipa::ToSchema;
 
 use crate::{
-    store::{crud, ConflictBehavior, InsertionError, QueryError, UpdateError},
-    subgraph::{query::StructuralQuery, Subgraph},
+    store::{
+        crud,
+        query::{AggregationQuery, AggregationResult},
+        ConflictBehavior, InsertionError, QueryError, UpdateError,
+    },
+    subgraph::{edges::OntologyEdgeKind, query::StructuralQuery, Subgraph},
 };
 
+/// A reference from a dependent ontology type to another type in the dependency tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OntologyTypeDependencyReference {
+    /// The type which is referenced by the dependent type.
+    #[schema(value_type = SHARED_VersionedUrl)]
+    pub type_id: VersionedUrl,
+    pub edge_kind: OntologyEdgeKind,
+}
+
+/// An ontology type which directly or transitively references another ontology type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OntologyTypeDependent {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    pub type_id: VersionedUrl,
+    /// The minimum number of references between the dependent type and the requested type. Types
+    /// referencing the requested type directly have a depth of `1`.
+    pub depth: u32,
+    /// The references of this type to the requested type or to other dependents.
+    pub references: Vec<OntologyTypeDependencyReference>,
+    /// The number of entities of this type if it's an [`EntityType`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = false)]
+    pub entity_count: Option<i64>,
+}
+
+/// The ontology types depending on a specific ontology type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OntologyTypeDependents {
+    /// The dependent types ordered by their depth.
+    pub dependents: Vec<OntologyTypeDependent>,
+    /// The number of distinct entities which are of the requested type or of any dependent
+    /// [`EntityType`].
+    pub entity_count: i64,
+}
+
 /// Describes the API of a store implementation for [`DataType`]s.
 #[async_trait]
 pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
@@ -30,6 +74,7 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
     ///
     /// - if any account referred to by `metadata` does not exist.
     /// - if the [`BaseUrl`] of the `data_type` already exists.
+    /// - if the actor is not allowed to create the type in the web of `metadata`.
     ///
     /// [`BaseUrl`]: type_system::url::BaseUrl
     async fn create_data_type<A: AuthorizationApi + Send + Sync>(
@@ -57,6 +102,7 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
     ///
     /// - if any account referred to by the metadata does not exist.
     /// - if any [`BaseUrl`] of the data type already exists.
+    /// - if the actor is not allowed to create the types in the webs of the metadata.
     ///
     /// [`BaseUrl`]: type_system::url::BaseUrl
     async fn create_data_types<A: AuthorizationApi + Send + Sync>(
@@ -80,11 +126,24 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
         query: &StructuralQuery<DataTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError>;
 
+    /// Aggregates the [`DataType`]s matching the [`AggregationQuery`].
+    ///
+    /// # Errors
+    ///
+    /// - if the aggregation could not be computed, e.g. if the aggregated path is not comparable
+    async fn aggregate_data_types<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        query: &AggregationQuery<DataTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError>;
+
     /// Update the definition of an existing [`DataType`].
     ///
     /// # Errors
     ///
     /// - if the [`DataType`] doesn't exist.
+    /// - if the actor is not allowed to update the [`DataType`].
     async fn update_data_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -97,6 +156,7 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
     /// # Errors
     ///
     /// - if the [`DataType`] doesn't exist.
+    /// - if the actor is not allowed to archive the [`DataType`].
     async fn archive_data_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -109,12 +169,36 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
     /// # Errors
     ///
     /// - if the [`DataType`] doesn't exist.
+    /// - if the actor is not allowed to archive the [`DataType`].
     async fn unarchive_data_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
         authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError>;
+
+    /// Returns all ontology types which directly or transitively depend on the [`DataType`].
+    ///
+    /// This includes the [`PropertyType`]s constraining their values on the [`DataType`] as well
+    /// as all types depending on these [`PropertyType`]s. Dependents are followed along
+    /// [`ConstrainsValuesOn`], [`ConstrainsPropertiesOn`] and [`InheritsFrom`] edges. Only types
+    /// and entities, which are not archived, are taken into account. Only entities
+    /// the actor is allowed to view are counted.
+    ///
+    /// # Errors
+    ///
+    /// - [`OntologyVersionDoesNotExist`] if the [`DataType`] doesn't exist.
+    ///
+    /// [`ConstrainsValuesOn`]: OntologyEdgeKind::ConstrainsValuesOn
+    /// [`ConstrainsPropertiesOn`]: OntologyEdgeKind::ConstrainsPropertiesOn
+    /// [`InheritsFrom`]: OntologyEdgeKind::InheritsFrom
+    /// [`OntologyVersionDoesNotExist`]: crate::store::OntologyVersionDoesNotExist
+    async fn get_data_type_dependents<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError>;
 }
 
 /// Describes the API of a store implementation for [`PropertyType`]s.
@@ -126,6 +210,7 @@ pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
     ///
     /// - if any account referred to by `metadata` does not exist.
     /// - if the [`BaseUrl`] of the `property_type` already exists.
+    /// - if the actor is not allowed to create the type in the web of `metadata`.
     ///
     /// [`BaseUrl`]: type_system::url::BaseUrl
     async fn create_property_type<A: AuthorizationApi + Send + Sync>(
@@ -153,6 +238,7 @@ pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
     ///
     /// - if any account referred to by the metadata does not exist.
     /// - if any [`BaseUrl`] of the property type already exists.
+    /// - if the actor is not allowed to create the types in the webs of the metadata.
     ///
     /// [`BaseUrl`]: type_system::url::BaseUrl
     async fn create_property_types<A: AuthorizationApi + Send + Sync>(
@@ -178,11 +264,24 @@ pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
         query: &StructuralQuery<PropertyTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError>;
 
+    /// Aggregates the [`PropertyType`]s matching the [`AggregationQuery`].
+    ///
+    /// # Errors
+    ///
+    /// - if the aggregation could not be computed, e.g. if the aggregated path is not comparable
+    async fn aggregate_property_types<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        query: &AggregationQuery<PropertyTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError>;
+
     /// Update the definition of an existing [`PropertyType`].
     ///
     /// # Errors
     ///
     /// - if the [`PropertyType`] doesn't exist.
+    /// - if the actor is not allowed to update the [`PropertyType`].
     async fn update_property_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -195,6 +294,7 @@ pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
     /// # Errors
     ///
     /// - if the [`PropertyType`] doesn't exist.
+    /// - if the actor is not allowed to archive the [`PropertyType`].
     async fn archive_property_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -207,12 +307,29 @@ pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
     /// # Errors
     ///
     /// - if the [`PropertyType`] doesn't exist.
+    /// - if the actor is not allowed to archive the [`PropertyType`].
     async fn unarchive_property_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
         authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError>;
+
+    /// Returns all ontology types which directly or transitively depend on the [`PropertyType`].
+    ///
+    /// See [`DataTypeStore::get_data_type_dependents`] for which dependents are returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`OntologyVersionDoesNotExist`] if the [`PropertyType`] doesn't exist.
+    ///
+    /// [`OntologyVersionDoesNotExist`]: crate::store::OntologyVersionDoesNotExist
+    async fn get_property_type_dependents<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError>;
 }
 
 /// Describes the API of a store implementation for [`EntityType`]s.
@@ -224,6 +341,7 @@ pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
     ///
     /// - if any account referred to by `metadata` does not exist.
     /// - if the [`BaseUrl`] of the `entity_type` already exists.
+    /// - if the actor is not allowed to create the type in the web of `metadata`.
     ///
     /// [`BaseUrl`]: type_system::url::BaseUrl
     async fn create_entity_type<A: AuthorizationApi + Send + Sync>(
@@ -251,6 +369,7 @@ pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
     ///
     /// - if any account referred to by the metadata does not exist.
     /// - if any [`BaseUrl`] of the entity type already exists.
+    /// - if the actor is not allowed to create the types in the webs of the metadata.
     ///
     /// [`BaseUrl`]: type_system::url::BaseUrl
     async fn create_entity_types<A: AuthorizationApi + Send + Sync>(
@@ -274,11 +393,24 @@ pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
         query: &StructuralQuery<EntityTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError>;
 
+    /// Aggregates the [`EntityType`]s matching the [`AggregationQuery`].
+    ///
+    /// # Errors
+    ///
+    /// - if the aggregation could not be computed, e.g. if the aggregated path is not comparable
+    async fn aggregate_entity_types<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        query: &AggregationQuery<EntityTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError>;
+
     /// Update the definition of an existing [`EntityType`].
     ///
     /// # Errors
     ///
     /// - if the [`EntityType`] doesn't exist.
+    /// - if the actor is not allowed to update the [`EntityType`].
     async fn update_entity_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -292,6 +424,7 @@ pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
     /// # Errors
     ///
     /// - if the [`EntityType`] doesn't exist.
+    /// - if the actor is not allowed to archive the [`EntityType`].
     async fn archive_entity_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
@@ -304,10 +437,29 @@ pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
     /// # Errors
     ///
     /// - if the [`EntityType`] doesn't exist.
+    /// - if the actor is not allowed to archive the [`EntityType`].
     async fn unarchive_entity_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
         authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError>;
+
+    /// Returns all ontology types which directly or transitively depend on the [`EntityType`].
+    ///
+    /// For an [`EntityType`] these are the [`EntityType`]s inheriting from it. The entities of the
+    /// [`EntityType`] itself are included in the total entity count. See
+    /// [`DataTypeStore::get_data_type_dependents`] for which dependents are returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`OntologyVersionDoesNotExist`] if the [`EntityType`] doesn't exist.
+    ///
+    /// [`OntologyVersionDoesNotExist`]: crate::store::OntologyVersionDoesNotExist
+    async fn get_entity_type_dependents<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError>;
 }
@@ -1,21 +1,26 @@
 mod knowledge;
 mod ontology;
 
+mod aggregation;
+mod batch;
+mod change_feed;
 mod migration;
 mod pool;
 mod query;
 mod traversal_context;
+mod webhook;
+
+use std::collections::HashMap;
 
 use async_trait::async_trait;
-use authorization::{schema::OwnerId, AuthorizationApi, VisibilityScope};
+use authorization::{schema::OwnerId, zanzibar::Consistency, AuthorizationApi, VisibilityScope};
 use error_stack::{Report, Result, ResultExt};
-#[cfg(hash_graph_test_environment)]
-use graph_types::knowledge::{
-    entity::{EntityEditionId, EntityId, EntityProperties, EntityTemporalMetadata},
-    link::LinkOrder,
-};
 use graph_types::{
     account::{AccountGroupId, AccountId},
+    knowledge::{
+        entity::{EntityEditionId, EntityId, EntityProperties, EntityTemporalMetadata, EntityUuid},
+        link::LinkOrder,
+    },
     ontology::{
         CustomOntologyMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
         OntologyTypeRecordId, OntologyTypeVersion, PartialCustomOntologyMetadata,
@@ -23,13 +28,11 @@ use graph_types::{
     provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
     web::WebId,
 };
-#[cfg(hash_graph_test_environment)]
-use temporal_versioning::{DecisionTime, Timestamp};
-use temporal_versioning::{LeftClosedTemporalInterval, TransactionTime};
+use temporal_versioning::{DecisionTime, LeftClosedTemporalInterval, Timestamp, TransactionTime};
 use time::OffsetDateTime;
-#[cfg(hash_graph_test_environment)]
-use tokio_postgres::{binary_copy::BinaryCopyInWriter, types::Type};
-use tokio_postgres::{error::SqlState, GenericClient};
+use tokio_postgres::{
+    binary_copy::BinaryCopyInWriter, error::SqlState, types::Type, GenericClient,
+};
 use type_system::{
     repr,
     url::{BaseUrl, VersionedUrl},
@@ -42,11 +45,18 @@ pub use self::{
 };
 #[cfg(hash_graph_test_environment)]
 use crate::store::error::DeletionError;
-use crate::store::{
-    error::{OntologyTypeIsNotOwned, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
-    postgres::ontology::{OntologyDatabaseType, OntologyId},
-    AccountStore, BaseUrlAlreadyExists, ConflictBehavior, InsertionError, QueryError, StoreError,
-    UpdateError,
+use crate::{
+    store::{
+        error::{
+            EntityUuidAlreadyExists, OntologyTypeIsNotOwned, OntologyVersionDoesNotExist,
+            VersionedUrlAlreadyExists,
+        },
+        postgres::ontology::{OntologyDatabaseType, OntologyId},
+        AccountStore, BaseUrlAlreadyExists, ConflictBehavior, InsertionError,
+        OntologyTypeDependencyReference, OntologyTypeDependent, OntologyTypeDependents, QueryError,
+        StoreError, UpdateError,
+    },
+    subgraph::edges::OntologyEdgeKind,
 };
 
 /// A Postgres-backed store
@@ -70,12 +80,13 @@ where
         Self { client }
     }
 
+    /// Inserts the [`BaseUrl`] and returns if it did not exist before.
     async fn create_base_url(
         &self,
         base_url: &BaseUrl,
         on_conflict: ConflictBehavior,
         location: OntologyLocation,
-    ) -> Result<(), InsertionError> {
+    ) -> Result<bool, InsertionError> {
         match on_conflict {
             ConflictBehavior::Fail => {
                 self.as_client()
@@ -94,6 +105,8 @@ where
                             .change_context(InsertionError)
                             .attach_printable(base_url.clone()),
                     })?;
+
+                Ok(true)
             }
             ConflictBehavior::Skip => {
                 let created = self
@@ -143,10 +156,10 @@ where
                             .change_context(InsertionError));
                     }
                 }
+
+                Ok(created)
             }
         }
-
-        Ok(())
     }
 
     async fn create_ontology_id(
@@ -333,6 +346,216 @@ where
         })
     }
 
+    /// Returns all ontology types which directly or transitively depend on the ontology type `T`
+    /// with the specified `id`.
+    ///
+    /// The dependents are searched breadth-first, so each dependent is reported with the minimum
+    /// number of references to the requested type. Archived types and entities are ignored and
+    /// only entities the actor is allowed to view are counted.
+    async fn read_ontology_type_dependents<T: OntologyDatabaseType, A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError> {
+        let ontology_id: OntologyId = self
+            .as_client()
+            .query_opt(
+                &format!(
+                    r#"
+                        SELECT ontology_id
+                        FROM ontology_ids
+                        JOIN {} USING (ontology_id)
+                        WHERE base_url = $1 AND version = $2;
+                    "#,
+                    T::table()
+                ),
+                &[&id.base_url.as_str(), &OntologyTypeVersion::new(id.version)],
+            )
+            .await
+            .change_context(QueryError)?
+            .ok_or_else(|| {
+                Report::new(OntologyVersionDoesNotExist)
+                    .attach_printable(id.clone())
+                    .change_context(QueryError)
+            })?
+            .get(0);
+
+        let mut type_ids = HashMap::from([(ontology_id, id.clone())]);
+        // Maps the ontology id of a dependent to its position in `dependents`.
+        let mut dependent_indices = HashMap::new();
+        let mut dependents = Vec::<OntologyTypeDependent>::new();
+        let mut entity_type_ids = Vec::new();
+        if T::table() == EntityType::table() {
+            entity_type_ids.push(ontology_id);
+        }
+
+        let mut frontier = vec![ontology_id];
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            depth += 1;
+            let rows = self
+                .as_client()
+                .query(
+                    r#"
+                        WITH edges AS (
+                            SELECT source_property_type_ontology_id AS source,
+                                   target_data_type_ontology_id AS target,
+                                   'CONSTRAINS_VALUES_ON' AS edge_kind,
+                                   FALSE AS source_is_entity_type
+                            FROM property_type_constrains_values_on
+                            UNION ALL
+                            SELECT source_property_type_ontology_id,
+                                   target_property_type_ontology_id,
+                                   'CONSTRAINS_PROPERTIES_ON',
+                                   FALSE
+                            FROM property_type_constrains_properties_on
+                            UNION ALL
+                            SELECT source_entity_type_ontology_id,
+                                   target_property_type_ontology_id,
+                                   'CONSTRAINS_PROPERTIES_ON',
+                                   TRUE
+                            FROM entity_type_constrains_properties_on
+                            UNION ALL
+                            SELECT source_entity_type_ontology_id,
+                                   target_entity_type_ontology_id,
+                                   'INHERITS_FROM',
+                                   TRUE
+                            FROM entity_type_inherits_from
+                        )
+                        SELECT
+                            edges.source,
+                            ontology_ids.base_url,
+                            ontology_ids.version,
+                            edges.target,
+                            edges.edge_kind,
+                            edges.source_is_entity_type
+                        FROM edges
+                        JOIN ontology_ids
+                          ON ontology_ids.ontology_id = edges.source
+                        JOIN ontology_temporal_metadata
+                          ON ontology_temporal_metadata.ontology_id = edges.source
+                        WHERE edges.target = ANY($1)
+                          AND ontology_temporal_metadata.transaction_time @> now()
+                        ORDER BY ontology_ids.base_url, ontology_ids.version, edges.edge_kind;
+                    "#,
+                    &[&frontier],
+                )
+                .await
+                .change_context(QueryError)?;
+
+            frontier = Vec::new();
+            for row in rows {
+                let source: OntologyId = row.get(0);
+                let target: OntologyId = row.get(3);
+                let edge_kind = match row.get::<_, &str>(4) {
+                    "CONSTRAINS_VALUES_ON" => OntologyEdgeKind::ConstrainsValuesOn,
+                    "CONSTRAINS_PROPERTIES_ON" => OntologyEdgeKind::ConstrainsPropertiesOn,
+                    "INHERITS_FROM" => OntologyEdgeKind::InheritsFrom,
+                    edge_kind => {
+                        return Err(Report::new(QueryError)
+                            .attach_printable(format!("unexpected edge kind `{edge_kind}`")));
+                    }
+                };
+                if source == ontology_id {
+                    // A type may reference itself, e.g. a recursive property type.
+                    continue;
+                }
+
+                let reference = OntologyTypeDependencyReference {
+                    type_id: type_ids[&target].clone(),
+                    edge_kind,
+                };
+                let index = *dependent_indices.entry(source).or_insert_with(|| {
+                    frontier.push(source);
+                    dependents.len()
+                });
+                if let Some(dependent) = dependents.get_mut(index) {
+                    if !dependent.references.contains(&reference) {
+                        dependent.references.push(reference);
+                    }
+                } else {
+                    let type_id = VersionedUrl::from(OntologyTypeRecordId {
+                        base_url: BaseUrl::new(row.get(1)).change_context(QueryError)?,
+                        version: row.get(2),
+                    });
+                    let is_entity_type: bool = row.get(5);
+                    if is_entity_type {
+                        entity_type_ids.push(source);
+                    }
+                    type_ids.insert(source, type_id.clone());
+                    dependents.push(OntologyTypeDependent {
+                        type_id,
+                        depth,
+                        references: vec![reference],
+                        entity_count: is_entity_type.then_some(0),
+                    });
+                }
+            }
+        }
+
+        let (viewable_entities, _) = authorization_api
+            .lookup_viewable_entities(actor_id, Consistency::FullyConsistent)
+            .await
+            .change_context(QueryError)?;
+        let viewable_uuids = viewable_entities.map(|entity_uuids| {
+            entity_uuids
+                .into_iter()
+                .map(EntityUuid::into_uuid)
+                .collect::<Vec<_>>()
+        });
+
+        let entity_counts = self
+            .as_client()
+            .query(
+                r#"
+                    SELECT
+                        entity_is_of_type.entity_type_ontology_id,
+                        count(DISTINCT (
+                            entity_temporal_metadata.owned_by_id,
+                            entity_temporal_metadata.entity_uuid
+                        ))
+                    FROM entity_is_of_type
+                    JOIN entity_editions
+                      ON entity_editions.entity_edition_id = entity_is_of_type.entity_edition_id
+                    JOIN entity_temporal_metadata
+                      ON entity_temporal_metadata.entity_edition_id
+                         = entity_is_of_type.entity_edition_id
+                    WHERE entity_is_of_type.entity_type_ontology_id = ANY($1)
+                      AND entity_temporal_metadata.decision_time @> now()
+                      AND entity_temporal_metadata.transaction_time @> now()
+                      AND NOT entity_editions.archived
+                      AND ($2::uuid[] IS NULL OR entity_temporal_metadata.entity_uuid = ANY($2))
+                    GROUP BY ROLLUP (entity_is_of_type.entity_type_ontology_id);
+                "#,
+                &[&entity_type_ids, &viewable_uuids],
+            )
+            .await
+            .change_context(QueryError)?;
+
+        let mut entity_count = 0;
+        for row in entity_counts {
+            let count = row.get(1);
+            match row.get::<_, Option<OntologyId>>(0) {
+                // The rollup row contains the number of distinct entities across all types.
+                None => entity_count = count,
+                Some(entity_type_id) => {
+                    if let Some(dependent) = dependent_indices
+                        .get(&entity_type_id)
+                        .and_then(|index| dependents.get_mut(*index))
+                    {
+                        dependent.entity_count = Some(count);
+                    }
+                }
+            }
+        }
+
+        Ok(OntologyTypeDependents {
+            dependents,
+            entity_count,
+        })
+    }
+
     async fn create_ontology_owned_metadata(
         &self,
         ontology_id: OntologyId,
@@ -728,6 +951,10 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
     /// - If the [`BaseUrl`] already exists and `on_conflict` is [`ConflictBehavior::Fail`]
     /// - If the [`VersionedUrl`] already exists and `on_conflict` is [`ConflictBehavior::Fail`]
     ///
+    /// If the [`BaseUrl`] did not exist before, the [`VisibilityScope`] of the owner is returned,
+    /// which is [`VisibilityScope::Public`] for external types as they don't have an owner. This is
+    /// used to set up the permissions of the type, which are shared between all of its versions.
+    ///
     /// [`BaseUrl`]: type_system::url::BaseUrl
     #[tracing::instrument(level = "info", skip(self))]
     async fn create_ontology_metadata(
@@ -740,13 +967,14 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
         Option<(
             OntologyId,
             LeftClosedTemporalInterval<TransactionTime>,
-            VisibilityScope,
+            Option<VisibilityScope>,
         )>,
         InsertionError,
     > {
         match custom_metadata {
             PartialCustomOntologyMetadata::Owned { owned_by_id } => {
-                self.create_base_url(&record_id.base_url, on_conflict, OntologyLocation::Owned)
+                let created_base_url = self
+                    .create_base_url(&record_id.base_url, on_conflict, OntologyLocation::Owned)
                     .await?;
                 let ontology_id = self.create_ontology_id(record_id, on_conflict).await?;
                 if let Some(ontology_id) = ontology_id {
@@ -756,18 +984,23 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     let visibility = self
                         .create_ontology_owned_metadata(ontology_id, *owned_by_id)
                         .await?;
-                    Ok(Some((ontology_id, transaction_time, visibility)))
+                    Ok(Some((
+                        ontology_id,
+                        transaction_time,
+                        created_base_url.then_some(visibility),
+                    )))
                 } else {
                     Ok(None)
                 }
             }
             PartialCustomOntologyMetadata::External { fetched_at } => {
-                self.create_base_url(
-                    &record_id.base_url,
-                    ConflictBehavior::Skip,
-                    OntologyLocation::External,
-                )
-                .await?;
+                let created_base_url = self
+                    .create_base_url(
+                        &record_id.base_url,
+                        ConflictBehavior::Skip,
+                        OntologyLocation::External,
+                    )
+                    .await?;
                 let ontology_id = self.create_ontology_id(record_id, on_conflict).await?;
                 if let Some(ontology_id) = ontology_id {
                     let transaction_time = self
@@ -778,7 +1011,7 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     Ok(Some((
                         ontology_id,
                         transaction_time,
-                        VisibilityScope::Public,
+                        created_base_url.then_some(VisibilityScope::Public),
                     )))
                 } else {
                     Ok(None)
@@ -932,8 +1165,6 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
         self.client.rollback().await.change_context(StoreError)
     }
 
-    #[doc(hidden)]
-    #[cfg(hash_graph_test_environment)]
     async fn insert_entity_ids(
         &self,
         entity_uuids: impl IntoIterator<Item = EntityId, IntoIter: Send> + Send,
@@ -960,15 +1191,21 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                 .attach_printable(entity_id.entity_uuid)?;
         }
 
-        writer.finish().await.change_context(InsertionError)
+        writer
+            .finish()
+            .await
+            .map_err(Report::new)
+            .map_err(|report| match report.current_context().code() {
+                Some(&SqlState::UNIQUE_VIOLATION) => report
+                    .change_context(EntityUuidAlreadyExists)
+                    .change_context(InsertionError),
+                _ => report.change_context(InsertionError),
+            })
     }
 
-    #[doc(hidden)]
-    #[cfg(hash_graph_test_environment)]
     async fn insert_entity_is_of_type(
         &self,
-        entity_edition_ids: impl IntoIterator<Item = EntityEditionId, IntoIter: Send> + Send,
-        entity_type_ontology_id: OntologyId,
+        entity_types: impl IntoIterator<Item = (EntityEditionId, OntologyId), IntoIter: Send> + Send,
     ) -> Result<u64, InsertionError> {
         let sink = self
             .client
@@ -983,7 +1220,7 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
         let writer = BinaryCopyInWriter::new(sink, &[Type::UUID, Type::UUID]);
 
         futures::pin_mut!(writer);
-        for entity_edition_id in entity_edition_ids {
+        for (entity_edition_id, entity_type_ontology_id) in entity_types {
             writer
                 .as_mut()
                 .write(&[&entity_edition_id, &entity_type_ontology_id])
@@ -994,8 +1231,6 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
         writer.finish().await.change_context(InsertionError)
     }
 
-    #[doc(hidden)]
-    #[cfg(hash_graph_test_environment)]
     async fn insert_entity_links(
         &self,
         left_right: &'static str,
@@ -1034,12 +1269,10 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
         writer.finish().await.change_context(InsertionError)
     }
 
-    #[doc(hidden)]
-    #[cfg(hash_graph_test_environment)]
     async fn insert_entity_records(
         &self,
         entities: impl IntoIterator<
-            Item = (EntityProperties, Option<LinkOrder>, Option<LinkOrder>),
+            Item = (EntityProperties, Option<LinkOrder>, Option<LinkOrder>, bool),
             IntoIter: Send,
         > + Send,
         actor_id: RecordCreatedById,
@@ -1075,7 +1308,7 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
             &[Type::JSONB, Type::INT4, Type::INT4, Type::UUID, Type::BOOL],
         );
         futures::pin_mut!(writer);
-        for (properties, left_to_right_order, right_to_left_order) in entities {
+        for (properties, left_to_right_order, right_to_left_order, archived) in entities {
             let properties = serde_json::to_value(properties).change_context(InsertionError)?;
 
             writer
@@ -1085,7 +1318,7 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     &left_to_right_order,
                     &right_to_left_order,
                     &actor_id,
-                    &false,
+                    &archived,
                 ])
                 .await
                 .change_context(InsertionError)?;
@@ -1102,6 +1335,7 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     left_to_right_order,
                     right_to_left_order,
                     record_created_by_id,
+                    record_archived_by_id,
                     archived
                 )
                 SELECT
@@ -1110,6 +1344,7 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     left_to_right_order,
                     right_to_left_order,
                     record_created_by_id,
+                    CASE WHEN archived THEN record_created_by_id END,
                     archived
                 FROM entity_editions_temp
                 RETURNING entity_edition_id;",
@@ -1129,8 +1364,6 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
         Ok(entity_edition_ids)
     }
 
-    #[doc(hidden)]
-    #[cfg(hash_graph_test_environment)]
     async fn insert_entity_versions(
         &self,
         entities: impl IntoIterator<
@@ -1372,6 +1605,11 @@ impl<C: AsClient> PostgresStore<C> {
         actor_id: AccountId,
         _authorization_api: &A,
     ) -> Result<(), DeletionError> {
+        self.as_client()
+            .client()
+            .simple_query("DELETE FROM webhooks;")
+            .await
+            .change_context(DeletionError)?;
         self.as_client()
             .client()
             .simple_query("DELETE FROM accounts;")
@@ -0,0 +1,56 @@
+use error_stack::{Result, ResultExt};
+use tokio_postgres::GenericClient;
+
+use crate::store::{
+    postgres::query::{PostgresQueryPath, PostgresRecord, SelectCompiler},
+    query::{AggregationQuery, AggregationResult, Filter},
+    AsClient, PostgresStore, QueryError, Record,
+};
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Computes the aggregation specified by the [`AggregationQuery`].
+    ///
+    /// If a `restriction` is passed, only records matching both, the query's filter and the
+    /// restriction, are aggregated.
+    pub(crate) async fn aggregate_records<R>(
+        &self,
+        query: &AggregationQuery<'_, R>,
+        restriction: Option<&Filter<'_, R>>,
+    ) -> Result<Vec<AggregationResult>, QueryError>
+    where
+        R: for<'p> Record<QueryPath<'p>: PostgresQueryPath> + PostgresRecord,
+    {
+        let temporal_axes = query.temporal_axes.clone().resolve();
+        let mut compiler = SelectCompiler::new(Some(&temporal_axes));
+
+        let group_index = query
+            .group_by
+            .as_ref()
+            .map(|path| compiler.add_group_by(path));
+        let value_index = compiler.add_aggregation(&query.aggregation);
+
+        compiler.add_filter(&query.filter);
+        if let Some(restriction) = restriction {
+            compiler.add_filter(restriction);
+        }
+        let (statement, parameters) = compiler.compile();
+
+        Ok(self
+            .as_client()
+            .query(&statement, parameters)
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| AggregationResult {
+                // Aggregating an empty set or grouping by a non-existing value results in `NULL`
+                group: group_index.map(|index| {
+                    row.get::<_, Option<serde_json::Value>>(index)
+                        .unwrap_or(serde_json::Value::Null)
+                }),
+                value: row
+                    .get::<_, Option<serde_json::Value>>(value_index)
+                    .unwrap_or(serde_json::Value::Null),
+            })
+            .collect())
+    }
+}
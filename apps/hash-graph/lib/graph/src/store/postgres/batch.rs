@@ -0,0 +1,301 @@
+use async_trait::async_trait;
+use authorization::{backend::PermissionAssertion, AuthorizationApi, VisibilityScope};
+use error_stack::{Report, Result, ResultExt};
+use graph_types::{
+    account::AccountId,
+    knowledge::{entity::EntityId, link::LinkData},
+    ontology::{CustomOntologyMetadata, DataTypeId, EntityTypeId, PropertyTypeId},
+};
+use type_system::url::VersionedUrl;
+
+use crate::{
+    knowledge::EntityValidationError,
+    store::{
+        error::{
+            EntityDoesNotExist, EntityIsAlreadyArchived, InvalidEntityReference,
+            OntologyVersionDoesNotExist, VersionedUrlAlreadyExists,
+        },
+        postgres::ontology::{
+            remove_data_type_relations, remove_entity_type_relations,
+            remove_property_type_relations,
+        },
+        AsClient, BaseUrlAlreadyExists, BatchStore, BulkItemFailure, DataTypeStore,
+        EntityReference, EntityStore, EntityTypeStore, InsertionError, PostgresStore,
+        PropertyTypeStore, QueryError, WriteOperation, WriteOperationOutput,
+    },
+};
+
+/// Returns the entity the `reference` points to.
+///
+/// `outputs` are the outputs of the operations preceding the operation containing the reference.
+fn resolve_entity_reference(
+    reference: EntityReference,
+    outputs: &[WriteOperationOutput],
+) -> Result<EntityId, InvalidEntityReference> {
+    match reference {
+        EntityReference::Existing(entity_id) => Ok(entity_id),
+        EntityReference::Created { operation_index } => match outputs.get(operation_index) {
+            Some(WriteOperationOutput::Entity(metadata)) => Ok(metadata.record_id().entity_id),
+            _ => Err(Report::new(InvalidEntityReference)
+                .attach_printable(format!("operation index: {operation_index}"))),
+        },
+    }
+}
+
+/// Returns why an operation of a batch was rejected if the failure was caused by the operation
+/// itself rather than by the store.
+fn rejection_reason<C>(report: &Report<C>) -> Option<String> {
+    report
+        .downcast_ref::<EntityValidationError>()
+        .map(ToString::to_string)
+        .or_else(|| {
+            report
+                .downcast_ref::<InvalidEntityReference>()
+                .map(ToString::to_string)
+        })
+        .or_else(|| {
+            report
+                .downcast_ref::<BaseUrlAlreadyExists>()
+                .map(ToString::to_string)
+        })
+        .or_else(|| {
+            report
+                .downcast_ref::<VersionedUrlAlreadyExists>()
+                .map(ToString::to_string)
+        })
+        .or_else(|| {
+            report
+                .downcast_ref::<OntologyVersionDoesNotExist>()
+                .map(ToString::to_string)
+        })
+        .or_else(|| {
+            report
+                .downcast_ref::<EntityDoesNotExist>()
+                .map(ToString::to_string)
+        })
+        .or_else(|| {
+            report
+                .downcast_ref::<EntityIsAlreadyArchived>()
+                .map(ToString::to_string)
+        })
+        .or_else(|| {
+            report
+                .downcast_ref::<PermissionAssertion>()
+                .map(ToString::to_string)
+        })
+}
+
+impl PostgresStore<tokio_postgres::Transaction<'_>> {
+    async fn apply_write_operation<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        operation: WriteOperation,
+        outputs: &[WriteOperationOutput],
+    ) -> Result<WriteOperationOutput, InsertionError> {
+        match operation {
+            WriteOperation::CreateDataType { schema, metadata } => self
+                .create_data_type(actor_id, authorization_api, *schema, metadata)
+                .await
+                .map(WriteOperationOutput::DataType),
+            WriteOperation::CreatePropertyType { schema, metadata } => self
+                .create_property_type(actor_id, authorization_api, *schema, metadata)
+                .await
+                .map(WriteOperationOutput::PropertyType),
+            WriteOperation::CreateEntityType { schema, metadata } => self
+                .create_entity_type(actor_id, authorization_api, *schema, metadata)
+                .await
+                .map(WriteOperationOutput::EntityType),
+            WriteOperation::CreateEntity {
+                owned_by_id,
+                entity_uuid,
+                entity_type_id,
+                properties,
+                link_data,
+            } => {
+                let link_data = match link_data {
+                    Some(link_data) => Some(LinkData {
+                        left_entity_id: resolve_entity_reference(link_data.left_entity, outputs)
+                            .change_context(InsertionError)?,
+                        right_entity_id: resolve_entity_reference(link_data.right_entity, outputs)
+                            .change_context(InsertionError)?,
+                        order: link_data.order,
+                    }),
+                    None => None,
+                };
+
+                self.create_entity(
+                    actor_id,
+                    authorization_api,
+                    owned_by_id,
+                    entity_uuid,
+                    None,
+                    false,
+                    entity_type_id,
+                    properties,
+                    link_data,
+                )
+                .await
+                .map(WriteOperationOutput::Entity)
+            }
+            WriteOperation::ArchiveDataType(id) => self
+                .archive_data_type(actor_id, authorization_api, &id)
+                .await
+                .change_context(InsertionError)
+                .map(WriteOperationOutput::ArchivedOntologyType),
+            WriteOperation::ArchivePropertyType(id) => self
+                .archive_property_type(actor_id, authorization_api, &id)
+                .await
+                .change_context(InsertionError)
+                .map(WriteOperationOutput::ArchivedOntologyType),
+            WriteOperation::ArchiveEntityType(id) => self
+                .archive_entity_type(actor_id, authorization_api, &id)
+                .await
+                .change_context(InsertionError)
+                .map(WriteOperationOutput::ArchivedOntologyType),
+            WriteOperation::ArchiveEntity { entity, cascade } => {
+                let entity_id =
+                    resolve_entity_reference(entity, outputs).change_context(InsertionError)?;
+                self.archive_entity(actor_id, authorization_api, entity_id, cascade)
+                    .await
+                    .change_context(InsertionError)
+                    .map(WriteOperationOutput::ArchivedEntity)
+            }
+        }
+    }
+}
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Returns the owner the relations of a created ontology type were written for.
+    ///
+    /// External types don't have an owner, so only the public viewer is written for them.
+    async fn ontology_owner_scope(
+        &self,
+        custom_metadata: &CustomOntologyMetadata,
+    ) -> Result<VisibilityScope, QueryError> {
+        match custom_metadata {
+            CustomOntologyMetadata::Owned { owned_by_id, .. } => {
+                self.entity_owner_scope(*owned_by_id).await
+            }
+            CustomOntologyMetadata::External { .. } => Ok(VisibilityScope::Public),
+        }
+    }
+
+    /// Removes the relations, which were written to the authorization backend when the element
+    /// of `output` was created.
+    ///
+    /// Archiving does not write any relations, so nothing is removed for archived elements.
+    async fn remove_created_relations<A: AuthorizationApi + Send + Sync>(
+        &self,
+        authorization_api: &mut A,
+        output: &WriteOperationOutput,
+    ) -> Result<(), InsertionError> {
+        match output {
+            WriteOperationOutput::DataType(metadata) => {
+                let owner = self
+                    .ontology_owner_scope(&metadata.custom)
+                    .await
+                    .change_context(InsertionError)?;
+                let data_type_id =
+                    DataTypeId::from_url(&VersionedUrl::from(metadata.record_id.clone()));
+                remove_data_type_relations(authorization_api, data_type_id, owner)
+                    .await
+                    .change_context(InsertionError)
+            }
+            WriteOperationOutput::PropertyType(metadata) => {
+                let owner = self
+                    .ontology_owner_scope(&metadata.custom)
+                    .await
+                    .change_context(InsertionError)?;
+                let property_type_id =
+                    PropertyTypeId::from_url(&VersionedUrl::from(metadata.record_id.clone()));
+                remove_property_type_relations(authorization_api, property_type_id, owner)
+                    .await
+                    .change_context(InsertionError)
+            }
+            WriteOperationOutput::EntityType(metadata) => {
+                let owner = self
+                    .ontology_owner_scope(&metadata.custom.common)
+                    .await
+                    .change_context(InsertionError)?;
+                let entity_type_id =
+                    EntityTypeId::from_url(&VersionedUrl::from(metadata.record_id.clone()));
+                remove_entity_type_relations(authorization_api, entity_type_id, owner)
+                    .await
+                    .change_context(InsertionError)
+            }
+            WriteOperationOutput::Entity(metadata) => {
+                let entity_id = metadata.record_id().entity_id;
+                let visibility_scope = self
+                    .entity_owner_scope(entity_id.owned_by_id)
+                    .await
+                    .change_context(InsertionError)?;
+                authorization_api
+                    .remove_entity_owner(visibility_scope, entity_id)
+                    .await
+                    .change_context(InsertionError)
+            }
+            WriteOperationOutput::ArchivedOntologyType(_)
+            | WriteOperationOutput::ArchivedEntity(_) => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl<C: AsClient> BatchStore for PostgresStore<C> {
+    #[tracing::instrument(level = "info", skip(self, authorization_api, operations))]
+    async fn write_batch<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        operations: Vec<WriteOperation>,
+    ) -> Result<Vec<WriteOperationOutput>, InsertionError> {
+        let mut transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let mut outputs = Vec::with_capacity(operations.len());
+        let mut failure = None;
+        for (index, operation) in operations.into_iter().enumerate() {
+            match transaction
+                .apply_write_operation(actor_id, authorization_api, operation, &outputs)
+                .await
+            {
+                Ok(output) => outputs.push(output),
+                Err(report) => {
+                    failure = Some(match rejection_reason(&report) {
+                        Some(reason) => report.attach(BulkItemFailure::new(index, reason)),
+                        None => report.attach_printable(format!("operation index: {index}")),
+                    });
+                    break;
+                }
+            }
+        }
+
+        let result = match failure {
+            Some(report) => {
+                // Dropping the transaction without committing rolls it back.
+                drop(transaction);
+                Err(report)
+            }
+            None => transaction.commit().await.change_context(InsertionError),
+        };
+
+        if let Err(mut error) = result {
+            // The relations of the created elements were written to the authorization backend,
+            // which is not part of the transaction.
+            for output in &outputs {
+                if let Err(auth_error) = self
+                    .remove_created_relations(authorization_api, output)
+                    .await
+                {
+                    // TODO: Use `add_child`
+                    //   see https://linear.app/hash/issue/GEN-105/add-ability-to-add-child-errors
+                    error.extend_one(auth_error);
+                }
+            }
+
+            return Err(error);
+        }
+
+        Ok(outputs)
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use authorization::{zanzibar::Consistency, AuthorizationApi};
+use error_stack::{Report, Result, ResultExt};
+use graph_types::{
+    account::AccountId,
+    knowledge::entity::{EntityId, EntityRecordId},
+    ontology::OntologyTypeRecordId,
+};
+use temporal_versioning::Timestamp;
+use tokio_postgres::GenericClient;
+use type_system::url::BaseUrl;
+
+use crate::store::{
+    AsClient, Change, ChangeCursor, ChangeFeedStore, ChangeKind, ChangedRecordId, Changes,
+    PostgresStore, QueryError,
+};
+
+#[async_trait]
+impl<C: AsClient> ChangeFeedStore for PostgresStore<C> {
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn read_changes<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        cursor: Option<ChangeCursor>,
+        limit: usize,
+    ) -> Result<Changes, QueryError> {
+        // An edition of an entity is created at the lower bound of the transaction time of the
+        // first row pointing to it. Rows, which point to the edition later on, only exist
+        // because the decision time of the entity was split by a later update.
+        //
+        // Ontology types are immutable, so they are only created (or unarchived) at the lower
+        // bound of their transaction time and archived at its upper bound.
+        //
+        // Erased entities are read from their tombstones. Entity UUIDs are unique, so the UUID of
+        // the entity identifies its erasure at the time it was erased.
+        //
+        // The transaction time is the start time of the writing transaction, so a transaction,
+        // which is still in flight, may commit changes older than already committed ones. Changes
+        // are therefore held back until all transactions started before them have finished. The
+        // changes of the reading transaction itself are not held back, as they are already visible
+        // to it.
+        //
+        // `pg_stat_activity` only reports the start of transactions of other roles to members of
+        // `pg_read_all_stats`, so the role has to be granted it if other roles write to the
+        // database.
+        let rows = self
+            .as_client()
+            .query(
+                r#"
+                    WITH horizon AS (
+                        SELECT coalesce(min(xact_start), now()) AS transaction_time
+                        FROM pg_stat_activity
+                        WHERE datname = current_database()
+                          AND backend_type = 'client backend'
+                          AND pid <> pg_backend_pid()
+                          AND xact_start IS NOT NULL
+                    )
+                    SELECT changes.*
+                    FROM (
+                        (SELECT DISTINCT ON (current.entity_edition_id)
+                            lower(current.transaction_time) AS transaction_time,
+                            current.entity_edition_id AS change_id,
+                            CASE
+                                WHEN entity_editions.archived THEN 'archived'
+                                WHEN EXISTS (
+                                    SELECT 1
+                                    FROM entity_temporal_metadata AS previous
+                                    WHERE previous.owned_by_id = current.owned_by_id
+                                      AND previous.entity_uuid = current.entity_uuid
+                                      AND lower(previous.transaction_time)
+                                        < lower(current.transaction_time)
+                                ) THEN 'updated'
+                                ELSE 'created'
+                            END AS change_kind,
+                            'entity' AS record_kind,
+                            current.owned_by_id,
+                            current.entity_uuid,
+                            NULL::TEXT AS base_url,
+                            NULL::BIGINT AS version
+                        FROM entity_temporal_metadata AS current
+                        JOIN entity_editions
+                          ON entity_editions.entity_edition_id = current.entity_edition_id
+                        WHERE NOT EXISTS (
+                            SELECT 1
+                            FROM entity_temporal_metadata AS earlier
+                            WHERE earlier.entity_edition_id = current.entity_edition_id
+                              AND lower(earlier.transaction_time)
+                                < lower(current.transaction_time)
+                        )
+                          AND ($1::TIMESTAMPTZ IS NULL
+                            OR (lower(current.transaction_time), current.entity_edition_id)
+                             > ($1, $2))
+                          AND (lower(current.transaction_time)
+                                < (SELECT transaction_time FROM horizon)
+                            OR current.xmin = pg_current_xact_id_if_assigned()::xid)
+                        ORDER BY current.entity_edition_id)
+
+                        UNION ALL
+
+                        SELECT
+                            ontology_changes.transaction_time,
+                            ontology_changes.ontology_id AS change_id,
+                            ontology_changes.change_kind,
+                            CASE
+                                WHEN data_types.ontology_id IS NOT NULL THEN 'dataType'
+                                WHEN property_types.ontology_id IS NOT NULL THEN 'propertyType'
+                                ELSE 'entityType'
+                            END AS record_kind,
+                            NULL::UUID AS owned_by_id,
+                            NULL::UUID AS entity_uuid,
+                            ontology_ids.base_url,
+                            ontology_ids.version
+                        FROM (
+                            SELECT
+                                lower(current.transaction_time) AS transaction_time,
+                                current.ontology_id,
+                                current.xmin,
+                                CASE
+                                    WHEN EXISTS (
+                                        SELECT 1
+                                        FROM ontology_temporal_metadata AS previous
+                                        WHERE previous.ontology_id = current.ontology_id
+                                          AND lower(previous.transaction_time)
+                                            < lower(current.transaction_time)
+                                    ) THEN 'updated'
+                                    ELSE 'created'
+                                END AS change_kind
+                            FROM ontology_temporal_metadata AS current
+
+                            UNION ALL
+
+                            SELECT
+                                upper(transaction_time) AS transaction_time,
+                                ontology_id,
+                                xmin,
+                                'archived' AS change_kind
+                            FROM ontology_temporal_metadata
+                            WHERE upper(transaction_time) IS NOT NULL
+                        ) AS ontology_changes
+                        JOIN ontology_ids
+                          ON ontology_ids.ontology_id = ontology_changes.ontology_id
+                        LEFT JOIN data_types
+                          ON data_types.ontology_id = ontology_changes.ontology_id
+                        LEFT JOIN property_types
+                          ON property_types.ontology_id = ontology_changes.ontology_id
+                        WHERE ($1::TIMESTAMPTZ IS NULL
+                            OR (ontology_changes.transaction_time, ontology_changes.ontology_id)
+                             > ($1, $2))
+                          AND (ontology_changes.transaction_time
+                                < (SELECT transaction_time FROM horizon)
+                            OR ontology_changes.xmin = pg_current_xact_id_if_assigned()::xid)
+
+                        UNION ALL
+
+                        SELECT
+                            erased_at AS transaction_time,
+                            entity_uuid AS change_id,
+                            'erased' AS change_kind,
+                            'erasedEntity' AS record_kind,
+                            owned_by_id,
+                            entity_uuid,
+                            NULL::TEXT AS base_url,
+                            NULL::BIGINT AS version
+                        FROM entity_tombstones
+                        WHERE ($1::TIMESTAMPTZ IS NULL OR (erased_at, entity_uuid) > ($1, $2))
+                          AND (erased_at < (SELECT transaction_time FROM horizon)
+                            OR entity_tombstones.xmin = pg_current_xact_id_if_assigned()::xid)
+                    ) AS changes
+                    ORDER BY transaction_time, change_id
+                    LIMIT $3;
+                "#,
+                &[
+                    &cursor.map(|cursor| cursor.transaction_time),
+                    &cursor.map(|cursor| cursor.change_id),
+                    &i64::try_from(limit).change_context(QueryError)?,
+                ],
+            )
+            .await
+            .change_context(QueryError)?;
+
+        let mut changes = Vec::with_capacity(rows.len());
+        let mut next_cursor = cursor;
+        for row in rows {
+            let transaction_time = Timestamp::from_anonymous(row.get("transaction_time"));
+            next_cursor = Some(ChangeCursor {
+                transaction_time,
+                change_id: row.get("change_id"),
+            });
+
+            let kind = match row.get::<_, &str>("change_kind") {
+                "created" => ChangeKind::Created,
+                "updated" => ChangeKind::Updated,
+                "archived" => ChangeKind::Archived,
+                "erased" => ChangeKind::Erased,
+                kind => {
+                    return Err(Report::new(QueryError)
+                        .attach_printable(format!("unknown change kind: {kind}")));
+                }
+            };
+
+            let record_kind: &str = row.get("record_kind");
+            let record_id = if record_kind == "entity" {
+                ChangedRecordId::Entity(EntityRecordId {
+                    entity_id: EntityId {
+                        owned_by_id: row.get("owned_by_id"),
+                        entity_uuid: row.get("entity_uuid"),
+                    },
+                    edition_id: row.get("change_id"),
+                })
+            } else if record_kind == "erasedEntity" {
+                ChangedRecordId::ErasedEntity(EntityId {
+                    owned_by_id: row.get("owned_by_id"),
+                    entity_uuid: row.get("entity_uuid"),
+                })
+            } else {
+                let ontology_record_id = OntologyTypeRecordId {
+                    base_url: BaseUrl::new(row.get("base_url")).change_context(QueryError)?,
+                    version: row.get("version"),
+                };
+                match record_kind {
+                    "dataType" => ChangedRecordId::DataType(ontology_record_id),
+                    "propertyType" => ChangedRecordId::PropertyType(ontology_record_id),
+                    _ => ChangedRecordId::EntityType(ontology_record_id),
+                }
+            };
+
+            changes.push(Change {
+                transaction_time,
+                kind,
+                record_id,
+            });
+        }
+
+        let entity_ids = changes
+            .iter()
+            .filter_map(|change| match change.record_id {
+                ChangedRecordId::Entity(record_id) => Some(record_id.entity_id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if !entity_ids.is_empty() {
+            let (permissions, _) = authorization_api
+                .can_view_entities(actor_id, entity_ids, Consistency::FullyConsistent)
+                .await
+                .change_context(QueryError)?;
+            changes.retain(|change| match change.record_id {
+                ChangedRecordId::Entity(record_id) => permissions
+                    .get(&record_id.entity_id)
+                    .copied()
+                    .unwrap_or(false),
+                _ => true,
+            });
+        }
+
+        // Erased entities don't have any relations left, so their erasure is only returned to
+        // actors who are allowed to create entities in the web the entity was owned by.
+        let erased_webs = changes
+            .iter()
+            .filter_map(|change| match change.record_id {
+                ChangedRecordId::ErasedEntity(entity_id) => Some(entity_id.owned_by_id),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        let mut permitted_webs = HashSet::new();
+        for owned_by_id in erased_webs {
+            if authorization_api
+                .can_create_entity(actor_id, owned_by_id, Consistency::FullyConsistent)
+                .await
+                .change_context(QueryError)?
+                .has_permission
+            {
+                permitted_webs.insert(owned_by_id);
+            }
+        }
+        changes.retain(|change| match change.record_id {
+            ChangedRecordId::ErasedEntity(entity_id) => {
+                permitted_webs.contains(&entity_id.owned_by_id)
+            }
+            _ => true,
+        });
+
+        Ok(Changes {
+            changes,
+            cursor: next_cursor,
+        })
+    }
+}
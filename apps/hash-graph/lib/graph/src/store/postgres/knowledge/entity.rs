@@ -1,13 +1,22 @@
+mod paths;
 mod read;
+mod validation;
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    iter,
+    str::FromStr,
+};
 
 use async_trait::async_trait;
 use authorization::{
+    backend::PermissionAssertion,
+    schema::EntityRelation,
     zanzibar::{Consistency, Zookie},
     AuthorizationApi, VisibilityScope,
 };
 use error_stack::{Report, Result, ResultExt};
+use futures::TryStreamExt;
 use graph_types::{
     account::{AccountGroupId, AccountId},
     knowledge::{
@@ -17,42 +26,86 @@ use graph_types::{
         },
         link::{EntityLinkOrder, LinkData},
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordCreatedById},
+    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
+};
+use temporal_versioning::{
+    DecisionTime, LimitedTemporalBound, RightBoundedTemporalInterval, TemporalBound, TimeAxis,
+    Timestamp,
 };
-use temporal_versioning::{DecisionTime, RightBoundedTemporalInterval, Timestamp};
-#[cfg(hash_graph_test_environment)]
-use tokio_postgres::GenericClient;
+use tokio_postgres::{error::SqlState, GenericClient};
 use type_system::url::VersionedUrl;
 use uuid::Uuid;
 
-#[cfg(hash_graph_test_environment)]
-use crate::store::error::DeletionError;
 use crate::{
+    knowledge::{
+        diff_entities, diff_properties, patch_properties, EntityDiff, EntityQueryPath,
+        EntityValidationError, PropertyPatchOperation,
+    },
     store::{
         crud::Read,
-        error::{EntityDoesNotExist, RaceConditionOnUpdate},
+        error::{
+            DeletionError, EntityDoesNotExist, EntityEditionMismatch, EntityHasLinks,
+            EntityIsAlreadyArchived, EntityIsNotArchived, EntityUuidAlreadyExists,
+            RaceConditionOnUpdate,
+        },
         postgres::{
-            knowledge::entity::read::EntityEdgeTraversalData, query::ReferenceTable,
+            knowledge::entity::{read::EntityEdgeTraversalData, validation::EntityValidationCache},
+            query::{ReferenceTable, SelectCompiler},
             TraversalContext,
         },
-        AsClient, EntityStore, InsertionError, PostgresStore, QueryError, Record, UpdateError,
+        query::{
+            AggregationQuery, AggregationResult, EntityQueryCursor, Filter, FilterExpression,
+            ParameterList,
+        },
+        AsClient, BulkItemFailure, EntityCreation, EntityHistoryEntry, EntityPathQuery,
+        EntityStore, EntityUpdate, InsertionError, PostgresStore, QueryError, Record, UpdateError,
     },
     subgraph::{
-        edges::{EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind, SharedEdgeKind},
-        identifier::{EntityIdWithInterval, EntityVertexId},
+        edges::{
+            EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind,
+            KnowledgeGraphTraversalFilters, SharedEdgeKind,
+        },
+        identifier::{EntityIdWithInterval, EntityVertexId, GraphElementVertexId},
         query::StructuralQuery,
-        temporal_axes::VariableAxis,
+        temporal_axes::{QueryTemporalAxes, QueryTemporalAxesUnresolved, VariableAxis},
         Subgraph,
     },
 };
 
 impl<C: AsClient> PostgresStore<C> {
+    /// Returns the UUIDs of the entities the actor is permitted to view.
+    ///
+    /// `None` is returned if the actor is permitted to view all entities.
+    async fn viewable_entity_uuids<A: AuthorizationApi + Sync>(
+        actor_id: AccountId,
+        authorization_api: &A,
+    ) -> Result<(Option<Vec<Uuid>>, Zookie<'static>), QueryError> {
+        let (viewable_entities, zookie) = authorization_api
+            .lookup_viewable_entities(actor_id, Consistency::FullyConsistent)
+            .await
+            .change_context(QueryError)?;
+        let viewable_uuids = viewable_entities.map(|entity_uuids| {
+            entity_uuids
+                .into_iter()
+                .map(EntityUuid::into_uuid)
+                .collect::<Vec<_>>()
+        });
+        Ok((viewable_uuids, zookie))
+    }
+
     /// Internal method to read an [`Entity`] into a [`TraversalContext`].
     ///
     /// This is used to recursively resolve a type, so the result can be reused.
     #[tracing::instrument(
         level = "trace",
-        skip(self, traversal_context, subgraph, authorization_api, zookie)
+        skip(
+            self,
+            traversal_context,
+            traversal_filters,
+            subgraph,
+            authorization_api,
+            zookie
+        )
     )]
     pub(crate) async fn traverse_entities<A>(
         &self,
@@ -62,6 +115,7 @@ impl<C: AsClient> PostgresStore<C> {
             RightBoundedTemporalInterval<VariableAxis>,
         )>,
         traversal_context: &mut TraversalContext,
+        traversal_filters: &KnowledgeGraphTraversalFilters<'_>,
         actor_id: AccountId,
         authorization_api: &A,
         zookie: Zookie<'static>,
@@ -190,6 +244,22 @@ impl<C: AsClient> PostgresStore<C> {
                         .change_context(QueryError)?
                         .0;
 
+                    let matching_edition_ids =
+                        if let Some(filter) = traversal_filters.get(edge_kind, edge_direction) {
+                            Some(
+                                self.filter_entity_editions(
+                                    knowledge_edges
+                                        .iter()
+                                        .map(|edge| edge.right_endpoint_edition_id),
+                                    filter,
+                                    &subgraph.temporal_axes.resolved,
+                                )
+                                .await?,
+                            )
+                        } else {
+                            None
+                        };
+
                     entity_queue.extend(
                         knowledge_edges
                             .into_iter()
@@ -203,6 +273,11 @@ impl<C: AsClient> PostgresStore<C> {
                                     .unwrap_or(true)
                                     .then_some(edge)
                             })
+                            .filter(|edge| {
+                                matching_edition_ids.as_ref().map_or(true, |edition_ids| {
+                                    edition_ids.contains(&edge.right_endpoint_edition_id)
+                                })
+                            })
                             .flat_map(|edge| {
                                 subgraph.insert_edge(
                                     &edge.left_endpoint,
@@ -235,6 +310,39 @@ impl<C: AsClient> PostgresStore<C> {
         Ok(())
     }
 
+    /// Returns the editions out of `edition_ids`, which match the `filter`.
+    async fn filter_entity_editions(
+        &self,
+        edition_ids: impl IntoIterator<Item = EntityEditionId, IntoIter: Send> + Send,
+        filter: &Filter<'_, Entity>,
+        temporal_axes: &QueryTemporalAxes,
+    ) -> Result<HashSet<EntityEditionId>, QueryError> {
+        let ids = edition_ids
+            .into_iter()
+            .map(EntityEditionId::into_uuid)
+            .collect::<Vec<_>>();
+        let edition_filter = Filter::<Entity>::In(
+            FilterExpression::Path(EntityQueryPath::EditionId),
+            ParameterList::Uuid(&ids),
+        );
+
+        let mut compiler = SelectCompiler::new(Some(temporal_axes));
+        let edition_id_index = compiler.add_selection_path(&EntityQueryPath::EditionId);
+        compiler.add_filter(filter);
+        compiler.add_filter(&edition_filter);
+
+        let (statement, parameters) = compiler.compile();
+
+        Ok(self
+            .as_client()
+            .query(&statement, parameters)
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| row.get(edition_id_index))
+            .collect())
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     #[cfg(hash_graph_test_environment)]
     pub async fn delete_entities(&mut self) -> Result<(), DeletionError> {
@@ -248,6 +356,7 @@ impl<C: AsClient> PostgresStore<C> {
                     DELETE FROM entity_temporal_metadata;
                     DELETE FROM entity_editions;
                     DELETE FROM entity_ids;
+                    DELETE FROM entity_tombstones;
                 ",
             )
             .await
@@ -255,6 +364,160 @@ impl<C: AsClient> PostgresStore<C> {
 
         Ok(())
     }
+
+    /// Returns the scope the owner relation of an entity owned by `owned_by_id` is written for.
+    pub(crate) async fn entity_owner_scope(
+        &self,
+        owned_by_id: OwnedById,
+    ) -> Result<VisibilityScope, QueryError> {
+        let is_account_group: bool = self
+            .as_client()
+            .query_one(
+                r#"
+                    SELECT EXISTS (
+                        SELECT 1 FROM account_groups WHERE account_group_id = $1
+                    );
+                "#,
+                &[&owned_by_id],
+            )
+            .await
+            .change_context(QueryError)?
+            .get(0);
+
+        let owned_by_uuid = owned_by_id.into_uuid();
+        Ok(if is_account_group {
+            VisibilityScope::AccountGroup(AccountGroupId::new(owned_by_uuid))
+        } else {
+            VisibilityScope::Account(AccountId::new(owned_by_uuid))
+        })
+    }
+
+    /// Sets the `archived` flag of the entity and, if `cascade` is set, of all link entities
+    /// attached to it.
+    ///
+    /// Returns the metadata of the entity followed by the metadata of the updated links.
+    async fn set_entity_archived<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        archived: bool,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        authorization_api
+            .can_update_entity(actor_id, entity_id, Consistency::FullyConsistent)
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let entity_metadata = transaction
+            .insert_entity_edition_with_archived(actor_id, entity_id, archived)
+            .await?
+            .ok_or_else(|| {
+                if archived {
+                    Report::new(EntityIsAlreadyArchived)
+                        .attach(entity_id)
+                        .change_context(UpdateError)
+                } else {
+                    Report::new(EntityIsNotArchived)
+                        .attach(entity_id)
+                        .change_context(UpdateError)
+                }
+            })?;
+
+        let mut metadata = vec![entity_metadata];
+        if cascade {
+            // Only links, which are currently not in the requested state, are updated.
+            let link_entity_ids = transaction
+                .as_client()
+                .query(
+                    r#"
+                        SELECT links.owned_by_id, links.entity_uuid
+                        FROM (
+                            SELECT owned_by_id, entity_uuid
+                            FROM entity_has_left_entity
+                            WHERE left_owned_by_id = $1 AND left_entity_uuid = $2
+                            UNION
+                            SELECT owned_by_id, entity_uuid
+                            FROM entity_has_right_entity
+                            WHERE right_owned_by_id = $1 AND right_entity_uuid = $2
+                        ) AS links
+                        JOIN entity_temporal_metadata USING (owned_by_id, entity_uuid)
+                        JOIN entity_editions USING (entity_edition_id)
+                        WHERE entity_temporal_metadata.transaction_time @> now()
+                          AND entity_temporal_metadata.decision_time @> now()
+                          AND entity_editions.archived != $3;
+                    "#,
+                    &[&entity_id.owned_by_id, &entity_id.entity_uuid, &archived],
+                )
+                .await
+                .change_context(UpdateError)?
+                .into_iter()
+                .map(|row| EntityId {
+                    owned_by_id: row.get(0),
+                    entity_uuid: row.get(1),
+                })
+                .collect::<Vec<_>>();
+
+            let (permissions, _) = authorization_api
+                .can_update_entities(
+                    actor_id,
+                    link_entity_ids.iter().copied(),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(UpdateError)?;
+            let forbidden_links = link_entity_ids
+                .iter()
+                .filter(|link_entity_id| {
+                    !permissions.get(*link_entity_id).copied().unwrap_or(false)
+                })
+                .collect::<Vec<_>>();
+            if !forbidden_links.is_empty() {
+                let mut report = Report::new(PermissionAssertion).change_context(UpdateError);
+                for link_entity_id in forbidden_links {
+                    report = report.attach(*link_entity_id);
+                }
+                return Err(report);
+            }
+
+            for link_entity_id in link_entity_ids {
+                if let Some(link_metadata) = transaction
+                    .insert_entity_edition_with_archived(actor_id, link_entity_id, archived)
+                    .await?
+                {
+                    metadata.push(link_metadata);
+                }
+            }
+        }
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
+    }
+
+    /// Reads the latest edition of the entity within the temporal axes.
+    ///
+    /// Returns `None` if the entity does not exist within the temporal axes.
+    async fn read_latest_entity_edition(
+        &self,
+        entity_id: EntityId,
+        temporal_axes: QueryTemporalAxes,
+    ) -> Result<Option<Entity>, QueryError> {
+        let time_axis = temporal_axes.variable_time_axis();
+
+        Ok(Read::<Entity>::read_vec(
+            self,
+            &Filter::for_entity_by_entity_id(entity_id),
+            Some(&temporal_axes),
+        )
+        .await?
+        .into_iter()
+        .max_by_key(|entity| entity.vertex_id(time_axis).revision_id))
+    }
 }
 
 #[async_trait]
@@ -284,6 +547,17 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
             entity_uuid: entity_uuid.unwrap_or_else(|| EntityUuid::new(Uuid::new_v4())),
         };
 
+        self.read_entity_validation_schemas(
+            &entity_type_id,
+            link_data
+                .as_ref()
+                .map(|link_data| (link_data.left_entity_id, link_data.right_entity_id)),
+        )
+        .await
+        .change_context(InsertionError)?
+        .validate(&properties)
+        .change_context(InsertionError)?;
+
         let transaction = self.transaction().await.change_context(InsertionError)?;
 
         let is_account_group: bool = transaction
@@ -304,7 +578,14 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                 &[&entity_id.owned_by_id, &entity_id.entity_uuid],
             )
             .await
-            .change_context(InsertionError)?
+            .map_err(Report::new)
+            .map_err(|report| match report.current_context().code() {
+                Some(&SqlState::UNIQUE_VIOLATION) => report
+                    .change_context(EntityUuidAlreadyExists)
+                    .attach_printable(entity_id.entity_uuid)
+                    .change_context(InsertionError),
+                _ => report.change_context(InsertionError),
+            })?
             .get(0);
 
         let owned_by_uuid = owned_by_id.into_uuid();
@@ -458,13 +739,258 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                 entity_type_id,
                 ProvenanceMetadata {
                     record_created_by_id: RecordCreatedById::new(actor_id),
-                    record_archived_by_id: None,
+                    record_archived_by_id: archived.then_some(RecordArchivedById::new(actor_id)),
                 },
                 archived,
             ))
         }
     }
 
+    #[tracing::instrument(level = "info", skip(self, authorization_api, entities))]
+    async fn create_entities<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entities: impl IntoIterator<Item = EntityCreation, IntoIter: Send> + Send,
+    ) -> Result<Vec<EntityMetadata>, InsertionError> {
+        let entities = entities
+            .into_iter()
+            .map(|entity| {
+                let entity_id = EntityId {
+                    owned_by_id: entity.owned_by_id,
+                    entity_uuid: entity
+                        .entity_uuid
+                        .unwrap_or_else(|| EntityUuid::new(Uuid::new_v4())),
+                };
+                (entity_id, entity)
+            })
+            .collect::<Vec<_>>();
+
+        let mut failures = Vec::new();
+
+        // Permissions are checked once per web instead of once per entity.
+        let mut permitted_webs = HashMap::new();
+        for (index, (entity_id, _)) in entities.iter().enumerate() {
+            let permitted = match permitted_webs.entry(entity_id.owned_by_id) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => *entry.insert(
+                    authorization_api
+                        .can_create_entity(
+                            actor_id,
+                            entity_id.owned_by_id,
+                            Consistency::FullyConsistent,
+                        )
+                        .await
+                        .change_context(InsertionError)?
+                        .has_permission,
+                ),
+            };
+            if !permitted {
+                failures.push(BulkItemFailure::new(
+                    index,
+                    "the actor is not allowed to create entities in the web",
+                ));
+            }
+        }
+
+        // Links may point to entities created in the same batch, so their types have to be known
+        // before validating.
+        let mut validation_cache = EntityValidationCache::default();
+        for (entity_id, entity) in &entities {
+            validation_cache.add_pending_entity(*entity_id, entity.entity_type_id.clone());
+        }
+        for (index, (_, entity)) in entities.iter().enumerate() {
+            let validation_failures = self
+                .read_cached_entity_validation_schemas(
+                    &mut validation_cache,
+                    &entity.entity_type_id,
+                    entity
+                        .link_data
+                        .as_ref()
+                        .map(|link_data| (link_data.left_entity_id, link_data.right_entity_id)),
+                )
+                .await
+                .change_context(InsertionError)?
+                .validation_failures(&entity.properties);
+            if !validation_failures.is_empty() {
+                failures.push(BulkItemFailure {
+                    index,
+                    reason: EntityValidationError.to_string(),
+                    validation_failures,
+                });
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(failures
+                .into_iter()
+                .fold(Report::new(InsertionError), |report, failure| {
+                    report.attach(failure)
+                }));
+        }
+
+        let owned_by_uuids = permitted_webs
+            .into_keys()
+            .map(OwnedById::into_uuid)
+            .collect::<Vec<_>>();
+
+        let transaction = self.transaction().await.change_context(InsertionError)?;
+
+        let account_groups = transaction
+            .as_client()
+            .query(
+                r#"
+                    SELECT account_group_id
+                    FROM account_groups
+                    WHERE account_group_id = ANY($1);
+                "#,
+                &[&owned_by_uuids],
+            )
+            .await
+            .change_context(InsertionError)?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect::<HashSet<Uuid>>();
+
+        transaction
+            .insert_entity_ids(entities.iter().map(|(entity_id, _)| *entity_id))
+            .await?;
+        transaction
+            .insert_entity_links(
+                "left",
+                entities.iter().filter_map(|(entity_id, entity)| {
+                    let link_data = entity.link_data.as_ref()?;
+                    Some((*entity_id, link_data.left_entity_id))
+                }),
+            )
+            .await?;
+        transaction
+            .insert_entity_links(
+                "right",
+                entities.iter().filter_map(|(entity_id, entity)| {
+                    let link_data = entity.link_data.as_ref()?;
+                    Some((*entity_id, link_data.right_entity_id))
+                }),
+            )
+            .await?;
+
+        let mut entity_type_ontology_ids = HashMap::new();
+        for (_, entity) in &entities {
+            if !entity_type_ontology_ids.contains_key(&entity.entity_type_id) {
+                let ontology_id = transaction
+                    .ontology_id_by_url(&entity.entity_type_id)
+                    .await
+                    .change_context(InsertionError)?;
+                entity_type_ontology_ids.insert(entity.entity_type_id.clone(), ontology_id);
+            }
+        }
+
+        let mut entity_records = Vec::with_capacity(entities.len());
+        let mut entity_versions = Vec::with_capacity(entities.len());
+        let mut entity_types = Vec::with_capacity(entities.len());
+        for (entity_id, entity) in entities {
+            let (left_to_right, right_to_left) =
+                entity.link_data.map_or((None, None), |link_data| {
+                    (link_data.order.left_to_right, link_data.order.right_to_left)
+                });
+            entity_records.push((
+                entity.properties,
+                left_to_right,
+                right_to_left,
+                entity.archived,
+            ));
+            entity_versions.push((entity_id, entity.decision_time));
+            entity_types.push((entity.entity_type_id, entity.archived));
+        }
+
+        let entity_edition_ids = transaction
+            .insert_entity_records(entity_records, RecordCreatedById::new(actor_id))
+            .await?;
+        let entity_temporal_metadata = transaction
+            .insert_entity_versions(entity_versions.iter().zip(&entity_edition_ids).map(
+                |((entity_id, decision_time), edition_id)| {
+                    (*entity_id, *edition_id, *decision_time)
+                },
+            ))
+            .await?;
+        transaction
+            .insert_entity_is_of_type(entity_edition_ids.iter().zip(&entity_types).map(
+                |(edition_id, (entity_type_id, _))| {
+                    (*edition_id, entity_type_ontology_ids[entity_type_id])
+                },
+            ))
+            .await?;
+
+        let mut added_owners = Vec::with_capacity(entity_versions.len());
+        let mut result = Ok(());
+        for (entity_id, _) in &entity_versions {
+            let owned_by_uuid = entity_id.owned_by_id.into_uuid();
+            let visibility_scope = if account_groups.contains(&owned_by_uuid) {
+                VisibilityScope::AccountGroup(AccountGroupId::new(owned_by_uuid))
+            } else {
+                VisibilityScope::Account(AccountId::new(owned_by_uuid))
+            };
+
+            if let Err(error) = authorization_api
+                .add_entity_owner(visibility_scope, *entity_id)
+                .await
+                .change_context(InsertionError)
+            {
+                result = Err(error);
+                break;
+            }
+            added_owners.push((visibility_scope, *entity_id));
+        }
+
+        if result.is_ok() {
+            result = transaction.commit().await.change_context(InsertionError);
+        }
+
+        if let Err(mut error) = result {
+            for (visibility_scope, entity_id) in added_owners {
+                if let Err(auth_error) = authorization_api
+                    .remove_entity_owner(visibility_scope, entity_id)
+                    .await
+                    .change_context(InsertionError)
+                {
+                    // TODO: Use `add_child`
+                    //   see https://linear.app/hash/issue/GEN-105/add-ability-to-add-child-errors
+                    error.extend_one(auth_error);
+                }
+            }
+
+            return Err(error);
+        }
+
+        Ok(entity_versions
+            .into_iter()
+            .zip(entity_edition_ids)
+            .zip(entity_temporal_metadata)
+            .zip(entity_types)
+            .map(
+                |(
+                    (((entity_id, _), edition_id), temporal_metadata),
+                    (entity_type_id, archived),
+                )| {
+                    EntityMetadata::new(
+                        EntityRecordId {
+                            entity_id,
+                            edition_id,
+                        },
+                        temporal_metadata,
+                        entity_type_id,
+                        ProvenanceMetadata {
+                            record_created_by_id: RecordCreatedById::new(actor_id),
+                            record_archived_by_id: archived
+                                .then_some(RecordArchivedById::new(actor_id)),
+                        },
+                        archived,
+                    )
+                },
+            )
+            .collect())
+    }
+
     #[doc(hidden)]
     #[cfg(hash_graph_test_environment)]
     async fn insert_entities_batched_by_type<A: AuthorizationApi + Sync>(
@@ -508,6 +1034,7 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                 link_data
                     .as_ref()
                     .and_then(|link_data| link_data.order.right_to_left),
+                false,
             ));
             entity_versions.push(decision_time);
         }
@@ -563,7 +1090,11 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
             .await?;
 
         transaction
-            .insert_entity_is_of_type(entity_edition_ids.iter().copied(), entity_type_ontology_id)
+            .insert_entity_is_of_type(
+                entity_edition_ids
+                    .iter()
+                    .map(|entity_edition_id| (*entity_edition_id, entity_type_ontology_id)),
+            )
             .await?;
 
         transaction.commit().await.change_context(InsertionError)?;
@@ -596,41 +1127,83 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
         actor_id: AccountId,
         authorization_api: &A,
         query: &StructuralQuery<Entity>,
-    ) -> Result<Subgraph, QueryError> {
+    ) -> Result<
+        (
+            Subgraph,
+            Option<EntityQueryCursor>,
+            HashMap<EntityVertexId, f32>,
+        ),
+        QueryError,
+    > {
         let StructuralQuery {
             ref filter,
             graph_resolve_depths,
+            ref traversal_filters,
             temporal_axes: ref unresolved_temporal_axes,
+            ref order_by,
+            limit,
+            ref cursor,
         } = *query;
 
         let temporal_axes = unresolved_temporal_axes.clone().resolve();
         let time_axis = temporal_axes.variable_time_axis();
 
-        let mut entities = Read::<Entity>::read_vec(self, filter, Some(&temporal_axes))
+        // The entities the actor is not allowed to view are excluded when reading, so pagination
+        // is not affected by them. As entity UUIDs are unique across webs, restricting the UUIDs
+        // restricts the entity IDs. The UUIDs are passed as a single array parameter, so the
+        // number of viewable entities is not limited by the number of query parameters.
+        let (viewable_uuids, zookie) =
+            Self::viewable_entity_uuids(actor_id, authorization_api).await?;
+        let restriction = viewable_uuids.as_deref().map(|uuids| {
+            Filter::In(
+                FilterExpression::Path(EntityQueryPath::Uuid),
+                ParameterList::Uuid(uuids),
+            )
+        });
+
+        let entities = self
+            .read_ranked_entities(
+                filter,
+                restriction.as_ref(),
+                Some(&temporal_axes),
+                order_by,
+                cursor.as_ref(),
+                limit,
+            )
             .await?
-            .into_iter()
-            .map(|entity| (entity.vertex_id(time_axis), entity))
-            .collect::<HashMap<_, _>>();
-        // TODO: The subgraph structure differs from the API interface. At the API the vertices
-        //       are stored in a nested `HashMap` and here it's flattened. We need to adjust the
-        //       the subgraph anyway so instead of refactoring this now this will just copy the ids.
-        //   see https://linear.app/hash/issue/H-297/revisit-subgraph-layout-to-allow-temporal-ontology-types
-        let filtered_ids = entities
-            .keys()
-            .map(|vertex_id| vertex_id.base_id)
-            .collect::<HashSet<_>>();
-
-        let (permissions, zookie) = authorization_api
-            .can_view_entities(actor_id, filtered_ids, Consistency::FullyConsistent)
-            .await
-            .change_context(QueryError)?;
+            .try_collect::<Vec<_>>()
+            .await?;
 
-        let permitted_ids = permissions
+        // If fewer entities than requested were returned, there are no more entities to read.
+        let next_cursor = limit
+            .filter(|&limit| entities.len() == limit)
+            .and_then(|_| entities.last())
+            .map(|ranked_entity| EntityQueryCursor {
+                sort_key: ranked_entity.sort_key.clone(),
+                vertex_id: ranked_entity.entity.vertex_id(time_axis),
+            });
+        let root_ids = entities
+            .iter()
+            .map(|ranked_entity| ranked_entity.entity.vertex_id(time_axis))
+            .collect::<Vec<_>>();
+        let search_ranks = entities
+            .iter()
+            .filter_map(|ranked_entity| {
+                Some((
+                    ranked_entity.entity.vertex_id(time_axis),
+                    ranked_entity.search_rank?,
+                ))
+            })
+            .collect::<HashMap<_, _>>();
+        let entities = entities
             .into_iter()
-            .filter_map(|(entity_id, has_permission)| has_permission.then_some(entity_id))
-            .collect::<HashSet<_>>();
-
-        entities.retain(|vertex_id, _| permitted_ids.contains(&vertex_id.base_id));
+            .map(|ranked_entity| {
+                (
+                    ranked_entity.entity.vertex_id(time_axis),
+                    ranked_entity.entity,
+                )
+            })
+            .collect::<HashMap<_, _>>();
 
         let mut subgraph = Subgraph::new(
             graph_resolve_depths,
@@ -639,9 +1212,11 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
         );
         subgraph.vertices.entities = entities;
 
-        for vertex_id in subgraph.vertices.entities.keys() {
-            subgraph.roots.insert((*vertex_id).into());
-        }
+        subgraph.roots = root_ids
+            .into_iter()
+            .filter(|vertex_id| subgraph.vertices.entities.contains_key(vertex_id))
+            .map(GraphElementVertexId::from)
+            .collect();
 
         let mut traversal_context = TraversalContext::default();
 
@@ -661,6 +1236,7 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                 })
                 .collect(),
             &mut traversal_context,
+            traversal_filters,
             actor_id,
             authorization_api,
             zookie,
@@ -672,64 +1248,880 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
             .read_traversed_vertices(self, &mut subgraph)
             .await?;
 
-        Ok(subgraph)
+        Ok((subgraph, next_cursor, search_ranks))
     }
 
-    #[tracing::instrument(level = "info", skip(self, properties, authorization_api))]
-    async fn update_entity<A: AuthorizationApi + Send + Sync>(
-        &mut self,
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn aggregate_entities<A: AuthorizationApi + Sync>(
+        &self,
         actor_id: AccountId,
-        authorization_api: &mut A,
+        authorization_api: &A,
+        query: &AggregationQuery<Entity>,
+    ) -> Result<Vec<AggregationResult>, QueryError> {
+        // Only entities the actor is allowed to view are aggregated, restricted in the same way as
+        // when reading entities.
+        let (viewable_uuids, _) = Self::viewable_entity_uuids(actor_id, authorization_api).await?;
+        let restriction = viewable_uuids.as_deref().map(|uuids| {
+            Filter::In(
+                FilterExpression::Path(EntityQueryPath::Uuid),
+                ParameterList::Uuid(uuids),
+            )
+        });
+        self.aggregate_records(query, restriction.as_ref()).await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn get_entity_history<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
         entity_id: EntityId,
-        decision_time: Option<Timestamp<DecisionTime>>,
-        archived: bool,
-        entity_type_id: VersionedUrl,
-        properties: EntityProperties,
-        link_order: EntityLinkOrder,
-    ) -> Result<EntityMetadata, UpdateError> {
+        temporal_axes: QueryTemporalAxesUnresolved,
+    ) -> Result<Vec<EntityHistoryEntry>, QueryError> {
         authorization_api
-            .can_update_entity(actor_id, entity_id, Consistency::FullyConsistent)
+            .can_view_entity(actor_id, entity_id, Consistency::FullyConsistent)
             .await
-            .change_context(UpdateError)?
+            .change_context(QueryError)?
             .assert_permission()
-            .change_context(UpdateError)?;
+            .change_context(QueryError)?;
 
-        let transaction = self.transaction().await.change_context(UpdateError)?;
+        let temporal_axes = temporal_axes.resolve();
+        let time_axis = temporal_axes.variable_time_axis();
 
-        if transaction
-            .as_client()
-            .query_opt(
-                r#"
-                 SELECT EXISTS (
-                    SELECT 1 FROM entity_ids WHERE owned_by_id = $1 AND entity_uuid = $2
-                 );"#,
-                &[&entity_id.owned_by_id, &entity_id.entity_uuid],
-            )
-            .await
-            .change_context(UpdateError)?
-            .is_none()
-        {
+        let mut entities = Read::<Entity>::read_vec(
+            self,
+            &Filter::for_entity_by_entity_id(entity_id),
+            Some(&temporal_axes),
+        )
+        .await?;
+        if entities.is_empty() {
             return Err(Report::new(EntityDoesNotExist)
                 .attach(entity_id)
-                .change_context(UpdateError));
+                .change_context(QueryError));
         }
+        entities.sort_by_key(|entity| entity.vertex_id(time_axis).revision_id);
 
-        let edition_id = transaction
-            .insert_entity_edition(
-                RecordCreatedById::new(actor_id),
-                archived,
-                &entity_type_id,
-                properties,
-                link_order,
-            )
+        let mut previous_properties = EntityProperties::empty();
+        Ok(entities
+            .into_iter()
+            .map(|entity| {
+                let property_diff = diff_properties(&previous_properties, &entity.properties);
+                previous_properties = entity.properties;
+                EntityHistoryEntry {
+                    metadata: entity.metadata,
+                    property_diff,
+                }
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn diff_entity<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        entity_id: EntityId,
+        time_axis: TimeAxis,
+        old_timestamp: Option<Timestamp<VariableAxis>>,
+        new_timestamp: Option<Timestamp<VariableAxis>>,
+    ) -> Result<EntityDiff, QueryError> {
+        authorization_api
+            .can_view_entity(actor_id, entity_id, Consistency::FullyConsistent)
+            .await
+            .change_context(QueryError)?
+            .assert_permission()
+            .change_context(QueryError)?;
+
+        // Both states are read relative to the same point in time
+        let now = Timestamp::<()>::now();
+        let temporal_axes = |timestamp: Option<Timestamp<VariableAxis>>| {
+            let timestamp = timestamp.unwrap_or_else(|| Timestamp::from_anonymous(now));
+            QueryTemporalAxes::from_variable_time_axis(
+                time_axis,
+                Timestamp::from_anonymous(now),
+                RightBoundedTemporalInterval::new(
+                    TemporalBound::Inclusive(timestamp),
+                    LimitedTemporalBound::Inclusive(timestamp),
+                ),
+            )
+        };
+
+        let old = self
+            .read_latest_entity_edition(entity_id, temporal_axes(old_timestamp))
+            .await?;
+        let new = self
+            .read_latest_entity_edition(entity_id, temporal_axes(new_timestamp))
+            .await?;
+        if old.is_none() && new.is_none() {
+            return Err(Report::new(EntityDoesNotExist)
+                .attach(entity_id)
+                .change_context(QueryError));
+        }
+
+        Ok(diff_entities(old, new))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn get_entity_paths<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        query: &EntityPathQuery,
+    ) -> Result<(Subgraph, Vec<Vec<EntityId>>), QueryError> {
+        let temporal_axes = query.temporal_axes.clone().resolve();
+
+        let paths = self
+            .find_entity_paths(actor_id, authorization_api, query, &temporal_axes)
+            .await?;
+        let subgraph = self
+            .create_entity_path_subgraph(query, temporal_axes, &paths)
+            .await?;
+
+        Ok((subgraph, paths))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, properties, authorization_api))]
+    async fn update_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        expected_edition_id: Option<EntityEditionId>,
+        decision_time: Option<Timestamp<DecisionTime>>,
+        archived: bool,
+        entity_type_id: VersionedUrl,
+        properties: EntityProperties,
+        link_order: EntityLinkOrder,
+    ) -> Result<EntityMetadata, UpdateError> {
+        authorization_api
+            .can_update_entity(actor_id, entity_id, Consistency::FullyConsistent)
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        if transaction
+            .as_client()
+            .query_opt(
+                r#"
+                 SELECT EXISTS (
+                    SELECT 1 FROM entity_ids WHERE owned_by_id = $1 AND entity_uuid = $2
+                 );"#,
+                &[&entity_id.owned_by_id, &entity_id.entity_uuid],
+            )
+            .await
+            .change_context(UpdateError)?
+            .is_none()
+        {
+            return Err(Report::new(EntityDoesNotExist)
+                .attach(entity_id)
+                .change_context(UpdateError));
+        }
+
+        let link_entity_ids = transaction
+            .as_client()
+            .query_opt(
+                r#"
+                    SELECT
+                        entity_has_left_entity.left_owned_by_id,
+                        entity_has_left_entity.left_entity_uuid,
+                        entity_has_right_entity.right_owned_by_id,
+                        entity_has_right_entity.right_entity_uuid
+                    FROM entity_has_left_entity
+                    JOIN entity_has_right_entity
+                      ON entity_has_right_entity.owned_by_id = entity_has_left_entity.owned_by_id
+                     AND entity_has_right_entity.entity_uuid = entity_has_left_entity.entity_uuid
+                    WHERE entity_has_left_entity.owned_by_id = $1
+                      AND entity_has_left_entity.entity_uuid = $2;
+                "#,
+                &[&entity_id.owned_by_id, &entity_id.entity_uuid],
+            )
+            .await
+            .change_context(UpdateError)?
+            .map(|row| {
+                (
+                    EntityId {
+                        owned_by_id: row.get(0),
+                        entity_uuid: row.get(1),
+                    },
+                    EntityId {
+                        owned_by_id: row.get(2),
+                        entity_uuid: row.get(3),
+                    },
+                )
+            });
+
+        if let Some(expected_edition_id) = expected_edition_id {
+            let current_edition_id = transaction
+                .lock_current_entity_edition(entity_id, decision_time)
+                .await?;
+            if current_edition_id != Some(expected_edition_id) {
+                let report = Report::new(EntityEditionMismatch).attach(entity_id);
+                return Err(match current_edition_id {
+                    Some(current_edition_id) => report.attach(current_edition_id),
+                    None => report,
+                }
+                .change_context(UpdateError));
+            }
+        }
+
+        transaction
+            .read_entity_validation_schemas(&entity_type_id, link_entity_ids)
             .await
+            .change_context(UpdateError)?
+            .validate(&properties)
             .change_context(UpdateError)?;
 
+        let edition_id = transaction
+            .insert_entity_edition(
+                RecordCreatedById::new(actor_id),
+                archived,
+                &entity_type_id,
+                properties,
+                link_order,
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        let temporal_metadata = transaction
+            .update_entity_temporal_metadata(entity_id, edition_id, decision_time)
+            .await?
+            .ok_or_else(|| {
+                Report::new(RaceConditionOnUpdate)
+                    .attach(entity_id)
+                    .change_context(UpdateError)
+            })?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(EntityMetadata::new(
+            EntityRecordId {
+                entity_id,
+                edition_id,
+            },
+            temporal_metadata,
+            entity_type_id,
+            ProvenanceMetadata {
+                record_created_by_id: RecordCreatedById::new(actor_id),
+                record_archived_by_id: archived.then_some(RecordArchivedById::new(actor_id)),
+            },
+            archived,
+        ))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api, entities))]
+    async fn update_entities<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entities: impl IntoIterator<Item = EntityUpdate, IntoIter: Send> + Send,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        let entities = entities.into_iter().collect::<Vec<_>>();
+
+        let (permissions, _) = authorization_api
+            .can_update_entities(
+                actor_id,
+                entities.iter().map(|entity| entity.entity_id),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        let mut failures = entities
+            .iter()
+            .enumerate()
+            .filter(|(_, entity)| !permissions.get(&entity.entity_id).copied().unwrap_or(false))
+            .map(|(index, _)| {
+                BulkItemFailure::new(index, "the actor is not allowed to update the entity")
+            })
+            .collect::<Vec<_>>();
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let (owned_by_ids, entity_uuids): (Vec<_>, Vec<_>) = entities
+            .iter()
+            .map(|entity| {
+                (
+                    entity.entity_id.owned_by_id.into_uuid(),
+                    entity.entity_id.entity_uuid.into_uuid(),
+                )
+            })
+            .unzip();
+
+        // Maps every existing entity to the entities it links, if it's a link entity.
+        let stored_entities = transaction
+            .as_client()
+            .query(
+                r#"
+                    SELECT
+                        owned_by_id,
+                        entity_uuid,
+                        entity_has_left_entity.left_owned_by_id,
+                        entity_has_left_entity.left_entity_uuid,
+                        entity_has_right_entity.right_owned_by_id,
+                        entity_has_right_entity.right_entity_uuid
+                    FROM unnest($1::UUID[], $2::UUID[]) AS requested(owned_by_id, entity_uuid)
+                    JOIN entity_ids USING (owned_by_id, entity_uuid)
+                    LEFT JOIN entity_has_left_entity USING (owned_by_id, entity_uuid)
+                    LEFT JOIN entity_has_right_entity USING (owned_by_id, entity_uuid);
+                "#,
+                &[&owned_by_ids, &entity_uuids],
+            )
+            .await
+            .change_context(UpdateError)?
+            .into_iter()
+            .map(|row| {
+                let entity_id = EntityId {
+                    owned_by_id: row.get(0),
+                    entity_uuid: row.get(1),
+                };
+                let link_entity_ids = match (row.get(2), row.get(3), row.get(4), row.get(5)) {
+                    (
+                        Some(left_owned_by_id),
+                        Some(left_entity_uuid),
+                        Some(right_owned_by_id),
+                        Some(right_entity_uuid),
+                    ) => Some((
+                        EntityId {
+                            owned_by_id: left_owned_by_id,
+                            entity_uuid: left_entity_uuid,
+                        },
+                        EntityId {
+                            owned_by_id: right_owned_by_id,
+                            entity_uuid: right_entity_uuid,
+                        },
+                    )),
+                    _ => None,
+                };
+                (entity_id, link_entity_ids)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut validation_cache = EntityValidationCache::default();
+        for (index, entity) in entities.iter().enumerate() {
+            let Some(link_entity_ids) = stored_entities.get(&entity.entity_id) else {
+                failures.push(BulkItemFailure::new(index, EntityDoesNotExist.to_string()));
+                continue;
+            };
+
+            if let Some(expected_edition_id) = entity.expected_edition_id {
+                let current_edition_id = transaction
+                    .lock_current_entity_edition(entity.entity_id, entity.decision_time)
+                    .await?;
+                if current_edition_id != Some(expected_edition_id) {
+                    failures.push(BulkItemFailure::new(
+                        index,
+                        EntityEditionMismatch.to_string(),
+                    ));
+                    continue;
+                }
+            }
+
+            let validation_failures = transaction
+                .read_cached_entity_validation_schemas(
+                    &mut validation_cache,
+                    &entity.entity_type_id,
+                    *link_entity_ids,
+                )
+                .await
+                .change_context(UpdateError)?
+                .validation_failures(&entity.properties);
+            if !validation_failures.is_empty() {
+                failures.push(BulkItemFailure {
+                    index,
+                    reason: EntityValidationError.to_string(),
+                    validation_failures,
+                });
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(failures
+                .into_iter()
+                .fold(Report::new(UpdateError), |report, failure| {
+                    report.attach(failure)
+                }));
+        }
+
+        let mut entity_type_ontology_ids = HashMap::new();
+        for entity in &entities {
+            if !entity_type_ontology_ids.contains_key(&entity.entity_type_id) {
+                let ontology_id = transaction
+                    .ontology_id_by_url(&entity.entity_type_id)
+                    .await
+                    .change_context(UpdateError)?;
+                entity_type_ontology_ids.insert(entity.entity_type_id.clone(), ontology_id);
+            }
+        }
+
+        let mut entity_records = Vec::with_capacity(entities.len());
+        let mut entity_versions = Vec::with_capacity(entities.len());
+        for entity in entities {
+            entity_records.push((
+                entity.properties,
+                entity.link_order.left_to_right,
+                entity.link_order.right_to_left,
+                entity.archived,
+            ));
+            entity_versions.push((
+                entity.entity_id,
+                entity.decision_time,
+                entity.entity_type_id,
+                entity.archived,
+            ));
+        }
+
+        let entity_edition_ids = transaction
+            .insert_entity_records(entity_records, RecordCreatedById::new(actor_id))
+            .await
+            .change_context(UpdateError)?;
+        transaction
+            .insert_entity_is_of_type(entity_edition_ids.iter().zip(&entity_versions).map(
+                |(edition_id, (_, _, entity_type_id, _))| {
+                    (*edition_id, entity_type_ontology_ids[entity_type_id])
+                },
+            ))
+            .await
+            .change_context(UpdateError)?;
+
+        let mut metadata = Vec::with_capacity(entity_versions.len());
+        for ((entity_id, decision_time, entity_type_id, archived), edition_id) in
+            entity_versions.into_iter().zip(entity_edition_ids)
+        {
+            let temporal_metadata = transaction
+                .update_entity_temporal_metadata(entity_id, edition_id, decision_time)
+                .await?
+                .ok_or_else(|| {
+                    Report::new(RaceConditionOnUpdate)
+                        .attach(entity_id)
+                        .change_context(UpdateError)
+                })?;
+
+            metadata.push(EntityMetadata::new(
+                EntityRecordId {
+                    entity_id,
+                    edition_id,
+                },
+                temporal_metadata,
+                entity_type_id,
+                ProvenanceMetadata {
+                    record_created_by_id: RecordCreatedById::new(actor_id),
+                    record_archived_by_id: archived.then_some(RecordArchivedById::new(actor_id)),
+                },
+                archived,
+            ));
+        }
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api, operations))]
+    async fn patch_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        decision_time: Option<Timestamp<DecisionTime>>,
+        operations: Vec<PropertyPatchOperation>,
+    ) -> Result<EntityMetadata, UpdateError> {
+        // The permission has to be checked before the patch is applied as a failing patch would
+        // reveal the properties of the entity.
+        authorization_api
+            .can_update_entity(actor_id, entity_id, Consistency::FullyConsistent)
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
+        let mut transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let edition_id = transaction
+            .lock_current_entity_edition(entity_id, decision_time)
+            .await?
+            .ok_or_else(|| {
+                Report::new(EntityDoesNotExist)
+                    .attach(entity_id)
+                    .change_context(UpdateError)
+            })?;
+
+        let row = transaction
+            .as_client()
+            .query_one(
+                r#"
+                    SELECT
+                        entity_editions.properties,
+                        entity_editions.archived,
+                        entity_editions.left_to_right_order,
+                        entity_editions.right_to_left_order,
+                        entity_types.schema->>'$id'
+                    FROM entity_editions
+                    JOIN entity_is_of_type
+                      ON entity_is_of_type.entity_edition_id = entity_editions.entity_edition_id
+                    JOIN entity_types
+                      ON entity_types.ontology_id = entity_is_of_type.entity_type_ontology_id
+                    WHERE entity_editions.entity_edition_id = $1;
+                "#,
+                &[&edition_id],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        let properties = patch_properties(&row.get(0), &operations).change_context(UpdateError)?;
+        let entity_type_id = VersionedUrl::from_str(row.get(4)).change_context(UpdateError)?;
+
+        // The patched properties are based on the locked edition, so it's passed as the expected
+        // edition.
+        let metadata = transaction
+            .update_entity(
+                actor_id,
+                authorization_api,
+                entity_id,
+                Some(edition_id),
+                decision_time,
+                row.get(1),
+                entity_type_id,
+                properties,
+                EntityLinkOrder {
+                    left_to_right: row.get(2),
+                    right_to_left: row.get(3),
+                },
+            )
+            .await?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn archive_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        self.set_entity_archived(actor_id, authorization_api, entity_id, true, cascade)
+            .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn unarchive_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        self.set_entity_archived(actor_id, authorization_api, entity_id, false, cascade)
+            .await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn erase_entity<A: AuthorizationApi + Send + Sync>(
+        &mut self,
+        actor_id: AccountId,
+        authorization_api: &mut A,
+        entity_id: EntityId,
+        erase_links: bool,
+    ) -> Result<Vec<EntityId>, DeletionError> {
+        authorization_api
+            .can_delete_entity(actor_id, entity_id, Consistency::FullyConsistent)
+            .await
+            .change_context(DeletionError)?
+            .assert_permission()
+            .change_context(DeletionError)?;
+
+        let transaction = self.transaction().await.change_context(DeletionError)?;
+
+        // Links are attached to the entity by their left or right entity. As links can be the left
+        // or right entity of other links, the links are collected recursively.
+        let mut link_entity_ids = transaction
+            .as_client()
+            .query(
+                r#"
+                    WITH RECURSIVE erased AS (
+                        SELECT owned_by_id, entity_uuid
+                        FROM entity_ids
+                        WHERE owned_by_id = $1 AND entity_uuid = $2
+                        UNION
+                        SELECT links.owned_by_id, links.entity_uuid
+                        FROM erased
+                        JOIN (
+                            SELECT
+                                owned_by_id,
+                                entity_uuid,
+                                left_owned_by_id AS target_owned_by_id,
+                                left_entity_uuid AS target_entity_uuid
+                            FROM entity_has_left_entity
+                            UNION ALL
+                            SELECT
+                                owned_by_id,
+                                entity_uuid,
+                                right_owned_by_id,
+                                right_entity_uuid
+                            FROM entity_has_right_entity
+                        ) AS links
+                          ON links.target_owned_by_id = erased.owned_by_id
+                         AND links.target_entity_uuid = erased.entity_uuid
+                    )
+                    SELECT owned_by_id, entity_uuid FROM erased;
+                "#,
+                &[&entity_id.owned_by_id, &entity_id.entity_uuid],
+            )
+            .await
+            .change_context(DeletionError)?
+            .into_iter()
+            .map(|row| EntityId {
+                owned_by_id: row.get(0),
+                entity_uuid: row.get(1),
+            })
+            .collect::<Vec<_>>();
+
+        // The recursion starts at the entity, so it's only missing if the entity doesn't exist.
+        let Some(position) = link_entity_ids
+            .iter()
+            .position(|link_entity_id| *link_entity_id == entity_id)
+        else {
+            return Err(Report::new(EntityDoesNotExist)
+                .attach(entity_id)
+                .change_context(DeletionError));
+        };
+        link_entity_ids.swap_remove(position);
+
+        if !erase_links && !link_entity_ids.is_empty() {
+            let mut report = Report::new(EntityHasLinks).attach(entity_id);
+            for link_entity_id in &link_entity_ids {
+                report = report.attach(*link_entity_id);
+            }
+            return Err(report.change_context(DeletionError));
+        }
+
+        if !link_entity_ids.is_empty() {
+            let (permissions, _) = authorization_api
+                .can_delete_entities(
+                    actor_id,
+                    link_entity_ids.iter().copied(),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(DeletionError)?;
+            let forbidden_links = link_entity_ids
+                .iter()
+                .copied()
+                .filter(|link_entity_id| !permissions.get(link_entity_id).copied().unwrap_or(false))
+                .collect::<Vec<_>>();
+            if !forbidden_links.is_empty() {
+                let mut report = Report::new(PermissionAssertion).change_context(DeletionError);
+                for link_entity_id in forbidden_links {
+                    report = report.attach(link_entity_id);
+                }
+                return Err(report);
+            }
+        }
+
+        let erased_entity_ids = iter::once(entity_id)
+            .chain(link_entity_ids)
+            .collect::<Vec<_>>();
+        let owned_by_ids = erased_entity_ids
+            .iter()
+            .map(|erased_entity_id| erased_entity_id.owned_by_id)
+            .collect::<Vec<_>>();
+        let entity_uuids = erased_entity_ids
+            .iter()
+            .map(|erased_entity_id| erased_entity_id.entity_uuid)
+            .collect::<Vec<_>>();
+
+        for statement in [
+            r#"
+                DELETE FROM entity_has_left_entity
+                WHERE (owned_by_id, entity_uuid) IN (
+                    SELECT * FROM unnest($1::UUID[], $2::UUID[])
+                );
+            "#,
+            r#"
+                DELETE FROM entity_has_right_entity
+                WHERE (owned_by_id, entity_uuid) IN (
+                    SELECT * FROM unnest($1::UUID[], $2::UUID[])
+                );
+            "#,
+            r#"
+                DELETE FROM entity_is_of_type
+                WHERE entity_edition_id IN (
+                    SELECT entity_edition_id
+                    FROM entity_temporal_metadata
+                    WHERE (owned_by_id, entity_uuid) IN (
+                        SELECT * FROM unnest($1::UUID[], $2::UUID[])
+                    )
+                );
+            "#,
+        ] {
+            transaction
+                .as_client()
+                .execute(statement, &[&owned_by_ids, &entity_uuids])
+                .await
+                .change_context(DeletionError)?;
+        }
+
+        let edition_ids = transaction
+            .as_client()
+            .query(
+                r#"
+                    DELETE FROM entity_temporal_metadata
+                    WHERE (owned_by_id, entity_uuid) IN (
+                        SELECT * FROM unnest($1::UUID[], $2::UUID[])
+                    )
+                    RETURNING entity_edition_id;
+                "#,
+                &[&owned_by_ids, &entity_uuids],
+            )
+            .await
+            .change_context(DeletionError)?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect::<HashSet<EntityEditionId>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        transaction
+            .as_client()
+            .execute(
+                "DELETE FROM entity_editions WHERE entity_edition_id = ANY($1);",
+                &[&edition_ids],
+            )
+            .await
+            .change_context(DeletionError)?;
+
+        transaction
+            .as_client()
+            .execute(
+                r#"
+                    DELETE FROM entity_ids
+                    WHERE (owned_by_id, entity_uuid) IN (
+                        SELECT * FROM unnest($1::UUID[], $2::UUID[])
+                    );
+                "#,
+                &[&owned_by_ids, &entity_uuids],
+            )
+            .await
+            .change_context(DeletionError)?;
+
+        transaction
+            .as_client()
+            .execute(
+                r#"
+                    INSERT INTO entity_tombstones (
+                        owned_by_id,
+                        entity_uuid,
+                        erased_by_id,
+                        erased_at
+                    )
+                    SELECT owned_by_id, entity_uuid, $3, now()
+                    FROM unnest($1::UUID[], $2::UUID[]) AS erased(owned_by_id, entity_uuid);
+                "#,
+                &[&owned_by_ids, &entity_uuids, &actor_id],
+            )
+            .await
+            .change_context(DeletionError)?;
+
+        // All relations of the erased entities are removed, not only the owners, so that no
+        // editor or viewer relation of an entity which doesn't exist anymore is left behind.
+        let mut relations = Vec::new();
+        for erased_entity_id in &erased_entity_ids {
+            let (entity_relations, _) = authorization_api
+                .get_entity_relations(*erased_entity_id, Consistency::FullyConsistent)
+                .await
+                .change_context(DeletionError)?;
+            relations.extend(
+                entity_relations
+                    .into_iter()
+                    .map(|(relation, scope)| (*erased_entity_id, relation, scope)),
+            );
+        }
+
+        let mut removed_relations = Vec::with_capacity(relations.len());
+        let mut result = Ok(());
+        for (erased_entity_id, relation, scope) in relations {
+            let removal = match relation {
+                EntityRelation::DirectOwner => {
+                    authorization_api
+                        .remove_entity_owner(scope, erased_entity_id)
+                        .await
+                }
+                EntityRelation::DirectEditor => {
+                    authorization_api
+                        .remove_entity_editor(scope, erased_entity_id)
+                        .await
+                }
+                EntityRelation::DirectViewer => {
+                    authorization_api
+                        .remove_entity_viewer(scope, erased_entity_id)
+                        .await
+                }
+            };
+            if let Err(error) = removal.change_context(DeletionError) {
+                result = Err(error);
+                break;
+            }
+            removed_relations.push((erased_entity_id, relation, scope));
+        }
+        if result.is_ok() {
+            result = transaction.commit().await.change_context(DeletionError);
+        }
+
+        if let Err(mut error) = result {
+            // The entities were not erased, so the removed relations are restored.
+            for (erased_entity_id, relation, scope) in removed_relations {
+                let restoration = match relation {
+                    EntityRelation::DirectOwner => {
+                        authorization_api
+                            .add_entity_owner(scope, erased_entity_id)
+                            .await
+                    }
+                    EntityRelation::DirectEditor => {
+                        authorization_api
+                            .add_entity_editor(scope, erased_entity_id)
+                            .await
+                    }
+                    EntityRelation::DirectViewer => {
+                        authorization_api
+                            .add_entity_viewer(scope, erased_entity_id)
+                            .await
+                    }
+                };
+                if let Err(auth_error) = restoration.change_context(DeletionError) {
+                    // TODO: Use `add_child`
+                    //   see https://linear.app/hash/issue/GEN-105/add-ability-to-add-child-errors
+                    error.extend_one(auth_error);
+                }
+            }
+
+            Err(error)
+        } else {
+            Ok(erased_entity_ids)
+        }
+    }
+}
+
+impl PostgresStore<tokio_postgres::Transaction<'_>> {
+    /// Points the current version of the entity to the specified edition.
+    ///
+    /// Returns `None` if the entity has no version at the specified decision time or if it was
+    /// updated concurrently.
+    async fn update_entity_temporal_metadata(
+        &self,
+        entity_id: EntityId,
+        edition_id: EntityEditionId,
+        decision_time: Option<Timestamp<DecisionTime>>,
+    ) -> Result<Option<EntityTemporalMetadata>, UpdateError> {
         // Calling `UPDATE` on `entity_temporal_metadata` will invoke a trigger that properly
         // updates the temporal versioning of the entity.
         let optional_row = if let Some(decision_time) = decision_time {
-            transaction
-                .as_client()
+            self.as_client()
                 .query_opt(
                     r#"
                         UPDATE entity_temporal_metadata
@@ -751,8 +2143,7 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                 )
                 .await
         } else {
-            transaction
-                .as_client()
+            self.as_client()
                 .query_opt(
                     r#"
                         UPDATE entity_temporal_metadata
@@ -770,34 +2161,185 @@ impl<C: AsClient> EntityStore for PostgresStore<C> {
                 .await
         }
         .change_context(UpdateError)?;
-        let row = optional_row.ok_or_else(|| {
-            Report::new(RaceConditionOnUpdate)
-                .attach(entity_id)
-                .change_context(UpdateError)
-        })?;
 
-        transaction.commit().await.change_context(UpdateError)?;
+        Ok(optional_row.map(|row| EntityTemporalMetadata {
+            decision_time: row.get(0),
+            transaction_time: row.get(1),
+        }))
+    }
 
-        Ok(EntityMetadata::new(
+    /// Returns the edition the entity currently points to at the specified decision time and locks
+    /// it until the transaction is finished.
+    ///
+    /// Returns `None` if the entity has no version at the specified decision time.
+    async fn lock_current_entity_edition(
+        &self,
+        entity_id: EntityId,
+        decision_time: Option<Timestamp<DecisionTime>>,
+    ) -> Result<Option<EntityEditionId>, UpdateError> {
+        let optional_row = if let Some(decision_time) = decision_time {
+            self.as_client()
+                .query_opt(
+                    r#"
+                        SELECT entity_edition_id
+                        FROM entity_temporal_metadata
+                        WHERE owned_by_id = $1
+                          AND entity_uuid = $2
+                          AND decision_time @> $3::TIMESTAMPTZ
+                          AND transaction_time @> now()
+                        FOR UPDATE;
+                    "#,
+                    &[
+                        &entity_id.owned_by_id,
+                        &entity_id.entity_uuid,
+                        &decision_time,
+                    ],
+                )
+                .await
+        } else {
+            self.as_client()
+                .query_opt(
+                    r#"
+                        SELECT entity_edition_id
+                        FROM entity_temporal_metadata
+                        WHERE owned_by_id = $1
+                          AND entity_uuid = $2
+                          AND decision_time @> now()
+                          AND transaction_time @> now()
+                        FOR UPDATE;
+                    "#,
+                    &[&entity_id.owned_by_id, &entity_id.entity_uuid],
+                )
+                .await
+        }
+        .change_context(UpdateError)?;
+
+        Ok(optional_row.map(|row| row.get(0)))
+    }
+
+    /// Inserts a copy of the current edition of the entity with the `archived` flag set as
+    /// specified and points the entity to it.
+    ///
+    /// Returns `None` if the entity is already in the requested state.
+    async fn insert_entity_edition_with_archived(
+        &self,
+        actor_id: AccountId,
+        entity_id: EntityId,
+        archived: bool,
+    ) -> Result<Option<EntityMetadata>, UpdateError> {
+        let row = self
+            .as_client()
+            .query_opt(
+                r#"
+                    SELECT
+                        entity_editions.entity_edition_id,
+                        entity_editions.archived,
+                        entity_types.schema->>'$id'
+                    FROM entity_temporal_metadata
+                    JOIN entity_editions
+                      ON entity_editions.entity_edition_id
+                       = entity_temporal_metadata.entity_edition_id
+                    JOIN entity_is_of_type
+                      ON entity_is_of_type.entity_edition_id
+                       = entity_temporal_metadata.entity_edition_id
+                    JOIN entity_types
+                      ON entity_types.ontology_id = entity_is_of_type.entity_type_ontology_id
+                    WHERE entity_temporal_metadata.owned_by_id = $1
+                      AND entity_temporal_metadata.entity_uuid = $2
+                      AND entity_temporal_metadata.transaction_time @> now()
+                      AND entity_temporal_metadata.decision_time @> now();
+                "#,
+                &[&entity_id.owned_by_id, &entity_id.entity_uuid],
+            )
+            .await
+            .change_context(UpdateError)?
+            .ok_or_else(|| {
+                Report::new(EntityDoesNotExist)
+                    .attach(entity_id)
+                    .change_context(UpdateError)
+            })?;
+
+        let current_edition_id: EntityEditionId = row.get(0);
+        if row.get::<_, bool>(1) == archived {
+            return Ok(None);
+        }
+        let entity_type_id = VersionedUrl::from_str(row.get(2)).change_context(UpdateError)?;
+
+        let record_created_by_id = RecordCreatedById::new(actor_id);
+        let record_archived_by_id = archived.then_some(RecordArchivedById::new(actor_id));
+        let edition_id: EntityEditionId = self
+            .as_client()
+            .query_one(
+                r#"
+                    INSERT INTO entity_editions (
+                        entity_edition_id,
+                        properties,
+                        left_to_right_order,
+                        right_to_left_order,
+                        record_created_by_id,
+                        record_archived_by_id,
+                        archived
+                    )
+                    SELECT
+                        gen_random_uuid(),
+                        properties,
+                        left_to_right_order,
+                        right_to_left_order,
+                        $2,
+                        $3,
+                        $4
+                    FROM entity_editions
+                    WHERE entity_edition_id = $1
+                    RETURNING entity_edition_id;
+                "#,
+                &[
+                    &current_edition_id,
+                    &record_created_by_id,
+                    &record_archived_by_id,
+                    &archived,
+                ],
+            )
+            .await
+            .change_context(UpdateError)?
+            .get(0);
+
+        self.as_client()
+            .query(
+                r#"
+                    INSERT INTO entity_is_of_type (entity_edition_id, entity_type_ontology_id)
+                    SELECT $2, entity_type_ontology_id
+                    FROM entity_is_of_type
+                    WHERE entity_edition_id = $1;
+                "#,
+                &[&current_edition_id, &edition_id],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        let temporal_metadata = self
+            .update_entity_temporal_metadata(entity_id, edition_id, None)
+            .await?
+            .ok_or_else(|| {
+                Report::new(RaceConditionOnUpdate)
+                    .attach(entity_id)
+                    .change_context(UpdateError)
+            })?;
+
+        Ok(Some(EntityMetadata::new(
             EntityRecordId {
                 entity_id,
                 edition_id,
             },
-            EntityTemporalMetadata {
-                decision_time: row.get(0),
-                transaction_time: row.get(1),
-            },
+            temporal_metadata,
             entity_type_id,
             ProvenanceMetadata {
-                record_created_by_id: RecordCreatedById::new(actor_id),
-                record_archived_by_id: None,
+                record_created_by_id,
+                record_archived_by_id,
             },
             archived,
-        ))
+        )))
     }
-}
 
-impl PostgresStore<tokio_postgres::Transaction<'_>> {
     async fn insert_entity_edition(
         &self,
         record_created_by_id: RecordCreatedById,
@@ -813,11 +2355,20 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     INSERT INTO entity_editions (
                         entity_edition_id,
                         record_created_by_id,
+                        record_archived_by_id,
                         archived,
                         properties,
                         left_to_right_order,
                         right_to_left_order
-                    ) VALUES (gen_random_uuid(), $1, $2, $3, $4, $5)
+                    ) VALUES (
+                        gen_random_uuid(),
+                        $1,
+                        CASE WHEN $2 THEN $1::UUID END,
+                        $2,
+                        $3,
+                        $4,
+                        $5
+                    )
                     RETURNING entity_edition_id;
                 "#,
                 &[
@@ -0,0 +1,422 @@
+use std::collections::{HashMap, HashSet};
+
+use authorization::{zanzibar::Consistency, AuthorizationApi};
+use error_stack::{Report, Result, ResultExt};
+use graph_types::{
+    account::AccountId,
+    knowledge::entity::{Entity, EntityId},
+};
+use temporal_versioning::{LeftClosedTemporalInterval, TemporalTagged, TimeAxis};
+use tokio_postgres::GenericClient;
+
+use crate::{
+    store::{
+        crud::Read, error::EntityDoesNotExist, query::Filter, AsClient, EntityPathQuery,
+        PostgresStore, QueryError, Record,
+    },
+    subgraph::{
+        edges::{EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind},
+        identifier::{EntityIdWithInterval, GraphElementVertexId},
+        temporal_axes::{QueryTemporalAxes, VariableAxis},
+        Subgraph,
+    },
+};
+
+/// A link between two entities, which can be followed from `source` to `target`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct EntityHop {
+    source: EntityId,
+    link: EntityId,
+    target: EntityId,
+}
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Reads all links attached to any of the `source_entity_ids` alongside the entity on the
+    /// other end of the link.
+    ///
+    /// Links are returned in both directions. Only links and targets, which exist within the
+    /// temporal axes at an overlapping point in time, are returned.
+    async fn read_entity_hops(
+        &self,
+        source_entity_ids: impl IntoIterator<Item = EntityId> + Send,
+        temporal_axes: &QueryTemporalAxes,
+    ) -> Result<Vec<EntityHop>, QueryError> {
+        let (pinned_axis, variable_axis) = match temporal_axes.variable_time_axis() {
+            TimeAxis::DecisionTime => ("transaction_time", "decision_time"),
+            TimeAxis::TransactionTime => ("decision_time", "transaction_time"),
+        };
+
+        let (owned_by_ids, entity_uuids): (Vec<_>, Vec<_>) = source_entity_ids
+            .into_iter()
+            .map(|entity_id| (entity_id.owned_by_id, entity_id.entity_uuid))
+            .unzip();
+
+        let mut hops = self
+            .as_client()
+            .query(
+                &format!(
+                    r#"
+                        WITH links AS (
+                            SELECT
+                                 left_edge.owned_by_id,
+                                 left_edge.entity_uuid,
+                                 left_edge.left_owned_by_id AS source_owned_by_id,
+                                 left_edge.left_entity_uuid AS source_entity_uuid,
+                                 right_edge.right_owned_by_id AS target_owned_by_id,
+                                 right_edge.right_entity_uuid AS target_entity_uuid
+                            FROM entity_has_left_entity AS left_edge
+                            JOIN entity_has_right_entity AS right_edge
+                              ON left_edge.owned_by_id = right_edge.owned_by_id
+                             AND left_edge.entity_uuid = right_edge.entity_uuid
+                            UNION ALL
+                            SELECT
+                                 left_edge.owned_by_id,
+                                 left_edge.entity_uuid,
+                                 right_edge.right_owned_by_id,
+                                 right_edge.right_entity_uuid,
+                                 left_edge.left_owned_by_id,
+                                 left_edge.left_entity_uuid
+                            FROM entity_has_left_entity AS left_edge
+                            JOIN entity_has_right_entity AS right_edge
+                              ON left_edge.owned_by_id = right_edge.owned_by_id
+                             AND left_edge.entity_uuid = right_edge.entity_uuid
+                        )
+                        SELECT DISTINCT
+                             links.source_owned_by_id,
+                             links.source_entity_uuid,
+                             links.owned_by_id,
+                             links.entity_uuid,
+                             links.target_owned_by_id,
+                             links.target_entity_uuid
+                        FROM unnest($1::uuid[], $2::uuid[])
+                             AS filter(owned_by_id, entity_uuid)
+
+                        JOIN links
+                          ON links.source_owned_by_id = filter.owned_by_id
+                         AND links.source_entity_uuid = filter.entity_uuid
+
+                        WHERE EXISTS (
+                            SELECT 1
+                              FROM entity_temporal_metadata AS link_metadata
+                              JOIN entity_temporal_metadata AS target_metadata
+                                ON target_metadata.owned_by_id = links.target_owned_by_id
+                               AND target_metadata.entity_uuid = links.target_entity_uuid
+                               AND target_metadata.{pinned_axis} @> $3::timestamptz
+                               AND target_metadata.{variable_axis} && $4::tstzrange
+                               AND target_metadata.{variable_axis}
+                                && link_metadata.{variable_axis}
+                             WHERE link_metadata.owned_by_id = links.owned_by_id
+                               AND link_metadata.entity_uuid = links.entity_uuid
+                               AND link_metadata.{pinned_axis} @> $3::timestamptz
+                               AND link_metadata.{variable_axis} && $4::tstzrange
+                        );
+                    "#
+                ),
+                &[
+                    &owned_by_ids,
+                    &entity_uuids,
+                    &temporal_axes.pinned_timestamp(),
+                    &temporal_axes.variable_interval(),
+                ],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| EntityHop {
+                source: EntityId {
+                    owned_by_id: row.get(0),
+                    entity_uuid: row.get(1),
+                },
+                link: EntityId {
+                    owned_by_id: row.get(2),
+                    entity_uuid: row.get(3),
+                },
+                target: EntityId {
+                    owned_by_id: row.get(4),
+                    entity_uuid: row.get(5),
+                },
+            })
+            .collect::<Vec<_>>();
+
+        // Sorting the hops makes the order of paths with the same length deterministic.
+        hops.sort_unstable();
+        Ok(hops)
+    }
+
+    /// Returns whether the entity exists within the temporal axes.
+    async fn entity_exists(
+        &self,
+        entity_id: EntityId,
+        temporal_axes: &QueryTemporalAxes,
+    ) -> Result<bool, QueryError> {
+        let (pinned_axis, variable_axis) = match temporal_axes.variable_time_axis() {
+            TimeAxis::DecisionTime => ("transaction_time", "decision_time"),
+            TimeAxis::TransactionTime => ("decision_time", "transaction_time"),
+        };
+
+        Ok(self
+            .as_client()
+            .query_one(
+                &format!(
+                    r#"
+                        SELECT EXISTS (
+                            SELECT 1 FROM entity_temporal_metadata
+                             WHERE owned_by_id = $1
+                               AND entity_uuid = $2
+                               AND {pinned_axis} @> $3::timestamptz
+                               AND {variable_axis} && $4::tstzrange
+                        );
+                    "#
+                ),
+                &[
+                    &entity_id.owned_by_id,
+                    &entity_id.entity_uuid,
+                    &temporal_axes.pinned_timestamp(),
+                    &temporal_axes.variable_interval(),
+                ],
+            )
+            .await
+            .change_context(QueryError)?
+            .get(0))
+    }
+
+    /// Searches for up to `limit` of the shortest paths from `source_entity_id` to
+    /// `target_entity_id`.
+    ///
+    /// The paths are searched breadth-first, so shorter paths are found before longer ones. Every
+    /// hop is authorized, links or targets the actor is not allowed to view are not followed. A
+    /// path never visits the same entity twice and every entity is reached by at most `limit`
+    /// partial paths, so the number of expanded paths is bounded by the number of entities.
+    ///
+    /// # Errors
+    ///
+    /// - [`EntityDoesNotExist`] if the source entity does not exist within the temporal axes
+    pub(super) async fn find_entity_paths<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        query: &EntityPathQuery,
+        temporal_axes: &QueryTemporalAxes,
+    ) -> Result<Vec<Vec<EntityId>>, QueryError> {
+        let zookie = authorization_api
+            .can_view_entity(
+                actor_id,
+                query.source_entity_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?
+            .assert_permission()
+            .change_context(QueryError)?;
+
+        if !self
+            .entity_exists(query.source_entity_id, temporal_axes)
+            .await?
+        {
+            return Err(Report::new(EntityDoesNotExist)
+                .attach(query.source_entity_id)
+                .change_context(QueryError));
+        }
+
+        let mut paths = Vec::new();
+        let mut partial_paths = vec![vec![query.source_entity_id]];
+        // The number of partial paths, which reached an entity
+        let mut visits = HashMap::<EntityId, usize>::from([(query.source_entity_id, 1)]);
+
+        for _ in 0..query.max_depth {
+            if partial_paths.is_empty() || paths.len() >= query.limit {
+                break;
+            }
+
+            let hops = self
+                .read_entity_hops(
+                    partial_paths
+                        .iter()
+                        .filter_map(|path| path.last().copied())
+                        .collect::<HashSet<_>>(),
+                    temporal_axes,
+                )
+                .await?;
+            if hops.is_empty() {
+                break;
+            }
+
+            let permissions = authorization_api
+                .can_view_entities(
+                    actor_id,
+                    hops.iter()
+                        .flat_map(|hop| [hop.link, hop.target])
+                        .collect::<HashSet<_>>(),
+                    Consistency::AtExactSnapshot(&zookie),
+                )
+                .await
+                .change_context(QueryError)?
+                .0;
+            let is_permitted =
+                |entity_id: &EntityId| permissions.get(entity_id).copied().unwrap_or(false);
+
+            let mut adjacency_list = HashMap::<EntityId, Vec<EntityHop>>::new();
+            for hop in hops {
+                if is_permitted(&hop.link) && is_permitted(&hop.target) {
+                    adjacency_list.entry(hop.source).or_default().push(hop);
+                }
+            }
+
+            let mut next_partial_paths = Vec::new();
+            for path in partial_paths {
+                let Some(hops) = path.last().and_then(|last| adjacency_list.get(last)) else {
+                    continue;
+                };
+
+                for hop in hops {
+                    if path.contains(&hop.link) || path.contains(&hop.target) {
+                        continue;
+                    }
+
+                    if hop.target != query.target_entity_id {
+                        let visits = visits.entry(hop.target).or_default();
+                        if *visits >= query.limit {
+                            continue;
+                        }
+                        *visits += 1;
+                    }
+
+                    let mut next_path = path.clone();
+                    next_path.extend([hop.link, hop.target]);
+                    if hop.target == query.target_entity_id {
+                        paths.push(next_path);
+                    } else {
+                        next_partial_paths.push(next_path);
+                    }
+                }
+            }
+            partial_paths = next_partial_paths;
+        }
+
+        paths.truncate(query.limit);
+        Ok(paths)
+    }
+
+    /// Creates a [`Subgraph`] rooted at the source entity of the `paths`, which contains all
+    /// entities of the `paths` and the knowledge graph edges between them.
+    pub(super) async fn create_entity_path_subgraph(
+        &self,
+        query: &EntityPathQuery,
+        temporal_axes: QueryTemporalAxes,
+        paths: &[Vec<EntityId>],
+    ) -> Result<Subgraph, QueryError> {
+        let time_axis = temporal_axes.variable_time_axis();
+
+        let entity_ids = paths
+            .iter()
+            .flatten()
+            .copied()
+            .chain([query.source_entity_id])
+            .collect::<HashSet<_>>();
+        let entities = Read::<Entity>::read_vec(
+            self,
+            &Filter::Any(
+                entity_ids
+                    .iter()
+                    .copied()
+                    .map(Filter::for_entity_by_entity_id)
+                    .collect(),
+            ),
+            Some(&temporal_axes),
+        )
+        .await?;
+
+        let mut subgraph = Subgraph::new(
+            GraphResolveDepths::default(),
+            query.temporal_axes.clone(),
+            temporal_axes,
+        );
+
+        let mut editions = HashMap::<EntityId, Vec<_>>::new();
+        for entity in &entities {
+            let interval: LeftClosedTemporalInterval<VariableAxis> = match time_axis {
+                TimeAxis::DecisionTime => {
+                    entity.metadata.temporal_versioning().decision_time.cast()
+                }
+                TimeAxis::TransactionTime => entity
+                    .metadata
+                    .temporal_versioning()
+                    .transaction_time
+                    .cast(),
+            };
+            editions
+                .entry(entity.metadata.record_id().entity_id)
+                .or_default()
+                .push((entity.vertex_id(time_axis), interval, entity.link_data));
+        }
+
+        if !editions.contains_key(&query.source_entity_id) {
+            return Err(Report::new(EntityDoesNotExist)
+                .attach(query.source_entity_id)
+                .change_context(QueryError));
+        }
+
+        // Links are always at odd positions of a path.
+        let link_entity_ids = paths
+            .iter()
+            .flat_map(|path| path.iter().skip(1).step_by(2))
+            .collect::<HashSet<_>>();
+        for (link_vertex_id, link_interval, link_data) in link_entity_ids
+            .into_iter()
+            .filter_map(|link_entity_id| editions.get(link_entity_id))
+            .flatten()
+        {
+            let Some(link_data) = link_data else {
+                continue;
+            };
+
+            for (edge_kind, endpoint_id) in [
+                (
+                    KnowledgeGraphEdgeKind::HasLeftEntity,
+                    link_data.left_entity_id,
+                ),
+                (
+                    KnowledgeGraphEdgeKind::HasRightEntity,
+                    link_data.right_entity_id,
+                ),
+            ] {
+                for (endpoint_vertex_id, endpoint_interval, _) in
+                    editions.get(&endpoint_id).into_iter().flatten()
+                {
+                    let Some(interval) = link_interval.intersect(*endpoint_interval) else {
+                        continue;
+                    };
+
+                    subgraph.insert_edge(
+                        link_vertex_id,
+                        edge_kind,
+                        EdgeDirection::Outgoing,
+                        EntityIdWithInterval {
+                            entity_id: endpoint_id,
+                            interval,
+                        },
+                    );
+                    subgraph.insert_edge(
+                        endpoint_vertex_id,
+                        edge_kind,
+                        EdgeDirection::Incoming,
+                        EntityIdWithInterval {
+                            entity_id: link_vertex_id.base_id,
+                            interval,
+                        },
+                    );
+                }
+            }
+        }
+
+        subgraph.roots = editions[&query.source_entity_id]
+            .iter()
+            .map(|(vertex_id, ..)| GraphElementVertexId::from(*vertex_id))
+            .collect();
+        for entity in entities {
+            subgraph.insert_vertex(entity.vertex_id(time_axis), entity);
+        }
+
+        Ok(subgraph)
+    }
+}
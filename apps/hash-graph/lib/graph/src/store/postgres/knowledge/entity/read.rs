@@ -1,9 +1,10 @@
 use std::{borrow::Cow, mem::swap, str::FromStr};
 
 use async_trait::async_trait;
-use error_stack::{Result, ResultExt};
+use error_stack::{Report, Result, ResultExt};
 use futures::{StreamExt, TryStreamExt};
 use graph_types::{
+    account::AccountId,
     knowledge::{
         entity::{
             Entity, EntityEditionId, EntityId, EntityMetadata, EntityRecordId,
@@ -11,7 +12,7 @@ use graph_types::{
         },
         link::{EntityLinkOrder, LinkData},
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordCreatedById},
+    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 use temporal_versioning::{
     LeftClosedTemporalInterval, RightBoundedTemporalInterval, TemporalTagged, TimeAxis, Timestamp,
@@ -28,31 +29,106 @@ use crate::{
         postgres::{
             ontology::OntologyId,
             query::{
-                Distinctness, ForeignKeyReference, ReferenceTable, SelectCompiler, Table, Transpile,
+                Distinctness, ForeignKeyReference, Ordering, ReferenceTable, SelectCompiler, Table,
+                Transpile,
             },
         },
-        query::Filter,
+        query::{EntityQueryCursor, Filter, Sorting},
         AsClient, PostgresStore, QueryError,
     },
     subgraph::{
         edges::{EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind, SharedEdgeKind},
-        identifier::{EntityTypeVertexId, EntityVertexId},
+        identifier::EntityTypeVertexId,
         temporal_axes::{PinnedAxis, QueryTemporalAxes, VariableAxis},
     },
 };
 
 #[async_trait]
 impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
+    type ReadStream = <Self as crud::ReadPaginated<Entity>>::ReadPaginatedStream;
     type Record = Entity;
 
-    type ReadStream = impl futures::Stream<Item = Result<Self::Record, QueryError>> + Send + Sync;
-
     #[tracing::instrument(level = "info", skip(self))]
     async fn read(
         &self,
         filter: &Filter<Entity>,
         temporal_axes: Option<&QueryTemporalAxes>,
     ) -> Result<Self::ReadStream, QueryError> {
+        crud::ReadPaginated::read_paginated(self, filter, temporal_axes, &[], None, None).await
+    }
+}
+
+#[async_trait]
+impl<C: AsClient> crud::ReadPaginated<Entity> for PostgresStore<C> {
+    type ReadPaginatedStream =
+        impl futures::Stream<Item = Result<Entity, QueryError>> + Send + Sync;
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn read_paginated(
+        &self,
+        filter: &Filter<Entity>,
+        temporal_axes: Option<&QueryTemporalAxes>,
+        sorting: &[Sorting<Entity>],
+        cursor: Option<&EntityQueryCursor>,
+        limit: Option<usize>,
+    ) -> Result<Self::ReadPaginatedStream, QueryError> {
+        Ok(self
+            .read_ranked_entities(filter, None, temporal_axes, sorting, cursor, limit)
+            .await?
+            .map_ok(|ranked_entity| ranked_entity.entity))
+    }
+}
+
+/// An [`Entity`] read by [`PostgresStore::read_ranked_entities`].
+pub(super) struct RankedEntity {
+    pub entity: Entity,
+    /// The rank of the entity for the [`Filter::Search`]es of the query.
+    pub search_rank: Option<f32>,
+    /// The values of the sorted paths of the entity, which are used in a cursor.
+    pub sort_key: Vec<serde_json::Value>,
+}
+
+/// A stream of [`RankedEntity`]s.
+pub(super) type RankedEntityStream =
+    impl futures::Stream<Item = Result<RankedEntity, QueryError>> + Send + Sync;
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Reads the [`Entity`]s matching `filter` alongside their rank for the [`Filter::Search`]es
+    /// contained in `filter`.
+    ///
+    /// The rank is `None` if `filter` does not contain a search. Sorting by
+    /// [`SortingPath::SearchRank`] sorts the entities by this rank.
+    ///
+    /// [`SortingPath::SearchRank`]: crate::store::query::SortingPath::SearchRank
+    ///
+    /// If a `restriction` is passed, only entities matching both filters are read. Unlike `filter`,
+    /// the restriction does not contribute to the rank.
+    #[tracing::instrument(level = "info", skip(self, restriction))]
+    pub(super) async fn read_ranked_entities(
+        &self,
+        filter: &Filter<Entity>,
+        restriction: Option<&Filter<'_, Entity>>,
+        temporal_axes: Option<&QueryTemporalAxes>,
+        sorting: &[Sorting<Entity>],
+        cursor: Option<&EntityQueryCursor>,
+        limit: Option<usize>,
+    ) -> Result<RankedEntityStream, QueryError> {
+        let cursor_sort_key = cursor
+            .map(|cursor| {
+                if cursor.sort_key.len() != sorting.len() {
+                    return Err(Report::new(QueryError).attach_printable(
+                        "the sort key of the cursor does not match the sorting of the query",
+                    ));
+                }
+                sorting
+                    .iter()
+                    .zip(&cursor.sort_key)
+                    .map(|(sorting, value)| sorting.cursor_parameter(value))
+                    .collect::<Result<Vec<_>, _>>()
+                    .change_context(QueryError)
+            })
+            .transpose()?;
+
         // We can't define these inline otherwise we'll drop while borrowed
         let left_entity_uuid_path = EntityQueryPath::EntityEdge {
             edge_kind: KnowledgeGraphEdgeKind::HasLeftEntity,
@@ -75,27 +151,47 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
             direction: EdgeDirection::Outgoing,
         };
 
+        // When paginating, the entities are ordered by their vertex id, i.e. by their entity id and
+        // the start of the variable time axis.
+        let paginated = cursor.is_some() || limit.is_some();
+        let variable_time_axis = temporal_axes.map_or(TimeAxis::DecisionTime, |temporal_axes| {
+            temporal_axes.variable_time_axis()
+        });
+        let variable_time_path = match variable_time_axis {
+            TimeAxis::DecisionTime => EntityQueryPath::DecisionTime,
+            TimeAxis::TransactionTime => EntityQueryPath::TransactionTime,
+        };
+        let ordering = |time_axis: Option<TimeAxis>| {
+            (paginated && time_axis.map_or(true, |time_axis| time_axis == variable_time_axis))
+                .then_some(Ordering::Ascending)
+        };
+
         let mut compiler = SelectCompiler::new(temporal_axes);
 
+        let sort_key_indices = sorting
+            .iter()
+            .map(|sorting| compiler.add_sorting(sorting, filter))
+            .collect::<Vec<_>>();
+
         let owned_by_id_index = compiler.add_distinct_selection_with_ordering(
             &EntityQueryPath::OwnedById,
             Distinctness::Distinct,
-            None,
+            ordering(None),
         );
         let entity_uuid_index = compiler.add_distinct_selection_with_ordering(
             &EntityQueryPath::Uuid,
             Distinctness::Distinct,
-            None,
+            ordering(None),
         );
         let decision_time_index = compiler.add_distinct_selection_with_ordering(
             &EntityQueryPath::DecisionTime,
             Distinctness::Distinct,
-            None,
+            ordering(Some(TimeAxis::DecisionTime)),
         );
         let transaction_time_index = compiler.add_distinct_selection_with_ordering(
             &EntityQueryPath::TransactionTime,
             Distinctness::Distinct,
-            None,
+            ordering(Some(TimeAxis::TransactionTime)),
         );
 
         let edition_id_index = compiler.add_selection_path(&EntityQueryPath::EditionId);
@@ -120,10 +216,39 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
 
         let record_created_by_id_index =
             compiler.add_selection_path(&EntityQueryPath::RecordCreatedById);
+        let record_archived_by_id_index =
+            compiler.add_selection_path(&EntityQueryPath::RecordArchivedById);
 
         let archived_index = compiler.add_selection_path(&EntityQueryPath::Archived);
 
+        // The rank has to be compiled before the filter, so it's able to reuse the joins of the
+        // selections.
+        let search_rank_index = compiler.add_search_rank_selection(filter);
+
         compiler.add_filter(filter);
+        if let Some(restriction) = restriction {
+            compiler.add_filter(restriction);
+        }
+        if let (Some(cursor), Some(sort_key)) = (cursor, &cursor_sort_key) {
+            compiler.add_cursor(
+                sort_key,
+                &[
+                    (
+                        &EntityQueryPath::OwnedById,
+                        &cursor.vertex_id.base_id.owned_by_id,
+                    ),
+                    (
+                        &EntityQueryPath::Uuid,
+                        &cursor.vertex_id.base_id.entity_uuid,
+                    ),
+                    (&variable_time_path, &cursor.vertex_id.revision_id),
+                ],
+            );
+        }
+        if let Some(limit) = limit {
+            compiler.set_limit(limit);
+        }
+
         let (statement, parameters) = compiler.compile();
 
         let stream = self
@@ -132,74 +257,95 @@ impl<C: AsClient> crud::Read<Entity> for PostgresStore<C> {
             .await
             .change_context(QueryError)?
             .map(|row| row.change_context(QueryError))
-            .and_then(move |row| async move {
-                let entity_type_id =
-                    VersionedUrl::from_str(row.get(type_id_index)).change_context(QueryError)?;
-
-                let link_data = {
-                    let left_owned_by_id: Option<Uuid> = row.get(left_entity_owned_by_id_index);
-                    let left_entity_uuid: Option<Uuid> = row.get(left_entity_uuid_index);
-                    let right_owned_by_id: Option<Uuid> = row.get(right_entity_owned_by_id_index);
-                    let right_entity_uuid: Option<Uuid> = row.get(right_entity_uuid_index);
-                    match (
-                        left_owned_by_id,
-                        left_entity_uuid,
-                        right_owned_by_id,
-                        right_entity_uuid,
-                    ) {
-                        (
-                            Some(left_owned_by_id),
-                            Some(left_entity_uuid),
-                            Some(right_owned_by_id),
-                            Some(right_entity_uuid),
-                        ) => Some(LinkData {
-                            left_entity_id: EntityId {
-                                owned_by_id: OwnedById::new(left_owned_by_id),
-                                entity_uuid: EntityUuid::new(left_entity_uuid),
+            .and_then(move |row| {
+                let sort_key_indices = sort_key_indices.clone();
+                async move {
+                    let entity_type_id = VersionedUrl::from_str(row.get(type_id_index))
+                        .change_context(QueryError)?;
+
+                    let link_data = {
+                        let left_owned_by_id: Option<Uuid> = row.get(left_entity_owned_by_id_index);
+                        let left_entity_uuid: Option<Uuid> = row.get(left_entity_uuid_index);
+                        let right_owned_by_id: Option<Uuid> =
+                            row.get(right_entity_owned_by_id_index);
+                        let right_entity_uuid: Option<Uuid> = row.get(right_entity_uuid_index);
+                        match (
+                            left_owned_by_id,
+                            left_entity_uuid,
+                            right_owned_by_id,
+                            right_entity_uuid,
+                        ) {
+                            (
+                                Some(left_owned_by_id),
+                                Some(left_entity_uuid),
+                                Some(right_owned_by_id),
+                                Some(right_entity_uuid),
+                            ) => Some(LinkData {
+                                left_entity_id: EntityId {
+                                    owned_by_id: OwnedById::new(left_owned_by_id),
+                                    entity_uuid: EntityUuid::new(left_entity_uuid),
+                                },
+                                right_entity_id: EntityId {
+                                    owned_by_id: OwnedById::new(right_owned_by_id),
+                                    entity_uuid: EntityUuid::new(right_entity_uuid),
+                                },
+                                order: EntityLinkOrder {
+                                    left_to_right: row.get(left_to_right_order_index),
+                                    right_to_left: row.get(right_to_left_order_index),
+                                },
+                            }),
+                            (None, None, None, None) => None,
+                            _ => unreachable!(
+                                "It's not possible to have a link entity with the left entityId \
+                                 or right entityId unspecified"
+                            ),
+                        }
+                    };
+
+                    let record_created_by_id =
+                        RecordCreatedById::new(row.get(record_created_by_id_index));
+                    let record_archived_by_id = row
+                        .get::<_, Option<AccountId>>(record_archived_by_id_index)
+                        .map(RecordArchivedById::new);
+
+                    let search_rank = search_rank_index.map(|index| row.get::<_, f32>(index));
+                    let sort_key = sort_key_indices
+                        .into_iter()
+                        .map(|index| {
+                            row.get::<_, Option<serde_json::Value>>(index)
+                                .unwrap_or(serde_json::Value::Null)
+                        })
+                        .collect();
+
+                    let entity = Entity {
+                        properties: row.get(properties_index),
+                        link_data,
+                        metadata: EntityMetadata::new(
+                            EntityRecordId {
+                                entity_id: EntityId {
+                                    owned_by_id: row.get(owned_by_id_index),
+                                    entity_uuid: row.get(entity_uuid_index),
+                                },
+                                edition_id: row.get(edition_id_index),
                             },
-                            right_entity_id: EntityId {
-                                owned_by_id: OwnedById::new(right_owned_by_id),
-                                entity_uuid: EntityUuid::new(right_entity_uuid),
+                            EntityTemporalMetadata {
+                                decision_time: row.get(decision_time_index),
+                                transaction_time: row.get(transaction_time_index),
                             },
-                            order: EntityLinkOrder {
-                                left_to_right: row.get(left_to_right_order_index),
-                                right_to_left: row.get(right_to_left_order_index),
+                            entity_type_id,
+                            ProvenanceMetadata {
+                                record_created_by_id,
+                                record_archived_by_id,
                             },
-                        }),
-                        (None, None, None, None) => None,
-                        _ => unreachable!(
-                            "It's not possible to have a link entity with the left entityId or \
-                             right entityId unspecified"
+                            row.get(archived_index),
                         ),
-                    }
-                };
-
-                let record_created_by_id =
-                    RecordCreatedById::new(row.get(record_created_by_id_index));
-
-                Ok(Entity {
-                    properties: row.get(properties_index),
-                    link_data,
-                    metadata: EntityMetadata::new(
-                        EntityRecordId {
-                            entity_id: EntityId {
-                                owned_by_id: row.get(owned_by_id_index),
-                                entity_uuid: row.get(entity_uuid_index),
-                            },
-                            edition_id: row.get(edition_id_index),
-                        },
-                        EntityTemporalMetadata {
-                            decision_time: row.get(decision_time_index),
-                            transaction_time: row.get(transaction_time_index),
-                        },
-                        entity_type_id,
-                        ProvenanceMetadata {
-                            record_created_by_id,
-                            record_archived_by_id: None,
-                        },
-                        row.get(archived_index),
-                    ),
-                })
+                    };
+                    Ok(RankedEntity {
+                        entity,
+                        search_rank,
+                        sort_key,
+                    })
+                }
             });
         Ok(stream)
     }
@@ -0,0 +1,260 @@
+use std::collections::{HashMap, HashSet};
+
+use error_stack::{Result, ResultExt};
+use graph_types::knowledge::entity::EntityId;
+use serde_json::Value;
+use tokio_postgres::GenericClient;
+use type_system::url::VersionedUrl;
+
+use crate::{
+    knowledge::{
+        collect_property_type_references, property_type_references, EntityValidationSchemas,
+        LinkValidationSchemas,
+    },
+    store::{postgres::ontology::OntologyId, AsClient, PostgresStore, QueryError},
+};
+
+/// Caches the schemas read while validating multiple entities at once.
+#[derive(Debug, Default)]
+pub(crate) struct EntityValidationCache {
+    schemas: HashMap<VersionedUrl, EntityValidationSchemas>,
+    entity_type_closures: HashMap<EntityId, Option<Vec<Value>>>,
+    /// The entity types of entities, which are created alongside the validated entities and are
+    /// therefore not yet stored.
+    pending_entity_types: HashMap<EntityId, VersionedUrl>,
+}
+
+impl EntityValidationCache {
+    /// Registers an entity, which is not yet stored, so links to it can be validated.
+    pub(crate) fn add_pending_entity(&mut self, entity_id: EntityId, entity_type_id: VersionedUrl) {
+        self.pending_entity_types.insert(entity_id, entity_type_id);
+    }
+}
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Same as [`Self::read_entity_validation_schemas`] but reuses the schemas stored in `cache`.
+    ///
+    /// # Errors
+    ///
+    /// - if the entity type does not exist
+    pub(crate) async fn read_cached_entity_validation_schemas<'c>(
+        &self,
+        cache: &'c mut EntityValidationCache,
+        entity_type_id: &VersionedUrl,
+        link_entity_ids: Option<(EntityId, EntityId)>,
+    ) -> Result<&'c EntityValidationSchemas, QueryError> {
+        let link = if let Some((left_entity_id, right_entity_id)) = link_entity_ids {
+            Some(LinkValidationSchemas {
+                left_entity_types: self
+                    .read_cached_entity_type_closure_of(cache, left_entity_id)
+                    .await?,
+                right_entity_types: self
+                    .read_cached_entity_type_closure_of(cache, right_entity_id)
+                    .await?,
+            })
+        } else {
+            None
+        };
+
+        if !cache.schemas.contains_key(entity_type_id) {
+            let schemas = self
+                .read_entity_validation_schemas(entity_type_id, None)
+                .await?;
+            cache.schemas.insert(entity_type_id.clone(), schemas);
+        }
+
+        let schemas = cache
+            .schemas
+            .get_mut(entity_type_id)
+            .expect("schemas should have been inserted");
+        schemas.link = link;
+        Ok(schemas)
+    }
+
+    async fn read_cached_entity_type_closure_of(
+        &self,
+        cache: &mut EntityValidationCache,
+        entity_id: EntityId,
+    ) -> Result<Option<Vec<Value>>, QueryError> {
+        if let Some(entity_types) = cache.entity_type_closures.get(&entity_id) {
+            return Ok(entity_types.clone());
+        }
+
+        let entity_types =
+            if let Some(entity_type_id) = cache.pending_entity_types.get(&entity_id).cloned() {
+                let ontology_id = self.ontology_id_by_url(&entity_type_id).await?;
+                Some(self.read_entity_type_closure(ontology_id).await?)
+            } else {
+                self.read_entity_type_closure_of(entity_id).await?
+            };
+        cache
+            .entity_type_closures
+            .insert(entity_id, entity_types.clone());
+        Ok(entity_types)
+    }
+
+    /// Reads all schemas required to validate an entity of the specified entity type.
+    ///
+    /// If `link_entity_ids` is passed, the entity is validated as a link between the left and the
+    /// right entity. A linked entity, which does not exist, is reported as a validation failure.
+    ///
+    /// # Errors
+    ///
+    /// - if the entity type does not exist
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn read_entity_validation_schemas(
+        &self,
+        entity_type_id: &VersionedUrl,
+        link_entity_ids: Option<(EntityId, EntityId)>,
+    ) -> Result<EntityValidationSchemas, QueryError> {
+        let entity_type_ontology_id = self.ontology_id_by_url(entity_type_id).await?;
+        let entity_types = self
+            .read_entity_type_closure(entity_type_ontology_id)
+            .await?;
+
+        let mut property_types = HashMap::new();
+        let mut property_type_queue = entity_types
+            .iter()
+            .filter_map(|entity_type| entity_type.get("properties"))
+            .flat_map(property_type_references)
+            .map(ToOwned::to_owned)
+            .collect::<HashSet<_>>();
+        let mut data_type_ids = HashSet::new();
+
+        // Property types may nest other property types, so they are resolved level by level.
+        while !property_type_queue.is_empty() {
+            let schemas = self
+                .read_schemas("property_types", property_type_queue.drain())
+                .await?;
+
+            let mut referenced_property_types = Vec::new();
+            let mut referenced_data_types = Vec::new();
+            for schema in schemas.values() {
+                collect_property_type_references(
+                    schema,
+                    &mut referenced_property_types,
+                    &mut referenced_data_types,
+                );
+            }
+            data_type_ids.extend(referenced_data_types.into_iter().map(ToOwned::to_owned));
+            property_type_queue.extend(
+                referenced_property_types
+                    .into_iter()
+                    .filter(|id| !property_types.contains_key(*id) && !schemas.contains_key(*id))
+                    .map(ToOwned::to_owned),
+            );
+
+            property_types.extend(schemas);
+        }
+
+        let data_types = self.read_schemas("data_types", data_type_ids).await?;
+        let patterns = EntityValidationSchemas::compile_patterns(&data_types);
+
+        let link = if let Some((left_entity_id, right_entity_id)) = link_entity_ids {
+            Some(LinkValidationSchemas {
+                left_entity_types: self.read_entity_type_closure_of(left_entity_id).await?,
+                right_entity_types: self.read_entity_type_closure_of(right_entity_id).await?,
+            })
+        } else {
+            None
+        };
+
+        Ok(EntityValidationSchemas {
+            entity_types,
+            property_types,
+            data_types,
+            patterns,
+            link,
+        })
+    }
+
+    /// Reads the schema of the entity type and the schemas of all of its ancestors.
+    async fn read_entity_type_closure(
+        &self,
+        ontology_id: OntologyId,
+    ) -> Result<Vec<Value>, QueryError> {
+        Ok(self
+            .as_client()
+            .query(
+                r#"
+                    SELECT schema
+                    FROM entity_types
+                    WHERE ontology_id = $1
+                    UNION ALL
+                    SELECT entity_types.schema
+                    FROM closed_entity_type_inherits_from
+                    JOIN entity_types
+                      ON entity_types.ontology_id = target_entity_type_ontology_id
+                    WHERE source_entity_type_ontology_id = $1;
+                "#,
+                &[&ontology_id],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect())
+    }
+
+    /// Reads the entity type closure of the current edition of the specified entity.
+    ///
+    /// Returns `None` if the entity does not exist.
+    async fn read_entity_type_closure_of(
+        &self,
+        entity_id: EntityId,
+    ) -> Result<Option<Vec<Value>>, QueryError> {
+        let Some(row) = self
+            .as_client()
+            .query_opt(
+                r#"
+                    SELECT entity_is_of_type.entity_type_ontology_id
+                    FROM entity_temporal_metadata
+                    JOIN entity_is_of_type
+                      ON entity_is_of_type.entity_edition_id
+                       = entity_temporal_metadata.entity_edition_id
+                    WHERE entity_temporal_metadata.owned_by_id = $1
+                      AND entity_temporal_metadata.entity_uuid = $2
+                      AND entity_temporal_metadata.transaction_time @> now()
+                      AND entity_temporal_metadata.decision_time @> now();
+                "#,
+                &[&entity_id.owned_by_id, &entity_id.entity_uuid],
+            )
+            .await
+            .change_context(QueryError)?
+        else {
+            return Ok(None);
+        };
+
+        self.read_entity_type_closure(row.get(0)).await.map(Some)
+    }
+
+    /// Reads the schemas from `table` with the specified `$id`s keyed by their `$id`.
+    async fn read_schemas(
+        &self,
+        table: &'static str,
+        ids: impl IntoIterator<Item = String> + Send,
+    ) -> Result<HashMap<String, Value>, QueryError> {
+        let ids = ids.into_iter().collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        Ok(self
+            .as_client()
+            .query(
+                &format!(
+                    r#"
+                        SELECT schema->>'$id', schema
+                        FROM {table}
+                        WHERE schema->>'$id' = ANY($1);
+                    "#
+                ),
+                &[&ids],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect())
+    }
+}
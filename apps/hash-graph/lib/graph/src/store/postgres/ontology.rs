@@ -4,16 +4,25 @@ mod ontology_id;
 mod property_type;
 mod read;
 
-#[cfg(hash_graph_test_environment)]
+use authorization::{AuthorizationApi, VisibilityScope};
 use error_stack::{Result, ResultExt};
-use graph_types::ontology::OntologyType;
+use graph_types::{
+    account::{AccountGroupId, AccountId},
+    ontology::{DataTypeId, EntityTypeId, OntologyType, PropertyTypeId},
+    provenance::OwnedById,
+};
 use tokio_postgres::Transaction;
-use type_system::{DataType, EntityType, PropertyType};
+use type_system::{url::BaseUrl, DataType, EntityType, PropertyType};
 
 pub use self::ontology_id::OntologyId;
-use crate::store::PostgresStore;
+pub(crate) use self::{
+    data_type::{data_type_relations, remove_data_type_relations},
+    entity_type::{entity_type_relations, remove_entity_type_relations},
+    property_type::{property_type_relations, remove_property_type_relations},
+};
 #[cfg(hash_graph_test_environment)]
-use crate::store::{error::DeletionError, AsClient};
+use crate::store::error::DeletionError;
+use crate::store::{AsClient, InsertionError, PostgresStore, QueryError};
 
 /// Provides an abstraction over elements of the Type System stored in the Database.
 ///
@@ -41,6 +50,117 @@ impl OntologyDatabaseType for EntityType {
     }
 }
 
+impl<C: AsClient> PostgresStore<C> {
+    /// Writes the relations of all ontology types to the authorization backend.
+    ///
+    /// Types created before ontology types had permissions and types restored from a snapshot
+    /// don't have any relations. Existing relations are kept, so this can be called on every
+    /// start. The relations of each kind of ontology type are written in a single batch.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the owners of the ontology types fails
+    /// - if writing the relations fails
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    pub async fn write_ontology_type_relations<A: AuthorizationApi + Send>(
+        &self,
+        authorization_api: &mut A,
+    ) -> Result<(), InsertionError> {
+        let relations = self
+            .read_ontology_type_owners::<DataType>()
+            .await
+            .change_context(InsertionError)?
+            .into_iter()
+            .flat_map(|(base_url, owner)| {
+                data_type_relations(DataTypeId::from_base_url(&base_url), owner)
+            })
+            .collect::<Vec<_>>();
+        authorization_api
+            .touch_data_type_relations(relations)
+            .await
+            .change_context(InsertionError)?;
+
+        let relations = self
+            .read_ontology_type_owners::<PropertyType>()
+            .await
+            .change_context(InsertionError)?
+            .into_iter()
+            .flat_map(|(base_url, owner)| {
+                property_type_relations(PropertyTypeId::from_base_url(&base_url), owner)
+            })
+            .collect::<Vec<_>>();
+        authorization_api
+            .touch_property_type_relations(relations)
+            .await
+            .change_context(InsertionError)?;
+
+        let relations = self
+            .read_ontology_type_owners::<EntityType>()
+            .await
+            .change_context(InsertionError)?
+            .into_iter()
+            .flat_map(|(base_url, owner)| {
+                entity_type_relations(EntityTypeId::from_base_url(&base_url), owner)
+            })
+            .collect::<Vec<_>>();
+        authorization_api
+            .touch_entity_type_relations(relations)
+            .await
+            .change_context(InsertionError)?;
+
+        Ok(())
+    }
+
+    /// Returns the [`BaseUrl`]s of the types stored in the table of `T` alongside the scope of
+    /// their owner.
+    ///
+    /// External types don't have an owner, so [`VisibilityScope::Public`] is returned for them.
+    async fn read_ontology_type_owners<T: OntologyDatabaseType>(
+        &self,
+    ) -> Result<Vec<(BaseUrl, VisibilityScope)>, QueryError> {
+        // Generally bad practice to construct a query without preparation, but it's not possible to
+        // pass a table name as a parameter and `T::table()` is well-defined, so this is a safe
+        // usage.
+        self.as_client()
+            .query(
+                &format!(
+                    r#"
+                        SELECT DISTINCT
+                            ontology_ids.base_url,
+                            ontology_owned_metadata.owned_by_id,
+                            EXISTS (
+                                SELECT 1
+                                FROM account_groups
+                                WHERE account_group_id = ontology_owned_metadata.owned_by_id
+                            )
+                        FROM {}
+                        JOIN ontology_ids USING (ontology_id)
+                        LEFT JOIN ontology_owned_metadata USING (ontology_id);
+                    "#,
+                    T::table()
+                ),
+                &[],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| {
+                let base_url = BaseUrl::new(row.get(0)).change_context(QueryError)?;
+                let owner = match row.get::<_, Option<OwnedById>>(1) {
+                    None => VisibilityScope::Public,
+                    Some(owned_by_id) if row.get(2) => {
+                        VisibilityScope::AccountGroup(AccountGroupId::new(owned_by_id.into_uuid()))
+                    }
+                    Some(owned_by_id) => {
+                        VisibilityScope::Account(AccountId::new(owned_by_id.into_uuid()))
+                    }
+                };
+                Ok((base_url, owner))
+            })
+            .collect()
+    }
+}
+
 impl PostgresStore<Transaction<'_>> {
     #[tracing::instrument(level = "trace", skip(self))]
     #[cfg(hash_graph_test_environment)]
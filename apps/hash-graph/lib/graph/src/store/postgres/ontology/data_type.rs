@@ -1,14 +1,17 @@
 use std::collections::HashSet;
 
 use async_trait::async_trait;
-use authorization::AuthorizationApi;
+use authorization::{
+    backend::ModifyRelationError, schema::DataTypeRelation, zanzibar::Consistency,
+    AuthorizationApi, VisibilityScope,
+};
 use error_stack::{Report, Result, ResultExt};
-use futures::{stream, TryStreamExt};
+use futures::TryStreamExt;
 use graph_types::{
     account::AccountId,
     ontology::{
-        DataTypeWithMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
-        PartialOntologyElementMetadata,
+        DataTypeId, DataTypeWithMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
+        PartialCustomOntologyMetadata, PartialOntologyElementMetadata,
     },
     provenance::{ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
@@ -21,8 +24,9 @@ use crate::{
     store::{
         crud::Read,
         postgres::{ontology::OntologyId, TraversalContext},
-        AsClient, ConflictBehavior, DataTypeStore, InsertionError, PostgresStore, QueryError,
-        Record, UpdateError,
+        query::{AggregationQuery, AggregationResult},
+        AsClient, ConflictBehavior, DataTypeStore, InsertionError, OntologyTypeDependents,
+        PostgresStore, QueryError, Record, UpdateError,
     },
     subgraph::{
         edges::GraphResolveDepths, query::StructuralQuery, temporal_axes::VariableAxis, Subgraph,
@@ -79,13 +83,60 @@ impl<C: AsClient> PostgresStore<C> {
     }
 }
 
+/// Returns the relations of a newly created [`DataType`].
+///
+/// The owner is granted access to the type, external types don't have an owner. Ontology types are
+/// viewable by everyone unless the public viewer is removed again.
+pub(crate) fn data_type_relations(
+    data_type_id: DataTypeId,
+    owner: VisibilityScope,
+) -> impl Iterator<Item = (DataTypeId, DataTypeRelation, VisibilityScope)> {
+    (owner != VisibilityScope::Public)
+        .then_some((data_type_id, DataTypeRelation::DirectOwner, owner))
+        .into_iter()
+        .chain([(
+            data_type_id,
+            DataTypeRelation::DirectViewer,
+            VisibilityScope::Public,
+        )])
+}
+
+/// Writes the [`data_type_relations`] of a newly created [`DataType`].
+pub(crate) async fn add_data_type_relations<A: AuthorizationApi + Send>(
+    authorization_api: &mut A,
+    data_type_id: DataTypeId,
+    owner: VisibilityScope,
+) -> Result<(), ModifyRelationError> {
+    authorization_api
+        .touch_data_type_relations(data_type_relations(data_type_id, owner))
+        .await?;
+    Ok(())
+}
+
+/// Reverts [`add_data_type_relations`].
+pub(crate) async fn remove_data_type_relations<A: AuthorizationApi + Send>(
+    authorization_api: &mut A,
+    data_type_id: DataTypeId,
+    owner: VisibilityScope,
+) -> Result<(), ModifyRelationError> {
+    if owner != VisibilityScope::Public {
+        authorization_api
+            .remove_data_type_owner(owner, data_type_id)
+            .await?;
+    }
+    authorization_api
+        .remove_data_type_viewer(VisibilityScope::Public, data_type_id)
+        .await?;
+    Ok(())
+}
+
 #[async_trait]
 impl<C: AsClient> DataTypeStore for PostgresStore<C> {
-    #[tracing::instrument(level = "info", skip(self, data_types, _authorization_api))]
-    async fn create_data_types<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, data_types, authorization_api))]
+    async fn create_data_types<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         data_types: impl IntoIterator<Item = (DataType, PartialOntologyElementMetadata), IntoIter: Send>
         + Send,
         on_conflict: ConflictBehavior,
@@ -98,8 +149,18 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
         };
 
         let mut inserted_data_type_metadata = Vec::new();
+        let mut inserted_data_type_owners = Vec::new();
         for (schema, metadata) in data_types {
-            if let Some((ontology_id, transaction_time, _visibility_scope)) = transaction
+            if let PartialCustomOntologyMetadata::Owned { owned_by_id } = metadata.custom {
+                authorization_api
+                    .can_create_data_type(actor_id, owned_by_id, Consistency::FullyConsistent)
+                    .await
+                    .change_context(InsertionError)?
+                    .assert_permission()
+                    .change_context(InsertionError)?;
+            }
+
+            if let Some((ontology_id, transaction_time, owner)) = transaction
                 .create_ontology_metadata(
                     provenance.record_created_by_id,
                     &metadata.record_id,
@@ -108,6 +169,9 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
                 )
                 .await?
             {
+                if let Some(owner) = owner {
+                    inserted_data_type_owners.push((DataTypeId::from_url(schema.id()), owner));
+                }
                 transaction
                     .insert_with_id(ontology_id, schema.clone())
                     .await?;
@@ -116,37 +180,60 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
                     provenance,
                     transaction_time,
                 ));
-
-                // TODO: Insert permission for data type, something like
-                //       ```
-                //       authorization_api.grant_permission(
-                //           visibility_scope,
-                //           Relation::Admin,
-                //           ontology_id
-                //       )
-                //       ```
-                //       Make sure this will revoke the permission if the transaction fails.
             }
         }
 
-        transaction.commit().await.change_context(InsertionError)?;
+        for &(data_type_id, owner) in &inserted_data_type_owners {
+            add_data_type_relations(authorization_api, data_type_id, owner)
+                .await
+                .change_context(InsertionError)?;
+        }
+
+        if let Err(mut error) = transaction.commit().await.change_context(InsertionError) {
+            for &(data_type_id, owner) in &inserted_data_type_owners {
+                if let Err(auth_error) =
+                    remove_data_type_relations(authorization_api, data_type_id, owner)
+                        .await
+                        .change_context(InsertionError)
+                {
+                    // TODO: Use `add_child`
+                    //   see https://linear.app/hash/issue/GEN-105/add-ability-to-add-child-errors
+                    error.extend_one(auth_error);
+                }
+            }
 
-        Ok(inserted_data_type_metadata)
+            Err(error)
+        } else {
+            Ok(inserted_data_type_metadata)
+        }
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
     async fn get_data_type<A: AuthorizationApi + Sync>(
         &self,
-        _actor_id: AccountId,
-        _authorization_api: &A,
+        actor_id: AccountId,
+        authorization_api: &A,
         query: &StructuralQuery<DataTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError> {
         let StructuralQuery {
             ref filter,
             graph_resolve_depths,
+            ref traversal_filters,
             temporal_axes: ref unresolved_temporal_axes,
+            ref order_by,
+            limit,
+            ref cursor,
         } = *query;
 
+        if !order_by.is_empty() || limit.is_some() || cursor.is_some() {
+            return Err(Report::new(QueryError)
+                .attach_printable("sorting and pagination are only supported for entities"));
+        }
+        if !traversal_filters.is_empty() {
+            return Err(Report::new(QueryError)
+                .attach_printable("traversal filters are only supported for entities"));
+        }
+
         let temporal_axes = unresolved_temporal_axes.clone().resolve();
         let time_axis = temporal_axes.variable_time_axis();
 
@@ -161,41 +248,73 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
             //   see https://linear.app/hash/issue/H-297
             let mut visited_ontology_ids = HashSet::new();
 
-            subgraph.vertices.data_types =
-                Read::<DataTypeWithMetadata>::read_vec(self, filter, Some(&temporal_axes))
-                    .await?
-                    .into_iter()
-                    .filter_map(|data_type| {
-                        // The records are already sorted by time, so we can just take the first
-                        // one
-                        visited_ontology_ids
-                            .insert(data_type.vertex_id(time_axis))
-                            .then(|| (data_type.vertex_id(time_axis), data_type))
-                    })
-                    .collect();
+            let data_types =
+                Read::<DataTypeWithMetadata>::read_vec(self, filter, Some(&temporal_axes)).await?;
+            let (viewable_data_types, _) = authorization_api
+                .can_view_data_types(
+                    actor_id,
+                    data_types
+                        .iter()
+                        .map(|data_type| DataTypeId::from_url(data_type.schema.id())),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(QueryError)?;
+
+            subgraph.vertices.data_types = data_types
+                .into_iter()
+                .filter(|data_type| {
+                    viewable_data_types[&DataTypeId::from_url(data_type.schema.id())]
+                })
+                .filter_map(|data_type| {
+                    // The records are already sorted by time, so we can just take the first
+                    // one
+                    visited_ontology_ids
+                        .insert(data_type.vertex_id(time_axis))
+                        .then(|| (data_type.vertex_id(time_axis), data_type))
+                })
+                .collect();
             for vertex_id in subgraph.vertices.data_types.keys() {
-                subgraph.roots.insert(vertex_id.clone().into());
+                subgraph.roots.push(vertex_id.clone().into());
             }
         } else {
             let mut traversal_context = TraversalContext::default();
-            let traversal_data = self
+            // The same vertex may be returned multiple times, but it must only be a root once
+            let mut root_ids = HashSet::new();
+            let data_type_ids = self
                 .read_ontology_ids::<DataTypeWithMetadata>(filter, Some(&temporal_axes))
                 .await?
-                .map_ok(|(vertex_id, ontology_id)| {
-                    subgraph.roots.insert(vertex_id.into());
-                    stream::iter(
-                        traversal_context
-                            .add_data_type_id(
-                                ontology_id,
-                                graph_resolve_depths,
-                                temporal_axes.variable_interval(),
-                            )
-                            .map(Ok::<_, Report<QueryError>>),
-                    )
-                })
-                .try_flatten()
                 .try_collect::<Vec<_>>()
                 .await?;
+            // Only the queried types are checked, the types they depend on are required to use
+            // them, so they are always resolved.
+            let (viewable_data_types, _) = authorization_api
+                .can_view_data_types(
+                    actor_id,
+                    data_type_ids
+                        .iter()
+                        .map(|(vertex_id, _)| DataTypeId::from_base_url(&vertex_id.base_id)),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(QueryError)?;
+
+            let traversal_data = data_type_ids
+                .into_iter()
+                .filter(|(vertex_id, _)| {
+                    viewable_data_types[&DataTypeId::from_base_url(&vertex_id.base_id)]
+                })
+                .flat_map(|(vertex_id, ontology_id)| {
+                    if root_ids.insert(vertex_id.clone()) {
+                        subgraph.roots.push(vertex_id.into());
+                    }
+                    traversal_context.add_data_type_id(
+                        ontology_id,
+                        graph_resolve_depths,
+                        temporal_axes.variable_interval(),
+                    )
+                })
+                .collect::<Vec<_>>();
 
             self.traverse_data_types(traversal_data, &mut traversal_context, &mut subgraph)
                 .await?;
@@ -208,13 +327,34 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
         Ok(subgraph)
     }
 
-    #[tracing::instrument(level = "info", skip(self, data_type, _authorization_api))]
-    async fn update_data_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
+    async fn aggregate_data_types<A: AuthorizationApi + Sync>(
+        &self,
+        _actor_id: AccountId,
+        _authorization_api: &A,
+        query: &AggregationQuery<DataTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError> {
+        self.aggregate_records(query, None).await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, data_type, authorization_api))]
+    async fn update_data_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         data_type: DataType,
     ) -> Result<OntologyElementMetadata, UpdateError> {
+        authorization_api
+            .can_update_data_type(
+                actor_id,
+                DataTypeId::from_url(data_type.id()),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         let transaction = self.transaction().await.change_context(UpdateError)?;
 
         let (_, metadata) = transaction
@@ -226,25 +366,58 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
         Ok(metadata)
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
-    async fn archive_data_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn archive_data_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        authorization_api
+            .can_archive_data_type(
+                actor_id,
+                DataTypeId::from_url(id),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         self.archive_ontology_type(id, RecordArchivedById::new(actor_id))
             .await
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
-    async fn unarchive_data_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn unarchive_data_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        authorization_api
+            .can_archive_data_type(
+                actor_id,
+                DataTypeId::from_url(id),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         self.unarchive_ontology_type(id, RecordCreatedById::new(actor_id))
             .await
     }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn get_data_type_dependents<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError> {
+        self.read_ontology_type_dependents::<DataType, _>(actor_id, authorization_api, id)
+            .await
+    }
 }
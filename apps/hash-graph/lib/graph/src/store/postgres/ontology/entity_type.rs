@@ -1,14 +1,18 @@
 use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
-use authorization::AuthorizationApi;
+use authorization::{
+    backend::ModifyRelationError, schema::EntityTypeRelation, zanzibar::Consistency,
+    AuthorizationApi, VisibilityScope,
+};
 use error_stack::{Report, Result, ResultExt};
-use futures::{stream, TryStreamExt};
+use futures::TryStreamExt;
 use graph_types::{
     account::AccountId,
     ontology::{
-        EntityTypeMetadata, EntityTypeWithMetadata, OntologyTemporalMetadata, OntologyTypeRecordId,
-        PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
+        EntityTypeId, EntityTypeMetadata, EntityTypeWithMetadata, OntologyTemporalMetadata,
+        OntologyTypeRecordId, PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata,
+        PartialEntityTypeMetadata,
     },
     provenance::{ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
@@ -28,8 +32,9 @@ use crate::{
             query::ReferenceTable,
             TraversalContext,
         },
-        AsClient, ConflictBehavior, EntityTypeStore, InsertionError, PostgresStore, QueryError,
-        Record, UpdateError,
+        query::{AggregationQuery, AggregationResult},
+        AsClient, ConflictBehavior, EntityTypeStore, InsertionError, OntologyTypeDependents,
+        PostgresStore, QueryError, Record, UpdateError,
     },
     subgraph::{
         edges::{EdgeDirection, GraphResolveDepths, OntologyEdgeKind},
@@ -204,13 +209,60 @@ impl<C: AsClient> PostgresStore<C> {
     }
 }
 
+/// Returns the relations of a newly created [`EntityType`].
+///
+/// The owner is granted access to the type, external types don't have an owner. Ontology types are
+/// viewable by everyone unless the public viewer is removed again.
+pub(crate) fn entity_type_relations(
+    entity_type_id: EntityTypeId,
+    owner: VisibilityScope,
+) -> impl Iterator<Item = (EntityTypeId, EntityTypeRelation, VisibilityScope)> {
+    (owner != VisibilityScope::Public)
+        .then_some((entity_type_id, EntityTypeRelation::DirectOwner, owner))
+        .into_iter()
+        .chain([(
+            entity_type_id,
+            EntityTypeRelation::DirectViewer,
+            VisibilityScope::Public,
+        )])
+}
+
+/// Writes the [`entity_type_relations`] of a newly created [`EntityType`].
+pub(crate) async fn add_entity_type_relations<A: AuthorizationApi + Send>(
+    authorization_api: &mut A,
+    entity_type_id: EntityTypeId,
+    owner: VisibilityScope,
+) -> Result<(), ModifyRelationError> {
+    authorization_api
+        .touch_entity_type_relations(entity_type_relations(entity_type_id, owner))
+        .await?;
+    Ok(())
+}
+
+/// Reverts [`add_entity_type_relations`].
+pub(crate) async fn remove_entity_type_relations<A: AuthorizationApi + Send>(
+    authorization_api: &mut A,
+    entity_type_id: EntityTypeId,
+    owner: VisibilityScope,
+) -> Result<(), ModifyRelationError> {
+    if owner != VisibilityScope::Public {
+        authorization_api
+            .remove_entity_type_owner(owner, entity_type_id)
+            .await?;
+    }
+    authorization_api
+        .remove_entity_type_viewer(VisibilityScope::Public, entity_type_id)
+        .await?;
+    Ok(())
+}
+
 #[async_trait]
 impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
-    #[tracing::instrument(level = "info", skip(self, entity_types, _authorization_api))]
-    async fn create_entity_types<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, entity_types, authorization_api))]
+    async fn create_entity_types<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         entity_types: impl IntoIterator<Item = (EntityType, PartialEntityTypeMetadata), IntoIter: Send>
         + Send,
         on_conflict: ConflictBehavior,
@@ -226,8 +278,18 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
         let mut inserted_entity_types = Vec::new();
         let mut inserted_entity_type_metadata =
             Vec::with_capacity(inserted_entity_types.capacity());
+        let mut inserted_entity_type_owners = Vec::new();
         for (schema, metadata) in entity_types {
-            if let Some((ontology_id, transaction_time, _visibility_scope)) = transaction
+            if let PartialCustomOntologyMetadata::Owned { owned_by_id } = metadata.custom.common {
+                authorization_api
+                    .can_create_entity_type(actor_id, owned_by_id, Consistency::FullyConsistent)
+                    .await
+                    .change_context(InsertionError)?
+                    .assert_permission()
+                    .change_context(InsertionError)?;
+            }
+
+            if let Some((ontology_id, transaction_time, owner)) = transaction
                 .create_ontology_metadata(
                     provenance.record_created_by_id,
                     &metadata.record_id,
@@ -236,6 +298,9 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
                 )
                 .await?
             {
+                if let Some(owner) = owner {
+                    inserted_entity_type_owners.push((EntityTypeId::from_url(schema.id()), owner));
+                }
                 transaction
                     .insert_entity_type_with_id(
                         ontology_id,
@@ -250,16 +315,6 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
                     provenance,
                     transaction_time,
                 ));
-
-                // TODO: Insert permission for entity type, something like
-                //       ```
-                //       authorization_api.grant_permission(
-                //           visibility_scope,
-                //           Relation::Admin,
-                //           ontology_id
-                //       )
-                //       ```
-                //       Make sure this will revoke the permission if the transaction fails.
             }
         }
 
@@ -277,24 +332,57 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
                 .attach_lazy(|| schema.clone())?;
         }
 
-        transaction.commit().await.change_context(InsertionError)?;
+        for &(entity_type_id, owner) in &inserted_entity_type_owners {
+            add_entity_type_relations(authorization_api, entity_type_id, owner)
+                .await
+                .change_context(InsertionError)?;
+        }
+
+        if let Err(mut error) = transaction.commit().await.change_context(InsertionError) {
+            for &(entity_type_id, owner) in &inserted_entity_type_owners {
+                if let Err(auth_error) =
+                    remove_entity_type_relations(authorization_api, entity_type_id, owner)
+                        .await
+                        .change_context(InsertionError)
+                {
+                    // TODO: Use `add_child`
+                    //   see https://linear.app/hash/issue/GEN-105/add-ability-to-add-child-errors
+                    error.extend_one(auth_error);
+                }
+            }
 
-        Ok(inserted_entity_type_metadata)
+            Err(error)
+        } else {
+            Ok(inserted_entity_type_metadata)
+        }
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
     async fn get_entity_type<A: AuthorizationApi + Sync>(
         &self,
-        _actor_id: AccountId,
-        _authorization_api: &A,
+        actor_id: AccountId,
+        authorization_api: &A,
         query: &StructuralQuery<EntityTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError> {
         let StructuralQuery {
             ref filter,
             graph_resolve_depths,
+            ref traversal_filters,
             temporal_axes: ref unresolved_temporal_axes,
+            ref order_by,
+            limit,
+            ref cursor,
         } = *query;
 
+        if !order_by.is_empty() || limit.is_some() || cursor.is_some() {
+            return Err(Report::new(QueryError)
+                .attach_printable("sorting and pagination are only supported for entities"));
+        }
+        if !traversal_filters.is_empty() {
+            return Err(Report::new(QueryError)
+                .attach_printable("traversal filters are only supported for entities"));
+        }
+
         let temporal_axes = unresolved_temporal_axes.clone().resolve();
         let time_axis = temporal_axes.variable_time_axis();
 
@@ -309,41 +397,74 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
             //   see https://linear.app/hash/issue/H-297
             let mut visited_ontology_ids = HashSet::new();
 
-            subgraph.vertices.entity_types =
+            let entity_types =
                 Read::<EntityTypeWithMetadata>::read_vec(self, filter, Some(&temporal_axes))
-                    .await?
-                    .into_iter()
-                    .filter_map(|entity_type| {
-                        // The records are already sorted by time, so we can just take the first
-                        // one
-                        visited_ontology_ids
-                            .insert(entity_type.vertex_id(time_axis))
-                            .then(|| (entity_type.vertex_id(time_axis), entity_type))
-                    })
-                    .collect();
+                    .await?;
+            let (viewable_entity_types, _) = authorization_api
+                .can_view_entity_types(
+                    actor_id,
+                    entity_types
+                        .iter()
+                        .map(|entity_type| EntityTypeId::from_url(entity_type.schema.id())),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(QueryError)?;
+
+            subgraph.vertices.entity_types = entity_types
+                .into_iter()
+                .filter(|entity_type| {
+                    viewable_entity_types[&EntityTypeId::from_url(entity_type.schema.id())]
+                })
+                .filter_map(|entity_type| {
+                    // The records are already sorted by time, so we can just take the first
+                    // one
+                    visited_ontology_ids
+                        .insert(entity_type.vertex_id(time_axis))
+                        .then(|| (entity_type.vertex_id(time_axis), entity_type))
+                })
+                .collect();
             for vertex_id in subgraph.vertices.entity_types.keys() {
-                subgraph.roots.insert(vertex_id.clone().into());
+                subgraph.roots.push(vertex_id.clone().into());
             }
         } else {
             let mut traversal_context = TraversalContext::default();
-            let traversal_data = self
+            // The same vertex may be returned multiple times, but it must only be a root once
+            let mut root_ids = HashSet::new();
+            let entity_type_ids = self
                 .read_ontology_ids::<EntityTypeWithMetadata>(filter, Some(&temporal_axes))
                 .await?
-                .map_ok(|(vertex_id, ontology_id)| {
-                    subgraph.roots.insert(vertex_id.into());
-                    stream::iter(
-                        traversal_context
-                            .add_entity_type_id(
-                                ontology_id,
-                                graph_resolve_depths,
-                                temporal_axes.variable_interval(),
-                            )
-                            .map(Ok::<_, Report<QueryError>>),
-                    )
-                })
-                .try_flatten()
                 .try_collect::<Vec<_>>()
                 .await?;
+            // Only the queried types are checked, the types they depend on are required to use
+            // them, so they are always resolved.
+            let (viewable_entity_types, _) = authorization_api
+                .can_view_entity_types(
+                    actor_id,
+                    entity_type_ids
+                        .iter()
+                        .map(|(vertex_id, _)| EntityTypeId::from_base_url(&vertex_id.base_id)),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(QueryError)?;
+
+            let traversal_data = entity_type_ids
+                .into_iter()
+                .filter(|(vertex_id, _)| {
+                    viewable_entity_types[&EntityTypeId::from_base_url(&vertex_id.base_id)]
+                })
+                .flat_map(|(vertex_id, ontology_id)| {
+                    if root_ids.insert(vertex_id.clone()) {
+                        subgraph.roots.push(vertex_id.into());
+                    }
+                    traversal_context.add_entity_type_id(
+                        ontology_id,
+                        graph_resolve_depths,
+                        temporal_axes.variable_interval(),
+                    )
+                })
+                .collect::<Vec<_>>();
 
             self.traverse_entity_types(traversal_data, &mut traversal_context, &mut subgraph)
                 .await?;
@@ -356,14 +477,35 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
         Ok(subgraph)
     }
 
-    #[tracing::instrument(level = "info", skip(self, entity_type, _authorization_api))]
-    async fn update_entity_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
+    async fn aggregate_entity_types<A: AuthorizationApi + Sync>(
+        &self,
+        _actor_id: AccountId,
+        _authorization_api: &A,
+        query: &AggregationQuery<EntityTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError> {
+        self.aggregate_records(query, None).await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, entity_type, authorization_api))]
+    async fn update_entity_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         entity_type: EntityType,
         label_property: Option<BaseUrl>,
     ) -> Result<EntityTypeMetadata, UpdateError> {
+        authorization_api
+            .can_update_entity_type(
+                actor_id,
+                EntityTypeId::from_url(entity_type.id()),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         let transaction = self.transaction().await.change_context(UpdateError)?;
 
         let url = entity_type.id();
@@ -411,25 +553,58 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
         ))
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
-    async fn archive_entity_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn archive_entity_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        authorization_api
+            .can_archive_entity_type(
+                actor_id,
+                EntityTypeId::from_url(id),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         self.archive_ontology_type(id, RecordArchivedById::new(actor_id))
             .await
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
-    async fn unarchive_entity_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn unarchive_entity_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        authorization_api
+            .can_archive_entity_type(
+                actor_id,
+                EntityTypeId::from_url(id),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         self.unarchive_ontology_type(id, RecordCreatedById::new(actor_id))
             .await
     }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn get_entity_type_dependents<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError> {
+        self.read_ontology_type_dependents::<EntityType, _>(actor_id, authorization_api, id)
+            .await
+    }
 }
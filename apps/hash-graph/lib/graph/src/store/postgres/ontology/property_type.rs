@@ -1,14 +1,17 @@
 use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
-use authorization::AuthorizationApi;
+use authorization::{
+    backend::ModifyRelationError, schema::PropertyTypeRelation, zanzibar::Consistency,
+    AuthorizationApi, VisibilityScope,
+};
 use error_stack::{Report, Result, ResultExt};
-use futures::{stream, TryStreamExt};
+use futures::TryStreamExt;
 use graph_types::{
     account::AccountId,
     ontology::{
-        OntologyElementMetadata, OntologyTemporalMetadata, PartialOntologyElementMetadata,
-        PropertyTypeWithMetadata,
+        OntologyElementMetadata, OntologyTemporalMetadata, PartialCustomOntologyMetadata,
+        PartialOntologyElementMetadata, PropertyTypeId, PropertyTypeWithMetadata,
     },
     provenance::{ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
@@ -25,8 +28,9 @@ use crate::{
             query::ReferenceTable,
             TraversalContext,
         },
-        AsClient, ConflictBehavior, InsertionError, PostgresStore, PropertyTypeStore, QueryError,
-        Record, UpdateError,
+        query::{AggregationQuery, AggregationResult},
+        AsClient, ConflictBehavior, InsertionError, OntologyTypeDependents, PostgresStore,
+        PropertyTypeStore, QueryError, Record, UpdateError,
     },
     subgraph::{
         edges::{EdgeDirection, GraphResolveDepths, OntologyEdgeKind},
@@ -176,13 +180,60 @@ impl<C: AsClient> PostgresStore<C> {
     }
 }
 
+/// Returns the relations of a newly created [`PropertyType`].
+///
+/// The owner is granted access to the type, external types don't have an owner. Ontology types are
+/// viewable by everyone unless the public viewer is removed again.
+pub(crate) fn property_type_relations(
+    property_type_id: PropertyTypeId,
+    owner: VisibilityScope,
+) -> impl Iterator<Item = (PropertyTypeId, PropertyTypeRelation, VisibilityScope)> {
+    (owner != VisibilityScope::Public)
+        .then_some((property_type_id, PropertyTypeRelation::DirectOwner, owner))
+        .into_iter()
+        .chain([(
+            property_type_id,
+            PropertyTypeRelation::DirectViewer,
+            VisibilityScope::Public,
+        )])
+}
+
+/// Writes the [`property_type_relations`] of a newly created [`PropertyType`].
+pub(crate) async fn add_property_type_relations<A: AuthorizationApi + Send>(
+    authorization_api: &mut A,
+    property_type_id: PropertyTypeId,
+    owner: VisibilityScope,
+) -> Result<(), ModifyRelationError> {
+    authorization_api
+        .touch_property_type_relations(property_type_relations(property_type_id, owner))
+        .await?;
+    Ok(())
+}
+
+/// Reverts [`add_property_type_relations`].
+pub(crate) async fn remove_property_type_relations<A: AuthorizationApi + Send>(
+    authorization_api: &mut A,
+    property_type_id: PropertyTypeId,
+    owner: VisibilityScope,
+) -> Result<(), ModifyRelationError> {
+    if owner != VisibilityScope::Public {
+        authorization_api
+            .remove_property_type_owner(owner, property_type_id)
+            .await?;
+    }
+    authorization_api
+        .remove_property_type_viewer(VisibilityScope::Public, property_type_id)
+        .await?;
+    Ok(())
+}
+
 #[async_trait]
 impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
-    #[tracing::instrument(level = "info", skip(self, property_types, _authorization_api))]
-    async fn create_property_types<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, property_types, authorization_api))]
+    async fn create_property_types<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         property_types: impl IntoIterator<
             Item = (PropertyType, PartialOntologyElementMetadata),
             IntoIter: Send,
@@ -200,8 +251,18 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
         let mut inserted_property_types = Vec::new();
         let mut inserted_property_type_metadata =
             Vec::with_capacity(inserted_property_types.capacity());
+        let mut inserted_property_type_owners = Vec::new();
         for (schema, metadata) in property_types {
-            if let Some((ontology_id, transaction_time, _visibility_scope)) = transaction
+            if let PartialCustomOntologyMetadata::Owned { owned_by_id } = metadata.custom {
+                authorization_api
+                    .can_create_property_type(actor_id, owned_by_id, Consistency::FullyConsistent)
+                    .await
+                    .change_context(InsertionError)?
+                    .assert_permission()
+                    .change_context(InsertionError)?;
+            }
+
+            if let Some((ontology_id, transaction_time, owner)) = transaction
                 .create_ontology_metadata(
                     provenance.record_created_by_id,
                     &metadata.record_id,
@@ -210,6 +271,10 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
                 )
                 .await?
             {
+                if let Some(owner) = owner {
+                    inserted_property_type_owners
+                        .push((PropertyTypeId::from_url(schema.id()), owner));
+                }
                 transaction
                     .insert_with_id(ontology_id, schema.clone())
                     .await?;
@@ -220,16 +285,6 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
                     provenance,
                     transaction_time,
                 ));
-
-                // TODO: Insert permission for property type, something like
-                //       ```
-                //       authorization_api.grant_permission(
-                //           visibility_scope,
-                //           Relation::Admin,
-                //           ontology_id
-                //       )
-                //       ```
-                //       Make sure this will revoke the permission if the transaction fails.
             }
         }
 
@@ -247,24 +302,57 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
                 .attach_lazy(|| schema.clone())?;
         }
 
-        transaction.commit().await.change_context(InsertionError)?;
+        for &(property_type_id, owner) in &inserted_property_type_owners {
+            add_property_type_relations(authorization_api, property_type_id, owner)
+                .await
+                .change_context(InsertionError)?;
+        }
 
-        Ok(inserted_property_type_metadata)
+        if let Err(mut error) = transaction.commit().await.change_context(InsertionError) {
+            for &(property_type_id, owner) in &inserted_property_type_owners {
+                if let Err(auth_error) =
+                    remove_property_type_relations(authorization_api, property_type_id, owner)
+                        .await
+                        .change_context(InsertionError)
+                {
+                    // TODO: Use `add_child`
+                    //   see https://linear.app/hash/issue/GEN-105/add-ability-to-add-child-errors
+                    error.extend_one(auth_error);
+                }
+            }
+
+            Err(error)
+        } else {
+            Ok(inserted_property_type_metadata)
+        }
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
     async fn get_property_type<A: AuthorizationApi + Sync>(
         &self,
-        _actor_id: AccountId,
-        _authorization_api: &A,
+        actor_id: AccountId,
+        authorization_api: &A,
         query: &StructuralQuery<PropertyTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError> {
         let StructuralQuery {
             ref filter,
             graph_resolve_depths,
+            ref traversal_filters,
             temporal_axes: ref unresolved_temporal_axes,
+            ref order_by,
+            limit,
+            ref cursor,
         } = *query;
 
+        if !order_by.is_empty() || limit.is_some() || cursor.is_some() {
+            return Err(Report::new(QueryError)
+                .attach_printable("sorting and pagination are only supported for entities"));
+        }
+        if !traversal_filters.is_empty() {
+            return Err(Report::new(QueryError)
+                .attach_printable("traversal filters are only supported for entities"));
+        }
+
         let temporal_axes = unresolved_temporal_axes.clone().resolve();
         let time_axis = temporal_axes.variable_time_axis();
 
@@ -279,41 +367,74 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
             //   see https://linear.app/hash/issue/H-297
             let mut visited_ontology_ids = HashSet::new();
 
-            subgraph.vertices.property_types =
+            let property_types =
                 Read::<PropertyTypeWithMetadata>::read_vec(self, filter, Some(&temporal_axes))
-                    .await?
-                    .into_iter()
-                    .filter_map(|property_type| {
-                        // The records are already sorted by time, so we can just take the first
-                        // one
-                        visited_ontology_ids
-                            .insert(property_type.vertex_id(time_axis))
-                            .then(|| (property_type.vertex_id(time_axis), property_type))
-                    })
-                    .collect();
+                    .await?;
+            let (viewable_property_types, _) = authorization_api
+                .can_view_property_types(
+                    actor_id,
+                    property_types
+                        .iter()
+                        .map(|property_type| PropertyTypeId::from_url(property_type.schema.id())),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(QueryError)?;
+
+            subgraph.vertices.property_types = property_types
+                .into_iter()
+                .filter(|property_type| {
+                    viewable_property_types[&PropertyTypeId::from_url(property_type.schema.id())]
+                })
+                .filter_map(|property_type| {
+                    // The records are already sorted by time, so we can just take the first
+                    // one
+                    visited_ontology_ids
+                        .insert(property_type.vertex_id(time_axis))
+                        .then(|| (property_type.vertex_id(time_axis), property_type))
+                })
+                .collect();
             for vertex_id in subgraph.vertices.property_types.keys() {
-                subgraph.roots.insert(vertex_id.clone().into());
+                subgraph.roots.push(vertex_id.clone().into());
             }
         } else {
             let mut traversal_context = TraversalContext::default();
-            let traversal_data = self
+            // The same vertex may be returned multiple times, but it must only be a root once
+            let mut root_ids = HashSet::new();
+            let property_type_ids = self
                 .read_ontology_ids::<PropertyTypeWithMetadata>(filter, Some(&temporal_axes))
                 .await?
-                .map_ok(|(vertex_id, ontology_id)| {
-                    subgraph.roots.insert(vertex_id.into());
-                    stream::iter(
-                        traversal_context
-                            .add_property_type_id(
-                                ontology_id,
-                                graph_resolve_depths,
-                                temporal_axes.variable_interval(),
-                            )
-                            .map(Ok::<_, Report<QueryError>>),
-                    )
-                })
-                .try_flatten()
                 .try_collect::<Vec<_>>()
                 .await?;
+            // Only the queried types are checked, the types they depend on are required to use
+            // them, so they are always resolved.
+            let (viewable_property_types, _) = authorization_api
+                .can_view_property_types(
+                    actor_id,
+                    property_type_ids
+                        .iter()
+                        .map(|(vertex_id, _)| PropertyTypeId::from_base_url(&vertex_id.base_id)),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(QueryError)?;
+
+            let traversal_data = property_type_ids
+                .into_iter()
+                .filter(|(vertex_id, _)| {
+                    viewable_property_types[&PropertyTypeId::from_base_url(&vertex_id.base_id)]
+                })
+                .flat_map(|(vertex_id, ontology_id)| {
+                    if root_ids.insert(vertex_id.clone()) {
+                        subgraph.roots.push(vertex_id.into());
+                    }
+                    traversal_context.add_property_type_id(
+                        ontology_id,
+                        graph_resolve_depths,
+                        temporal_axes.variable_interval(),
+                    )
+                })
+                .collect::<Vec<_>>();
 
             self.traverse_property_types(traversal_data, &mut traversal_context, &mut subgraph)
                 .await?;
@@ -326,13 +447,34 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
         Ok(subgraph)
     }
 
-    #[tracing::instrument(level = "info", skip(self, property_type, _authorization_api))]
-    async fn update_property_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
+    async fn aggregate_property_types<A: AuthorizationApi + Sync>(
+        &self,
+        _actor_id: AccountId,
+        _authorization_api: &A,
+        query: &AggregationQuery<PropertyTypeWithMetadata>,
+    ) -> Result<Vec<AggregationResult>, QueryError> {
+        self.aggregate_records(query, None).await
+    }
+
+    #[tracing::instrument(level = "info", skip(self, property_type, authorization_api))]
+    async fn update_property_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         property_type: PropertyType,
     ) -> Result<OntologyElementMetadata, UpdateError> {
+        authorization_api
+            .can_update_property_type(
+                actor_id,
+                PropertyTypeId::from_url(property_type.id()),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         let transaction = self.transaction().await.change_context(UpdateError)?;
 
         // This clone is currently necessary because we extract the references as we insert them.
@@ -359,25 +501,58 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
         Ok(metadata)
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
-    async fn archive_property_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn archive_property_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        authorization_api
+            .can_archive_property_type(
+                actor_id,
+                PropertyTypeId::from_url(id),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         self.archive_ontology_type(id, RecordArchivedById::new(actor_id))
             .await
     }
 
-    #[tracing::instrument(level = "info", skip(self, _authorization_api))]
-    async fn unarchive_property_type<A: AuthorizationApi + Sync>(
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn unarchive_property_type<A: AuthorizationApi + Send + Sync>(
         &mut self,
         actor_id: AccountId,
-        _authorization_api: &mut A,
+        authorization_api: &mut A,
         id: &VersionedUrl,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
+        authorization_api
+            .can_archive_property_type(
+                actor_id,
+                PropertyTypeId::from_url(id),
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(UpdateError)?
+            .assert_permission()
+            .change_context(UpdateError)?;
+
         self.unarchive_ontology_type(id, RecordCreatedById::new(actor_id))
             .await
     }
+
+    #[tracing::instrument(level = "info", skip(self, authorization_api))]
+    async fn get_property_type_dependents<A: AuthorizationApi + Sync>(
+        &self,
+        actor_id: AccountId,
+        authorization_api: &A,
+        id: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError> {
+        self.read_ontology_type_dependents::<PropertyType, _>(actor_id, authorization_api, id)
+            .await
+    }
 }
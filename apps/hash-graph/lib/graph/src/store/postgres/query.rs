@@ -31,6 +31,9 @@ use crate::store::{postgres::query::table::Relation, Record};
 pub trait PostgresRecord: Record {
     /// The [`Table`] used for this `Query`.
     fn base_table() -> Table;
+
+    /// The paths, which uniquely identify a record at a point in time.
+    fn identifying_paths() -> &'static [Self::QueryPath<'static>];
 }
 
 /// An absolute path inside of a query pointing to an attribute.
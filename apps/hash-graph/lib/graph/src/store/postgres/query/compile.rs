@@ -1,4 +1,9 @@
-use std::{collections::HashSet, fmt::Display, marker::PhantomData};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    marker::PhantomData,
+};
 
 use postgres_types::ToSql;
 use temporal_versioning::TimeAxis;
@@ -8,12 +13,15 @@ use crate::{
     store::{
         postgres::query::{
             table::{EntityTemporalMetadata, OntologyIds, OntologyTemporalMetadata},
-            Alias, AliasedColumn, AliasedTable, Column, Condition, Distinctness, EqualityOperator,
-            Expression, Function, JoinExpression, OrderByExpression, Ordering, PostgresQueryPath,
-            PostgresRecord, SelectExpression, SelectStatement, Table, Transpile, WhereExpression,
-            WindowStatement, WithExpression,
+            Alias, AliasedColumn, AliasedTable, Column, Condition, Constant, Distinctness,
+            EqualityOperator, Expression, Function, JoinExpression, OrderByExpression, Ordering,
+            PostgresQueryPath, PostgresRecord, SelectExpression, SelectStatement, SortingStatement,
+            Table, Transpile, WhereExpression, WindowStatement, WithExpression,
+        },
+        query::{
+            Aggregation, Filter, FilterExpression, NullOrdering, Parameter, ParameterList,
+            ParameterType, Sorting, SortingDirection, SortingPath,
         },
-        query::{Filter, FilterExpression, Parameter, ParameterList, ParameterType},
     },
     subgraph::temporal_axes::QueryTemporalAxes,
 };
@@ -36,8 +44,26 @@ pub struct CompilerArtifacts<'p> {
     temporal_tables: TemporalTableInfo,
 }
 
+/// A key the distinct rows are sorted by in the [`SortingStatement`].
+struct SortKey {
+    alias: Cow<'static, str>,
+    ordering: Ordering,
+    nulls: Option<NullOrdering>,
+}
+
+/// A value of a cursor, which is compared against the value of a row.
+struct CursorKey {
+    expression: Expression,
+    value: Option<Expression>,
+    ordering: Ordering,
+    nulls: Option<NullOrdering>,
+}
+
 pub struct SelectCompiler<'p, T> {
     statement: SelectStatement,
+    sorting: Option<SortingStatement>,
+    sort_keys: Vec<SortKey>,
+    distinct_aliases: HashMap<AliasedColumn, Cow<'static, str>>,
     artifacts: CompilerArtifacts<'p>,
     temporal_axes: Option<&'p QueryTemporalAxes>,
     _marker: PhantomData<fn(*const T)>,
@@ -58,8 +84,13 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
                 }),
                 joins: Vec::new(),
                 where_expression: WhereExpression::default(),
+                group_by: Vec::new(),
                 order_by_expression: OrderByExpression::default(),
+                limit: None,
             },
+            sorting: None,
+            sort_keys: Vec::new(),
+            distinct_aliases: HashMap::new(),
             artifacts: CompilerArtifacts {
                 parameters: Vec::new(),
                 condition_index: 0,
@@ -239,8 +270,35 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
         R::QueryPath<'q>: PostgresQueryPath,
     {
         let column = self.compile_path_column(path);
+        if let Some(sorting) = &mut self.sorting {
+            // The distinct rows are sorted in the outer query, which refers to the column by its
+            // alias. The distinct columns have to precede the sort keys in the subquery.
+            let alias = Cow::Owned(format!("distinct_{}", self.distinct_aliases.len()));
+            if distinctness == Distinctness::Distinct {
+                self.statement.distinct.push(Expression::Column(column));
+                self.statement.order_by_expression.insert(
+                    self.statement.distinct.len() - 1,
+                    column,
+                    ordering.unwrap_or(Ordering::Ascending),
+                );
+            }
+            if let Some(ordering) = ordering {
+                sorting.order_by_expression.push_expression(
+                    Expression::Alias(alias.clone()),
+                    ordering,
+                    None,
+                );
+            }
+            self.distinct_aliases.insert(column, alias.clone());
+            self.statement.selects.push(SelectExpression::with_alias(
+                Expression::Column(column),
+                alias,
+            ));
+            return self.statement.selects.len() - 1;
+        }
+
         if distinctness == Distinctness::Distinct {
-            self.statement.distinct.push(column);
+            self.statement.distinct.push(Expression::Column(column));
         }
         if let Some(ordering) = ordering {
             self.statement.order_by_expression.push(column, ordering);
@@ -251,6 +309,176 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
         self.statement.selects.len() - 1
     }
 
+    /// Sorts the selection by the key of the passed [`Sorting`].
+    ///
+    /// As `DISTINCT ON` requires the leftmost `ORDER BY` expressions to match the distinct
+    /// expressions, the distinct rows are sorted in an outer query. If the path refers to multiple
+    /// values of a row, the first value in the sorting order is used. JSON `null` values are
+    /// sorted like SQL `NULL` values.
+    ///
+    /// When sorting by [`SortingPath::SearchRank`], the records are ranked for the
+    /// [`Filter::Search`]es contained in `filter`. If `filter` does not contain a search, all
+    /// records have the same rank. The rank is only used to sort the distinct rows and does not
+    /// affect which row is picked for a record.
+    ///
+    /// Returns the index of the sorted value, which is selected as JSON, so it can be used in a
+    /// cursor. Sortings take precedence over orderings added afterwards and have to be added before
+    /// any distinct selection is added.
+    pub fn add_sorting<'q, 'f: 'p>(
+        &mut self,
+        sorting: &'p Sorting<'q, R>,
+        filter: &'p Filter<'f, R>,
+    ) -> usize
+    where
+        R::QueryPath<'q>: PostgresQueryPath,
+        R::QueryPath<'f>: PostgresQueryPath,
+    {
+        match &sorting.path {
+            SortingPath::Path(path) => {
+                let column = self.compile_path_column(path);
+                let expression = if column.column.parameter_type() == ParameterType::Any {
+                    Expression::Function(Function::NullIf(
+                        Box::new(Expression::Column(column)),
+                        Box::new(Expression::Constant(Constant::String("null"))),
+                    ))
+                } else {
+                    Expression::Column(column)
+                };
+                self.add_sort_key(expression, sorting.direction, sorting.nulls, true)
+            }
+            SortingPath::SearchRank => {
+                let rank = self
+                    .compile_search_rank(filter)
+                    .unwrap_or(Expression::Constant(Constant::UnsignedInteger(0)));
+                self.add_sort_key(rank, sorting.direction, sorting.nulls, false)
+            }
+        }
+    }
+
+    /// Adds `expression` as sort key of the distinct rows.
+    ///
+    /// If `pick_distinct_row` is set, the expression is also used to pick the row of each record
+    /// in the `DISTINCT ON` statement.
+    fn add_sort_key(
+        &mut self,
+        expression: Expression,
+        direction: SortingDirection,
+        nulls: Option<NullOrdering>,
+        pick_distinct_row: bool,
+    ) -> usize {
+        let ordering = match direction {
+            SortingDirection::Ascending => Ordering::Ascending,
+            SortingDirection::Descending => Ordering::Descending,
+        };
+        let alias = Cow::Owned(format!("sort_key_{}", self.sort_keys.len()));
+
+        if pick_distinct_row {
+            self.statement
+                .order_by_expression
+                .push_expression(expression.clone(), ordering, nulls);
+        }
+        self.sorting
+            .get_or_insert_with(SortingStatement::default)
+            .order_by_expression
+            .push_expression(Expression::Alias(alias.clone()), ordering, nulls);
+        self.sort_keys.push(SortKey {
+            alias: alias.clone(),
+            ordering,
+            nulls,
+        });
+
+        self.statement.selects.push(SelectExpression::new(
+            Expression::Function(Function::ToJson(Box::new(expression.clone()))),
+            None,
+        ));
+        self.statement
+            .selects
+            .push(SelectExpression::with_alias(expression, alias));
+        self.statement.selects.len() - 2
+    }
+
+    /// Selects the rank of the records for the [`Filter::Search`]es contained in `filter`.
+    ///
+    /// Returns `None` if `filter` does not contain a search. Otherwise, the selected rank is a
+    /// `real`.
+    pub fn add_search_rank_selection<'f: 'p>(
+        &mut self,
+        filter: &'p Filter<'f, R>,
+    ) -> Option<impl RowIndex + Display + Copy>
+    where
+        R::QueryPath<'f>: PostgresQueryPath,
+    {
+        let rank = self.compile_search_rank(filter)?;
+        self.statement
+            .selects
+            .push(SelectExpression::new(rank, None));
+        Some(self.statement.selects.len() - 1)
+    }
+
+    /// Selects the result of the [`Aggregation`] over all rows of a group as JSON value.
+    ///
+    /// If a path refers to a time interval, the lower bound of the interval is aggregated.
+    pub fn add_aggregation<'q>(
+        &mut self,
+        aggregation: &'p Aggregation<'q, R>,
+    ) -> impl RowIndex + Display + Copy
+    where
+        R::QueryPath<'q>: PostgresQueryPath,
+        R::QueryPath<'static>: PostgresQueryPath,
+    {
+        let function = match aggregation {
+            Aggregation::Count => Function::CountDistinct(
+                R::identifying_paths()
+                    .iter()
+                    .map(|path| Expression::Column(self.compile_path_column(path)))
+                    .collect(),
+            ),
+            Aggregation::CountDistinct(path) => {
+                let column = self.compile_path_column(path);
+                Function::CountDistinct(vec![Self::lower_bound_expression(column)])
+            }
+            Aggregation::Min(path) => {
+                let column = self.compile_path_column(path);
+                Function::Min(Box::new(Self::lower_bound_expression(column)))
+            }
+            Aggregation::Max(path) => {
+                let column = self.compile_path_column(path);
+                Function::Max(Box::new(Self::lower_bound_expression(column)))
+            }
+        };
+
+        self.statement.selects.push(SelectExpression::new(
+            Expression::Function(Function::ToJson(Box::new(Expression::Function(function)))),
+            None,
+        ));
+        self.statement.selects.len() - 1
+    }
+
+    /// Groups the selection by the value at the specified path and selects the value as JSON.
+    pub fn add_group_by<'q>(&mut self, path: &'p R::QueryPath<'q>) -> impl RowIndex + Display + Copy
+    where
+        R::QueryPath<'q>: PostgresQueryPath,
+    {
+        let column = self.compile_path_column(path);
+        self.statement.group_by.push(column);
+        self.statement
+            .order_by_expression
+            .push(column, Ordering::Ascending);
+        self.statement.selects.push(SelectExpression::new(
+            Expression::Function(Function::ToJson(Box::new(Expression::Column(column)))),
+            None,
+        ));
+        self.statement.selects.len() - 1
+    }
+
+    fn lower_bound_expression(column: AliasedColumn) -> Expression {
+        if column.column.parameter_type() == ParameterType::TimeInterval {
+            Expression::Function(Function::Lower(Box::new(Expression::Column(column))))
+        } else {
+            Expression::Column(column)
+        }
+    }
+
     /// Adds a new filter to the selection.
     pub fn add_filter<'f: 'p>(&mut self, filter: &'p Filter<'f, R>)
     where
@@ -261,12 +489,120 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
         self.statement.where_expression.add_condition(condition);
     }
 
+    /// Restricts the selection to rows, which are ordered strictly after the `cursor`.
+    ///
+    /// If sortings were added, `sort_key` contains the values of the sort keys of the last row
+    /// read, where `None` represents `NULL`. `record_id` is a list of paths and the values of the
+    /// last row read. Rows are compared lexicographically, first by the sort keys and then in the
+    /// order of the list, so the selection should be ordered by the same paths. If a path refers to
+    /// a time interval, the lower bound of the interval is compared.
+    pub fn add_cursor<'q, 'c: 'p>(
+        &mut self,
+        sort_key: &'p [Option<Parameter<'c>>],
+        record_id: &[(&'p R::QueryPath<'q>, &'p (dyn ToSql + Sync))],
+    ) where
+        R::QueryPath<'q>: PostgresQueryPath,
+    {
+        let mut keys = self
+            .sort_keys
+            .iter()
+            .map(|sort_key| (sort_key.alias.clone(), sort_key.ordering, sort_key.nulls))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .zip(sort_key)
+            .map(|((alias, ordering, nulls), value)| CursorKey {
+                expression: Expression::Alias(alias),
+                value: value
+                    .as_ref()
+                    .map(|parameter| self.compile_parameter(parameter).0),
+                ordering,
+                nulls: Some(nulls.unwrap_or(match ordering {
+                    Ordering::Ascending => NullOrdering::Last,
+                    Ordering::Descending => NullOrdering::First,
+                })),
+            })
+            .collect::<Vec<_>>();
+
+        for &(path, parameter) in record_id {
+            let column = self.compile_path_column(path);
+            let expression = match self.distinct_aliases.get(&column) {
+                Some(alias) if column.column.parameter_type() == ParameterType::TimeInterval => {
+                    Expression::Function(Function::Lower(Box::new(Expression::Alias(
+                        alias.clone(),
+                    ))))
+                }
+                Some(alias) => Expression::Alias(alias.clone()),
+                None => Self::lower_bound_expression(column),
+            };
+            self.artifacts.parameters.push(parameter);
+            keys.push(CursorKey {
+                expression,
+                value: Some(Expression::Parameter(self.artifacts.parameters.len())),
+                ordering: Ordering::Ascending,
+                nulls: None,
+            });
+        }
+
+        // (a, b, c) > (x, y, z) <=> a > x OR (a = x AND b > y) OR (a = x AND b = y AND c > z)
+        let condition = Condition::Any(
+            keys.iter()
+                .enumerate()
+                .map(|(idx, key)| {
+                    Condition::All(
+                        keys[..idx]
+                            .iter()
+                            .map(|key| {
+                                Condition::Equal(Some(key.expression.clone()), key.value.clone())
+                            })
+                            .chain([Self::compile_cursor_key(key)])
+                            .collect(),
+                    )
+                })
+                .collect(),
+        );
+        match &mut self.sorting {
+            Some(sorting) => sorting.where_expression.add_condition(condition),
+            None => self.statement.where_expression.add_condition(condition),
+        }
+    }
+
+    /// Compiles a condition, which is true if the row is ordered strictly after the cursor value.
+    ///
+    /// `nulls` is only specified for sort keys, which may be `NULL`.
+    fn compile_cursor_key(key: &CursorKey) -> Condition {
+        let expression = key.expression.clone();
+        match (&key.value, key.nulls) {
+            (Some(value), nulls) => {
+                let after = match key.ordering {
+                    Ordering::Ascending => Condition::Greater(expression.clone(), value.clone()),
+                    Ordering::Descending => Condition::Less(expression.clone(), value.clone()),
+                };
+                if nulls == Some(NullOrdering::Last) {
+                    Condition::Any(vec![after, Condition::Equal(Some(expression), None)])
+                } else {
+                    after
+                }
+            }
+            (None, Some(NullOrdering::First)) => Condition::NotEqual(Some(expression), None),
+            (None, _) => Condition::Any(Vec::new()),
+        }
+    }
+
+    /// Limits the number of rows returned by the statement.
+    pub fn set_limit(&mut self, limit: usize) {
+        match &mut self.sorting {
+            Some(sorting) => sorting.limit = Some(limit),
+            None => self.statement.limit = Some(limit),
+        }
+    }
+
     /// Transpiles the statement into SQL and the parameter to be passed to a prepared statement.
     pub fn compile(&self) -> (String, &[&'p (dyn ToSql + Sync)]) {
-        (
-            self.statement.transpile_to_string(),
-            &self.artifacts.parameters,
-        )
+        let statement = match &self.sorting {
+            Some(sorting) => sorting.transpile_with_subquery(&self.statement),
+            None => self.statement.transpile_to_string(),
+        };
+        (statement, &self.artifacts.parameters)
     }
 
     /// Compiles a [`Filter`] to a `Condition`.
@@ -292,18 +628,46 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
                     .collect(),
             ),
             Filter::Not(filter) => Condition::Not(Box::new(self.compile_filter(filter))),
+            Filter::Equal(Some(lhs), Some(rhs)) => {
+                let [lhs, rhs] = self.compile_equality([lhs, rhs]);
+                Condition::Equal(Some(lhs), Some(rhs))
+            }
             Filter::Equal(lhs, rhs) => Condition::Equal(
                 lhs.as_ref()
                     .map(|expression| self.compile_filter_expression(expression).0),
                 rhs.as_ref()
                     .map(|expression| self.compile_filter_expression(expression).0),
             ),
+            Filter::NotEqual(Some(lhs), Some(rhs)) => {
+                let [lhs, rhs] = self.compile_equality([lhs, rhs]);
+                Condition::NotEqual(Some(lhs), Some(rhs))
+            }
             Filter::NotEqual(lhs, rhs) => Condition::NotEqual(
                 lhs.as_ref()
                     .map(|expression| self.compile_filter_expression(expression).0),
                 rhs.as_ref()
                     .map(|expression| self.compile_filter_expression(expression).0),
             ),
+            Filter::Greater(lhs, rhs) => {
+                let [lhs, rhs] = self.compile_comparison([lhs, rhs]);
+                Condition::Greater(lhs, rhs)
+            }
+            Filter::GreaterOrEqual(lhs, rhs) => {
+                let [lhs, rhs] = self.compile_comparison([lhs, rhs]);
+                Condition::GreaterOrEqual(lhs, rhs)
+            }
+            Filter::Less(lhs, rhs) => {
+                let [lhs, rhs] = self.compile_comparison([lhs, rhs]);
+                Condition::Less(lhs, rhs)
+            }
+            Filter::LessOrEqual(lhs, rhs) => {
+                let [lhs, rhs] = self.compile_comparison([lhs, rhs]);
+                Condition::LessOrEqual(lhs, rhs)
+            }
+            Filter::Between(value, lower, upper) => {
+                let [value, lower, upper] = self.compile_comparison([value, lower, upper]);
+                Condition::Between(value, lower, upper)
+            }
             Filter::In(lhs, rhs) => Condition::In(
                 self.compile_filter_expression(lhs).0,
                 self.compile_parameter_list(rhs).0,
@@ -359,6 +723,74 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
 
                 Condition::ContainsSegment(left_filter, right_filter)
             }
+            Filter::Search(document, query) => {
+                let (vector, query) = self.compile_search_expressions(document, query);
+                Condition::Search(vector, query)
+            }
+        }
+    }
+
+    /// Compiles the expressions of a [`Filter::Search`] to a text search vector and a text search
+    /// query.
+    ///
+    /// Values, which are not JSON, are converted to JSON first, so every searched value is
+    /// converted to a text search vector the same way.
+    fn compile_search_expressions<'f: 'p>(
+        &mut self,
+        document: &'p FilterExpression<'f, R>,
+        query: &'p FilterExpression<'f, R>,
+    ) -> (Expression, Expression)
+    where
+        R::QueryPath<'f>: PostgresQueryPath,
+    {
+        let (document, document_type) = self.compile_filter_expression(document);
+        let document = if document_type == ParameterType::Any {
+            document
+        } else {
+            Expression::Function(Function::ToJson(Box::new(document)))
+        };
+
+        let (query, query_type) = self.compile_filter_expression(query);
+        let query = if query_type == ParameterType::Any {
+            Expression::Function(Function::JsonExtractText(Box::new(query)))
+        } else {
+            query
+        };
+
+        (
+            Expression::Function(Function::JsonToTsVector(Box::new(document))),
+            Expression::Function(Function::WebSearchToTsQuery(Box::new(query))),
+        )
+    }
+
+    /// Compiles the rank of a record for the [`Filter::Search`]es contained in `filter`.
+    ///
+    /// The rank is the highest rank of any search, which is not negated. Returns `None` if `filter`
+    /// does not contain a search.
+    fn compile_search_rank<'f: 'p>(&mut self, filter: &'p Filter<'f, R>) -> Option<Expression>
+    where
+        R::QueryPath<'f>: PostgresQueryPath,
+    {
+        let mut ranks = Vec::new();
+        let mut filters = vec![filter];
+        while let Some(filter) = filters.pop() {
+            match filter {
+                Filter::All(nested) | Filter::Any(nested) => filters.extend(nested.iter().rev()),
+                Filter::Search(document, query) => {
+                    let (vector, query) = self.compile_search_expressions(document, query);
+                    ranks.push(Expression::Function(Function::TsRank(
+                        Box::new(vector),
+                        Box::new(query),
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        match ranks.len() {
+            0 => None,
+            1 => ranks.pop(),
+            _ => Some(Expression::Function(Function::Greatest(ranks))),
         }
     }
 
@@ -404,7 +836,9 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
                 from: version_column.table(),
                 joins: vec![],
                 where_expression: WhereExpression::default(),
+                group_by: Vec::new(),
                 order_by_expression: OrderByExpression::default(),
+                limit: None,
             },
         );
 
@@ -501,41 +935,106 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
                 let parameter_type = column.column.parameter_type();
                 (Expression::Column(column), parameter_type)
             }
-            FilterExpression::Parameter(parameter) => {
-                let parameter_type = match parameter {
-                    Parameter::Number(number) => {
-                        self.artifacts.parameters.push(number);
-                        ParameterType::Number
-                    }
-                    Parameter::Text(text) => {
-                        self.artifacts.parameters.push(text);
-                        ParameterType::Text
-                    }
-                    Parameter::Boolean(bool) => {
-                        self.artifacts.parameters.push(bool);
-                        ParameterType::Boolean
-                    }
-                    Parameter::Any(json) => {
-                        self.artifacts.parameters.push(json);
-                        ParameterType::Any
-                    }
-                    Parameter::Uuid(uuid) => {
-                        self.artifacts.parameters.push(uuid);
-                        ParameterType::Uuid
-                    }
-                    Parameter::OntologyTypeVersion(version) => {
-                        self.artifacts.parameters.push(version);
-                        ParameterType::OntologyTypeVersion
-                    }
-                };
-                (
-                    Expression::Parameter(self.artifacts.parameters.len()),
-                    parameter_type,
-                )
-            }
+            FilterExpression::Parameter(parameter) => self.compile_parameter(parameter),
         }
     }
 
+    fn compile_parameter<'f: 'p>(
+        &mut self,
+        parameter: &'p Parameter<'f>,
+    ) -> (Expression, ParameterType) {
+        let parameter_type = match parameter {
+            Parameter::Integer(number) => {
+                self.artifacts.parameters.push(number);
+                ParameterType::Integer
+            }
+            Parameter::Number(number) => {
+                self.artifacts.parameters.push(number);
+                return (
+                    Expression::Function(Function::TextToNumeric(Box::new(Expression::Parameter(
+                        self.artifacts.parameters.len(),
+                    )))),
+                    ParameterType::Number,
+                );
+            }
+            Parameter::Text(text) => {
+                self.artifacts.parameters.push(text);
+                ParameterType::Text
+            }
+            Parameter::Boolean(bool) => {
+                self.artifacts.parameters.push(bool);
+                ParameterType::Boolean
+            }
+            Parameter::Any(json) => {
+                self.artifacts.parameters.push(json);
+                ParameterType::Any
+            }
+            Parameter::Uuid(uuid) => {
+                self.artifacts.parameters.push(uuid);
+                ParameterType::Uuid
+            }
+            Parameter::OntologyTypeVersion(version) => {
+                self.artifacts.parameters.push(version);
+                ParameterType::OntologyTypeVersion
+            }
+        };
+        (
+            Expression::Parameter(self.artifacts.parameters.len()),
+            parameter_type,
+        )
+    }
+
+    /// Compiles the operands of an equality check.
+    ///
+    /// If a number is compared to a JSON value, the number is converted to `jsonb`, so values of
+    /// other types are still considered unequal instead of being `NULL`. `jsonb` compares numbers
+    /// numerically, so `1` and `1.0` are equal.
+    fn compile_equality<'f: 'p>(
+        &mut self,
+        expressions: [&'p FilterExpression<'f, R>; 2],
+    ) -> [Expression; 2]
+    where
+        R::QueryPath<'f>: PostgresQueryPath,
+    {
+        let compiled = expressions.map(|expression| self.compile_filter_expression(expression));
+        let json = compiled
+            .iter()
+            .any(|(_, parameter_type)| *parameter_type == ParameterType::Any);
+
+        compiled.map(|(expression, parameter_type)| {
+            if json && parameter_type == ParameterType::Number {
+                Expression::Function(Function::ToJson(Box::new(expression)))
+            } else {
+                expression
+            }
+        })
+    }
+
+    /// Compiles the operands of an ordering comparison.
+    ///
+    /// If any operand is a number, JSON operands are cast to `numeric`, so they are compared
+    /// numerically instead of using the `jsonb` ordering.
+    fn compile_comparison<'f: 'p, const N: usize>(
+        &mut self,
+        expressions: [&'p FilterExpression<'f, R>; N],
+    ) -> [Expression; N]
+    where
+        R::QueryPath<'f>: PostgresQueryPath,
+    {
+        let compiled = expressions.map(|expression| self.compile_filter_expression(expression));
+        let numeric = compiled
+            .iter()
+            .any(|(_, parameter_type)| *parameter_type == ParameterType::Number);
+
+        compiled.map(|(expression, parameter_type)| {
+            if numeric && parameter_type == ParameterType::Any {
+                Expression::Function(Function::JsonToNumeric(Box::new(expression)))
+            } else {
+                expression
+            }
+        })
+    }
+
     pub fn compile_parameter_list<'f: 'p>(
         &mut self,
         parameters: &'p ParameterList<'f>,
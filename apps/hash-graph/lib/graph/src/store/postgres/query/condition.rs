@@ -16,12 +16,15 @@ pub enum Condition {
     LessOrEqual(Expression, Expression),
     Greater(Expression, Expression),
     GreaterOrEqual(Expression, Expression),
+    Between(Expression, Expression, Expression),
     In(Expression, Expression),
     TimeIntervalContainsTimestamp(Expression, Expression),
     Overlap(Expression, Expression),
     StartsWith(Expression, Expression),
     EndsWith(Expression, Expression),
     ContainsSegment(Expression, Expression),
+    /// Matches a text search vector against a text search query.
+    Search(Expression, Expression),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -107,6 +110,13 @@ impl Transpile for Condition {
                 fmt.write_str(" >= ")?;
                 rhs.transpile(fmt)
             }
+            Self::Between(value, lower, upper) => {
+                value.transpile(fmt)?;
+                fmt.write_str(" BETWEEN ")?;
+                lower.transpile(fmt)?;
+                fmt.write_str(" AND ")?;
+                upper.transpile(fmt)
+            }
             Self::In(lhs, rhs) => {
                 lhs.transpile(fmt)?;
                 fmt.write_str(" = ANY(")?;
@@ -141,6 +151,11 @@ impl Transpile for Condition {
                 rhs.transpile(fmt)?;
                 fmt.write_str(" || '%'")
             }
+            Self::Search(vector, query) => {
+                vector.transpile(fmt)?;
+                fmt.write_str(" @@ ")?;
+                query.transpile(fmt)
+            }
         }
     }
 }
@@ -149,7 +164,7 @@ impl Transpile for Condition {
 mod tests {
     use std::borrow::Cow;
 
-    use graph_types::ontology::DataTypeWithMetadata;
+    use graph_types::ontology::{DataTypeWithMetadata, OntologyTypeVersion};
     use postgres_types::ToSql;
 
     use crate::{
@@ -256,7 +271,7 @@ mod tests {
                 ),
                 Filter::Equal(
                     Some(FilterExpression::Path(DataTypeQueryPath::Version)),
-                    Some(FilterExpression::Parameter(Parameter::Number(1))),
+                    Some(FilterExpression::Parameter(Parameter::Integer(1))),
                 ),
             ]),
             r#"("ontology_ids_0_1_0"."base_url" = $1) AND ("ontology_ids_0_1_0"."version" = $2)"#,
@@ -290,7 +305,7 @@ mod tests {
                 ),
                 Filter::Equal(
                     Some(FilterExpression::Path(DataTypeQueryPath::Version)),
-                    Some(FilterExpression::Parameter(Parameter::Number(1))),
+                    Some(FilterExpression::Parameter(Parameter::Integer(1))),
                 ),
             ]),
             r#"(("ontology_ids_0_1_0"."base_url" = $1) OR ("ontology_ids_0_1_0"."version" = $2))"#,
@@ -315,6 +330,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transpile_ordering_condition() {
+        test_condition(
+            &Filter::Greater(
+                FilterExpression::Path(DataTypeQueryPath::Version),
+                FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(1),
+                )),
+            ),
+            r#""ontology_ids_0_1_0"."version" > $1"#,
+            &[&OntologyTypeVersion::new(1)],
+        );
+
+        test_condition(
+            &Filter::LessOrEqual(
+                FilterExpression::Path(DataTypeQueryPath::Version),
+                FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(3),
+                )),
+            ),
+            r#""ontology_ids_0_1_0"."version" <= $1"#,
+            &[&OntologyTypeVersion::new(3)],
+        );
+
+        test_condition(
+            &Filter::Between(
+                FilterExpression::Path(DataTypeQueryPath::Version),
+                FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(2),
+                )),
+                FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(5),
+                )),
+            ),
+            r#""ontology_ids_0_1_0"."version" BETWEEN $1 AND $2"#,
+            &[&OntologyTypeVersion::new(2), &OntologyTypeVersion::new(5)],
+        );
+    }
+
+    #[test]
+    fn transpile_search_condition() {
+        test_condition(
+            &Filter::Search(
+                FilterExpression::Path(DataTypeQueryPath::Schema(None)),
+                FilterExpression::Parameter(Parameter::Text(Cow::Borrowed("text"))),
+            ),
+            r#"jsonb_to_tsvector('simple'::regconfig, "data_types_0_1_0"."schema", '["string"]'::jsonb) @@ websearch_to_tsquery('simple'::regconfig, $1)"#,
+            &[&"text"],
+        );
+
+        test_condition(
+            &Filter::Search(
+                FilterExpression::Path(DataTypeQueryPath::Title),
+                FilterExpression::Parameter(Parameter::Text(Cow::Borrowed("text"))),
+            ),
+            r#"jsonb_to_tsvector('simple'::regconfig, to_jsonb("data_types_0_1_0"."schema"->>'title'), '["string"]'::jsonb) @@ websearch_to_tsquery('simple'::regconfig, $1)"#,
+            &[&"text"],
+        );
+    }
+
     #[test]
     fn render_without_parameters() {
         test_condition(
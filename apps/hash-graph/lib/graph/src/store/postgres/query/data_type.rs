@@ -18,6 +18,11 @@ impl PostgresRecord for DataTypeWithMetadata {
     fn base_table() -> Table {
         Table::OntologyTemporalMetadata
     }
+
+    fn identifying_paths() -> &'static [Self::QueryPath<'static>] {
+        static PATHS: [DataTypeQueryPath<'static>; 1] = [DataTypeQueryPath::OntologyId];
+        &PATHS
+    }
 }
 
 impl PostgresQueryPath for DataTypeQueryPath<'_> {
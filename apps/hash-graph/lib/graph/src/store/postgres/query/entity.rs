@@ -18,6 +18,12 @@ impl PostgresRecord for Entity {
     fn base_table() -> Table {
         Table::EntityTemporalMetadata
     }
+
+    fn identifying_paths() -> &'static [Self::QueryPath<'static>] {
+        static PATHS: [EntityQueryPath<'static>; 2] =
+            [EntityQueryPath::OwnedById, EntityQueryPath::Uuid];
+        &PATHS
+    }
 }
 
 impl PostgresQueryPath for EntityQueryPath<'_> {
@@ -32,6 +38,7 @@ impl PostgresQueryPath for EntityQueryPath<'_> {
             | Self::LeftToRightOrder
             | Self::RightToLeftOrder
             | Self::RecordCreatedById
+            | Self::RecordArchivedById
             | Self::Archived => vec![Relation::EntityEditions],
             Self::EntityTypeEdge {
                 edge_kind: SharedEdgeKind::IsOfType,
@@ -95,6 +102,7 @@ impl PostgresQueryPath for EntityQueryPath<'_> {
             Self::Archived => Column::EntityEditions(EntityEditions::Archived),
             Self::OwnedById => Column::EntityTemporalMetadata(EntityTemporalMetadata::OwnedById),
             Self::RecordCreatedById => Column::EntityEditions(EntityEditions::RecordCreatedById),
+            Self::RecordArchivedById => Column::EntityEditions(EntityEditions::RecordArchivedById),
             Self::EntityTypeEdge { path, .. } => path.terminating_column(),
             Self::EntityEdge {
                 edge_kind: KnowledgeGraphEdgeKind::HasLeftEntity,
@@ -18,6 +18,11 @@ impl PostgresRecord for EntityTypeWithMetadata {
     fn base_table() -> Table {
         Table::OntologyTemporalMetadata
     }
+
+    fn identifying_paths() -> &'static [Self::QueryPath<'static>] {
+        static PATHS: [EntityTypeQueryPath<'static>; 1] = [EntityTypeQueryPath::OntologyId];
+        &PATHS
+    }
 }
 
 impl PostgresQueryPath for EntityTypeQueryPath<'_> {
@@ -1,11 +1,16 @@
-use std::fmt::{self, Write};
+use std::{
+    borrow::Cow,
+    fmt::{self, Write},
+};
 
 use crate::store::postgres::query::{AliasedColumn, Transpile, WindowStatement};
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Function {
     Min(Box<Expression>),
     Max(Box<Expression>),
+    CountDistinct(Vec<Expression>),
+    ToJson(Box<Expression>),
     JsonExtractText(Box<Expression>),
     JsonExtractPath(Vec<Expression>),
     JsonContains(Box<Expression>, Box<Expression>),
@@ -14,6 +19,18 @@ pub enum Function {
     Lower(Box<Expression>),
     Upper(Box<Expression>),
     Now,
+    /// Converts all strings in a JSON value to a text search vector.
+    JsonToTsVector(Box<Expression>),
+    /// Parses a text search query written in the web search syntax.
+    WebSearchToTsQuery(Box<Expression>),
+    /// Ranks a text search vector by how well it matches a text search query.
+    TsRank(Box<Expression>, Box<Expression>),
+    Greatest(Vec<Expression>),
+    /// Casts a textual representation of a number to `numeric`.
+    TextToNumeric(Box<Expression>),
+    /// Casts a JSON value to `numeric` if it is a number and `NULL` otherwise.
+    JsonToNumeric(Box<Expression>),
+    NullIf(Box<Expression>, Box<Expression>),
 }
 
 impl Transpile for Function {
@@ -29,6 +46,27 @@ impl Transpile for Function {
                 expression.transpile(fmt)?;
                 fmt.write_char(')')
             }
+            Self::CountDistinct(expressions) => {
+                fmt.write_str("COUNT(DISTINCT ")?;
+                if let [expression] = expressions.as_slice() {
+                    expression.transpile(fmt)?;
+                } else {
+                    fmt.write_char('(')?;
+                    for (i, expression) in expressions.iter().enumerate() {
+                        if i > 0 {
+                            fmt.write_str(", ")?;
+                        }
+                        expression.transpile(fmt)?;
+                    }
+                    fmt.write_char(')')?;
+                }
+                fmt.write_char(')')
+            }
+            Self::ToJson(expression) => {
+                fmt.write_str("to_jsonb(")?;
+                expression.transpile(fmt)?;
+                fmt.write_char(')')
+            }
             Self::JsonExtractPath(paths) => {
                 fmt.write_str("jsonb_extract_path(")?;
                 for (i, expression) in paths.iter().enumerate() {
@@ -84,11 +122,61 @@ impl Transpile for Function {
                 expression.transpile(fmt)?;
                 fmt.write_char(')')
             }
+            // The text search configuration and the JSON filter have to match the expression of
+            // the full-text index on the entity properties, otherwise the index is not used.
+            Self::JsonToTsVector(expression) => {
+                fmt.write_str("jsonb_to_tsvector('simple'::regconfig, ")?;
+                expression.transpile(fmt)?;
+                fmt.write_str(r#", '["string"]'::jsonb)"#)
+            }
+            Self::WebSearchToTsQuery(expression) => {
+                fmt.write_str("websearch_to_tsquery('simple'::regconfig, ")?;
+                expression.transpile(fmt)?;
+                fmt.write_char(')')
+            }
+            Self::TsRank(vector, query) => {
+                fmt.write_str("ts_rank(")?;
+                vector.transpile(fmt)?;
+                fmt.write_str(", ")?;
+                query.transpile(fmt)?;
+                fmt.write_char(')')
+            }
+            Self::Greatest(expressions) => {
+                fmt.write_str("GREATEST(")?;
+                for (i, expression) in expressions.iter().enumerate() {
+                    if i > 0 {
+                        fmt.write_str(", ")?;
+                    }
+                    expression.transpile(fmt)?;
+                }
+                fmt.write_char(')')
+            }
+            Self::TextToNumeric(expression) => {
+                fmt.write_str("((")?;
+                expression.transpile(fmt)?;
+                fmt.write_str(")::text::numeric)")
+            }
+            // Casting a JSON value, which is not a number, to `numeric` raises an error, so the
+            // type has to be checked first.
+            Self::JsonToNumeric(expression) => {
+                fmt.write_str("(CASE WHEN jsonb_typeof(")?;
+                expression.transpile(fmt)?;
+                fmt.write_str(") = 'number' THEN (")?;
+                expression.transpile(fmt)?;
+                fmt.write_str(")::numeric END)")
+            }
+            Self::NullIf(value, null_value) => {
+                fmt.write_str("NULLIF(")?;
+                value.transpile(fmt)?;
+                fmt.write_str(", ")?;
+                null_value.transpile(fmt)?;
+                fmt.write_char(')')
+            }
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Constant {
     Boolean(bool),
     String(&'static str),
@@ -106,7 +194,7 @@ impl Transpile for Constant {
 }
 
 /// A compiled expression in Postgres.
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Expression {
     Asterisk,
     Column(AliasedColumn),
@@ -117,6 +205,8 @@ pub enum Expression {
     Constant(Constant),
     Function(Function),
     Window(Box<Self>, WindowStatement),
+    /// Refers to a selection of a subquery by its alias.
+    Alias(Cow<'static, str>),
 }
 
 impl Transpile for Expression {
@@ -133,6 +223,7 @@ impl Transpile for Expression {
                 window.transpile(fmt)?;
                 fmt.write_char(')')
             }
+            Self::Alias(alias) => write!(fmt, r#""{alias}""#),
         }
     }
 }
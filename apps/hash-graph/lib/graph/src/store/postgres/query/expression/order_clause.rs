@@ -1,6 +1,9 @@
 use std::fmt;
 
-use crate::store::postgres::query::{AliasedColumn, Transpile};
+use crate::store::{
+    postgres::query::{AliasedColumn, Expression, Transpile},
+    query::NullOrdering,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Ordering {
@@ -10,12 +13,29 @@ pub enum Ordering {
 
 #[derive(Debug, Default, PartialEq, Eq, Hash)]
 pub struct OrderByExpression {
-    columns: Vec<(AliasedColumn, Ordering)>,
+    columns: Vec<(Expression, Ordering, Option<NullOrdering>)>,
 }
 
 impl OrderByExpression {
     pub fn push(&mut self, column: AliasedColumn, ordering: Ordering) {
-        self.columns.push((column, ordering));
+        self.columns
+            .push((Expression::Column(column), ordering, None));
+    }
+
+    /// Orders by an arbitrary [`Expression`] instead of a column.
+    pub fn push_expression(
+        &mut self,
+        expression: Expression,
+        ordering: Ordering,
+        null_ordering: Option<NullOrdering>,
+    ) {
+        self.columns.push((expression, ordering, null_ordering));
+    }
+
+    /// Inserts the `column` at `index`, so it takes precedence over the following expressions.
+    pub fn insert(&mut self, index: usize, column: AliasedColumn, ordering: Ordering) {
+        self.columns
+            .insert(index, (Expression::Column(column), ordering, None));
     }
 
     pub fn is_empty(&self) -> bool {
@@ -30,7 +50,7 @@ impl Transpile for OrderByExpression {
         }
 
         fmt.write_str("ORDER BY ")?;
-        for (idx, (column, ordering)) in self.columns.iter().enumerate() {
+        for (idx, (column, ordering, null_ordering)) in self.columns.iter().enumerate() {
             if idx > 0 {
                 fmt.write_str(", ")?;
             }
@@ -39,6 +59,11 @@ impl Transpile for OrderByExpression {
                 Ordering::Ascending => write!(fmt, " ASC")?,
                 Ordering::Descending => write!(fmt, " DESC")?,
             }
+            match null_ordering {
+                Some(NullOrdering::First) => write!(fmt, " NULLS FIRST")?,
+                Some(NullOrdering::Last) => write!(fmt, " NULLS LAST")?,
+                None => {}
+            }
         }
 
         Ok(())
@@ -102,4 +127,43 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn order_with_null_ordering() {
+        let mut order_by_expression = OrderByExpression::default();
+        order_by_expression.push_expression(
+            Expression::Column(
+                DataTypeQueryPath::Title
+                    .terminating_column()
+                    .aliased(Alias {
+                        condition_index: 1,
+                        chain_depth: 2,
+                        number: 3,
+                    }),
+            ),
+            Ordering::Descending,
+            Some(NullOrdering::Last),
+        );
+        order_by_expression.push_expression(
+            Expression::Column(
+                DataTypeQueryPath::Version
+                    .terminating_column()
+                    .aliased(Alias {
+                        condition_index: 4,
+                        chain_depth: 5,
+                        number: 6,
+                    }),
+            ),
+            Ordering::Ascending,
+            Some(NullOrdering::First),
+        );
+
+        assert_eq!(
+            trim_whitespace(order_by_expression.transpile_to_string()),
+            trim_whitespace(
+                r#"ORDER BY "data_types_1_2_3"."schema"->>'title' DESC NULLS LAST,
+                "ontology_ids_4_5_6"."version" ASC NULLS FIRST"#
+            )
+        );
+    }
 }
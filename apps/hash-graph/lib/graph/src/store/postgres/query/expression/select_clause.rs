@@ -1,18 +1,33 @@
-use std::fmt;
+use std::{borrow::Cow, fmt};
 
 use crate::store::postgres::query::{AliasedColumn, Expression, Transpile};
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct SelectExpression {
     expression: Expression,
-    alias: Option<&'static str>,
+    alias: Option<Cow<'static, str>>,
 }
 
 impl SelectExpression {
     #[must_use]
     #[inline]
     pub const fn new(expression: Expression, alias: Option<&'static str>) -> Self {
-        Self { expression, alias }
+        Self {
+            expression,
+            alias: match alias {
+                Some(alias) => Some(Cow::Borrowed(alias)),
+                None => None,
+            },
+        }
+    }
+
+    /// Creates a selection with an alias, which is not known at compile time.
+    #[must_use]
+    pub const fn with_alias(expression: Expression, alias: Cow<'static, str>) -> Self {
+        Self {
+            expression,
+            alias: Some(alias),
+        }
     }
 
     #[must_use]
@@ -18,6 +18,11 @@ impl PostgresRecord for PropertyTypeWithMetadata {
     fn base_table() -> Table {
         Table::OntologyTemporalMetadata
     }
+
+    fn identifying_paths() -> &'static [Self::QueryPath<'static>] {
+        static PATHS: [PropertyTypeQueryPath<'static>; 1] = [PropertyTypeQueryPath::OntologyId];
+        &PATHS
+    }
 }
 
 impl PostgresQueryPath for PropertyTypeQueryPath<'_> {
@@ -4,7 +4,7 @@ mod window;
 use std::fmt;
 
 pub use self::{
-    select::{Distinctness, SelectStatement},
+    select::{Distinctness, SelectStatement, SortingStatement},
     window::WindowStatement,
 };
 use crate::store::postgres::query::Transpile;
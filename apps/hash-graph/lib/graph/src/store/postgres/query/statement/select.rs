@@ -1,19 +1,21 @@
 use std::fmt::{self, Write};
 
 use crate::store::postgres::query::{
-    expression::OrderByExpression, AliasedColumn, AliasedTable, JoinExpression, SelectExpression,
-    Transpile, WhereExpression, WithExpression,
+    expression::OrderByExpression, AliasedColumn, AliasedTable, Expression, JoinExpression,
+    SelectExpression, Transpile, WhereExpression, WithExpression,
 };
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct SelectStatement {
     pub with: WithExpression,
-    pub distinct: Vec<AliasedColumn>,
+    pub distinct: Vec<Expression>,
     pub selects: Vec<SelectExpression>,
     pub from: AliasedTable,
     pub joins: Vec<JoinExpression>,
     pub where_expression: WhereExpression,
+    pub group_by: Vec<AliasedColumn>,
     pub order_by_expression: OrderByExpression,
+    pub limit: Option<usize>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -64,15 +66,63 @@ impl Transpile for SelectStatement {
             self.where_expression.transpile(fmt)?;
         }
 
+        if !self.group_by.is_empty() {
+            fmt.write_str("\nGROUP BY ")?;
+            for (idx, column) in self.group_by.iter().enumerate() {
+                if idx > 0 {
+                    fmt.write_str(", ")?;
+                }
+                column.transpile(fmt)?;
+            }
+        }
+
         if !self.order_by_expression.is_empty() {
             fmt.write_char('\n')?;
             self.order_by_expression.transpile(fmt)?;
         }
 
+        if let Some(limit) = self.limit {
+            write!(fmt, "\nLIMIT {limit}")?;
+        }
+
         Ok(())
     }
 }
 
+/// The outer query of a [`SelectStatement`], which sorts the distinct rows of the statement.
+///
+/// `DISTINCT ON` requires the leftmost `ORDER BY` expressions to match the distinct expressions, so
+/// the distinct rows cannot be sorted by other expressions in the same statement. Instead, the
+/// statement is used as subquery and the outer query refers to its selections by their alias.
+#[derive(Debug, Default, PartialEq, Eq, Hash)]
+pub struct SortingStatement {
+    pub where_expression: WhereExpression,
+    pub order_by_expression: OrderByExpression,
+    pub limit: Option<usize>,
+}
+
+impl SortingStatement {
+    /// Transpiles the sorting statement with `statement` as subquery.
+    pub fn transpile_with_subquery(&self, statement: &SelectStatement) -> String {
+        let mut transpiled = format!(
+            "SELECT *\nFROM ({}) AS \"distinct_records\"",
+            statement.transpile_to_string()
+        );
+        if !self.where_expression.is_empty() {
+            transpiled.push('\n');
+            transpiled.push_str(&self.where_expression.transpile_to_string());
+        }
+        if !self.order_by_expression.is_empty() {
+            transpiled.push('\n');
+            transpiled.push_str(&self.order_by_expression.transpile_to_string());
+        }
+        if let Some(limit) = self.limit {
+            transpiled.push_str(&format!("\nLIMIT {limit}"));
+        }
+        transpiled
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::borrow::Cow;
@@ -82,6 +132,7 @@ mod tests {
         ontology::{DataTypeWithMetadata, EntityTypeWithMetadata, PropertyTypeWithMetadata},
     };
     use postgres_types::ToSql;
+    use temporal_versioning::Timestamp;
     use uuid::Uuid;
 
     use crate::{
@@ -92,11 +143,14 @@ mod tests {
                 test_helper::trim_whitespace, Distinctness, Ordering, PostgresRecord,
                 SelectCompiler,
             },
-            query::{Filter, FilterExpression, JsonPath, Parameter, PathToken},
+            query::{
+                Aggregation, Filter, FilterExpression, JsonPath, NullOrdering, NumericParameter,
+                Parameter, PathToken, Sorting, SortingDirection, SortingPath,
+            },
         },
         subgraph::{
             edges::{EdgeDirection, KnowledgeGraphEdgeKind, OntologyEdgeKind, SharedEdgeKind},
-            temporal_axes::QueryTemporalAxesUnresolved,
+            temporal_axes::{QueryTemporalAxesUnresolved, VariableAxis},
         },
     };
 
@@ -224,7 +278,7 @@ mod tests {
             ),
             Filter::Equal(
                 Some(FilterExpression::Path(DataTypeQueryPath::Version)),
-                Some(FilterExpression::Parameter(Parameter::Number(1))),
+                Some(FilterExpression::Parameter(Parameter::Integer(1))),
             ),
         ]);
         compiler.add_filter(&filter);
@@ -361,7 +415,7 @@ mod tests {
                         path: DataTypeQueryPath::Version,
                     },
                 )),
-                Some(FilterExpression::Parameter(Parameter::Number(1))),
+                Some(FilterExpression::Parameter(Parameter::Integer(1))),
             ),
         ]);
         compiler.add_filter(&filter);
@@ -653,6 +707,232 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entity_with_cursor() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+        let pinned_timestamp = temporal_axes.pinned_timestamp();
+        let mut compiler = SelectCompiler::<Entity>::new(Some(&temporal_axes));
+        compiler.add_distinct_selection_with_ordering(
+            &EntityQueryPath::Uuid,
+            Distinctness::Distinct,
+            Some(Ordering::Ascending),
+        );
+        compiler.add_distinct_selection_with_ordering(
+            &EntityQueryPath::DecisionTime,
+            Distinctness::Distinct,
+            Some(Ordering::Ascending),
+        );
+
+        let entity_uuid = Uuid::nil();
+        let revision_id = Timestamp::<VariableAxis>::now();
+        compiler.add_cursor(
+            &[],
+            &[
+                (&EntityQueryPath::Uuid, &entity_uuid),
+                (&EntityQueryPath::DecisionTime, &revision_id),
+            ],
+        );
+        compiler.set_limit(10);
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT
+                DISTINCT ON("entity_temporal_metadata_0_0_0"."entity_uuid", "entity_temporal_metadata_0_0_0"."decision_time")
+                "entity_temporal_metadata_0_0_0"."entity_uuid",
+                "entity_temporal_metadata_0_0_0"."decision_time"
+            FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+            WHERE "entity_temporal_metadata_0_0_0"."transaction_time" @> $1::TIMESTAMPTZ
+              AND "entity_temporal_metadata_0_0_0"."decision_time" && $2
+              AND ((("entity_temporal_metadata_0_0_0"."entity_uuid" > $3))
+                OR (("entity_temporal_metadata_0_0_0"."entity_uuid" = $3)
+                    AND (lower("entity_temporal_metadata_0_0_0"."decision_time") > $4)))
+            ORDER BY "entity_temporal_metadata_0_0_0"."entity_uuid" ASC,
+                     "entity_temporal_metadata_0_0_0"."decision_time" ASC
+            LIMIT 10
+            "#,
+            &[
+                &pinned_timestamp,
+                &temporal_axes.variable_interval(),
+                &entity_uuid,
+                &revision_id,
+            ],
+        );
+    }
+
+    #[test]
+    fn entity_with_sorting() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+        let pinned_timestamp = temporal_axes.pinned_timestamp();
+        let mut compiler = SelectCompiler::<Entity>::new(Some(&temporal_axes));
+        let json_path = JsonPath::from_path_tokens(vec![PathToken::Field(Cow::Borrowed(
+            r#"$."https://blockprotocol.org/@alice/types/property-type/name/""#,
+        ))]);
+
+        let filter = Filter::All(Vec::new());
+        let sorting = Sorting {
+            path: SortingPath::Path(EntityQueryPath::Properties(Some(json_path.clone()))),
+            direction: SortingDirection::Descending,
+            nulls: Some(NullOrdering::Last),
+        };
+        compiler.add_sorting(&sorting, &filter);
+        compiler.add_distinct_selection_with_ordering(
+            &EntityQueryPath::Uuid,
+            Distinctness::Distinct,
+            None,
+        );
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM (SELECT
+                    DISTINCT ON("entity_temporal_metadata_0_0_0"."entity_uuid")
+                    to_jsonb(NULLIF(jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath), 'null')),
+                    NULLIF(jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath), 'null') AS "sort_key_0",
+                    "entity_temporal_metadata_0_0_0"."entity_uuid" AS "distinct_0"
+                FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+                INNER JOIN "entity_editions" AS "entity_editions_0_1_0"
+                  ON "entity_editions_0_1_0"."entity_edition_id" = "entity_temporal_metadata_0_0_0"."entity_edition_id"
+                WHERE "entity_temporal_metadata_0_0_0"."transaction_time" @> $2::TIMESTAMPTZ
+                  AND "entity_temporal_metadata_0_0_0"."decision_time" && $3
+                ORDER BY "entity_temporal_metadata_0_0_0"."entity_uuid" ASC,
+                         NULLIF(jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath), 'null') DESC NULLS LAST) AS "distinct_records"
+            ORDER BY "sort_key_0" DESC NULLS LAST
+            "#,
+            &[
+                &json_path,
+                &pinned_timestamp,
+                &temporal_axes.variable_interval(),
+            ],
+        );
+    }
+
+    #[test]
+    fn entity_with_search_rank_sorting() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+        let pinned_timestamp = temporal_axes.pinned_timestamp();
+        let mut compiler = SelectCompiler::<Entity>::new(Some(&temporal_axes));
+
+        let filter = Filter::All(Vec::new());
+        let sorting = Sorting {
+            path: SortingPath::SearchRank,
+            direction: SortingDirection::Descending,
+            nulls: None,
+        };
+        compiler.add_sorting(&sorting, &filter);
+        compiler.add_distinct_selection_with_ordering(
+            &EntityQueryPath::Uuid,
+            Distinctness::Distinct,
+            None,
+        );
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM (SELECT
+                    DISTINCT ON("entity_temporal_metadata_0_0_0"."entity_uuid")
+                    to_jsonb(0),
+                    0 AS "sort_key_0",
+                    "entity_temporal_metadata_0_0_0"."entity_uuid" AS "distinct_0"
+                FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+                WHERE "entity_temporal_metadata_0_0_0"."transaction_time" @> $1::TIMESTAMPTZ
+                  AND "entity_temporal_metadata_0_0_0"."decision_time" && $2
+                ORDER BY "entity_temporal_metadata_0_0_0"."entity_uuid" ASC) AS "distinct_records"
+            ORDER BY "sort_key_0" DESC
+            "#,
+            &[&pinned_timestamp, &temporal_axes.variable_interval()],
+        );
+    }
+
+    #[test]
+    fn entity_with_sorting_and_cursor() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+        let pinned_timestamp = temporal_axes.pinned_timestamp();
+        let mut compiler = SelectCompiler::<Entity>::new(Some(&temporal_axes));
+        let json_path = JsonPath::from_path_tokens(vec![PathToken::Field(Cow::Borrowed(
+            r#"$."https://blockprotocol.org/@alice/types/property-type/name/""#,
+        ))]);
+
+        let filter = Filter::All(Vec::new());
+        let sorting = Sorting {
+            path: SortingPath::Path(EntityQueryPath::Properties(Some(json_path.clone()))),
+            direction: SortingDirection::Descending,
+            nulls: Some(NullOrdering::Last),
+        };
+        compiler.add_sorting(&sorting, &filter);
+        compiler.add_distinct_selection_with_ordering(
+            &EntityQueryPath::Uuid,
+            Distinctness::Distinct,
+            Some(Ordering::Ascending),
+        );
+
+        let sort_key = [Some(Parameter::Any(serde_json::json!("Alice")))];
+        let entity_uuid = Uuid::nil();
+        compiler.add_cursor(&sort_key, &[(&EntityQueryPath::Uuid, &entity_uuid)]);
+        compiler.set_limit(10);
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM (SELECT
+                    DISTINCT ON("entity_temporal_metadata_0_0_0"."entity_uuid")
+                    to_jsonb(NULLIF(jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath), 'null')),
+                    NULLIF(jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath), 'null') AS "sort_key_0",
+                    "entity_temporal_metadata_0_0_0"."entity_uuid" AS "distinct_0"
+                FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+                INNER JOIN "entity_editions" AS "entity_editions_0_1_0"
+                  ON "entity_editions_0_1_0"."entity_edition_id" = "entity_temporal_metadata_0_0_0"."entity_edition_id"
+                WHERE "entity_temporal_metadata_0_0_0"."transaction_time" @> $2::TIMESTAMPTZ
+                  AND "entity_temporal_metadata_0_0_0"."decision_time" && $3
+                ORDER BY "entity_temporal_metadata_0_0_0"."entity_uuid" ASC,
+                         NULLIF(jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath), 'null') DESC NULLS LAST) AS "distinct_records"
+            WHERE ((((("sort_key_0" < $4) OR ("sort_key_0" IS NULL))))
+                OR (("sort_key_0" = $4) AND ("distinct_0" > $5)))
+            ORDER BY "sort_key_0" DESC NULLS LAST, "distinct_0" ASC
+            LIMIT 10
+            "#,
+            &[
+                &json_path,
+                &pinned_timestamp,
+                &temporal_axes.variable_interval(),
+                &serde_json::json!("Alice"),
+                &entity_uuid,
+            ],
+        );
+    }
+
+    #[test]
+    fn entity_aggregation() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+        let pinned_timestamp = temporal_axes.pinned_timestamp();
+        let mut compiler = SelectCompiler::<Entity>::new(Some(&temporal_axes));
+
+        let group_by = EntityQueryPath::OwnedById;
+        compiler.add_group_by(&group_by);
+        compiler.add_aggregation(&Aggregation::Count);
+        let aggregation = Aggregation::Max(EntityQueryPath::DecisionTime);
+        compiler.add_aggregation(&aggregation);
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT
+                to_jsonb("entity_temporal_metadata_0_0_0"."owned_by_id"),
+                to_jsonb(COUNT(DISTINCT ("entity_temporal_metadata_0_0_0"."owned_by_id", "entity_temporal_metadata_0_0_0"."entity_uuid"))),
+                to_jsonb(MAX(lower("entity_temporal_metadata_0_0_0"."decision_time")))
+            FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+            WHERE "entity_temporal_metadata_0_0_0"."transaction_time" @> $1::TIMESTAMPTZ
+              AND "entity_temporal_metadata_0_0_0"."decision_time" && $2
+            GROUP BY "entity_temporal_metadata_0_0_0"."owned_by_id"
+            ORDER BY "entity_temporal_metadata_0_0_0"."owned_by_id" ASC
+            "#,
+            &[&pinned_timestamp, &temporal_axes.variable_interval()],
+        );
+    }
+
     #[test]
     fn entity_property_query() {
         let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
@@ -728,6 +1008,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entity_property_number_query() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+        let pinned_timestamp = temporal_axes.pinned_timestamp();
+        let mut compiler = SelectCompiler::<Entity>::with_asterisk(Some(&temporal_axes));
+        let json_path = JsonPath::from_path_tokens(vec![PathToken::Field(Cow::Borrowed(
+            r#"$."https://blockprotocol.org/@alice/types/property-type/age/""#,
+        ))]);
+        let age = NumericParameter::new(serde_json::Number::from(9_007_199_254_740_993_i64));
+
+        let filter = Filter::Greater(
+            FilterExpression::Path(EntityQueryPath::Properties(Some(json_path.clone()))),
+            FilterExpression::Parameter(Parameter::Number(age.clone())),
+        );
+        compiler.add_filter(&filter);
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+            INNER JOIN "entity_editions" AS "entity_editions_0_1_0"
+              ON "entity_editions_0_1_0"."entity_edition_id" = "entity_temporal_metadata_0_0_0"."entity_edition_id"
+            WHERE "entity_temporal_metadata_0_0_0"."transaction_time" @> $2::TIMESTAMPTZ
+              AND "entity_temporal_metadata_0_0_0"."decision_time" && $3
+              AND (CASE WHEN jsonb_typeof(jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath)) = 'number'
+                        THEN (jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath))::numeric END)
+                > (($4)::text::numeric)
+            "#,
+            &[
+                &json_path,
+                &pinned_timestamp,
+                &temporal_axes.variable_interval(),
+                &age,
+            ],
+        );
+    }
+
+    #[test]
+    fn entity_property_number_equality_query() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+        let pinned_timestamp = temporal_axes.pinned_timestamp();
+        let mut compiler = SelectCompiler::<Entity>::with_asterisk(Some(&temporal_axes));
+        let json_path = JsonPath::from_path_tokens(vec![PathToken::Field(Cow::Borrowed(
+            r#"$."https://blockprotocol.org/@alice/types/property-type/age/""#,
+        ))]);
+        let age = NumericParameter::new(serde_json::Number::from(42));
+
+        let filter = Filter::NotEqual(
+            Some(FilterExpression::Path(EntityQueryPath::Properties(Some(
+                json_path.clone(),
+            )))),
+            Some(FilterExpression::Parameter(Parameter::Number(age.clone()))),
+        );
+        compiler.add_filter(&filter);
+
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+            INNER JOIN "entity_editions" AS "entity_editions_0_1_0"
+              ON "entity_editions_0_1_0"."entity_edition_id" = "entity_temporal_metadata_0_0_0"."entity_edition_id"
+            WHERE "entity_temporal_metadata_0_0_0"."transaction_time" @> $2::TIMESTAMPTZ
+              AND "entity_temporal_metadata_0_0_0"."decision_time" && $3
+              AND jsonb_path_query_first("entity_editions_0_1_0"."properties", $1::text::jsonpath)
+                  != to_jsonb((($4)::text::numeric))
+            "#,
+            &[
+                &json_path,
+                &pinned_timestamp,
+                &temporal_axes.variable_interval(),
+                &age,
+            ],
+        );
+    }
+
     #[test]
     fn entity_outgoing_link_query() {
         let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
@@ -744,7 +1101,7 @@ mod tests {
                 }),
                 direction: EdgeDirection::Incoming,
             })),
-            Some(FilterExpression::Parameter(Parameter::Number(10))),
+            Some(FilterExpression::Parameter(Parameter::Integer(10))),
         );
         compiler.add_filter(&filter);
 
@@ -793,7 +1150,7 @@ mod tests {
                 }),
                 direction: EdgeDirection::Incoming,
             })),
-            Some(FilterExpression::Parameter(Parameter::Number(10))),
+            Some(FilterExpression::Parameter(Parameter::Integer(10))),
         );
         compiler.add_filter(&filter);
 
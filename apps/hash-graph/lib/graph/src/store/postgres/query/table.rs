@@ -664,6 +664,7 @@ pub enum EntityEditions<'p> {
     LeftToRightOrder,
     RightToLeftOrder,
     RecordCreatedById,
+    RecordArchivedById,
     Archived,
 }
 
@@ -671,7 +672,10 @@ impl<'p> EntityEditions<'p> {
     pub const fn nullable(self) -> bool {
         match self {
             Self::EditionId | Self::Archived | Self::RecordCreatedById => false,
-            Self::Properties(_) | Self::LeftToRightOrder | Self::RightToLeftOrder => true,
+            Self::Properties(_)
+            | Self::LeftToRightOrder
+            | Self::RightToLeftOrder
+            | Self::RecordArchivedById => true,
         }
     }
 
@@ -684,6 +688,7 @@ impl<'p> EntityEditions<'p> {
             Self::LeftToRightOrder => (EntityEditions::LeftToRightOrder, None),
             Self::RightToLeftOrder => (EntityEditions::RightToLeftOrder, None),
             Self::RecordCreatedById => (EntityEditions::RecordCreatedById, None),
+            Self::RecordArchivedById => (EntityEditions::RecordArchivedById, None),
             Self::Archived => (EntityEditions::Archived, None),
             Self::Properties(None) => (EntityEditions::Properties(None), None),
             Self::Properties(Some(path)) => {
@@ -705,6 +710,7 @@ impl EntityEditions<'static> {
             Self::LeftToRightOrder => "left_to_right_order",
             Self::RightToLeftOrder => "right_to_left_order",
             Self::RecordCreatedById => "record_created_by_id",
+            Self::RecordArchivedById => "record_archived_by_id",
             Self::Archived => "archived",
         };
         table.transpile(fmt)?;
@@ -713,9 +719,11 @@ impl EntityEditions<'static> {
 
     pub const fn parameter_type(self) -> ParameterType {
         match self {
-            Self::EditionId | Self::RecordCreatedById => ParameterType::Uuid,
+            Self::EditionId | Self::RecordCreatedById | Self::RecordArchivedById => {
+                ParameterType::Uuid
+            }
             Self::Properties(_) => ParameterType::Any,
-            Self::LeftToRightOrder | Self::RightToLeftOrder => ParameterType::Number,
+            Self::LeftToRightOrder | Self::RightToLeftOrder => ParameterType::Integer,
             Self::Archived => ParameterType::Boolean,
         }
     }
@@ -742,7 +750,7 @@ impl EntityIsOfType {
     pub const fn parameter_type(self) -> ParameterType {
         match self {
             Self::EntityEditionId | Self::EntityTypeOntologyId => ParameterType::Uuid,
-            Self::InheritanceDepth => ParameterType::Number,
+            Self::InheritanceDepth => ParameterType::Integer,
         }
     }
 }
@@ -889,7 +897,7 @@ impl EntityTypeConstrainsPropertiesOn {
             Self::SourceEntityTypeOntologyId | Self::TargetPropertyTypeOntologyId => {
                 ParameterType::Uuid
             }
-            Self::InheritanceDepth => ParameterType::Number,
+            Self::InheritanceDepth => ParameterType::Integer,
         }
     }
 }
@@ -920,7 +928,7 @@ impl EntityTypeInheritsFrom {
             Self::SourceEntityTypeOntologyId | Self::TargetEntityTypeOntologyId => {
                 ParameterType::Uuid
             }
-            Self::InheritanceDepth => ParameterType::Number,
+            Self::InheritanceDepth => ParameterType::Integer,
         }
     }
 }
@@ -951,7 +959,7 @@ impl EntityTypeConstrainsLinksOn {
             Self::SourceEntityTypeOntologyId | Self::TargetEntityTypeOntologyId => {
                 ParameterType::Uuid
             }
-            Self::InheritanceDepth => ParameterType::Number,
+            Self::InheritanceDepth => ParameterType::Integer,
         }
     }
 }
@@ -982,7 +990,7 @@ impl EntityTypeConstrainsLinkDestinationsOn {
             Self::SourceEntityTypeOntologyId | Self::TargetEntityTypeOntologyId => {
                 ParameterType::Uuid
             }
-            Self::InheritanceDepth => ParameterType::Number,
+            Self::InheritanceDepth => ParameterType::Integer,
         }
     }
 }
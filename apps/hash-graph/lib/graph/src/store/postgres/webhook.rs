@@ -0,0 +1,400 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use error_stack::{Report, Result, ResultExt};
+use graph_types::{
+    account::AccountId, knowledge::entity::EntityEditionId, ontology::OntologyTypeRecordId,
+    provenance::RecordCreatedById,
+};
+use temporal_versioning::Timestamp;
+use tokio_postgres::{GenericClient, Row};
+use type_system::url::BaseUrl;
+use uuid::Uuid;
+
+use crate::store::{
+    error::{DeletionError, WebhookDoesNotExist},
+    AsClient, Change, ChangeCursor, ChangedRecordId, InsertionError, PostgresStore, QueryError,
+    UpdateError, Webhook, WebhookDeadLetter, WebhookFilter, WebhookStore, WebhookTarget,
+};
+
+/// The key of the advisory lock held while delivering changes to the webhooks.
+const WEBHOOK_DELIVERY_LOCK: &str = "webhook_delivery";
+
+fn webhook_from_row(row: &Row) -> Result<Webhook, QueryError> {
+    Ok(Webhook {
+        webhook_id: row.get("webhook_id"),
+        url: row.get("url"),
+        filter: WebhookFilter {
+            entity_type_base_url: row
+                .get::<_, Option<String>>("entity_type_base_url")
+                .map(BaseUrl::new)
+                .transpose()
+                .change_context(QueryError)?,
+            owned_by_id: row.get("owned_by_id"),
+        },
+        record_created_by_id: row.get("record_created_by_id"),
+    })
+}
+
+#[async_trait]
+impl<C: AsClient> WebhookStore for PostgresStore<C> {
+    #[tracing::instrument(level = "info", skip(self, secret))]
+    async fn register_webhook(
+        &mut self,
+        actor_id: AccountId,
+        url: String,
+        secret: String,
+        filter: WebhookFilter,
+    ) -> Result<Webhook, InsertionError> {
+        let webhook = Webhook {
+            webhook_id: Uuid::new_v4(),
+            url,
+            filter,
+            record_created_by_id: RecordCreatedById::new(actor_id),
+        };
+
+        // The cursor starts at the current transaction time, so changes which happened before the
+        // registration are not delivered.
+        self.as_client()
+            .execute(
+                r#"
+                    INSERT INTO webhooks (
+                        webhook_id,
+                        url,
+                        secret,
+                        entity_type_base_url,
+                        owned_by_id,
+                        record_created_by_id,
+                        cursor_transaction_time,
+                        cursor_change_id
+                    ) VALUES ($1, $2, $3, $4, $5, $6, now(), $7);
+                "#,
+                &[
+                    &webhook.webhook_id,
+                    &webhook.url,
+                    &secret,
+                    &webhook
+                        .filter
+                        .entity_type_base_url
+                        .as_ref()
+                        .map(BaseUrl::as_str),
+                    &webhook.filter.owned_by_id,
+                    &webhook.record_created_by_id,
+                    &Uuid::nil(),
+                ],
+            )
+            .await
+            .change_context(InsertionError)?;
+
+        Ok(webhook)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_webhooks(&self, actor_id: AccountId) -> Result<Vec<Webhook>, QueryError> {
+        self.as_client()
+            .query(
+                r#"
+                    SELECT
+                        webhook_id,
+                        url,
+                        entity_type_base_url,
+                        owned_by_id,
+                        record_created_by_id
+                    FROM webhooks
+                    WHERE record_created_by_id = $1;
+                "#,
+                &[&RecordCreatedById::new(actor_id)],
+            )
+            .await
+            .change_context(QueryError)?
+            .iter()
+            .map(webhook_from_row)
+            .collect()
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn delete_webhook(
+        &mut self,
+        actor_id: AccountId,
+        webhook_id: Uuid,
+    ) -> Result<(), DeletionError> {
+        let deleted = self
+            .as_client()
+            .execute(
+                "DELETE FROM webhooks WHERE webhook_id = $1 AND record_created_by_id = $2;",
+                &[&webhook_id, &RecordCreatedById::new(actor_id)],
+            )
+            .await
+            .change_context(DeletionError)?;
+
+        if deleted == 0 {
+            return Err(Report::new(WebhookDoesNotExist)
+                .attach_printable(webhook_id)
+                .change_context(DeletionError));
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_webhook_dead_letters(
+        &self,
+        actor_id: AccountId,
+        webhook_id: Uuid,
+    ) -> Result<Vec<WebhookDeadLetter>, QueryError> {
+        let rows = self
+            .as_client()
+            .query(
+                r#"
+                    SELECT
+                        webhook_dead_letters.payload,
+                        webhook_dead_letters.attempts,
+                        webhook_dead_letters.last_error,
+                        webhook_dead_letters.failed_at
+                    FROM webhook_dead_letters
+                    JOIN webhooks
+                      ON webhooks.webhook_id = webhook_dead_letters.webhook_id
+                    WHERE webhooks.webhook_id = $1
+                      AND webhooks.record_created_by_id = $2
+                    ORDER BY webhook_dead_letters.failed_at;
+                "#,
+                &[&webhook_id, &RecordCreatedById::new(actor_id)],
+            )
+            .await
+            .change_context(QueryError)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(WebhookDeadLetter {
+                    webhook_id,
+                    payload: row.get("payload"),
+                    attempts: u32::try_from(row.get::<_, i32>("attempts"))
+                        .change_context(QueryError)?,
+                    last_error: row.get("last_error"),
+                    failed_at: row.get("failed_at"),
+                })
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn lock_webhook_delivery(&mut self) -> Result<bool, QueryError> {
+        // The lock is bound to the session instead of the transaction, so changes can be delivered
+        // without keeping a transaction open.
+        Ok(self
+            .as_client()
+            .query_one(
+                "SELECT pg_try_advisory_lock(hashtext($1));",
+                &[&WEBHOOK_DELIVERY_LOCK],
+            )
+            .await
+            .change_context(QueryError)?
+            .get(0))
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn unlock_webhook_delivery(&mut self) -> Result<(), QueryError> {
+        let unlocked: bool = self
+            .as_client()
+            .query_one(
+                "SELECT pg_advisory_unlock(hashtext($1));",
+                &[&WEBHOOK_DELIVERY_LOCK],
+            )
+            .await
+            .change_context(QueryError)?
+            .get(0);
+
+        if !unlocked {
+            return Err(Report::new(QueryError)
+                .attach_printable("the webhook delivery lock was not held by this session"));
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_webhook_targets(&self) -> Result<Vec<WebhookTarget>, QueryError> {
+        let rows = self
+            .as_client()
+            .query(
+                r#"
+                    SELECT
+                        webhook_id,
+                        url,
+                        secret,
+                        entity_type_base_url,
+                        owned_by_id,
+                        record_created_by_id,
+                        cursor_transaction_time,
+                        cursor_change_id
+                    FROM webhooks;
+                "#,
+                &[],
+            )
+            .await
+            .change_context(QueryError)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(WebhookTarget {
+                    webhook: webhook_from_row(row)?,
+                    secret: row.get("secret"),
+                    cursor: ChangeCursor {
+                        transaction_time: Timestamp::from_anonymous(
+                            row.get("cursor_transaction_time"),
+                        ),
+                        change_id: row.get("cursor_change_id"),
+                    },
+                })
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(level = "info", skip(self, changes))]
+    async fn filter_webhook_changes(
+        &self,
+        filter: &WebhookFilter,
+        mut changes: Vec<Change>,
+    ) -> Result<Vec<Change>, QueryError> {
+        if let Some(entity_type_base_url) = &filter.entity_type_base_url {
+            let edition_ids = changes
+                .iter()
+                .filter_map(|change| match change.record_id {
+                    ChangedRecordId::Entity(record_id) => Some(record_id.edition_id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            let matching_edition_ids = self
+                .as_client()
+                .query(
+                    r#"
+                        SELECT entity_is_of_type.entity_edition_id
+                        FROM entity_is_of_type
+                        JOIN ontology_ids
+                          ON ontology_ids.ontology_id = entity_is_of_type.entity_type_ontology_id
+                        WHERE entity_is_of_type.entity_edition_id = ANY($1)
+                          AND ontology_ids.base_url = $2;
+                    "#,
+                    &[&edition_ids, &entity_type_base_url.as_str()],
+                )
+                .await
+                .change_context(QueryError)?
+                .into_iter()
+                .map(|row| row.get(0))
+                .collect::<HashSet<EntityEditionId>>();
+
+            // Ontology types and erased entities don't have an entity type, so only entities can
+            // match.
+            changes.retain(|change| match change.record_id {
+                ChangedRecordId::Entity(record_id) => {
+                    matching_edition_ids.contains(&record_id.edition_id)
+                }
+                _ => false,
+            });
+        }
+
+        if let Some(owned_by_id) = filter.owned_by_id {
+            let base_urls = changes
+                .iter()
+                .filter_map(|change| match &change.record_id {
+                    ChangedRecordId::DataType(record_id)
+                    | ChangedRecordId::PropertyType(record_id)
+                    | ChangedRecordId::EntityType(record_id) => Some(record_id.base_url.as_str()),
+                    ChangedRecordId::Entity(_) | ChangedRecordId::ErasedEntity(_) => None,
+                })
+                .collect::<Vec<_>>();
+
+            let owned_record_ids = if base_urls.is_empty() {
+                HashSet::new()
+            } else {
+                self.as_client()
+                    .query(
+                        r#"
+                            SELECT ontology_ids.base_url, ontology_ids.version
+                            FROM ontology_ids
+                            JOIN ontology_owned_metadata
+                              ON ontology_owned_metadata.ontology_id = ontology_ids.ontology_id
+                            WHERE ontology_ids.base_url = ANY($1)
+                              AND ontology_owned_metadata.owned_by_id = $2;
+                        "#,
+                        &[&base_urls, &owned_by_id],
+                    )
+                    .await
+                    .change_context(QueryError)?
+                    .into_iter()
+                    .map(|row| {
+                        Ok(OntologyTypeRecordId {
+                            base_url: BaseUrl::new(row.get(0)).change_context(QueryError)?,
+                            version: row.get(1),
+                        })
+                    })
+                    .collect::<Result<HashSet<_>, QueryError>>()?
+            };
+
+            changes.retain(|change| match &change.record_id {
+                ChangedRecordId::DataType(record_id)
+                | ChangedRecordId::PropertyType(record_id)
+                | ChangedRecordId::EntityType(record_id) => owned_record_ids.contains(record_id),
+                ChangedRecordId::Entity(record_id) => {
+                    record_id.entity_id.owned_by_id == owned_by_id
+                }
+                ChangedRecordId::ErasedEntity(entity_id) => entity_id.owned_by_id == owned_by_id,
+            });
+        }
+
+        Ok(changes)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn advance_webhook_cursor(
+        &mut self,
+        webhook_id: Uuid,
+        cursor: ChangeCursor,
+    ) -> Result<(), UpdateError> {
+        self.as_client()
+            .execute(
+                r#"
+                    UPDATE webhooks
+                    SET cursor_transaction_time = $2,
+                        cursor_change_id = $3
+                    WHERE webhook_id = $1;
+                "#,
+                &[&webhook_id, &cursor.transaction_time, &cursor.change_id],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self, dead_letter))]
+    async fn insert_webhook_dead_letter(
+        &mut self,
+        dead_letter: WebhookDeadLetter,
+    ) -> Result<(), InsertionError> {
+        self.as_client()
+            .execute(
+                r#"
+                    INSERT INTO webhook_dead_letters (
+                        webhook_id,
+                        payload,
+                        attempts,
+                        last_error,
+                        failed_at
+                    ) VALUES ($1, $2, $3, $4, $5);
+                "#,
+                &[
+                    &dead_letter.webhook_id,
+                    &dead_letter.payload,
+                    &i32::try_from(dead_letter.attempts).change_context(InsertionError)?,
+                    &dead_letter.last_error,
+                    &dead_letter.failed_at,
+                ],
+            )
+            .await
+            .change_context(InsertionError)?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
+mod aggregation;
 mod filter;
 mod path;
+mod sorting;
 
 use std::{collections::HashMap, fmt};
 
@@ -9,8 +11,19 @@ use serde::{
 };
 
 pub use self::{
-    filter::{Filter, FilterExpression, Parameter, ParameterConversionError, ParameterList},
+    aggregation::{
+        Aggregation, AggregationQuery, AggregationResult, DataTypeAggregationQuery,
+        EntityAggregationQuery, EntityTypeAggregationQuery, PropertyTypeAggregationQuery,
+    },
+    filter::{
+        Filter, FilterExpression, NumericParameter, Parameter, ParameterConversionError,
+        ParameterList,
+    },
     path::{JsonPath, PathToken},
+    sorting::{
+        EntityQueryCursor, EntityQuerySorting, NullOrdering, QueryCursor, Sorting,
+        SortingDirection, SortingPath,
+    },
 };
 
 pub trait QueryPath {
@@ -52,6 +65,7 @@ pub fn parse_query_token<'de, T: Deserialize<'de>, E: de::Error>(
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ParameterType {
     Boolean,
+    Integer,
     Number,
     OntologyTypeVersion,
     Text,
@@ -68,6 +82,7 @@ impl fmt::Display for ParameterType {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Boolean => fmt.write_str("boolean"),
+            Self::Integer => fmt.write_str("integer"),
             Self::Number => fmt.write_str("number"),
             Self::OntologyTypeVersion => fmt.write_str("ontology type version"),
             Self::Text => fmt.write_str("text"),
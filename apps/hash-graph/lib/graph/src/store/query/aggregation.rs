@@ -0,0 +1,216 @@
+use std::fmt;
+
+use derivative::Derivative;
+use graph_types::{
+    knowledge::entity::Entity,
+    ontology::{DataTypeWithMetadata, EntityTypeWithMetadata, PropertyTypeWithMetadata},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{
+    openapi::{ArrayBuilder, ObjectBuilder, OneOfBuilder, Ref, RefOr, Schema, SchemaType},
+    ToSchema,
+};
+
+use crate::{
+    store::{query::Filter, Record},
+    subgraph::temporal_axes::QueryTemporalAxesUnresolved,
+};
+
+/// An aggregate function applied to the records matching a query.
+#[derive(Derivative, Deserialize)]
+#[derivative(
+    Debug(bound = "R::QueryPath<'p>: fmt::Debug"),
+    PartialEq(bound = "R::QueryPath<'p>: PartialEq")
+)]
+#[serde(
+    rename_all = "camelCase",
+    bound = "'de: 'p, R::QueryPath<'p>: Deserialize<'de>"
+)]
+pub enum Aggregation<'p, R: Record> {
+    /// Counts the number of distinct records.
+    Count,
+    /// Counts the number of distinct, non-`null` values at the specified path.
+    CountDistinct(R::QueryPath<'p>),
+    /// Returns the smallest value at the specified path.
+    ///
+    /// If the path refers to a time interval, the lower bound of the interval is used.
+    Min(R::QueryPath<'p>),
+    /// Returns the largest value at the specified path.
+    ///
+    /// If the path refers to a time interval, the lower bound of the interval is used.
+    Max(R::QueryPath<'p>),
+}
+
+/// Aggregates the records matching the filter without reading the records themselves.
+///
+/// If `groupBy` is specified, one result is returned for each distinct value at the specified
+/// path, otherwise a single result is returned.
+///
+/// # Examples
+///
+/// The following query counts the entities of each type in the web
+/// `12345678-90ab-cdef-1234-567890abcdef`:
+///
+/// ```json
+/// {
+///   "filter": {
+///     "equal": [
+///       { "path": ["ownedById"] },
+///       { "parameter": "12345678-90ab-cdef-1234-567890abcdef" }
+///     ]
+///   },
+///   "temporalAxes": {
+///     "pinned": {
+///       "axis": "transactionTime",
+///       "timestamp": null
+///     },
+///     "variable": {
+///       "axis": "decisionTime",
+///       "interval": {
+///         "start": null,
+///         "end": null
+///       }
+///     }
+///   },
+///   "aggregation": "count",
+///   "groupBy": ["type", "versionedUrl"]
+/// }
+/// ```
+#[derive(Derivative, Deserialize)]
+#[derivative(Debug(bound = "R::QueryPath<'p>: fmt::Debug"))]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct AggregationQuery<'p, R: Record> {
+    #[serde(bound = "'de: 'p, R::QueryPath<'p>: Deserialize<'de>")]
+    pub filter: Filter<'p, R>,
+    pub temporal_axes: QueryTemporalAxesUnresolved,
+    #[serde(bound = "'de: 'p, R::QueryPath<'p>: Deserialize<'de>")]
+    pub aggregation: Aggregation<'p, R>,
+    #[serde(default, bound = "'de: 'p, R::QueryPath<'p>: Deserialize<'de>")]
+    pub group_by: Option<R::QueryPath<'p>>,
+}
+
+/// The result of an [`AggregationQuery`] for a single group.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregationResult {
+    /// The value at the `groupBy` path of the records in this group. It's omitted if the query
+    /// was not grouped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub group: Option<serde_json::Value>,
+    /// The result of the aggregate function.
+    #[schema(value_type = Object)]
+    pub value: serde_json::Value,
+}
+
+impl<'p, R: Record> AggregationQuery<'p, R> {
+    fn generate_schema(query_token: &'static str) -> RefOr<Schema> {
+        let path = || {
+            ArrayBuilder::new().items(
+                OneOfBuilder::new()
+                    .item(Ref::from_schema_name(query_token))
+                    .item(ObjectBuilder::new().schema_type(SchemaType::String))
+                    .item(ObjectBuilder::new().schema_type(SchemaType::Number)),
+            )
+        };
+
+        ObjectBuilder::new()
+            .property("filter", Ref::from_schema_name("Filter"))
+            .required("filter")
+            .property(
+                "temporalAxes",
+                Ref::from_schema_name(QueryTemporalAxesUnresolved::schema().0),
+            )
+            .required("temporalAxes")
+            .property(
+                "aggregation",
+                OneOfBuilder::new()
+                    .item(
+                        ObjectBuilder::new()
+                            .schema_type(SchemaType::String)
+                            .enum_values(Some(["count"])),
+                    )
+                    .item(
+                        ObjectBuilder::new()
+                            .property("countDistinct", path())
+                            .required("countDistinct"),
+                    )
+                    .item(ObjectBuilder::new().property("min", path()).required("min"))
+                    .item(ObjectBuilder::new().property("max", path()).required("max")),
+            )
+            .required("aggregation")
+            .property("groupBy", path())
+            .into()
+    }
+}
+
+pub type DataTypeAggregationQuery = AggregationQuery<'static, DataTypeWithMetadata>;
+pub type PropertyTypeAggregationQuery = AggregationQuery<'static, PropertyTypeWithMetadata>;
+pub type EntityTypeAggregationQuery = AggregationQuery<'static, EntityTypeWithMetadata>;
+pub type EntityAggregationQuery = AggregationQuery<'static, Entity>;
+
+impl<'p> ToSchema<'_> for AggregationQuery<'p, DataTypeWithMetadata> {
+    fn schema() -> (&'static str, RefOr<Schema>) {
+        (
+            "DataTypeAggregationQuery",
+            Self::generate_schema("DataTypeQueryToken"),
+        )
+    }
+}
+
+impl<'p> ToSchema<'_> for AggregationQuery<'p, PropertyTypeWithMetadata> {
+    fn schema() -> (&'static str, RefOr<Schema>) {
+        (
+            "PropertyTypeAggregationQuery",
+            Self::generate_schema("PropertyTypeQueryToken"),
+        )
+    }
+}
+
+impl<'p> ToSchema<'_> for AggregationQuery<'p, EntityTypeWithMetadata> {
+    fn schema() -> (&'static str, RefOr<Schema>) {
+        (
+            "EntityTypeAggregationQuery",
+            Self::generate_schema("EntityTypeQueryToken"),
+        )
+    }
+}
+
+impl<'p> ToSchema<'_> for AggregationQuery<'p, Entity> {
+    fn schema() -> (&'static str, RefOr<Schema>) {
+        (
+            "EntityAggregationQuery",
+            Self::generate_schema("EntityQueryToken"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{knowledge::EntityQueryPath, ontology::EntityTypeQueryPath};
+
+    #[test]
+    fn deserialize() {
+        let value = json!("count");
+        assert_eq!(
+            Aggregation::<Entity>::deserialize(&value).expect("could not deserialize aggregation"),
+            Aggregation::Count
+        );
+
+        let value = json!({ "countDistinct": ["ownedById"] });
+        assert_eq!(
+            Aggregation::<Entity>::deserialize(&value).expect("could not deserialize aggregation"),
+            Aggregation::CountDistinct(EntityQueryPath::OwnedById)
+        );
+
+        let value = json!({ "max": ["version"] });
+        assert_eq!(
+            Aggregation::<EntityTypeWithMetadata>::deserialize(&value)
+                .expect("could not deserialize aggregation"),
+            Aggregation::Max(EntityTypeQueryPath::Version)
+        );
+    }
+}
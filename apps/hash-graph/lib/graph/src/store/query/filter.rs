@@ -1,11 +1,13 @@
-use std::{borrow::Cow, fmt, str::FromStr};
+use std::{borrow::Cow, error::Error, fmt, str::FromStr};
 
+use bytes::BytesMut;
 use derivative::Derivative;
 use error_stack::{bail, Context, Report, ResultExt};
 use graph_types::{
     knowledge::entity::{Entity, EntityId},
     ontology::OntologyTypeVersion,
 };
+use postgres_types::{IsNull, ToSql, Type};
 use serde::Deserialize;
 use serde_json::{Number, Value};
 use type_system::url::{BaseUrl, VersionedUrl};
@@ -42,11 +44,28 @@ pub enum Filter<'p, R: Record + ?Sized> {
         Option<FilterExpression<'p, R>>,
         Option<FilterExpression<'p, R>>,
     ),
+    Greater(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    GreaterOrEqual(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    Less(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    LessOrEqual(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    /// Checks if the first expression lies within the inclusive range spanned by the second and
+    /// the third expression.
+    Between(
+        FilterExpression<'p, R>,
+        FilterExpression<'p, R>,
+        FilterExpression<'p, R>,
+    ),
     #[serde(skip)]
     In(FilterExpression<'p, R>, ParameterList<'p>),
     StartsWith(FilterExpression<'p, R>, FilterExpression<'p, R>),
     EndsWith(FilterExpression<'p, R>, FilterExpression<'p, R>),
     ContainsSegment(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    /// Searches the text values of the first expression for the query in the second expression.
+    ///
+    /// If the first expression is a JSON value, e.g. `["properties"]` for entities, all string
+    /// values contained in it are searched. The query supports the web search syntax of Postgres,
+    /// i.e. quoted phrases, `or`, and `-` to exclude words.
+    Search(FilterExpression<'p, R>, FilterExpression<'p, R>),
 }
 
 impl<'p, R> Filter<'p, R>
@@ -122,6 +141,27 @@ where
                 ) => parameter.convert_to_parameter_type(path.expected_type())?,
                 (..) => {}
             },
+            Self::Greater(lhs, rhs)
+            | Self::GreaterOrEqual(lhs, rhs)
+            | Self::Less(lhs, rhs)
+            | Self::LessOrEqual(lhs, rhs) => match (lhs, rhs) {
+                (FilterExpression::Parameter(parameter), FilterExpression::Path(path))
+                | (FilterExpression::Path(path), FilterExpression::Parameter(parameter)) => {
+                    parameter.convert_to_parameter_type(path.expected_type())?;
+                }
+                (..) => {}
+            },
+            Self::Between(value, lower, upper) => {
+                if let FilterExpression::Path(path) = value {
+                    let expected_type = path.expected_type();
+                    if let FilterExpression::Parameter(parameter) = lower {
+                        parameter.convert_to_parameter_type(expected_type)?;
+                    }
+                    if let FilterExpression::Parameter(parameter) = upper {
+                        parameter.convert_to_parameter_type(expected_type)?;
+                    }
+                }
+            }
             Self::In(lhs, rhs) => {
                 if let FilterExpression::Parameter(parameter) = lhs {
                     match rhs {
@@ -133,7 +173,8 @@ where
             }
             Self::StartsWith(lhs, rhs)
             | Self::EndsWith(lhs, rhs)
-            | Self::ContainsSegment(lhs, rhs) => {
+            | Self::ContainsSegment(lhs, rhs)
+            | Self::Search(lhs, rhs) => {
                 // TODO: We need to find a way to support lists in addition to strings as well
                 if let FilterExpression::Parameter(parameter) = lhs {
                     parameter.convert_to_parameter_type(ParameterType::Text)?;
@@ -163,19 +204,96 @@ pub enum FilterExpression<'p, R: Record + ?Sized> {
     Parameter(Parameter<'p>),
 }
 
+/// A parameter value in a [`FilterExpression`].
+///
+/// Numbers in a request are always deserialized as [`Parameter::Number`] and are converted to
+/// [`Parameter::Integer`] or [`Parameter::OntologyTypeVersion`] when compared against a path of
+/// that type. When compared against a JSON value, the number is kept. Orderings compare both sides
+/// as `numeric`, equality checks compare the number as `jsonb`.
 #[derive(Debug, PartialEq, Eq, Deserialize)]
 #[serde(untagged)]
 pub enum Parameter<'p> {
     Boolean(bool),
-    Number(i32),
+    Number(NumericParameter),
     Text(Cow<'p, str>),
     Any(Value),
     #[serde(skip)]
+    Integer(i32),
+    #[serde(skip)]
     Uuid(Uuid),
     #[serde(skip)]
     OntologyTypeVersion(OntologyTypeVersion),
 }
 
+/// A number used as [`Parameter`].
+///
+/// Integers are represented exactly, so they don't lose precision above 2^53. Integers outside of
+/// the 64-bit range are parsed as floating point numbers, so they cannot be represented exactly
+/// and are rejected when converting the parameter. The number is passed to Postgres as text and
+/// has to be cast to `numeric`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct NumericParameter(Number);
+
+impl NumericParameter {
+    #[must_use]
+    pub const fn new(number: Number) -> Self {
+        Self(number)
+    }
+
+    #[must_use]
+    pub const fn as_number(&self) -> &Number {
+        &self.0
+    }
+
+    /// Returns if the number is represented without losing precision.
+    ///
+    /// Floating point numbers at or above 2^53 are always integral but not every integer in that
+    /// range can be represented, so they may have been rounded when parsing the request.
+    fn is_exact(&self) -> bool {
+        match self.0.as_f64() {
+            Some(number) if self.0.is_f64() => number.abs() < 2_f64.powi(53),
+            _ => true,
+        }
+    }
+
+    /// Converts the number to an `i32` if it is integral and fits into the range.
+    fn to_i32(&self) -> Option<i32> {
+        match self.0.as_i64() {
+            Some(number) => i32::try_from(number).ok(),
+            None => self.0.as_f64().and_then(float_to_integer),
+        }
+    }
+}
+
+impl From<i32> for NumericParameter {
+    fn from(number: i32) -> Self {
+        Self(Number::from(number))
+    }
+}
+
+impl fmt::Display for NumericParameter {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl ToSql for NumericParameter {
+    // Ideally, we want to accept `NUMERIC`, but its binary format would require converting the
+    // number into base-10000 digits, so we accept `TEXT` instead and cast it to `NUMERIC` in
+    // postgres.
+    postgres_types::accepts!(TEXT);
+
+    postgres_types::to_sql_checked!();
+
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>>
+    where
+        Self: Sized,
+    {
+        self.0.to_string().to_sql(ty, out)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ParameterList<'p> {
     Uuid(&'p [Uuid]),
@@ -185,7 +303,8 @@ impl Parameter<'_> {
     fn to_owned(&self) -> Parameter<'static> {
         match self {
             Parameter::Boolean(bool) => Parameter::Boolean(*bool),
-            Parameter::Number(number) => Parameter::Number(*number),
+            Parameter::Number(number) => Parameter::Number(number.clone()),
+            Parameter::Integer(number) => Parameter::Integer(*number),
             Parameter::Text(text) => Parameter::Text(Cow::Owned(text.to_string())),
             Parameter::Any(value) => Parameter::Any(value.clone()),
             Parameter::Uuid(uuid) => Parameter::Uuid(*uuid),
@@ -209,6 +328,7 @@ impl fmt::Display for ParameterConversionError {
                 boolean.to_string()
             }
             Parameter::Number(number) => number.to_string(),
+            Parameter::Integer(number) => number.to_string(),
             Parameter::Any(Value::Number(number)) => number.to_string(),
             Parameter::Text(text) => text.to_string(),
             Parameter::Any(Value::String(string)) => string.clone(),
@@ -225,14 +345,26 @@ impl fmt::Display for ParameterConversionError {
 impl Context for ParameterConversionError {}
 
 impl Parameter<'_> {
-    fn convert_to_parameter_type(
+    pub(super) fn convert_to_parameter_type(
         &mut self,
         expected: ParameterType,
     ) -> Result<(), Report<ParameterConversionError>> {
         match (&mut *self, expected) {
+            // Numbers which may have been rounded would silently compare incorrectly
+            (Parameter::Number(number), ParameterType::Number | ParameterType::Any)
+                if !number.is_exact() =>
+            {
+                return Err(Report::new(ParameterConversionError {
+                    actual: self.to_owned(),
+                    expected,
+                })
+                .attach_printable("the number cannot be represented exactly"));
+            }
+
             // identity
             (Parameter::Boolean(_), ParameterType::Boolean)
             | (Parameter::Number(_), ParameterType::Number)
+            | (Parameter::Integer(_), ParameterType::Integer)
             | (Parameter::Text(_), ParameterType::Text)
             | (Parameter::Any(_), ParameterType::Any) => {}
 
@@ -245,28 +377,62 @@ impl Parameter<'_> {
             }
 
             // Number conversions
-            (Parameter::Number(number), ParameterType::Any) => {
-                *self = Parameter::Any(Value::Number(Number::from(*number)));
+            (Parameter::Number(_), ParameterType::Any) => {
+                // Numbers are compared numerically against JSON values, so they are kept.
             }
             (Parameter::Any(Value::Number(number)), ParameterType::Number) => {
+                let number = NumericParameter::new(number.clone());
+                if !number.is_exact() {
+                    return Err(Report::new(ParameterConversionError {
+                        actual: self.to_owned(),
+                        expected,
+                    })
+                    .attach_printable("the number cannot be represented exactly"));
+                }
+                *self = Parameter::Number(number);
+            }
+            (Parameter::Number(number), ParameterType::Integer) => {
+                *self = Parameter::Integer(number.to_i32().ok_or_else(|| {
+                    Report::new(ParameterConversionError {
+                        actual: self.to_owned(),
+                        expected,
+                    })
+                })?);
+            }
+            (Parameter::Any(Value::Number(number)), ParameterType::Integer) => {
                 let number = number.as_i64().ok_or_else(|| {
                     Report::new(ParameterConversionError {
                         actual: self.to_owned(),
                         expected,
                     })
                 })?;
-                *self = Parameter::Number(i32::try_from(number).change_context_lazy(|| {
+                *self = Parameter::Integer(i32::try_from(number).change_context_lazy(|| {
                     ParameterConversionError {
                         actual: self.to_owned(),
-                        expected: ParameterType::OntologyTypeVersion,
+                        expected,
                     }
                 })?);
             }
+            (Parameter::Integer(number), ParameterType::Number | ParameterType::Any) => {
+                *self = Parameter::Number(NumericParameter::from(*number));
+            }
             (Parameter::Number(number), ParameterType::OntologyTypeVersion) => {
+                let version = number
+                    .to_i32()
+                    .and_then(|version| u32::try_from(version).ok())
+                    .ok_or_else(|| {
+                        Report::new(ParameterConversionError {
+                            actual: self.to_owned(),
+                            expected,
+                        })
+                    })?;
+                *self = Parameter::OntologyTypeVersion(OntologyTypeVersion::new(version));
+            }
+            (Parameter::Integer(number), ParameterType::OntologyTypeVersion) => {
                 *self = Parameter::OntologyTypeVersion(OntologyTypeVersion::new(
                     u32::try_from(*number).change_context_lazy(|| ParameterConversionError {
                         actual: self.to_owned(),
-                        expected: ParameterType::OntologyTypeVersion,
+                        expected,
                     })?,
                 ));
             }
@@ -311,6 +477,17 @@ impl Parameter<'_> {
     }
 }
 
+/// Converts `number` to an `i32` if it does not have a fractional part and fits into the range.
+#[expect(
+    clippy::float_cmp,
+    clippy::cast_possible_truncation,
+    reason = "The value is checked to be integral and in range before casting"
+)]
+fn float_to_integer(number: f64) -> Option<i32> {
+    (number.trunc() == number && number >= f64::from(i32::MIN) && number <= f64::from(i32::MAX))
+        .then_some(number as i32)
+}
+
 #[cfg(test)]
 mod tests {
     use graph_types::{
@@ -405,4 +582,141 @@ mod tests {
             &expected,
         );
     }
+
+    fn number(value: Value) -> Parameter<'static> {
+        let Value::Number(number) = value else {
+            panic!("not a number: {value}");
+        };
+        Parameter::Number(NumericParameter::new(number))
+    }
+
+    #[test]
+    fn number_conversion() {
+        let mut parameter = number(json!(3));
+        parameter
+            .convert_to_parameter_type(ParameterType::Integer)
+            .expect("could not convert integral number");
+        assert_eq!(parameter, Parameter::Integer(3));
+
+        let mut parameter = number(json!(3.0));
+        parameter
+            .convert_to_parameter_type(ParameterType::Integer)
+            .expect("could not convert integral floating point number");
+        assert_eq!(parameter, Parameter::Integer(3));
+
+        let mut parameter = number(json!(2.5));
+        parameter
+            .convert_to_parameter_type(ParameterType::Integer)
+            .expect_err("converted fractional number to integer");
+
+        let mut parameter = number(json!(1_000_000_000_000_i64));
+        parameter
+            .convert_to_parameter_type(ParameterType::Integer)
+            .expect_err("converted out-of-range number to integer");
+
+        let mut parameter = number(json!(-1));
+        parameter
+            .convert_to_parameter_type(ParameterType::OntologyTypeVersion)
+            .expect_err("converted negative number to version");
+
+        let mut parameter = number(json!(9_007_199_254_740_993_i64));
+        parameter
+            .convert_to_parameter_type(ParameterType::Any)
+            .expect("could not compare number to JSON");
+        assert_eq!(parameter, number(json!(9_007_199_254_740_993_i64)));
+
+        let mut parameter = Parameter::Any(json!(2.5));
+        parameter
+            .convert_to_parameter_type(ParameterType::Number)
+            .expect("could not convert JSON number");
+        assert_eq!(parameter, number(json!(2.5)));
+    }
+
+    #[test]
+    fn large_integer_is_exact() {
+        let parameter: Parameter<'_> =
+            serde_json::from_str("9007199254740993").expect("could not parse parameter");
+        assert_eq!(parameter, number(json!(9_007_199_254_740_993_i64)));
+    }
+
+    #[test]
+    fn inexact_number_is_rejected() {
+        let mut parameter: Parameter<'_> =
+            serde_json::from_str("18446744073709551617").expect("could not parse parameter");
+        parameter
+            .convert_to_parameter_type(ParameterType::Any)
+            .expect_err("compared a rounded number to JSON");
+
+        let mut parameter = Parameter::Any(json!(1e300));
+        parameter
+            .convert_to_parameter_type(ParameterType::Number)
+            .expect_err("converted a rounded JSON number");
+
+        let mut parameter = number(json!(18_446_744_073_709_551_615_u64));
+        parameter
+            .convert_to_parameter_type(ParameterType::Number)
+            .expect("could not convert the largest unsigned integer");
+    }
+
+    #[test]
+    fn ordering() {
+        let expected = json!({
+          "greaterOrEqual": [
+            { "path": ["version"] },
+            { "parameter": 3 }
+          ]
+        });
+
+        test_filter_representation(
+            &Filter::GreaterOrEqual(
+                FilterExpression::<DataTypeWithMetadata>::Path(DataTypeQueryPath::Version),
+                FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(3),
+                )),
+            ),
+            &expected,
+        );
+    }
+
+    #[test]
+    fn between() {
+        let expected = json!({
+          "between": [
+            { "path": ["version"] },
+            { "parameter": 2 },
+            { "parameter": 5 }
+          ]
+        });
+
+        test_filter_representation(
+            &Filter::Between(
+                FilterExpression::<DataTypeWithMetadata>::Path(DataTypeQueryPath::Version),
+                FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(2),
+                )),
+                FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(5),
+                )),
+            ),
+            &expected,
+        );
+    }
+
+    #[test]
+    fn search() {
+        let expected = json!({
+          "search": [
+            { "path": ["properties"] },
+            { "parameter": "alice" }
+          ]
+        });
+
+        test_filter_representation(
+            &Filter::Search(
+                FilterExpression::<Entity>::Path(EntityQueryPath::Properties(None)),
+                FilterExpression::Parameter(Parameter::Text(Cow::Borrowed("alice"))),
+            ),
+            &expected,
+        );
+    }
 }
@@ -0,0 +1,262 @@
+use std::{borrow::Cow, fmt};
+
+use derivative::Derivative;
+use error_stack::Report;
+use graph_types::knowledge::entity::Entity;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use utoipa::{
+    openapi::{ArrayBuilder, ObjectBuilder, OneOfBuilder, Ref, RefOr, Schema, SchemaType},
+    ToSchema,
+};
+
+use crate::{
+    store::{
+        query::{NumericParameter, Parameter, ParameterConversionError, ParameterType, QueryPath},
+        Record,
+    },
+    subgraph::identifier::EntityVertexId,
+};
+
+/// The direction in which records are sorted.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SortingDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// Specifies whether `null` values are sorted before or after non-`null` values.
+///
+/// If not specified, `null` values are sorted after non-`null` values in ascending order and before
+/// them in descending order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum NullOrdering {
+    First,
+    Last,
+}
+
+/// The key, which the records of a query are sorted by.
+///
+/// In addition to the paths of the record, records can be sorted by keys, which are not part of the
+/// record itself and therefore cannot be used in filters.
+#[derive(Debug, PartialEq)]
+pub enum SortingPath<P> {
+    /// The value at the path of the record.
+    Path(P),
+    /// The relevance of the record for the [`Filter::Search`] filters of the query.
+    ///
+    /// The rank is the highest rank of any search filter, which is not negated. Records are not
+    /// ranked if the query does not contain a search filter.
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # use graph::{knowledge::EntityQueryPath, store::query::SortingPath};
+    /// let path = SortingPath::<EntityQueryPath>::deserialize(json!(["searchRank"]))?;
+    /// assert_eq!(path, SortingPath::SearchRank);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [`Filter::Search`]: crate::store::query::Filter::Search
+    SearchRank,
+}
+
+impl<P: QueryPath> SortingPath<P> {
+    /// Returns the type of the value the records are sorted by.
+    pub fn expected_type(&self) -> ParameterType {
+        match self {
+            Self::Path(path) => path.expected_type(),
+            Self::SearchRank => ParameterType::Number,
+        }
+    }
+}
+
+impl<'de, P> Deserialize<'de> for SortingPath<P>
+where
+    P: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let tokens = Vec::<Value>::deserialize(deserializer)?;
+        match tokens.as_slice() {
+            [Value::String(token)] if token == "searchRank" => Ok(Self::SearchRank),
+            _ => P::deserialize(Value::Array(tokens))
+                .map(Self::Path)
+                .map_err(de::Error::custom),
+        }
+    }
+}
+
+/// Sorts the records of a query by the value at the specified path.
+#[derive(Derivative, Deserialize)]
+#[derivative(
+    Debug(bound = "R::QueryPath<'p>: fmt::Debug"),
+    PartialEq(bound = "R::QueryPath<'p>: PartialEq")
+)]
+#[serde(
+    deny_unknown_fields,
+    rename_all = "camelCase",
+    bound = "'de: 'p, R::QueryPath<'p>: Deserialize<'de>"
+)]
+pub struct Sorting<'p, R: Record> {
+    pub path: SortingPath<R::QueryPath<'p>>,
+    #[serde(default)]
+    pub direction: SortingDirection,
+    #[serde(default)]
+    pub nulls: Option<NullOrdering>,
+}
+
+pub type EntityQuerySorting = Sorting<'static, Entity>;
+
+impl<'p, R: Record> Sorting<'p, R>
+where
+    R::QueryPath<'p>: QueryPath,
+{
+    /// Converts the value of the sorted path in a [`QueryCursor`] to a [`Parameter`].
+    ///
+    /// `null` is converted to `None`. JSON values are passed as they are, so they are compared in
+    /// the same way as they are sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterConversionError`] if the value cannot be converted to the type of the
+    /// path.
+    pub fn cursor_parameter<'v>(
+        &self,
+        value: &'v Value,
+    ) -> Result<Option<Parameter<'v>>, Report<ParameterConversionError>> {
+        let expected = self.path.expected_type();
+        let mut parameter = match value {
+            Value::Null => return Ok(None),
+            _ if expected == ParameterType::Any => Parameter::Any(value.clone()),
+            Value::Bool(bool) => Parameter::Boolean(*bool),
+            Value::Number(number) => Parameter::Number(NumericParameter::new(number.clone())),
+            Value::String(string) => Parameter::Text(Cow::Borrowed(string)),
+            Value::Array(_) | Value::Object(_) => Parameter::Any(value.clone()),
+        };
+        parameter.convert_to_parameter_type(expected)?;
+        Ok(Some(parameter))
+    }
+}
+
+/// The position of a record in the results of a paginated query.
+///
+/// The cursor is returned alongside a page of records and can be passed to the next query to
+/// continue after the last returned record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct QueryCursor<V> {
+    /// The values of the sorted paths of the record in the order of the sortings of the query.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sort_key: Vec<Value>,
+    pub vertex_id: V,
+}
+
+pub type EntityQueryCursor = QueryCursor<EntityVertexId>;
+
+impl ToSchema<'_> for QueryCursor<EntityVertexId> {
+    fn schema() -> (&'static str, RefOr<Schema>) {
+        (
+            "EntityQueryCursor",
+            ObjectBuilder::new()
+                .property(
+                    "sortKey",
+                    ArrayBuilder::new().items(
+                        ObjectBuilder::new()
+                            .schema_type(SchemaType::Value)
+                            .nullable(true),
+                    ),
+                )
+                .property(
+                    "vertexId",
+                    Ref::from_schema_name(EntityVertexId::schema().0),
+                )
+                .required("vertexId")
+                .into(),
+        )
+    }
+}
+
+impl<'p> ToSchema<'_> for Sorting<'p, Entity> {
+    fn schema() -> (&'static str, RefOr<Schema>) {
+        (
+            "EntityQuerySorting",
+            ObjectBuilder::new()
+                .property(
+                    "path",
+                    ArrayBuilder::new().items(
+                        OneOfBuilder::new()
+                            .item(Ref::from_schema_name("EntityQueryToken"))
+                            .item(ObjectBuilder::new().schema_type(SchemaType::String))
+                            .item(ObjectBuilder::new().schema_type(SchemaType::Number)),
+                    ),
+                )
+                .required("path")
+                .property(
+                    "direction",
+                    Ref::from_schema_name(SortingDirection::schema().0),
+                )
+                .property("nulls", Ref::from_schema_name(NullOrdering::schema().0))
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        knowledge::EntityQueryPath,
+        store::query::{JsonPath, PathToken},
+    };
+
+    #[test]
+    fn deserialize() {
+        let name = "https://blockprotocol.org/@alice/types/property-type/name/";
+
+        let value = json!({
+            "path": ["properties", name],
+            "direction": "descending",
+            "nulls": "last",
+        });
+        assert_eq!(
+            Sorting::<Entity>::deserialize(&value).expect("could not deserialize sorting"),
+            Sorting {
+                path: SortingPath::Path(EntityQueryPath::Properties(Some(
+                    JsonPath::from_path_tokens(vec![PathToken::Field(Cow::Borrowed(name))])
+                ))),
+                direction: SortingDirection::Descending,
+                nulls: Some(NullOrdering::Last),
+            }
+        );
+
+        let value = json!({ "path": ["uuid"] });
+        assert_eq!(
+            Sorting::<Entity>::deserialize(&value).expect("could not deserialize sorting"),
+            Sorting {
+                path: SortingPath::Path(EntityQueryPath::Uuid),
+                direction: SortingDirection::Ascending,
+                nulls: None,
+            }
+        );
+
+        let value = json!({ "path": ["searchRank"], "direction": "descending" });
+        assert_eq!(
+            Sorting::<Entity>::deserialize(&value).expect("could not deserialize sorting"),
+            Sorting {
+                path: SortingPath::SearchRank,
+                direction: SortingDirection::Descending,
+                nulls: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use derivative::Derivative;
+use error_stack::Result;
+use graph_types::{
+    account::AccountId,
+    provenance::{OwnedById, RecordCreatedById},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use temporal_versioning::Timestamp;
+use type_system::url::BaseUrl;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::store::{
+    error::DeletionError, Change, ChangeCursor, InsertionError, QueryError, UpdateError,
+};
+
+/// Restricts the changes which are delivered to a webhook.
+///
+/// A change is delivered if it matches all specified conditions. If no condition is specified,
+/// every change is delivered.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WebhookFilter {
+    /// Only changes of entities of this entity type are delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = SHARED_BaseUrl)]
+    pub entity_type_base_url: Option<BaseUrl>,
+    /// Only changes of entities and ontology types owned by this web are delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = false)]
+    pub owned_by_id: Option<OwnedById>,
+}
+
+/// A target the changes of the graph are delivered to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub webhook_id: Uuid,
+    pub url: String,
+    pub filter: WebhookFilter,
+    pub record_created_by_id: RecordCreatedById,
+}
+
+/// A [`Webhook`] together with the state required to deliver changes to it.
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub struct WebhookTarget {
+    pub webhook: Webhook,
+    /// The secret used to sign the payloads sent to the webhook.
+    ///
+    /// The secret is required in plain text to sign the payloads, so it's stored unencrypted. It's
+    /// never returned by the API and not part of snapshots.
+    #[derivative(Debug = "ignore")]
+    pub secret: String,
+    /// The position in the change feed up to which changes were delivered.
+    pub cursor: ChangeCursor,
+}
+
+/// A payload which could not be delivered to a webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeadLetter {
+    pub webhook_id: Uuid,
+    #[schema(value_type = Object)]
+    pub payload: Value,
+    pub attempts: u32,
+    /// The error of the last delivery attempt.
+    pub last_error: String,
+    pub failed_at: Timestamp<()>,
+}
+
+/// Describes the API of a store implementation for webhooks.
+#[async_trait]
+pub trait WebhookStore {
+    /// Registers a new webhook for the actor.
+    ///
+    /// Only changes happening after the registration are delivered to the webhook. The `secret` is
+    /// stored in plain text, see [`WebhookTarget::secret`].
+    ///
+    /// # Errors
+    ///
+    /// - if the webhook could not be inserted into the store
+    async fn register_webhook(
+        &mut self,
+        actor_id: AccountId,
+        url: String,
+        secret: String,
+        filter: WebhookFilter,
+    ) -> Result<Webhook, InsertionError>;
+
+    /// Returns the webhooks registered by the actor.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the webhooks from the store failed
+    async fn get_webhooks(&self, actor_id: AccountId) -> Result<Vec<Webhook>, QueryError>;
+
+    /// Deletes a webhook registered by the actor including its dead letters.
+    ///
+    /// # Errors
+    ///
+    /// - [`WebhookDoesNotExist`] if the actor has not registered a webhook with the ID
+    /// - if the webhook could not be deleted from the store
+    ///
+    /// [`WebhookDoesNotExist`]: crate::store::error::WebhookDoesNotExist
+    async fn delete_webhook(
+        &mut self,
+        actor_id: AccountId,
+        webhook_id: Uuid,
+    ) -> Result<(), DeletionError>;
+
+    /// Returns the payloads which could not be delivered to a webhook registered by the actor.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the dead letters from the store failed
+    async fn get_webhook_dead_letters(
+        &self,
+        actor_id: AccountId,
+        webhook_id: Uuid,
+    ) -> Result<Vec<WebhookDeadLetter>, QueryError>;
+
+    /// Tries to acquire the exclusive right to deliver changes to the webhooks.
+    ///
+    /// Returns `false` if the right is currently held by someone else. The right has to be released
+    /// by calling [`unlock_webhook_delivery`].
+    ///
+    /// # Errors
+    ///
+    /// - if the lock could not be requested from the store
+    ///
+    /// [`unlock_webhook_delivery`]: Self::unlock_webhook_delivery
+    async fn lock_webhook_delivery(&mut self) -> Result<bool, QueryError>;
+
+    /// Releases the right to deliver changes acquired by [`lock_webhook_delivery`].
+    ///
+    /// # Errors
+    ///
+    /// - if the lock could not be released
+    ///
+    /// [`lock_webhook_delivery`]: Self::lock_webhook_delivery
+    async fn unlock_webhook_delivery(&mut self) -> Result<(), QueryError>;
+
+    /// Returns all registered webhooks together with their delivery state.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the webhooks from the store failed
+    async fn get_webhook_targets(&self) -> Result<Vec<WebhookTarget>, QueryError>;
+
+    /// Returns the `changes` which match `filter` in the same order.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the records of the changes from the store failed
+    async fn filter_webhook_changes(
+        &self,
+        filter: &WebhookFilter,
+        changes: Vec<Change>,
+    ) -> Result<Vec<Change>, QueryError>;
+
+    /// Stores the position in the change feed up to which changes were delivered to the webhook.
+    ///
+    /// # Errors
+    ///
+    /// - if the webhook could not be updated
+    async fn advance_webhook_cursor(
+        &mut self,
+        webhook_id: Uuid,
+        cursor: ChangeCursor,
+    ) -> Result<(), UpdateError>;
+
+    /// Stores a payload which could not be delivered to a webhook.
+    ///
+    /// # Errors
+    ///
+    /// - if the dead letter could not be inserted into the store
+    async fn insert_webhook_dead_letter(
+        &mut self,
+        dead_letter: WebhookDeadLetter,
+    ) -> Result<(), InsertionError>;
+}
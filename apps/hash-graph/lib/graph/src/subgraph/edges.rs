@@ -11,7 +11,8 @@ use std::{
 pub use self::{
     edge::{EdgeDirection, OutwardEdge},
     kind::{
-        EdgeKind, EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphEdgeKind, OntologyEdgeKind,
+        EdgeKind, EdgeResolveDepths, EdgeTraversalFilters, GraphResolveDepths,
+        KnowledgeGraphEdgeKind, KnowledgeGraphTraversalFilters, OntologyEdgeKind,
         OutgoingEdgeResolveDepth, SharedEdgeKind,
     },
 };
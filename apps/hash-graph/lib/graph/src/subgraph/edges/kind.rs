@@ -1,16 +1,24 @@
 use std::{collections::HashSet, convert::identity};
 
+use error_stack::Report;
+use graph_types::knowledge::entity::Entity;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{
+    openapi::{ObjectBuilder, Ref, RefOr, Schema},
+    ToSchema,
+};
 
-use crate::subgraph::{
-    edges::{
-        endpoint::{EdgeEndpointSet, EntityIdWithIntervalSet},
-        AdjacencyList, EdgeDirection, Edges,
-    },
-    identifier::{
-        DataTypeVertexId, EdgeEndpoint, EntityIdWithInterval, EntityTypeVertexId, EntityVertexId,
-        PropertyTypeVertexId, VertexId,
+use crate::{
+    store::query::{Filter, ParameterConversionError},
+    subgraph::{
+        edges::{
+            endpoint::{EdgeEndpointSet, EntityIdWithIntervalSet},
+            AdjacencyList, EdgeDirection, Edges,
+        },
+        identifier::{
+            DataTypeVertexId, EdgeEndpoint, EntityIdWithInterval, EntityTypeVertexId,
+            EntityVertexId, PropertyTypeVertexId, VertexId,
+        },
     },
 };
 
@@ -216,6 +224,104 @@ impl GraphResolveDepths {
     }
 }
 
+/// The [`Filter`]s an [`Entity`] has to match to be reached by following a knowledge graph edge
+/// in the respective direction.
+///
+/// If no filter is specified for a direction, every [`Entity`] is followed.
+#[derive(Debug, Default, Deserialize)]
+#[serde(bound = "'de: 'p", deny_unknown_fields)]
+pub struct EdgeTraversalFilters<'p> {
+    #[serde(default)]
+    pub incoming: Option<Filter<'p, Entity>>,
+    #[serde(default)]
+    pub outgoing: Option<Filter<'p, Entity>>,
+}
+
+/// Restricts which entities are followed when traversing the knowledge graph edges.
+///
+/// Alongside the [`GraphResolveDepths`], which specify how far the edges are followed, the filters
+/// specify which entities may be followed on each hop. An [`Entity`] which is reached through an
+/// edge, but does not match the filter of the edge, is neither added to the subgraph nor traversed
+/// any further.
+///
+/// Reaching a link entity from its endpoint follows an `incoming` edge, while reaching the
+/// endpoint from the link entity follows an `outgoing` edge. To only follow the links of a certain
+/// link type from an entity to the right endpoint of the link, the filter on
+/// `hasLeftEntity.incoming` has to match the link entity type. Additionally, the filter on
+/// `hasRightEntity.outgoing` may restrict the type of the right endpoint.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", bound = "'de: 'p", deny_unknown_fields)]
+pub struct KnowledgeGraphTraversalFilters<'p> {
+    #[serde(default)]
+    pub has_left_entity: EdgeTraversalFilters<'p>,
+    #[serde(default)]
+    pub has_right_entity: EdgeTraversalFilters<'p>,
+}
+
+impl<'p> KnowledgeGraphTraversalFilters<'p> {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.has_left_entity.incoming.is_none()
+            && self.has_left_entity.outgoing.is_none()
+            && self.has_right_entity.incoming.is_none()
+            && self.has_right_entity.outgoing.is_none()
+    }
+
+    /// Converts the contained [`Parameter`]s of all filters to match the type of their paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterConversionError`] if conversion fails.
+    ///
+    /// [`Parameter`]: crate::store::query::Parameter
+    pub fn convert_parameters(&mut self) -> Result<(), Report<ParameterConversionError>> {
+        [
+            &mut self.has_left_entity.incoming,
+            &mut self.has_left_entity.outgoing,
+            &mut self.has_right_entity.incoming,
+            &mut self.has_right_entity.outgoing,
+        ]
+        .into_iter()
+        .flatten()
+        .try_for_each(Filter::convert_parameters)
+    }
+
+    /// Returns the filter for following the edge of the given kind in the given direction.
+    #[must_use]
+    pub const fn get(
+        &self,
+        kind: KnowledgeGraphEdgeKind,
+        direction: EdgeDirection,
+    ) -> Option<&Filter<'p, Entity>> {
+        let filters = match kind {
+            KnowledgeGraphEdgeKind::HasLeftEntity => &self.has_left_entity,
+            KnowledgeGraphEdgeKind::HasRightEntity => &self.has_right_entity,
+        };
+        match direction {
+            EdgeDirection::Incoming => filters.incoming.as_ref(),
+            EdgeDirection::Outgoing => filters.outgoing.as_ref(),
+        }
+    }
+}
+
+impl ToSchema<'_> for KnowledgeGraphTraversalFilters<'_> {
+    fn schema() -> (&'static str, RefOr<Schema>) {
+        let edge_filters = || {
+            ObjectBuilder::new()
+                .property("incoming", Ref::from_schema_name("Filter"))
+                .property("outgoing", Ref::from_schema_name("Filter"))
+        };
+
+        (
+            "KnowledgeGraphTraversalFilters",
+            ObjectBuilder::new()
+                .property("hasLeftEntity", edge_filters())
+                .property("hasRightEntity", edge_filters())
+                .into(),
+        )
+    }
+}
+
 pub trait GraphResolveDepthIndex {
     fn depth_mut(self, direction: EdgeDirection, dephts: &mut GraphResolveDepths) -> &mut u8;
 }
@@ -7,13 +7,19 @@ use graph_types::{
 };
 use serde::Deserialize;
 use utoipa::{
-    openapi::{ObjectBuilder, Ref, RefOr, Schema},
+    openapi::{ArrayBuilder, ObjectBuilder, Ref, RefOr, Schema, SchemaType},
     ToSchema,
 };
 
 use crate::{
-    store::{query::Filter, Record},
-    subgraph::{edges::GraphResolveDepths, temporal_axes::QueryTemporalAxesUnresolved},
+    store::{
+        query::{EntityQueryCursor, EntityQuerySorting, Filter, QueryCursor, Sorting},
+        Record,
+    },
+    subgraph::{
+        edges::{GraphResolveDepths, KnowledgeGraphTraversalFilters},
+        temporal_axes::QueryTemporalAxesUnresolved,
+    },
 };
 
 /// Structural queries are the main entry point to read data from the Graph.
@@ -47,6 +53,31 @@ use crate::{
 /// an in-depth explanation of the depth of a query, please see the documentation on
 /// [`GraphResolveDepths`].
 ///
+/// # Traversal filters
+///
+/// When traversing the knowledge graph, `traversalFilters` may restrict which entities are
+/// followed along the `hasLeftEntity` and `hasRightEntity` edges. For example, this allows to only
+/// follow links of a certain link type. Please see the documentation on
+/// [`KnowledgeGraphTraversalFilters`] for more information. Currently, traversal filters are only
+/// supported for entities.
+///
+/// # Sorting
+///
+/// By default, the root vertices are returned in an unspecified order. `orderBy` accepts a list of
+/// [`Sorting`]s, each consisting of a [`RecordPath`], a direction, and whether `null` values are
+/// sorted first or last. The root vertices are sorted by the first path, ties are broken by the
+/// following ones. Currently, sorting is only supported for entities.
+///
+/// Entities can also be sorted by their relevance for the `search` filters of the query by using
+/// the `["searchRank"]` path.
+///
+/// # Pagination
+///
+/// A query can be restricted to return at most `limit` root vertices. The response then contains a
+/// cursor, which can be passed as `cursor` in the next query to continue after the last returned
+/// vertex. If the query is sorted, the cursor contains the sorted values of the last vertex, so the
+/// next query has to use the same `orderBy`. Currently, pagination is only supported for entities.
+///
 /// # Examples
 ///
 /// Typically, a structural will be deserialized from a JSON request. The following examples assume,
@@ -157,17 +188,25 @@ use crate::{
 /// [`RecordPath`]: crate::store::query::QueryPath
 /// [`Parameter`]: crate::store::query::Parameter
 #[derive(Deserialize, Derivative)]
-#[derivative(Debug(bound = "R::QueryPath<'p>: Debug"))]
+#[derivative(Debug(bound = "R::QueryPath<'p>: Debug, R::VertexId: Debug"))]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct StructuralQuery<'p, R: Record> {
     #[serde(bound = "'de: 'p, R::QueryPath<'p>: Deserialize<'de>")]
     pub filter: Filter<'p, R>,
     pub graph_resolve_depths: GraphResolveDepths,
+    #[serde(default, bound = "'de: 'p")]
+    pub traversal_filters: KnowledgeGraphTraversalFilters<'p>,
     pub temporal_axes: QueryTemporalAxesUnresolved,
+    #[serde(default, bound = "'de: 'p, R::QueryPath<'p>: Deserialize<'de>")]
+    pub order_by: Vec<Sorting<'p, R>>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default, bound = "R::VertexId: Deserialize<'de>")]
+    pub cursor: Option<QueryCursor<R::VertexId>>,
 }
 
 impl<'p, R: Record> StructuralQuery<'p, R> {
-    fn generate_schema() -> RefOr<Schema> {
+    fn generate_schema() -> ObjectBuilder {
         ObjectBuilder::new()
             .property("filter", Ref::from_schema_name("Filter"))
             .required("filter")
@@ -181,7 +220,6 @@ impl<'p, R: Record> StructuralQuery<'p, R> {
                 Ref::from_schema_name(QueryTemporalAxesUnresolved::schema().0),
             )
             .required("temporalAxes")
-            .into()
     }
 }
 
@@ -194,7 +232,7 @@ impl<'p> ToSchema<'_> for StructuralQuery<'p, DataTypeWithMetadata> {
     fn schema() -> (&'static str, RefOr<Schema>) {
         (
             "DataTypeStructuralQuery",
-            StructuralQuery::<'p, DataTypeWithMetadata>::generate_schema(),
+            StructuralQuery::<'p, DataTypeWithMetadata>::generate_schema().into(),
         )
     }
 }
@@ -203,7 +241,7 @@ impl<'p> ToSchema<'_> for StructuralQuery<'p, PropertyTypeWithMetadata> {
     fn schema() -> (&'static str, RefOr<Schema>) {
         (
             "PropertyTypeStructuralQuery",
-            StructuralQuery::<'p, PropertyTypeWithMetadata>::generate_schema(),
+            StructuralQuery::<'p, PropertyTypeWithMetadata>::generate_schema().into(),
         )
     }
 }
@@ -212,7 +250,7 @@ impl<'p> ToSchema<'_> for StructuralQuery<'p, EntityTypeWithMetadata> {
     fn schema() -> (&'static str, RefOr<Schema>) {
         (
             "EntityTypeStructuralQuery",
-            StructuralQuery::<'p, EntityTypeWithMetadata>::generate_schema(),
+            StructuralQuery::<'p, EntityTypeWithMetadata>::generate_schema().into(),
         )
     }
 }
@@ -221,7 +259,28 @@ impl<'p> ToSchema<'_> for StructuralQuery<'p, Entity> {
     fn schema() -> (&'static str, RefOr<Schema>) {
         (
             "EntityStructuralQuery",
-            StructuralQuery::<'p, Entity>::generate_schema(),
+            StructuralQuery::<'p, Entity>::generate_schema()
+                .property(
+                    "traversalFilters",
+                    Ref::from_schema_name(KnowledgeGraphTraversalFilters::schema().0),
+                )
+                .property(
+                    "limit",
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::Integer)
+                        .minimum(Some(0.0))
+                        .nullable(true),
+                )
+                .property(
+                    "orderBy",
+                    ArrayBuilder::new()
+                        .items(Ref::from_schema_name(EntityQuerySorting::schema().0)),
+                )
+                .property(
+                    "cursor",
+                    Ref::from_schema_name(EntityQueryCursor::schema().0),
+                )
+                .into(),
         )
     }
 }
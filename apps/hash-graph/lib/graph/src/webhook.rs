@@ -0,0 +1,495 @@
+//! Delivery of changes to registered webhooks.
+//!
+//! Every registered [`Webhook`] keeps a cursor into the change feed. The [`WebhookDispatcher`]
+//! reads the changes after that cursor with the permissions of the actor who registered the
+//! webhook, drops the changes not matching the [`WebhookFilter`], and sends each remaining change
+//! as signed JSON payload to the webhook. Payloads which can't be delivered after retrying are
+//! stored as [`WebhookDeadLetter`].
+//!
+//! Changes are delivered at least once: if the dispatcher stops before the cursor is advanced,
+//! the changes are sent again on the next run. The webhooks are sent to concurrently, so a slow or
+//! failing webhook does not delay the delivery to other webhooks.
+//!
+//! [`Webhook`]: crate::store::Webhook
+//! [`WebhookFilter`]: crate::store::WebhookFilter
+
+use std::{
+    error::Error,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use authorization::{AuthorizationApi, AuthorizationApiPool};
+use error_stack::{Context, Report, ResultExt};
+use futures::future;
+use hmac::{Hmac, Mac};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::CONTENT_TYPE,
+    redirect, Client, Response,
+};
+use serde::Serialize;
+use sha2::Sha256;
+use temporal_versioning::Timestamp;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::store::{
+    Change, ChangeCursor, ChangeFeedStore, StorePool, WebhookDeadLetter, WebhookStore,
+    WebhookTarget,
+};
+
+/// The header containing the signature of the payload as returned by [`sign_payload`].
+pub const SIGNATURE_HEADER: &str = "X-Hash-Signature-256";
+/// The header containing the Unix timestamp in seconds at which the payload was signed.
+pub const TIMESTAMP_HEADER: &str = "X-Hash-Timestamp";
+/// The header containing the ID of the webhook the payload is sent to.
+pub const WEBHOOK_ID_HEADER: &str = "X-Hash-Webhook-Id";
+
+#[derive(Debug)]
+#[must_use]
+pub struct WebhookDeliveryError;
+
+impl Context for WebhookDeliveryError {}
+
+impl fmt::Display for WebhookDeliveryError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Could not deliver changes to webhooks")
+    }
+}
+
+/// The JSON body sent to a webhook for every change.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload<'c> {
+    pub webhook_id: Uuid,
+    pub change: &'c Change,
+}
+
+/// Returns the value of the [`SIGNATURE_HEADER`] for a payload sent at `timestamp`.
+///
+/// The signature is the hex encoded HMAC-SHA256 of `{timestamp}.{body}` using `secret` as key,
+/// prefixed with `sha256=`. The `timestamp` is sent in the [`TIMESTAMP_HEADER`], so receivers can
+/// reject payloads, which are replayed later on.
+#[must_use]
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Returns if `address` is routable on the internet.
+pub(crate) fn is_public_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => {
+            let [first, second, third, _] = address.octets();
+            // `100.64.0.0/10` is shared between the customers of a carrier.
+            let shared = first == 100 && (second & 0b1100_0000) == 64;
+            let documentation = matches!(
+                (first, second, third),
+                (192, 0, 2) | (198, 51, 100) | (203, 0, 113)
+            );
+            !(address.is_loopback()
+                || address.is_private()
+                || address.is_link_local()
+                || address.is_unspecified()
+                || address.is_broadcast()
+                || documentation
+                || address.is_multicast()
+                || shared)
+        }
+        IpAddr::V6(address) => {
+            if let Some(address) = address.to_ipv4_mapped() {
+                return is_public_address(IpAddr::V4(address));
+            }
+            let [first, ..] = address.segments();
+            // `fc00::/7` are unique local and `fe80::/10` are link-local addresses.
+            let unique_local = (first & 0xFE00) == 0xFC00;
+            let link_local = (first & 0xFFC0) == 0xFE80;
+            !(address.is_loopback()
+                || address.is_unspecified()
+                || address.is_multicast()
+                || unique_local
+                || link_local)
+        }
+    }
+}
+
+/// Resolves the hosts of webhooks to their public addresses only.
+///
+/// The addresses are resolved again for every request, so a host could point to a private address
+/// after the webhook was registered. Hosts given as IP address are not resolved, they are checked
+/// when the webhook is registered.
+struct PublicAddressResolver;
+
+#[derive(Debug)]
+struct NoPublicAddress;
+
+impl fmt::Display for NoPublicAddress {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("the host does not resolve to a public address")
+    }
+}
+
+impl Error for NoPublicAddress {}
+
+async fn resolve_public_addresses(name: Name) -> Result<Addrs, Box<dyn Error + Send + Sync>> {
+    let addresses = tokio::net::lookup_host((name.as_str(), 0))
+        .await?
+        .filter(|address| is_public_address(address.ip()))
+        .collect::<Vec<SocketAddr>>();
+    if addresses.is_empty() {
+        return Err(Box::new(NoPublicAddress));
+    }
+    Ok(Box::new(addresses.into_iter()))
+}
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(resolve_public_addresses(name))
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct WebhookDeliveryConfig {
+    /// The number of times a payload is sent before it's stored as dead letter.
+    pub max_attempts: u32,
+    /// The time to wait before the second attempt. It's doubled for every further attempt.
+    pub initial_backoff: Duration,
+    /// The time to wait for a webhook to respond.
+    pub request_timeout: Duration,
+    /// The maximum number of changes read for a webhook at once.
+    pub batch_size: usize,
+    /// The time to wait between looking for new changes.
+    pub poll_interval: Duration,
+}
+
+impl Default for WebhookDeliveryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(10),
+            batch_size: 100,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Sends the changes of the graph to the registered webhooks.
+///
+/// Several dispatchers may run against the same database, e.g. one per running graph. Only one of
+/// them delivers changes at a time, so changes are not delivered once per dispatcher.
+pub struct WebhookDispatcher {
+    client: Client,
+    config: WebhookDeliveryConfig,
+}
+
+/// The changes read for a webhook, which are waiting to be sent.
+struct PendingDelivery {
+    target: WebhookTarget,
+    /// The position in the change feed after the read changes.
+    cursor: ChangeCursor,
+    payloads: Vec<serde_json::Value>,
+}
+
+impl WebhookDispatcher {
+    /// Creates a new dispatcher.
+    ///
+    /// # Errors
+    ///
+    /// - if the HTTP client could not be created
+    pub fn new(config: WebhookDeliveryConfig) -> Result<Self, Report<WebhookDeliveryError>> {
+        Ok(Self {
+            // A redirect could point to any address, so redirects are not followed.
+            client: Client::builder()
+                .timeout(config.request_timeout)
+                .redirect(redirect::Policy::none())
+                .dns_resolver(Arc::new(PublicAddressResolver))
+                .build()
+                .change_context(WebhookDeliveryError)?,
+            config,
+        })
+    }
+
+    /// Delivers the changes after the cursor of every webhook and returns the number of
+    /// delivered payloads.
+    ///
+    /// At most [`batch_size`] changes are read per webhook. The webhooks are sent to concurrently,
+    /// while the changes of a single webhook are sent in order. Payloads which could not be
+    /// delivered are stored as dead letters and are not counted. If another dispatcher is
+    /// currently delivering changes, nothing is delivered.
+    ///
+    /// A failure to read or update the state of a single webhook is logged and does not affect
+    /// the delivery to other webhooks. The changes of that webhook are delivered again on the next
+    /// run.
+    ///
+    /// # Errors
+    ///
+    /// - if the delivery lock could not be acquired or released
+    /// - if reading the webhooks from the store failed
+    ///
+    /// [`batch_size`]: WebhookDeliveryConfig::batch_size
+    pub async fn deliver_pending<S, A>(
+        &self,
+        store: &mut S,
+        authorization_api: &A,
+    ) -> Result<usize, Report<WebhookDeliveryError>>
+    where
+        S: WebhookStore + ChangeFeedStore + Send,
+        A: AuthorizationApi + Sync,
+    {
+        if !store
+            .lock_webhook_delivery()
+            .await
+            .change_context(WebhookDeliveryError)?
+        {
+            return Ok(0);
+        }
+
+        let delivered = self.deliver_locked(store, authorization_api).await;
+        let unlocked = store
+            .unlock_webhook_delivery()
+            .await
+            .change_context(WebhookDeliveryError);
+
+        let delivered = delivered?;
+        unlocked?;
+        Ok(delivered)
+    }
+
+    /// Delivers changes to the registered webhooks until the task is cancelled.
+    pub async fn run<S, A>(self, store_pool: Arc<S>, authorization_api_pool: Arc<A>)
+    where
+        S: StorePool + Send + Sync,
+        A: AuthorizationApiPool + Send + Sync,
+    {
+        loop {
+            // The connections are only held while delivering, not while waiting for new changes.
+            if let Err(report) = self
+                .deliver_from_pools(&store_pool, &authorization_api_pool)
+                .await
+            {
+                tracing::error!(error=?report, "Could not deliver changes to webhooks");
+            }
+
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    async fn deliver_from_pools<S, A>(
+        &self,
+        store_pool: &S,
+        authorization_api_pool: &A,
+    ) -> Result<(), Report<WebhookDeliveryError>>
+    where
+        S: StorePool + Send + Sync,
+        A: AuthorizationApiPool + Send + Sync,
+    {
+        let mut store = store_pool.acquire().await.map_err(|report| {
+            tracing::error!(error=?report, "Could not acquire store");
+            Report::new(WebhookDeliveryError)
+        })?;
+
+        let authorization_api = authorization_api_pool.acquire().await.map_err(|error| {
+            tracing::error!(?error, "Could not acquire access to the authorization API");
+            Report::new(WebhookDeliveryError)
+        })?;
+
+        let delivered = self.deliver_pending(&mut store, &authorization_api).await?;
+        if delivered > 0 {
+            tracing::debug!(delivered, "Delivered changes to webhooks");
+        }
+        Ok(())
+    }
+
+    async fn deliver_locked<S, A>(
+        &self,
+        store: &mut S,
+        authorization_api: &A,
+    ) -> Result<usize, Report<WebhookDeliveryError>>
+    where
+        S: WebhookStore + ChangeFeedStore + Send,
+        A: AuthorizationApi + Sync,
+    {
+        let targets = store
+            .get_webhook_targets()
+            .await
+            .change_context(WebhookDeliveryError)?;
+
+        let mut pending = Vec::with_capacity(targets.len());
+        for target in targets {
+            let webhook_id = target.webhook.webhook_id;
+            match self.read_pending(store, authorization_api, target).await {
+                Ok(Some(delivery)) => pending.push(delivery),
+                Ok(None) => {}
+                Err(report) => {
+                    tracing::error!(%webhook_id, error=?report, "Could not read changes");
+                }
+            }
+        }
+
+        let dead_letters =
+            future::join_all(pending.iter().map(|delivery| self.send_all(delivery))).await;
+
+        let mut delivered = 0;
+        for (delivery, dead_letters) in pending.iter().zip(dead_letters) {
+            let webhook_id = delivery.target.webhook.webhook_id;
+            let sent = delivery.payloads.len() - dead_letters.len();
+            match Self::complete(store, delivery, dead_letters).await {
+                Ok(()) => delivered += sent,
+                Err(report) => {
+                    tracing::error!(%webhook_id, error=?report, "Could not update webhook");
+                }
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Reads the changes after the cursor of `target`, which match its filter.
+    ///
+    /// Returns `None` if there are no new changes.
+    async fn read_pending<S, A>(
+        &self,
+        store: &mut S,
+        authorization_api: &A,
+        target: WebhookTarget,
+    ) -> Result<Option<PendingDelivery>, Report<WebhookDeliveryError>>
+    where
+        S: WebhookStore + ChangeFeedStore + Send,
+        A: AuthorizationApi + Sync,
+    {
+        let webhook_id = target.webhook.webhook_id;
+
+        let changes = store
+            .read_changes(
+                target.webhook.record_created_by_id.as_account_id(),
+                authorization_api,
+                Some(target.cursor),
+                self.config.batch_size,
+            )
+            .await
+            .change_context(WebhookDeliveryError)?;
+        let Some(cursor) = changes.cursor.filter(|cursor| *cursor != target.cursor) else {
+            return Ok(None);
+        };
+
+        let payloads = store
+            .filter_webhook_changes(&target.webhook.filter, changes.changes)
+            .await
+            .change_context(WebhookDeliveryError)?
+            .iter()
+            .map(|change| serde_json::to_value(WebhookPayload { webhook_id, change }))
+            .collect::<Result<Vec<_>, _>>()
+            .change_context(WebhookDeliveryError)?;
+
+        Ok(Some(PendingDelivery {
+            target,
+            cursor,
+            payloads,
+        }))
+    }
+
+    /// Sends the payloads of `delivery` in order and returns the dead letters of the payloads,
+    /// which could not be delivered.
+    async fn send_all(&self, delivery: &PendingDelivery) -> Vec<WebhookDeadLetter> {
+        let webhook_id = delivery.target.webhook.webhook_id;
+
+        let mut dead_letters = Vec::new();
+        for payload in &delivery.payloads {
+            if let Err((attempts, last_error)) = self.send(&delivery.target, payload).await {
+                tracing::warn!(%webhook_id, attempts, %last_error, "Could not deliver change");
+                dead_letters.push(WebhookDeadLetter {
+                    webhook_id,
+                    payload: payload.clone(),
+                    attempts,
+                    last_error,
+                    failed_at: Timestamp::now(),
+                });
+            }
+        }
+        dead_letters
+    }
+
+    /// Stores the dead letters of `delivery` and advances the cursor of the webhook.
+    async fn complete<S>(
+        store: &mut S,
+        delivery: &PendingDelivery,
+        dead_letters: Vec<WebhookDeadLetter>,
+    ) -> Result<(), Report<WebhookDeliveryError>>
+    where
+        S: WebhookStore + Send,
+    {
+        for dead_letter in dead_letters {
+            store
+                .insert_webhook_dead_letter(dead_letter)
+                .await
+                .change_context(WebhookDeliveryError)?;
+        }
+
+        store
+            .advance_webhook_cursor(delivery.target.webhook.webhook_id, delivery.cursor)
+            .await
+            .change_context(WebhookDeliveryError)?;
+
+        Ok(())
+    }
+
+    /// Sends `payload` to the webhook until it responds with a success status.
+    ///
+    /// Returns the number of attempts and the last error if the payload could not be delivered.
+    async fn send(
+        &self,
+        target: &WebhookTarget,
+        payload: &serde_json::Value,
+    ) -> Result<(), (u32, String)> {
+        let body = payload.to_string().into_bytes();
+
+        let mut backoff = self.config.initial_backoff;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            // Every attempt is signed with the time it's sent at.
+            let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+            let result = self
+                .client
+                .post(&target.webhook.url)
+                .header(CONTENT_TYPE, "application/json")
+                .header(WEBHOOK_ID_HEADER, target.webhook.webhook_id.to_string())
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(
+                    SIGNATURE_HEADER,
+                    sign_payload(&target.secret, timestamp, &body),
+                )
+                .body(body.clone())
+                .send()
+                .await
+                .and_then(Response::error_for_status)
+                .map_err(|error| error.to_string())
+                .and_then(|response| {
+                    // Redirects are not followed, so the payload was not delivered.
+                    if response.status().is_redirection() {
+                        Err(format!("the webhook redirected with {}", response.status()))
+                    } else {
+                        Ok(())
+                    }
+                });
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err(error) if attempts >= self.config.max_attempts => {
+                    return Err((attempts, error));
+                }
+                Err(error) => {
+                    tracing::debug!(%error, attempts, "Could not deliver payload, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+}
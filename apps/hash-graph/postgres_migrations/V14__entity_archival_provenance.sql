@@ -0,0 +1,5 @@
+-- The archiver of editions archived before this migration is unknown, so it's left `NULL`
+ALTER TABLE
+  "entity_editions"
+ADD COLUMN
+  "record_archived_by_id" UUID REFERENCES "accounts";
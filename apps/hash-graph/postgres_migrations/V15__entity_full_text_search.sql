@@ -0,0 +1,6 @@
+-- The expression has to match the expression used when searching for entities, otherwise the index
+-- is not used.
+CREATE INDEX
+  "entity_editions_properties_search_idx" ON "entity_editions" USING GIN (
+    jsonb_to_tsvector('simple'::regconfig, "properties", '["string"]'::jsonb)
+  );
@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS
+  "webhooks" (
+    "webhook_id" UUID PRIMARY KEY,
+    "url" TEXT NOT NULL,
+    -- The secret is required to sign the payloads, so it's stored in plain text. It must not be
+    -- logged or exported.
+    "secret" TEXT NOT NULL,
+    "entity_type_base_url" TEXT,
+    "owned_by_id" UUID,
+    "record_created_by_id" UUID NOT NULL REFERENCES "accounts",
+    -- The position in the change feed up to which changes were delivered.
+    "cursor_transaction_time" TIMESTAMP WITH TIME ZONE NOT NULL,
+    "cursor_change_id" UUID NOT NULL
+  );
+
+CREATE TABLE IF NOT EXISTS
+  "webhook_dead_letters" (
+    "webhook_id" UUID NOT NULL REFERENCES "webhooks" ON DELETE CASCADE,
+    "payload" JSONB NOT NULL,
+    "attempts" INTEGER NOT NULL,
+    "last_error" TEXT NOT NULL,
+    "failed_at" TIMESTAMP WITH TIME ZONE NOT NULL
+  );
+
+CREATE INDEX "webhook_dead_letters_webhook_id_idx" ON "webhook_dead_letters" ("webhook_id");
@@ -0,0 +1,11 @@
+-- Entities which were permanently erased. Only the identifiers are kept, the editions and their
+-- properties are removed. An entity ID may appear multiple times if it was reused after erasure.
+CREATE TABLE IF NOT EXISTS
+  "entity_tombstones" (
+    "owned_by_id" UUID NOT NULL,
+    "entity_uuid" UUID NOT NULL,
+    "erased_by_id" UUID NOT NULL REFERENCES "accounts",
+    "erased_at" TIMESTAMP WITH TIME ZONE NOT NULL
+  );
+
+CREATE INDEX "entity_tombstones_entity_id_idx" ON "entity_tombstones" ("owned_by_id", "entity_uuid");
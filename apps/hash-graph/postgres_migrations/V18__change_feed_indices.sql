@@ -0,0 +1,22 @@
+-- Used by the change feed to scan the editions of entities ordered by the start of their transaction
+-- time and to look up earlier rows of an edition or an entity.
+CREATE INDEX "entity_temporal_metadata_transaction_time_idx" ON "entity_temporal_metadata" (
+  lower("transaction_time"),
+  "entity_edition_id"
+);
+
+CREATE INDEX "entity_temporal_metadata_edition_transaction_time_idx" ON "entity_temporal_metadata" (
+  "entity_edition_id",
+  lower("transaction_time")
+);
+
+CREATE INDEX "entity_temporal_metadata_entity_transaction_time_idx" ON "entity_temporal_metadata" (
+  "owned_by_id",
+  "entity_uuid",
+  lower("transaction_time")
+);
+
+CREATE INDEX "ontology_temporal_metadata_transaction_time_idx" ON "ontology_temporal_metadata" (
+  "ontology_id",
+  lower("transaction_time")
+);
@@ -0,0 +1,30 @@
+-- Entities are identified by their UUID alone in the authorization backend, so the permissions of
+-- an entity would also apply to an entity in another web sharing its UUID. Entity UUIDs therefore
+-- have to be unique across all webs.
+--
+-- Entities sharing a UUID cannot be merged automatically, so the migration fails with a list of the
+-- affected entities before the index is created. They have to be resolved manually by removing
+-- all but one of the entities sharing a UUID before the migration is run again.
+DO $pga$
+  DECLARE
+    _duplicates TEXT;
+  BEGIN
+    SELECT string_agg(duplicate.entity_ids, E'\n')
+      INTO _duplicates
+      FROM (
+        SELECT entity_uuid || ': ' || string_agg(owned_by_id::TEXT, ', ' ORDER BY owned_by_id) AS entity_ids
+          FROM entity_ids
+         GROUP BY entity_uuid
+        HAVING count(*) > 1
+      ) AS duplicate;
+
+    IF _duplicates IS NOT NULL THEN
+      RAISE EXCEPTION 'Entity UUIDs are used in more than one web'
+      USING ERRCODE = 'unique_violation',
+            DETAIL = 'The following entity UUIDs are used by the listed webs:' || E'\n' || _duplicates,
+            HINT = 'Remove the duplicated entities so every entity UUID is used in a single web.';
+    END IF;
+  END
+$pga$;
+
+CREATE UNIQUE INDEX "entity_ids_entity_uuid_idx" ON "entity_ids" ("entity_uuid");
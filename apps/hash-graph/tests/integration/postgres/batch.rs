@@ -0,0 +1,198 @@
+use graph::store::{
+    BatchLinkData, BulkItemFailure, EntityReference, WriteOperation, WriteOperationOutput,
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    knowledge::{
+        entity::{EntityId, EntityProperties, EntityUuid},
+        link::EntityLinkOrder,
+    },
+    ontology::{
+        PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
+    },
+    provenance::OwnedById,
+};
+use type_system::{
+    repr,
+    url::{BaseUrl, VersionedUrl},
+    EntityType,
+};
+use uuid::Uuid;
+
+use crate::DatabaseTestWrapper;
+
+fn person_type_id() -> VersionedUrl {
+    VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    }
+}
+
+fn friend_of_type_id() -> VersionedUrl {
+    VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    }
+}
+
+#[tokio::test]
+async fn create_type_and_linked_entities() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let bob = serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity");
+    let person_type = EntityType::try_from(
+        serde_json::from_str::<repr::EntityType>(entity_type::PERSON_V1)
+            .expect("could not parse entity type representation"),
+    )
+    .expect("could not parse entity type");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [entity_type::LINK_V1, entity_type::link::FRIEND_OF_V1],
+        )
+        .await
+        .expect("could not seed database");
+    let owned_by_id = OwnedById::new(api.account_id.into_uuid());
+
+    let outputs = api
+        .write_batch(vec![
+            WriteOperation::CreateEntityType {
+                metadata: PartialEntityTypeMetadata {
+                    record_id: person_type.id().clone().into(),
+                    custom: PartialCustomEntityTypeMetadata {
+                        common: PartialCustomOntologyMetadata::Owned { owned_by_id },
+                        label_property: None,
+                    },
+                },
+                schema: Box::new(person_type),
+            },
+            WriteOperation::CreateEntity {
+                owned_by_id,
+                entity_uuid: None,
+                entity_type_id: person_type_id(),
+                properties: alice,
+                link_data: None,
+            },
+            WriteOperation::CreateEntity {
+                owned_by_id,
+                entity_uuid: None,
+                entity_type_id: person_type_id(),
+                properties: bob,
+                link_data: None,
+            },
+            WriteOperation::CreateEntity {
+                owned_by_id,
+                entity_uuid: None,
+                entity_type_id: friend_of_type_id(),
+                properties: EntityProperties::empty(),
+                link_data: Some(BatchLinkData {
+                    left_entity: EntityReference::Created { operation_index: 1 },
+                    right_entity: EntityReference::Created { operation_index: 2 },
+                    order: EntityLinkOrder {
+                        left_to_right: None,
+                        right_to_left: None,
+                    },
+                }),
+            },
+        ])
+        .await
+        .expect("could not write batch");
+
+    let [
+        WriteOperationOutput::EntityType(_),
+        WriteOperationOutput::Entity(alice_metadata),
+        WriteOperationOutput::Entity(bob_metadata),
+        WriteOperationOutput::Entity(_),
+    ] = outputs.as_slice()
+    else {
+        panic!("unexpected batch outputs: {outputs:?}");
+    };
+
+    let link_entity = api
+        .get_link_entity_target(alice_metadata.record_id().entity_id, friend_of_type_id())
+        .await
+        .expect("could not fetch entity");
+    let link_data = link_entity.link_data.expect("entity is not a link");
+
+    assert_eq!(
+        link_data.left_entity_id,
+        alice_metadata.record_id().entity_id
+    );
+    assert_eq!(
+        link_data.right_entity_id,
+        bob_metadata.record_id().entity_id
+    );
+}
+
+#[tokio::test]
+async fn rollback_on_failure() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+    let owned_by_id = OwnedById::new(api.account_id.into_uuid());
+    let alice_id = EntityId {
+        owned_by_id,
+        entity_uuid: EntityUuid::new(Uuid::new_v4()),
+    };
+
+    let report = api
+        .write_batch(vec![
+            WriteOperation::CreateEntity {
+                owned_by_id,
+                entity_uuid: Some(alice_id.entity_uuid),
+                entity_type_id: person_type_id(),
+                properties: alice,
+                link_data: None,
+            },
+            WriteOperation::CreateEntity {
+                owned_by_id,
+                entity_uuid: None,
+                entity_type_id: friend_of_type_id(),
+                properties: EntityProperties::empty(),
+                link_data: Some(BatchLinkData {
+                    left_entity: EntityReference::Created { operation_index: 0 },
+                    // References can only point to earlier operations.
+                    right_entity: EntityReference::Created { operation_index: 1 },
+                    order: EntityLinkOrder {
+                        left_to_right: None,
+                        right_to_left: None,
+                    },
+                }),
+            },
+        ])
+        .await
+        .expect_err("could write batch with invalid reference");
+
+    let failures = report
+        .request_ref::<BulkItemFailure>()
+        .map(|failure| failure.index)
+        .collect::<Vec<_>>();
+    assert_eq!(failures, [1]);
+
+    assert!(
+        api.get_entities(alice_id)
+            .await
+            .expect("could not get entities")
+            .is_empty()
+    );
+}
@@ -0,0 +1,105 @@
+use graph::store::{ChangeKind, ChangedRecordId};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::knowledge::entity::EntityProperties;
+use type_system::url::{BaseUrl, VersionedUrl};
+
+use crate::DatabaseTestWrapper;
+
+#[tokio::test]
+async fn read_changes() {
+    let person: EntityProperties =
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+    let created_metadata = api
+        .create_entity(person.clone(), person_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+    let archived_metadata = api
+        .archive_entity(created_metadata.record_id().entity_id, false)
+        .await
+        .expect("could not archive entity")
+        .remove(0);
+    let erased_entity_id = api
+        .create_entity(person, person_type_id, None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+    api.erase_entity(erased_entity_id, false)
+        .await
+        .expect("could not erase entity");
+
+    let all_changes = api
+        .read_changes(None, 10_000)
+        .await
+        .expect("could not read changes")
+        .changes;
+
+    let kind_of = |record_id: ChangedRecordId| {
+        all_changes
+            .iter()
+            .find(|change| change.record_id == record_id)
+            .map(|change| change.kind)
+    };
+    assert_eq!(
+        kind_of(ChangedRecordId::Entity(created_metadata.record_id())),
+        Some(ChangeKind::Created)
+    );
+    assert_eq!(
+        kind_of(ChangedRecordId::Entity(archived_metadata.record_id())),
+        Some(ChangeKind::Archived)
+    );
+    assert_eq!(
+        kind_of(ChangedRecordId::ErasedEntity(erased_entity_id)),
+        Some(ChangeKind::Erased)
+    );
+    // The editions of the erased entity are removed together with their changes.
+    assert!(!all_changes.iter().any(|change| matches!(
+        change.record_id,
+        ChangedRecordId::Entity(record_id) if record_id.entity_id == erased_entity_id
+    )));
+    assert!(all_changes.iter().any(|change| matches!(
+        &change.record_id,
+        ChangedRecordId::DataType(record_id)
+            if record_id.base_url.as_str()
+                == "https://blockprotocol.org/@blockprotocol/types/data-type/text/"
+    )));
+
+    // Reading the feed page by page returns the same changes in the same order.
+    let mut paged_changes = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = api
+            .read_changes(cursor, 2)
+            .await
+            .expect("could not read changes");
+        if page.changes.is_empty() {
+            assert_eq!(page.cursor, cursor);
+            break;
+        }
+        paged_changes.extend(page.changes);
+        cursor = page.cursor;
+    }
+    assert_eq!(paged_changes, all_changes);
+}
@@ -1,8 +1,16 @@
-use graph::store::{
-    error::{OntologyTypeIsNotOwned, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
-    BaseUrlAlreadyExists,
+use graph::{
+    store::{
+        error::{OntologyTypeIsNotOwned, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
+        BaseUrlAlreadyExists, OntologyTypeDependencyReference,
+    },
+    subgraph::edges::OntologyEdgeKind,
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use type_system::{
+    repr,
+    url::{BaseUrl, VersionedUrl},
+    DataType,
 };
-use type_system::{repr, DataType};
 
 use crate::DatabaseTestWrapper;
 
@@ -249,3 +257,92 @@ async fn update_external_with_owned() {
         "wrong error, expected `OntologyTypeIsNotOwned`, got {report:?}"
     );
 }
+
+#[tokio::test]
+async fn dependents() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let text_data_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@blockprotocol/types/data-type/text/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+    let name_property_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/property-type/name/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+    let person_entity_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    for person in [entity::PERSON_ALICE_V1, entity::PERSON_BOB_V1] {
+        api.create_entity(
+            serde_json::from_str(person).expect("could not parse entity"),
+            person_entity_type_id.clone(),
+            None,
+        )
+        .await
+        .expect("could not create entity");
+    }
+
+    let dependents = api
+        .get_data_type_dependents(&text_data_type_id)
+        .await
+        .expect("could not read dependents");
+    assert_eq!(dependents.entity_count, 2);
+    assert_eq!(dependents.dependents.len(), 2);
+
+    let name_dependent = &dependents.dependents[0];
+    assert_eq!(name_dependent.type_id, name_property_type_id);
+    assert_eq!(name_dependent.depth, 1);
+    assert_eq!(name_dependent.entity_count, None);
+    assert_eq!(
+        name_dependent.references,
+        [OntologyTypeDependencyReference {
+            type_id: text_data_type_id,
+            edge_kind: OntologyEdgeKind::ConstrainsValuesOn,
+        }]
+    );
+
+    let person_dependent = &dependents.dependents[1];
+    assert_eq!(person_dependent.type_id, person_entity_type_id);
+    assert_eq!(person_dependent.depth, 2);
+    assert_eq!(person_dependent.entity_count, Some(2));
+    assert_eq!(
+        person_dependent.references,
+        [OntologyTypeDependencyReference {
+            type_id: name_property_type_id,
+            edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+        }]
+    );
+
+    let report = api
+        .get_data_type_dependents(&person_entity_type_id)
+        .await
+        .expect_err("could read dependents of an entity type as data type");
+    assert!(
+        report.contains::<OntologyVersionDoesNotExist>(),
+        "wrong error, expected `OntologyVersionDoesNotExist`, got {report:?}"
+    );
+}
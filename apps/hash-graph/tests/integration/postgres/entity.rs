@@ -1,5 +1,14 @@
+use std::borrow::Cow;
+
+use graph::{
+    knowledge::{diff_properties, PropertyDiff, PropertyPatchError, PropertyPatchOperation},
+    store::{error::EntityEditionMismatch, query::PathToken},
+};
 use graph_test_data::{data_type, entity, entity_type, property_type};
-use graph_types::knowledge::{entity::EntityProperties, link::EntityLinkOrder};
+use graph_types::knowledge::{
+    entity::{EntityEditionId, EntityMetadata, EntityProperties},
+    link::EntityLinkOrder,
+};
 use temporal_versioning::ClosedTemporalBound;
 use type_system::url::{BaseUrl, VersionedUrl};
 
@@ -87,6 +96,67 @@ async fn query() {
     assert_eq!(queried_organizations[0].properties, organization);
 }
 
+#[tokio::test]
+async fn search() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let mut entity_ids = Vec::new();
+    for person in [entity::PERSON_ALICE_V1, entity::PERSON_BOB_V1] {
+        let properties: EntityProperties =
+            serde_json::from_str(person).expect("could not parse entity");
+        entity_ids.push(
+            api.create_entity(properties, person_type_id.clone(), None)
+                .await
+                .expect("could not create entity")
+                .record_id()
+                .entity_id,
+        );
+    }
+
+    let found = api
+        .search_entities("alice")
+        .await
+        .expect("could not search entities");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].0, entity_ids[0]);
+    assert!(found[0].1 > 0.0);
+
+    let found = api
+        .search_entities("alice or bob")
+        .await
+        .expect("could not search entities");
+    assert_eq!(found.len(), 2);
+    assert!(found.iter().all(|(_, rank)| *rank > 0.0));
+    assert!(found[0].1 >= found[1].1);
+
+    assert!(
+        api.search_entities("charles")
+            .await
+            .expect("could not search entities")
+            .is_empty()
+    );
+}
+
 #[tokio::test]
 async fn update() {
     let page_v1: EntityProperties =
@@ -122,6 +192,7 @@ async fn update() {
     let v2_metadata = api
         .update_entity(
             v1_metadata.record_id().entity_id,
+            None,
             page_v2.clone(),
             VersionedUrl {
                 base_url: BaseUrl::new(
@@ -168,4 +239,193 @@ async fn update() {
         .expect("could not get entity v2");
 
     assert_eq!(entity_v2.properties, page_v2);
+
+    let history = api
+        .get_entity_history(v2_metadata.record_id().entity_id)
+        .await
+        .expect("could not get entity history");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].metadata.record_id(), v1_metadata.record_id());
+    assert_eq!(history[1].metadata.record_id(), v2_metadata.record_id());
+    assert!(
+        history[0]
+            .property_diff
+            .iter()
+            .all(|diff| matches!(diff, PropertyDiff::Added { .. }))
+    );
+    assert_eq!(
+        history[1].property_diff,
+        diff_properties(&page_v1, &page_v2)
+    );
+    assert!(!history[1].property_diff.is_empty());
+
+    let diff = api
+        .diff_entity(
+            v2_metadata.record_id().entity_id,
+            entity_v1_timestamp,
+            entity_v2_timestamp,
+        )
+        .await
+        .expect("could not diff entity");
+    assert_eq!(
+        diff.old_metadata.as_ref().map(EntityMetadata::record_id),
+        Some(v1_metadata.record_id())
+    );
+    assert_eq!(
+        diff.new_metadata.as_ref().map(EntityMetadata::record_id),
+        Some(v2_metadata.record_id())
+    );
+    assert_eq!(diff.property_diff, history[1].property_diff);
+    assert!(diff.entity_type_id_diff.is_none());
+    assert!(diff.link_data_diff.is_none());
+}
+
+#[tokio::test]
+async fn update_with_expected_edition() {
+    let page_v1: EntityProperties =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: EntityProperties =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+    let page_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/page/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+    let link_order = EntityLinkOrder {
+        left_to_right: None,
+        right_to_left: None,
+    };
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::TEXT_V1],
+            [entity_type::PAGE_V1],
+        )
+        .await
+        .expect("could not seed database:");
+
+    let v1_metadata = api
+        .create_entity(page_v1.clone(), page_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+    let entity_id = v1_metadata.record_id().entity_id;
+
+    let v2_metadata = api
+        .update_entity(
+            entity_id,
+            Some(v1_metadata.record_id().edition_id),
+            page_v2.clone(),
+            page_type_id.clone(),
+            link_order,
+        )
+        .await
+        .expect("could not update entity");
+
+    // The second client still expects the first edition.
+    let report = api
+        .update_entity(
+            entity_id,
+            Some(v1_metadata.record_id().edition_id),
+            page_v1,
+            page_type_id,
+            link_order,
+        )
+        .await
+        .expect_err("could update entity with outdated edition");
+    assert!(report.contains::<EntityEditionMismatch>());
+    assert_eq!(
+        report.request_ref::<EntityEditionId>().next(),
+        Some(&v2_metadata.record_id().edition_id)
+    );
+
+    let entity = api
+        .get_latest_entity(entity_id)
+        .await
+        .expect("could not get entity");
+    assert_eq!(entity.properties, page_v2);
+}
+
+#[tokio::test]
+async fn patch() {
+    let page_v1: EntityProperties =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: EntityProperties =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+    let text_path = vec![PathToken::Field(Cow::Borrowed(
+        "https://blockprotocol.org/@alice/types/property-type/text/",
+    ))];
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::TEXT_V1],
+            [entity_type::PAGE_V1],
+        )
+        .await
+        .expect("could not seed database:");
+
+    let v1_metadata = api
+        .create_entity(
+            page_v1,
+            VersionedUrl {
+                base_url: BaseUrl::new(
+                    "https://blockprotocol.org/@alice/types/entity-type/page/".to_owned(),
+                )
+                .expect("couldn't construct Base URL"),
+                version: 1,
+            },
+            None,
+        )
+        .await
+        .expect("could not create entity");
+    let entity_id = v1_metadata.record_id().entity_id;
+
+    let v2_metadata = api
+        .patch_entity(
+            entity_id,
+            vec![
+                PropertyPatchOperation::Test {
+                    path: text_path.clone(),
+                    value: serde_json::json!("Intentionally blank page"),
+                },
+                PropertyPatchOperation::Replace {
+                    path: text_path.clone(),
+                    value: serde_json::json!("This page was intentionally left blank"),
+                },
+            ],
+        )
+        .await
+        .expect("could not patch entity");
+    assert_ne!(
+        v1_metadata.record_id().edition_id,
+        v2_metadata.record_id().edition_id
+    );
+
+    // The test operation fails as the value was replaced by the previous patch.
+    let report = api
+        .patch_entity(
+            entity_id,
+            vec![
+                PropertyPatchOperation::Test {
+                    path: text_path.clone(),
+                    value: serde_json::json!("Intentionally blank page"),
+                },
+                PropertyPatchOperation::Remove { path: text_path },
+            ],
+        )
+        .await
+        .expect_err("could apply patch with failing test");
+    assert!(report.contains::<PropertyPatchError>());
+
+    let entity = api
+        .get_latest_entity(entity_id)
+        .await
+        .expect("could not get entity");
+    assert_eq!(entity.properties, page_v2);
+    assert_eq!(entity.metadata.record_id(), v2_metadata.record_id());
 }
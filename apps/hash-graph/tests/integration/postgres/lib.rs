@@ -6,28 +6,38 @@
     clippy::unwrap_used
 )]
 
+mod batch;
+mod change_feed;
 mod data_type;
 mod entity;
 mod entity_type;
 mod links;
+mod permissions;
 mod property_type;
+mod webhook;
 
 use std::{borrow::Cow, str::FromStr};
 
 use authorization::NoAuthorization;
 use error_stack::Result;
 use graph::{
-    knowledge::EntityQueryPath,
+    knowledge::{EntityDiff, EntityQueryPath, PropertyPatchOperation},
     load_env,
     ontology::EntityTypeQueryPath,
     store::{
-        query::{Filter, FilterExpression, Parameter},
-        AccountStore, ConflictBehavior, DataTypeStore, DatabaseConnectionInfo, DatabaseType,
-        EntityStore, EntityTypeStore, InsertionError, PostgresStore, PostgresStorePool,
-        PropertyTypeStore, QueryError, StorePool, UpdateError,
+        error::DeletionError,
+        query::{Filter, FilterExpression, Parameter, Sorting, SortingDirection, SortingPath},
+        AccountStore, BatchStore, ChangeCursor, ChangeFeedStore, Changes, ConflictBehavior,
+        DataTypeStore, DatabaseConnectionInfo, DatabaseType, EntityHistoryEntry, EntityPathQuery,
+        EntityStore, EntityTypeStore, InsertionError, OntologyTypeDependents, PostgresStore,
+        PostgresStorePool, PropertyTypeStore, QueryError, StorePool, UpdateError, Webhook,
+        WebhookDeadLetter, WebhookFilter, WebhookStore, WriteOperation, WriteOperationOutput,
     },
     subgraph::{
-        edges::{EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind, SharedEdgeKind},
+        edges::{
+            EdgeDirection, EdgeResolveDepths, EdgeTraversalFilters, GraphResolveDepths,
+            KnowledgeGraphEdgeKind, KnowledgeGraphTraversalFilters, SharedEdgeKind,
+        },
         identifier::{
             DataTypeVertexId, EntityTypeVertexId, GraphElementVertexId, PropertyTypeVertexId,
         },
@@ -37,12 +47,13 @@ use graph::{
             VariableTemporalAxisUnresolved,
         },
     },
+    webhook::{WebhookDeliveryError, WebhookDispatcher},
     Environment,
 };
 use graph_types::{
     account::AccountId,
     knowledge::{
-        entity::{Entity, EntityId, EntityMetadata, EntityProperties, EntityUuid},
+        entity::{Entity, EntityEditionId, EntityId, EntityMetadata, EntityProperties, EntityUuid},
         link::{EntityLinkOrder, LinkData},
     },
     ontology::{
@@ -52,7 +63,9 @@ use graph_types::{
     },
     provenance::OwnedById,
 };
-use temporal_versioning::{DecisionTime, LimitedTemporalBound, TemporalBound, Timestamp};
+use temporal_versioning::{
+    DecisionTime, LimitedTemporalBound, TemporalBound, TemporalTagged, TimeAxis, Timestamp,
+};
 use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
 use tokio_postgres::{NoTls, Transaction};
 use type_system::{repr, url::VersionedUrl, DataType, EntityType, PropertyType};
@@ -266,6 +279,7 @@ impl DatabaseApi<'_> {
                 &StructuralQuery {
                     filter: Filter::for_versioned_url(url),
                     graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(
@@ -273,6 +287,9 @@ impl DatabaseApi<'_> {
                             None,
                         ),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
             .await?
@@ -282,6 +299,15 @@ impl DatabaseApi<'_> {
             .expect("no data type found"))
     }
 
+    pub async fn get_data_type_dependents(
+        &self,
+        url: &VersionedUrl,
+    ) -> Result<OntologyTypeDependents, QueryError> {
+        self.store
+            .get_data_type_dependents(self.account_id, &NoAuthorization, url)
+            .await
+    }
+
     pub async fn update_data_type(
         &mut self,
         data_type: DataType,
@@ -324,6 +350,7 @@ impl DatabaseApi<'_> {
                 &StructuralQuery {
                     filter: Filter::for_versioned_url(url),
                     graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(
@@ -331,6 +358,9 @@ impl DatabaseApi<'_> {
                             None,
                         ),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
             .await?
@@ -380,6 +410,7 @@ impl DatabaseApi<'_> {
                 &StructuralQuery {
                     filter: Filter::for_versioned_url(url),
                     graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(
@@ -387,6 +418,9 @@ impl DatabaseApi<'_> {
                             None,
                         ),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
             .await?
@@ -435,6 +469,7 @@ impl DatabaseApi<'_> {
                 &StructuralQuery {
                     filter: Filter::for_entity_by_entity_id(entity_id),
                     graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(
@@ -442,9 +477,13 @@ impl DatabaseApi<'_> {
                             None,
                         ),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
             .await?
+            .0
             .vertices
             .entities
             .into_values()
@@ -464,6 +503,7 @@ impl DatabaseApi<'_> {
                 &StructuralQuery {
                     filter: Filter::for_entity_by_entity_id(entity_id),
                     graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(
@@ -471,9 +511,13 @@ impl DatabaseApi<'_> {
                             Some(LimitedTemporalBound::Inclusive(timestamp)),
                         ),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
             .await?
+            .0
             .vertices
             .entities
             .into_values()
@@ -491,13 +535,18 @@ impl DatabaseApi<'_> {
                 &StructuralQuery {
                     filter: Filter::for_entity_by_entity_id(entity_id),
                     graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(None, None),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
             .await?
+            .0
             .vertices
             .entities
             .into_values()
@@ -506,9 +555,111 @@ impl DatabaseApi<'_> {
         Ok(entities.into_iter().next().unwrap())
     }
 
+    /// Searches the properties of all entities for `query` and returns the found entities
+    /// alongside their rank, starting with the highest rank.
+    pub async fn search_entities(&self, query: &str) -> Result<Vec<(EntityId, f32)>, QueryError> {
+        let (subgraph, _, search_ranks) = self
+            .store
+            .get_entity(
+                self.account_id,
+                &NoAuthorization,
+                &StructuralQuery {
+                    filter: Filter::Search(
+                        FilterExpression::Path(EntityQueryPath::Properties(None)),
+                        FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(query))),
+                    ),
+                    graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
+                    temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                        pinned: PinnedTemporalAxisUnresolved::new(None),
+                        variable: VariableTemporalAxisUnresolved::new(None, None),
+                    },
+                    order_by: vec![Sorting {
+                        path: SortingPath::SearchRank,
+                        direction: SortingDirection::Descending,
+                        nulls: None,
+                    }],
+                    limit: None,
+                    cursor: None,
+                },
+            )
+            .await?;
+
+        Ok(subgraph
+            .roots
+            .into_iter()
+            .filter_map(|vertex_id| match vertex_id {
+                GraphElementVertexId::KnowledgeGraph(vertex_id) => {
+                    Some((vertex_id.base_id, search_ranks[&vertex_id]))
+                }
+                _ => None,
+            })
+            .collect())
+    }
+
+    pub async fn get_entity_history(
+        &self,
+        entity_id: EntityId,
+    ) -> Result<Vec<EntityHistoryEntry>, QueryError> {
+        self.store
+            .get_entity_history(
+                self.account_id,
+                &NoAuthorization,
+                entity_id,
+                QueryTemporalAxesUnresolved::default(),
+            )
+            .await
+    }
+
+    pub async fn diff_entity(
+        &self,
+        entity_id: EntityId,
+        old_timestamp: Timestamp<DecisionTime>,
+        new_timestamp: Timestamp<DecisionTime>,
+    ) -> Result<EntityDiff, QueryError> {
+        self.store
+            .diff_entity(
+                self.account_id,
+                &NoAuthorization,
+                entity_id,
+                TimeAxis::DecisionTime,
+                Some(old_timestamp.cast()),
+                Some(new_timestamp.cast()),
+            )
+            .await
+    }
+
+    pub async fn get_entity_paths(
+        &self,
+        source_entity_id: EntityId,
+        target_entity_id: EntityId,
+        max_depth: u8,
+        limit: usize,
+    ) -> Result<Vec<Vec<EntityId>>, QueryError> {
+        let (_, paths) = self
+            .store
+            .get_entity_paths(
+                self.account_id,
+                &NoAuthorization,
+                &EntityPathQuery {
+                    source_entity_id,
+                    target_entity_id,
+                    max_depth,
+                    limit,
+                    temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                        pinned: PinnedTemporalAxisUnresolved::new(None),
+                        variable: VariableTemporalAxisUnresolved::new(None, None),
+                    },
+                },
+            )
+            .await?;
+        Ok(paths)
+    }
+
     pub async fn update_entity(
         &mut self,
         entity_id: EntityId,
+        expected_edition_id: Option<EntityEditionId>,
         properties: EntityProperties,
         entity_type_id: VersionedUrl,
         link_order: EntityLinkOrder,
@@ -518,6 +669,7 @@ impl DatabaseApi<'_> {
                 self.account_id,
                 &mut NoAuthorization,
                 entity_id,
+                expected_edition_id,
                 Some(generate_decision_time()),
                 false,
                 entity_type_id,
@@ -527,6 +679,105 @@ impl DatabaseApi<'_> {
             .await
     }
 
+    pub async fn patch_entity(
+        &mut self,
+        entity_id: EntityId,
+        operations: Vec<PropertyPatchOperation>,
+    ) -> Result<EntityMetadata, UpdateError> {
+        self.store
+            .patch_entity(
+                self.account_id,
+                &mut NoAuthorization,
+                entity_id,
+                Some(generate_decision_time()),
+                operations,
+            )
+            .await
+    }
+
+    pub async fn read_changes(
+        &self,
+        cursor: Option<ChangeCursor>,
+        limit: usize,
+    ) -> Result<Changes, QueryError> {
+        self.store
+            .read_changes(self.account_id, &NoAuthorization, cursor, limit)
+            .await
+    }
+
+    pub async fn register_webhook(
+        &mut self,
+        url: String,
+        secret: String,
+        filter: WebhookFilter,
+    ) -> Result<Webhook, InsertionError> {
+        self.store
+            .register_webhook(self.account_id, url, secret, filter)
+            .await
+    }
+
+    pub async fn deliver_webhooks(
+        &mut self,
+        dispatcher: &WebhookDispatcher,
+    ) -> Result<usize, WebhookDeliveryError> {
+        dispatcher
+            .deliver_pending(&mut self.store, &NoAuthorization)
+            .await
+    }
+
+    pub async fn get_webhook_dead_letters(
+        &self,
+        webhook_id: Uuid,
+    ) -> Result<Vec<WebhookDeadLetter>, QueryError> {
+        self.store
+            .get_webhook_dead_letters(self.account_id, webhook_id)
+            .await
+    }
+
+    pub async fn archive_entity(
+        &mut self,
+        entity_id: EntityId,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        self.store
+            .archive_entity(self.account_id, &mut NoAuthorization, entity_id, cascade)
+            .await
+    }
+
+    pub async fn unarchive_entity(
+        &mut self,
+        entity_id: EntityId,
+        cascade: bool,
+    ) -> Result<Vec<EntityMetadata>, UpdateError> {
+        self.store
+            .unarchive_entity(self.account_id, &mut NoAuthorization, entity_id, cascade)
+            .await
+    }
+
+    pub async fn erase_entity(
+        &mut self,
+        entity_id: EntityId,
+        erase_links: bool,
+    ) -> Result<Vec<EntityId>, DeletionError> {
+        self.store
+            .erase_entity(
+                self.account_id,
+                &mut NoAuthorization,
+                entity_id,
+                erase_links,
+            )
+            .await
+    }
+
+    pub async fn write_batch(
+        &mut self,
+        operations: Vec<WriteOperation>,
+    ) -> Result<Vec<WriteOperationOutput>, InsertionError> {
+        self.store
+            .write_batch(self.account_id, &mut NoAuthorization, operations)
+            .await
+    }
+
     async fn create_link_entity(
         &mut self,
         properties: EntityProperties,
@@ -613,6 +864,7 @@ impl DatabaseApi<'_> {
                 &StructuralQuery {
                     filter,
                     graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(
@@ -620,9 +872,13 @@ impl DatabaseApi<'_> {
                             None,
                         ),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
-            .await?;
+            .await?
+            .0;
 
         let roots = subgraph
             .roots
@@ -641,6 +897,81 @@ impl DatabaseApi<'_> {
         }
     }
 
+    /// Returns the link entities of the given type and their right endpoints by traversing the
+    /// graph from the source entity.
+    pub async fn get_linked_entities(
+        &self,
+        source_entity_id: EntityId,
+        link_type_id: &VersionedUrl,
+    ) -> Result<Vec<Entity>, QueryError> {
+        let link_type_filter = Filter::All(vec![
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                    edge_kind: SharedEdgeKind::IsOfType,
+                    path: EntityTypeQueryPath::BaseUrl,
+                    inheritance_depth: Some(0),
+                })),
+                Some(FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(
+                    link_type_id.base_url.as_str(),
+                )))),
+            ),
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                    edge_kind: SharedEdgeKind::IsOfType,
+                    path: EntityTypeQueryPath::Version,
+                    inheritance_depth: Some(0),
+                })),
+                Some(FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(link_type_id.version),
+                ))),
+            ),
+        ]);
+
+        let subgraph = self
+            .store
+            .get_entity(
+                self.account_id,
+                &NoAuthorization,
+                &StructuralQuery {
+                    filter: Filter::for_entity_by_entity_id(source_entity_id),
+                    graph_resolve_depths: GraphResolveDepths {
+                        has_left_entity: EdgeResolveDepths {
+                            incoming: 1,
+                            outgoing: 0,
+                        },
+                        has_right_entity: EdgeResolveDepths {
+                            incoming: 0,
+                            outgoing: 1,
+                        },
+                        ..GraphResolveDepths::default()
+                    },
+                    traversal_filters: KnowledgeGraphTraversalFilters {
+                        has_left_entity: EdgeTraversalFilters {
+                            incoming: Some(link_type_filter),
+                            outgoing: None,
+                        },
+                        has_right_entity: EdgeTraversalFilters::default(),
+                    },
+                    temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                        pinned: PinnedTemporalAxisUnresolved::new(None),
+                        variable: VariableTemporalAxisUnresolved::new(None, None),
+                    },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
+                },
+            )
+            .await?
+            .0;
+
+        Ok(subgraph
+            .vertices
+            .entities
+            .into_values()
+            .filter(|entity| entity.metadata.record_id().entity_id != source_entity_id)
+            .collect())
+    }
+
     pub async fn get_latest_entity_links(
         &self,
         source_entity_id: EntityId,
@@ -680,13 +1011,18 @@ impl DatabaseApi<'_> {
                 &StructuralQuery {
                     filter,
                     graph_resolve_depths: GraphResolveDepths::default(),
+                    traversal_filters: KnowledgeGraphTraversalFilters::default(),
                     temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
                         pinned: PinnedTemporalAxisUnresolved::new(None),
                         variable: VariableTemporalAxisUnresolved::new(None, None),
                     },
+                    order_by: Vec::new(),
+                    limit: None,
+                    cursor: None,
                 },
             )
-            .await?;
+            .await?
+            .0;
 
         Ok(subgraph
             .roots
@@ -699,27 +1035,6 @@ impl DatabaseApi<'_> {
             })
             .collect())
     }
-
-    async fn archive_entity(
-        &mut self,
-        entity_id: EntityId,
-        properties: EntityProperties,
-        entity_type_id: VersionedUrl,
-        link_order: EntityLinkOrder,
-    ) -> Result<EntityMetadata, UpdateError> {
-        self.store
-            .update_entity(
-                self.account_id,
-                &mut NoAuthorization,
-                entity_id,
-                None,
-                true,
-                entity_type_id,
-                properties,
-                link_order,
-            )
-            .await
-    }
 }
 
 #[tokio::test]
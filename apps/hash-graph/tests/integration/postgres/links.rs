@@ -1,6 +1,11 @@
+use graph::store::error::EntityDoesNotExist;
 use graph_test_data::{data_type, entity, entity_type, property_type};
-use graph_types::knowledge::{entity::EntityProperties, link::EntityLinkOrder};
+use graph_types::{
+    knowledge::entity::{EntityId, EntityProperties, EntityUuid},
+    provenance::RecordArchivedById,
+};
 use type_system::url::{BaseUrl, VersionedUrl};
+use uuid::Uuid;
 
 use crate::DatabaseTestWrapper;
 
@@ -195,6 +200,244 @@ async fn get_entity_links() {
     );
 }
 
+#[tokio::test]
+async fn traverse_links_by_type() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let bob = serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity");
+    let charles = serde_json::from_str(entity::PERSON_CHARLES_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let friend_link_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let acquaintance_entity_link_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/acquaintance-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let alice_metadata = api
+        .create_entity(alice, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+
+    let bob_metadata = api
+        .create_entity(bob, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+
+    let charles_metadata = api
+        .create_entity(charles, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity");
+
+    let friend_link_metadata = api
+        .create_link_entity(
+            EntityProperties::empty(),
+            friend_link_type_id.clone(),
+            None,
+            alice_metadata.record_id().entity_id,
+            bob_metadata.record_id().entity_id,
+        )
+        .await
+        .expect("could not create link");
+
+    api.create_link_entity(
+        EntityProperties::empty(),
+        acquaintance_entity_link_type_id,
+        None,
+        alice_metadata.record_id().entity_id,
+        charles_metadata.record_id().entity_id,
+    )
+    .await
+    .expect("could not create link");
+
+    let mut linked_entity_ids = api
+        .get_linked_entities(alice_metadata.record_id().entity_id, &friend_link_type_id)
+        .await
+        .expect("could not traverse links")
+        .into_iter()
+        .map(|entity| entity.metadata.record_id().entity_id)
+        .collect::<Vec<_>>();
+    linked_entity_ids.sort();
+
+    let mut expected_entity_ids = vec![
+        friend_link_metadata.record_id().entity_id,
+        bob_metadata.record_id().entity_id,
+    ];
+    expected_entity_ids.sort();
+
+    assert_eq!(linked_entity_ids, expected_entity_ids);
+}
+
+#[tokio::test]
+async fn find_paths() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let bob = serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity");
+    let charles = serde_json::from_str(entity::PERSON_CHARLES_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let friend_link_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let alice_id = api
+        .create_entity(alice, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+    let bob_id = api
+        .create_entity(bob, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+    let charles_id = api
+        .create_entity(charles, person_type_id, None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+
+    let alice_bob_link_id = api
+        .create_link_entity(
+            EntityProperties::empty(),
+            friend_link_type_id.clone(),
+            None,
+            alice_id,
+            bob_id,
+        )
+        .await
+        .expect("could not create link")
+        .record_id()
+        .entity_id;
+    // Links are followed in both directions, so this link is followed from Bob to Charles.
+    let charles_bob_link_id = api
+        .create_link_entity(
+            EntityProperties::empty(),
+            friend_link_type_id.clone(),
+            None,
+            charles_id,
+            bob_id,
+        )
+        .await
+        .expect("could not create link")
+        .record_id()
+        .entity_id;
+    let alice_charles_link_id = api
+        .create_link_entity(
+            EntityProperties::empty(),
+            friend_link_type_id,
+            None,
+            alice_id,
+            charles_id,
+        )
+        .await
+        .expect("could not create link")
+        .record_id()
+        .entity_id;
+
+    let direct_path = vec![alice_id, alice_charles_link_id, charles_id];
+    let indirect_path = vec![
+        alice_id,
+        alice_bob_link_id,
+        bob_id,
+        charles_bob_link_id,
+        charles_id,
+    ];
+
+    assert_eq!(
+        api.get_entity_paths(alice_id, charles_id, 1, 10)
+            .await
+            .expect("could not find paths"),
+        [direct_path.clone()]
+    );
+    assert_eq!(
+        api.get_entity_paths(alice_id, charles_id, 2, 10)
+            .await
+            .expect("could not find paths"),
+        [direct_path.clone(), indirect_path]
+    );
+    assert_eq!(
+        api.get_entity_paths(alice_id, charles_id, 2, 1)
+            .await
+            .expect("could not find paths"),
+        [direct_path]
+    );
+    assert!(
+        api.get_entity_paths(alice_id, alice_id, 2, 10)
+            .await
+            .expect("could not find paths")
+            .is_empty()
+    );
+
+    let unknown_id = EntityId {
+        owned_by_id: alice_id.owned_by_id,
+        entity_uuid: EntityUuid::new(Uuid::new_v4()),
+    };
+    assert!(
+        api.get_entity_paths(unknown_id, charles_id, 2, 10)
+            .await
+            .expect_err("could find paths from an entity which does not exist")
+            .contains::<EntityDoesNotExist>()
+    );
+}
+
 #[tokio::test]
 async fn remove_link() {
     let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
@@ -243,7 +486,7 @@ async fn remove_link() {
     let link_entity_metadata = api
         .create_link_entity(
             EntityProperties::empty(),
-            friend_link_type_id.clone(),
+            friend_link_type_id,
             None,
             alice_metadata.record_id().entity_id,
             bob_metadata.record_id().entity_id,
@@ -258,17 +501,9 @@ async fn remove_link() {
             .is_empty()
     );
 
-    api.archive_entity(
-        link_entity_metadata.record_id().entity_id,
-        EntityProperties::empty(),
-        friend_link_type_id,
-        EntityLinkOrder {
-            left_to_right: None,
-            right_to_left: None,
-        },
-    )
-    .await
-    .expect("could not remove link");
+    api.archive_entity(link_entity_metadata.record_id().entity_id, false)
+        .await
+        .expect("could not remove link");
 
     assert!(
         api.get_latest_entity_links(alice_metadata.record_id().entity_id)
@@ -277,3 +512,211 @@ async fn remove_link() {
             .is_empty()
     );
 }
+
+#[tokio::test]
+async fn archive_entity_with_links() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let bob = serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let friend_link_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let alice_id = api
+        .create_entity(alice, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+
+    let bob_id = api
+        .create_entity(bob, person_type_id, None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+
+    let link_entity_id = api
+        .create_link_entity(
+            EntityProperties::empty(),
+            friend_link_type_id,
+            None,
+            alice_id,
+            bob_id,
+        )
+        .await
+        .expect("could not create link")
+        .record_id()
+        .entity_id;
+
+    let archived = api
+        .archive_entity(bob_id, true)
+        .await
+        .expect("could not archive entity");
+    assert_eq!(archived.len(), 2);
+    assert_eq!(archived[0].record_id().entity_id, bob_id);
+    assert_eq!(archived[1].record_id().entity_id, link_entity_id);
+    assert!(archived.iter().all(|metadata| metadata.archived()));
+    assert!(
+        api.get_latest_entity_links(alice_id)
+            .await
+            .expect("could not fetch links")
+            .is_empty()
+    );
+
+    let bob = api
+        .get_latest_entity(bob_id)
+        .await
+        .expect("could not get entity");
+    assert_eq!(
+        bob.metadata.provenance().record_archived_by_id,
+        Some(RecordArchivedById::new(api.account_id))
+    );
+
+    api.archive_entity(bob_id, false)
+        .await
+        .expect_err("could archive an archived entity");
+
+    let unarchived = api
+        .unarchive_entity(bob_id, true)
+        .await
+        .expect("could not unarchive entity");
+    assert_eq!(unarchived.len(), 2);
+    assert!(unarchived.iter().all(|metadata| !metadata.archived()));
+    assert_eq!(
+        api.get_latest_entity_links(alice_id)
+            .await
+            .expect("could not fetch links")
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn erase_entity_with_links() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let bob = serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let friend_link_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let alice_id = api
+        .create_entity(alice, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+
+    let bob_id = api
+        .create_entity(bob, person_type_id, None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+
+    let link_entity_id = api
+        .create_link_entity(
+            EntityProperties::empty(),
+            friend_link_type_id,
+            None,
+            alice_id,
+            bob_id,
+        )
+        .await
+        .expect("could not create link")
+        .record_id()
+        .entity_id;
+
+    api.erase_entity(bob_id, false)
+        .await
+        .expect_err("could erase an entity with links without erasing the links");
+
+    let erased = api
+        .erase_entity(bob_id, true)
+        .await
+        .expect("could not erase entity");
+    assert_eq!(erased, [bob_id, link_entity_id]);
+
+    assert!(
+        api.get_entities(bob_id)
+            .await
+            .expect("could not read entities")
+            .is_empty()
+    );
+    assert!(
+        api.get_entities(link_entity_id)
+            .await
+            .expect("could not read entities")
+            .is_empty()
+    );
+    assert!(
+        api.get_latest_entity_links(alice_id)
+            .await
+            .expect("could not fetch links")
+            .is_empty()
+    );
+    assert_eq!(
+        api.get_entities(alice_id)
+            .await
+            .expect("could not read entities")
+            .len(),
+        1
+    );
+
+    api.erase_entity(bob_id, false)
+        .await
+        .expect_err("could erase an erased entity");
+}
@@ -0,0 +1,636 @@
+use authorization::{
+    backend::{InMemoryBackend, PermissionAssertion, ZanzibarBackend},
+    schema::OwnerId,
+    zanzibar::{Consistency, ZanzibarClient},
+    AuthorizationApi, VisibilityScope,
+};
+use graph::{
+    store::{
+        error::EntityUuidAlreadyExists, query::Filter, AccountStore, BatchStore, BulkItemFailure,
+        DataTypeStore, EntityStore, WriteOperation,
+    },
+    subgraph::{
+        edges::{GraphResolveDepths, KnowledgeGraphTraversalFilters},
+        query::StructuralQuery,
+        temporal_axes::{
+            PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
+            VariableTemporalAxisUnresolved,
+        },
+    },
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    account::AccountId,
+    knowledge::{
+        entity::{EntityId, EntityMetadata, EntityProperties, EntityUuid},
+        link::EntityLinkOrder,
+    },
+    ontology::{DataTypeId, PartialCustomOntologyMetadata, PartialOntologyElementMetadata},
+    provenance::OwnedById,
+    web::WebId,
+};
+use type_system::{
+    repr,
+    url::{BaseUrl, VersionedUrl},
+    DataType,
+};
+use uuid::Uuid;
+
+use crate::{DatabaseApi, DatabaseTestWrapper};
+
+const SCHEMA: &str = include_str!("../../../lib/authorization/schemas/v1__initial_schema.zed");
+
+async fn authorization_api() -> ZanzibarClient<InMemoryBackend> {
+    let mut backend = InMemoryBackend::new();
+    backend
+        .import_schema(SCHEMA)
+        .await
+        .expect("could not import schema");
+    ZanzibarClient::new(backend)
+}
+
+fn parse_data_type(data_type: &str) -> DataType {
+    let data_type_repr: repr::DataType =
+        serde_json::from_str(data_type).expect("could not parse data type representation");
+    DataType::try_from(data_type_repr).expect("could not parse data type")
+}
+
+fn person_entity_type_id() -> VersionedUrl {
+    VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    }
+}
+
+/// Seeds the person entity type and creates Bob next to the seeded account of Alice.
+async fn seed_people(
+    database: &mut DatabaseTestWrapper,
+    authorization_api: &mut ZanzibarClient<InMemoryBackend>,
+) -> (DatabaseApi<'_>, AccountId, AccountId) {
+    let api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let alice = api.account_id;
+    authorization_api
+        .add_web_owner(OwnerId::from(alice), WebId::from(alice))
+        .await
+        .expect("could not add web owner");
+    let bob = AccountId::new(Uuid::new_v4());
+    api.store
+        .insert_account_id(bob, authorization_api, bob)
+        .await
+        .expect("could not insert account id");
+
+    (api, alice, bob)
+}
+
+async fn create_person(
+    api: &mut DatabaseApi<'_>,
+    authorization_api: &mut ZanzibarClient<InMemoryBackend>,
+    owner: AccountId,
+) -> EntityMetadata {
+    let person: EntityProperties =
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    api.store
+        .create_entity(
+            owner,
+            authorization_api,
+            OwnedById::new(owner.into_uuid()),
+            None,
+            None,
+            false,
+            person_entity_type_id(),
+            person,
+            None,
+        )
+        .await
+        .expect("could not create entity")
+}
+
+/// Returns how many editions of the entity the actor is able to read.
+async fn count_viewable_editions(
+    api: &DatabaseApi<'_>,
+    authorization_api: &ZanzibarClient<InMemoryBackend>,
+    actor_id: AccountId,
+    entity_id: EntityId,
+) -> usize {
+    api.store
+        .get_entity(
+            actor_id,
+            authorization_api,
+            &StructuralQuery {
+                filter: Filter::for_entity_by_entity_id(entity_id),
+                graph_resolve_depths: GraphResolveDepths::default(),
+                traversal_filters: KnowledgeGraphTraversalFilters::default(),
+                temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(None),
+                    variable: VariableTemporalAxisUnresolved::new(None, None),
+                },
+                order_by: Vec::new(),
+                limit: None,
+                cursor: None,
+            },
+        )
+        .await
+        .expect("could not read entity")
+        .0
+        .vertices
+        .entities
+        .len()
+}
+
+/// Returns how many data types with the URL the actor is able to read.
+async fn count_viewable_data_types(
+    api: &DatabaseApi<'_>,
+    authorization_api: &ZanzibarClient<InMemoryBackend>,
+    actor_id: AccountId,
+    url: &VersionedUrl,
+) -> usize {
+    api.store
+        .get_data_type(
+            actor_id,
+            authorization_api,
+            &StructuralQuery {
+                filter: Filter::for_versioned_url(url),
+                graph_resolve_depths: GraphResolveDepths::default(),
+                traversal_filters: KnowledgeGraphTraversalFilters::default(),
+                temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(None),
+                    variable: VariableTemporalAxisUnresolved::new(None, None),
+                },
+                order_by: Vec::new(),
+                limit: None,
+                cursor: None,
+            },
+        )
+        .await
+        .expect("could not read data type")
+        .vertices
+        .data_types
+        .len()
+}
+
+fn owned_by(account_id: AccountId) -> PartialCustomOntologyMetadata {
+    PartialCustomOntologyMetadata::Owned {
+        owned_by_id: OwnedById::new(account_id.into_uuid()),
+    }
+}
+
+#[tokio::test]
+async fn data_type_permissions() {
+    let object_dt_v1 = parse_data_type(data_type::OBJECT_V1);
+    let object_dt_v2 = parse_data_type(data_type::OBJECT_V2);
+    let data_type_id = DataTypeId::from_url(object_dt_v1.id());
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+    let mut authorization_api = authorization_api().await;
+
+    let alice = api.account_id;
+    authorization_api
+        .add_web_owner(OwnerId::from(alice), WebId::from(alice))
+        .await
+        .expect("could not add web owner");
+    let bob = AccountId::new(Uuid::new_v4());
+    api.store
+        .insert_account_id(bob, &mut authorization_api, bob)
+        .await
+        .expect("could not insert account id");
+
+    api.store
+        .create_data_type(
+            bob,
+            &mut authorization_api,
+            object_dt_v1.clone(),
+            PartialOntologyElementMetadata {
+                record_id: object_dt_v1.id().clone().into(),
+                custom: owned_by(alice),
+            },
+        )
+        .await
+        .expect_err("could create a data type in another web");
+
+    api.store
+        .create_data_type(
+            alice,
+            &mut authorization_api,
+            object_dt_v1.clone(),
+            PartialOntologyElementMetadata {
+                record_id: object_dt_v1.id().clone().into(),
+                custom: owned_by(alice),
+            },
+        )
+        .await
+        .expect("could not create data type");
+    assert!(
+        authorization_api
+            .can_archive_data_type(alice, data_type_id, Consistency::FullyConsistent)
+            .await
+            .expect("could not check permission")
+            .has_permission
+    );
+
+    api.store
+        .update_data_type(bob, &mut authorization_api, object_dt_v2.clone())
+        .await
+        .expect_err("could update a data type without being an editor");
+
+    authorization_api
+        .add_data_type_editor(VisibilityScope::Account(bob), data_type_id)
+        .await
+        .expect("could not add data type editor");
+    api.store
+        .update_data_type(bob, &mut authorization_api, object_dt_v2)
+        .await
+        .expect("could not update data type as editor");
+    assert!(
+        !authorization_api
+            .can_archive_data_type(bob, data_type_id, Consistency::FullyConsistent)
+            .await
+            .expect("could not check permission")
+            .has_permission
+    );
+}
+
+#[tokio::test]
+async fn write_ontology_type_relations() {
+    let object_dt = parse_data_type(data_type::OBJECT_V1);
+    let boolean_dt = parse_data_type(data_type::BOOLEAN_V1);
+    let object_dt_id = DataTypeId::from_url(object_dt.id());
+    let boolean_dt_id = DataTypeId::from_url(boolean_dt.id());
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+    let mut authorization_api = authorization_api().await;
+    let alice = api.account_id;
+
+    // Types created without writing their relations, like types created before ontology types had
+    // permissions or types restored from a snapshot.
+    api.create_owned_data_type(object_dt)
+        .await
+        .expect("could not create data type");
+    api.create_external_data_type(boolean_dt)
+        .await
+        .expect("could not create data type");
+    assert!(
+        !authorization_api
+            .can_update_data_type(alice, object_dt_id, Consistency::FullyConsistent)
+            .await
+            .expect("could not check permission")
+            .has_permission
+    );
+
+    // Writing the relations a second time must not fail for the already existing relations.
+    for _ in 0..2 {
+        api.store
+            .write_ontology_type_relations(&mut authorization_api)
+            .await
+            .expect("could not write ontology type relations");
+    }
+
+    // Owned and external types are viewable by everyone.
+    let bob = AccountId::new(Uuid::new_v4());
+    for data_type_id in [object_dt_id, boolean_dt_id] {
+        assert!(
+            authorization_api
+                .can_view_data_type(bob, data_type_id, Consistency::FullyConsistent)
+                .await
+                .expect("could not check permission")
+                .has_permission
+        );
+    }
+
+    assert!(
+        authorization_api
+            .can_archive_data_type(alice, object_dt_id, Consistency::FullyConsistent)
+            .await
+            .expect("could not check permission")
+            .has_permission
+    );
+    assert!(
+        !authorization_api
+            .can_update_data_type(alice, boolean_dt_id, Consistency::FullyConsistent)
+            .await
+            .expect("could not check permission")
+            .has_permission
+    );
+}
+
+#[tokio::test]
+async fn entity_uuid_is_unique_across_webs() {
+    let person: EntityProperties =
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut authorization_api = authorization_api().await;
+    let (mut api, alice, bob) = seed_people(&mut database, &mut authorization_api).await;
+
+    let entity_uuid = EntityUuid::new(Uuid::new_v4());
+    api.store
+        .create_entity(
+            alice,
+            &mut authorization_api,
+            OwnedById::new(alice.into_uuid()),
+            Some(entity_uuid),
+            None,
+            false,
+            person_entity_type_id(),
+            person.clone(),
+            None,
+        )
+        .await
+        .expect("could not create entity");
+
+    // Permissions are attached to the entity UUID, so Bob would be the owner of Alice's entity if
+    // he could reuse the UUID in his web.
+    let report = api
+        .store
+        .create_entity(
+            bob,
+            &mut authorization_api,
+            OwnedById::new(bob.into_uuid()),
+            Some(entity_uuid),
+            None,
+            false,
+            person_entity_type_id(),
+            person,
+            None,
+        )
+        .await
+        .expect_err("could reuse an entity UUID in another web");
+    assert!(
+        report.contains::<EntityUuidAlreadyExists>(),
+        "wrong error, expected `EntityUuidAlreadyExists`, got {report:?}"
+    );
+}
+
+#[tokio::test]
+async fn entity_editor_can_update_entity() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut authorization_api = authorization_api().await;
+    let (mut api, alice, bob) = seed_people(&mut database, &mut authorization_api).await;
+
+    let metadata = create_person(&mut api, &mut authorization_api, alice).await;
+    let entity_id = metadata.record_id().entity_id;
+    let person_v2: EntityProperties =
+        serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity");
+
+    api.store
+        .update_entity(
+            bob,
+            &mut authorization_api,
+            entity_id,
+            None,
+            None,
+            false,
+            person_entity_type_id(),
+            person_v2.clone(),
+            EntityLinkOrder {
+                left_to_right: None,
+                right_to_left: None,
+            },
+        )
+        .await
+        .expect_err("could update an entity without being an editor");
+
+    authorization_api
+        .add_entity_editor(VisibilityScope::Account(bob), entity_id)
+        .await
+        .expect("could not add entity editor");
+    let updated = api
+        .store
+        .update_entity(
+            bob,
+            &mut authorization_api,
+            entity_id,
+            None,
+            None,
+            false,
+            person_entity_type_id(),
+            person_v2,
+            EntityLinkOrder {
+                left_to_right: None,
+                right_to_left: None,
+            },
+        )
+        .await
+        .expect("could not update entity as editor");
+    assert_eq!(updated.record_id().entity_id, entity_id);
+    assert_ne!(
+        updated.record_id().edition_id,
+        metadata.record_id().edition_id
+    );
+
+    // Editing does not allow to change who has access to the entity.
+    assert!(
+        !authorization_api
+            .can_share_entity(bob, entity_id, Consistency::FullyConsistent)
+            .await
+            .expect("could not check permission")
+            .has_permission
+    );
+}
+
+#[tokio::test]
+async fn unsharing_entity_revokes_view() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut authorization_api = authorization_api().await;
+    let (mut api, alice, bob) = seed_people(&mut database, &mut authorization_api).await;
+
+    let entity_id = create_person(&mut api, &mut authorization_api, alice)
+        .await
+        .record_id()
+        .entity_id;
+    assert_eq!(
+        count_viewable_editions(&api, &authorization_api, bob, entity_id).await,
+        0
+    );
+
+    authorization_api
+        .add_entity_viewer(VisibilityScope::Account(bob), entity_id)
+        .await
+        .expect("could not add entity viewer");
+    assert_eq!(
+        count_viewable_editions(&api, &authorization_api, bob, entity_id).await,
+        1
+    );
+
+    authorization_api
+        .remove_entity_viewer(VisibilityScope::Account(bob), entity_id)
+        .await
+        .expect("could not remove entity viewer");
+    assert_eq!(
+        count_viewable_editions(&api, &authorization_api, bob, entity_id).await,
+        0
+    );
+    assert_eq!(
+        count_viewable_editions(&api, &authorization_api, alice, entity_id).await,
+        1
+    );
+}
+
+#[tokio::test]
+async fn erasing_entity_removes_relations() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut authorization_api = authorization_api().await;
+    let (mut api, alice, bob) = seed_people(&mut database, &mut authorization_api).await;
+
+    let entity_id = create_person(&mut api, &mut authorization_api, alice)
+        .await
+        .record_id()
+        .entity_id;
+    authorization_api
+        .add_entity_editor(VisibilityScope::Account(bob), entity_id)
+        .await
+        .expect("could not add entity editor");
+    authorization_api
+        .add_entity_viewer(VisibilityScope::Account(bob), entity_id)
+        .await
+        .expect("could not add entity viewer");
+
+    api.store
+        .erase_entity(alice, &mut authorization_api, entity_id, false)
+        .await
+        .expect("could not erase entity");
+
+    let (relations, _) = authorization_api
+        .get_entity_relations(entity_id, Consistency::FullyConsistent)
+        .await
+        .expect("could not read entity relations");
+    assert!(relations.is_empty(), "remaining relations: {relations:?}");
+    assert!(
+        !authorization_api
+            .can_view_entity(bob, entity_id, Consistency::FullyConsistent)
+            .await
+            .expect("could not check permission")
+            .has_permission
+    );
+}
+
+#[tokio::test]
+async fn batch_reports_forbidden_operation() {
+    let person: EntityProperties =
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut authorization_api = authorization_api().await;
+    let (mut api, alice, bob) = seed_people(&mut database, &mut authorization_api).await;
+
+    let report = api
+        .store
+        .write_batch(
+            bob,
+            &mut authorization_api,
+            vec![
+                WriteOperation::CreateEntity {
+                    owned_by_id: OwnedById::new(bob.into_uuid()),
+                    entity_uuid: None,
+                    entity_type_id: person_entity_type_id(),
+                    properties: person.clone(),
+                    link_data: None,
+                },
+                WriteOperation::CreateEntity {
+                    owned_by_id: OwnedById::new(alice.into_uuid()),
+                    entity_uuid: None,
+                    entity_type_id: person_entity_type_id(),
+                    properties: person,
+                    link_data: None,
+                },
+            ],
+        )
+        .await
+        .expect_err("could create an entity in another web");
+
+    assert!(report.contains::<PermissionAssertion>());
+    let failures = report
+        .request_ref::<BulkItemFailure>()
+        .map(|failure| failure.index)
+        .collect::<Vec<_>>();
+    assert_eq!(failures, [1]);
+}
+
+#[tokio::test]
+async fn data_type_viewer_permissions() {
+    let object_dt = parse_data_type(data_type::OBJECT_V1);
+    let object_dt_url = object_dt.id().clone();
+    let data_type_id = DataTypeId::from_url(&object_dt_url);
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+    let mut authorization_api = authorization_api().await;
+
+    let alice = api.account_id;
+    authorization_api
+        .add_web_owner(OwnerId::from(alice), WebId::from(alice))
+        .await
+        .expect("could not add web owner");
+    let bob = AccountId::new(Uuid::new_v4());
+
+    api.store
+        .create_data_type(
+            alice,
+            &mut authorization_api,
+            object_dt,
+            PartialOntologyElementMetadata {
+                record_id: object_dt_url.clone().into(),
+                custom: owned_by(alice),
+            },
+        )
+        .await
+        .expect("could not create data type");
+    assert_eq!(
+        count_viewable_data_types(&api, &authorization_api, bob, &object_dt_url).await,
+        1
+    );
+
+    authorization_api
+        .remove_data_type_viewer(VisibilityScope::Public, data_type_id)
+        .await
+        .expect("could not remove public viewer");
+    assert_eq!(
+        count_viewable_data_types(&api, &authorization_api, bob, &object_dt_url).await,
+        0
+    );
+    assert_eq!(
+        count_viewable_data_types(&api, &authorization_api, alice, &object_dt_url).await,
+        1
+    );
+
+    authorization_api
+        .add_data_type_viewer(VisibilityScope::Account(bob), data_type_id)
+        .await
+        .expect("could not add viewer");
+    assert_eq!(
+        count_viewable_data_types(&api, &authorization_api, bob, &object_dt_url).await,
+        1
+    );
+    assert!(
+        !authorization_api
+            .can_update_data_type(bob, data_type_id, Consistency::FullyConsistent)
+            .await
+            .expect("could not check permission")
+            .has_permission
+    );
+}
@@ -0,0 +1,178 @@
+use std::{net::SocketAddr, time::Duration};
+
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use graph::{
+    store::WebhookFilter,
+    webhook::{
+        sign_payload, WebhookDeliveryConfig, WebhookDispatcher, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+        WEBHOOK_ID_HEADER,
+    },
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::knowledge::entity::EntityProperties;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use type_system::url::{BaseUrl, VersionedUrl};
+
+use crate::DatabaseTestWrapper;
+
+/// Starts a local HTTP server standing in for a webhook, which responds to every request with
+/// `status` and forwards the received headers and body.
+fn start_receiver(status: StatusCode) -> (SocketAddr, mpsc::UnboundedReceiver<(HeaderMap, Bytes)>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let router = Router::new().route(
+        "/",
+        post(move |headers: HeaderMap, body: Bytes| {
+            let sender = sender.clone();
+            async move {
+                sender
+                    .send((headers, body))
+                    .expect("could not forward request");
+                status
+            }
+        }),
+    );
+
+    let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+        .serve(router.into_make_service());
+    let address = server.local_addr();
+    tokio::spawn(server);
+
+    (address, receiver)
+}
+
+#[tokio::test]
+async fn deliver_changes() {
+    let person: EntityProperties =
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let person_type_base_url =
+        BaseUrl::new("https://blockprotocol.org/@alice/types/entity-type/person/".to_owned())
+            .expect("couldn't construct Base URL");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::NAME_V1],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let dispatcher = WebhookDispatcher::new(WebhookDeliveryConfig {
+        max_attempts: 2,
+        initial_backoff: Duration::from_millis(1),
+        ..WebhookDeliveryConfig::default()
+    })
+    .expect("could not create dispatcher");
+
+    let (address, mut requests) = start_receiver(StatusCode::OK);
+    let webhook = api
+        .register_webhook(
+            format!("http://{address}/"),
+            "secret".to_owned(),
+            WebhookFilter {
+                entity_type_base_url: Some(person_type_base_url.clone()),
+                owned_by_id: None,
+            },
+        )
+        .await
+        .expect("could not register webhook");
+
+    let metadata = api
+        .create_entity(
+            person,
+            VersionedUrl {
+                base_url: person_type_base_url.clone(),
+                version: 1,
+            },
+            None,
+        )
+        .await
+        .expect("could not create entity");
+
+    // The seeded ontology types don't match the filter, so only the entity is delivered.
+    assert_eq!(
+        api.deliver_webhooks(&dispatcher)
+            .await
+            .expect("could not deliver changes"),
+        1
+    );
+    let (headers, body) = requests.try_recv().expect("webhook was not called");
+    assert!(requests.try_recv().is_err());
+
+    assert_eq!(
+        headers[WEBHOOK_ID_HEADER].to_str().expect("invalid header"),
+        webhook.webhook_id.to_string()
+    );
+    let timestamp = headers[TIMESTAMP_HEADER]
+        .to_str()
+        .expect("invalid header")
+        .parse()
+        .expect("invalid timestamp");
+    assert_eq!(
+        headers[SIGNATURE_HEADER].to_str().expect("invalid header"),
+        sign_payload("secret", timestamp, &body)
+    );
+    let payload: Value = serde_json::from_slice(&body).expect("could not parse payload");
+    assert_eq!(payload["change"]["kind"], "created");
+    assert_eq!(payload["change"]["recordKind"], "entity");
+    assert_eq!(
+        payload["change"]["recordId"],
+        serde_json::to_value(metadata.record_id()).expect("could not serialize record id")
+    );
+
+    // The cursor of the webhook was advanced, so nothing is delivered twice.
+    assert_eq!(
+        api.deliver_webhooks(&dispatcher)
+            .await
+            .expect("could not deliver changes"),
+        0
+    );
+    assert!(requests.try_recv().is_err());
+
+    // All changes in the test share the transaction time, so the entity is delivered to a webhook
+    // registered afterwards as well.
+    let (address, mut failed_requests) = start_receiver(StatusCode::INTERNAL_SERVER_ERROR);
+    let failing_webhook = api
+        .register_webhook(
+            format!("http://{address}/"),
+            "secret".to_owned(),
+            WebhookFilter {
+                entity_type_base_url: Some(person_type_base_url),
+                owned_by_id: None,
+            },
+        )
+        .await
+        .expect("could not register webhook");
+
+    assert_eq!(
+        api.deliver_webhooks(&dispatcher)
+            .await
+            .expect("could not deliver changes"),
+        0
+    );
+    assert!(failed_requests.try_recv().is_ok());
+    assert!(failed_requests.try_recv().is_ok());
+    assert!(failed_requests.try_recv().is_err());
+
+    let dead_letters = api
+        .get_webhook_dead_letters(failing_webhook.webhook_id)
+        .await
+        .expect("could not read dead letters");
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].attempts, 2);
+    assert_eq!(
+        dead_letters[0].payload["change"]["recordId"],
+        payload["change"]["recordId"]
+    );
+}